@@ -9,10 +9,12 @@ interface ICapabilityIssuer {
     /// @notice Issue a capability token for an intent
     /// @param intent The intent to issue a capability for
     /// @param scaledLimitsHash Hash of the scaled limits from VagalBrake
+    /// @param expiresAt Token expiry, no later than the intent's notAfter
     /// @return tokenId The issued token ID
     function issueCapability(
         Types.Intent calldata intent,
-        bytes32 scaledLimitsHash
+        bytes32 scaledLimitsHash,
+        uint256 expiresAt
     ) external returns (uint256 tokenId);
 
     /// @notice Revoke a capability token
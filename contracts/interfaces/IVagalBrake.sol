@@ -8,8 +8,14 @@ import "../src/core/Types.sol";
 interface IVagalBrake {
     /// @notice Issue capability with vagal brake applied
     /// @param intent The intent to process
+    /// @param scaledLimitsHash Hash of the scaled limits, checked by the issuer
+    /// @param expiresAt Token expiry, no later than the intent's notAfter
     /// @return tokenId The issued capability token ID
-    function issueWithBrake(Types.Intent calldata intent) external returns (uint256 tokenId);
+    function issueWithBrake(
+        Types.Intent calldata intent,
+        bytes32 scaledLimitsHash,
+        uint256 expiresAt
+    ) external returns (uint256 tokenId);
 
     /// @notice Preview scaled limits without issuing
     /// @param intent The intent to preview
@@ -25,7 +25,7 @@ contract DeployCore is Script {
         console.log("ANSStateManager deployed at:", address(ansStateManager));
 
         // 3. Deploy CapabilityIssuer (depends on AfferentInbox, vagalBrake will be set later)
-        CapabilityIssuer capabilityIssuer = new CapabilityIssuer(address(afferentInbox), address(0)); // Temporary address(0)
+        CapabilityIssuer capabilityIssuer = new CapabilityIssuer(address(afferentInbox), address(0), address(ansStateManager), address(0)); // VagalBrake and ReflexArc are set below
         console.log("CapabilityIssuer deployed at:", address(capabilityIssuer));
 
         // 4. Deploy VagalBrake (depends on ANSStateManager and CapabilityIssuer)
@@ -132,6 +132,7 @@ contract ANSStateManager is Events {
     /// @param timestamp The timestamp of the transition
     function _setState(uint256 executorId, uint8 newState, uint32 tone, uint64 timestamp) internal {
         ExecutorState storage s = executorStates[executorId];
+        uint8 fromState = s.state;
 
         // Update state
         s.state = newState;
@@ -146,6 +147,7 @@ contract ANSStateManager is Events {
 
         // Emit canonical events
         emit VagalToneUpdated(uint256(tone), newState, timestamp);
+        emit StateChanged(fromState, newState, _vtiFromTone(tone), timestamp);
 
         // Notify ReflexArc if configured (fail silently)
         if (reflexArc != address(0)) {
@@ -157,6 +159,15 @@ contract ANSStateManager is Events {
         }
     }
 
+    /// @notice Convert a danger tone in ppm to the spec VTI in basis points
+    ///         (higher is safer), rounded to the nearest basis point
+    /// @param tone The tone value in ppm (0-1,000,000)
+    /// @return The VTI in basis points (0-10,000)
+    function _vtiFromTone(uint32 tone) internal pure returns (uint256) {
+        uint256 ppm = tone > 1_000_000 ? 1_000_000 : uint256(tone);
+        return 10000 - (ppm + 50) / 100;
+    }
+
     /// @notice Set ReflexArc contract address
     /// @param _reflexArc The ReflexArc contract address
     function setReflexArc(address _reflexArc) external {
@@ -19,6 +19,9 @@ contract CapabilityIssuer is Events {
     /// @notice Mapping from token ID to token metadata
     mapping(uint256 => Types.TokenMeta) public tokenMeta;
 
+    /// @notice Mapping from token ID to the planner that owns it
+    mapping(uint256 => address) public tokenOwner;
+
     /// @notice Mapping from executor ID to list of active token IDs
     mapping(uint256 => uint256[]) public activeTokens;
 
@@ -68,6 +71,15 @@ contract CapabilityIssuer is Events {
     uint256 public circuitBreakerTimeout = 300; // 5 minutes timeout
     uint256 public circuitBreakerRecovery = 3;  // Successes needed in half-open
 
+    /// @notice Maximum simultaneously-active tokens per executor
+    uint256 public maxActivePerExecutor = 16;
+
+    /// @notice Executors quarantined from issuance while the rest of the fleet operates
+    mapping(uint256 => bool) public executorPaused;
+
+    /// @notice Nonces already issued for, per planner
+    mapping(address => mapping(uint256 => bool)) public usedNonces;
+
     /// @notice Constructor with dependency injection
     /// @param _afferentInbox Address of the AfferentInbox contract
     /// @param _vagalBrake Address of the VagalBrake contract
@@ -96,11 +108,18 @@ contract CapabilityIssuer is Events {
     /// @notice Issue a capability token for an intent
     /// @param intent The intent to issue a capability for
     /// @param scaledLimitsHash Hash of the scaled limits from VagalBrake
+    /// @param expiresAt Token expiry, no later than the intent's notAfter
     /// @return tokenId The issued token ID
     function issueCapability(
         Types.Intent calldata intent,
-        bytes32 scaledLimitsHash
+        bytes32 scaledLimitsHash,
+        uint256 expiresAt
     ) external returns (uint256 tokenId) {
+        // A paused executor is blocked outright, whatever the ANS state allows
+        if (executorPaused[intent.executorId]) {
+            revert ExecutorPaused(intent.executorId);
+        }
+
         // ER7: Check circuit breaker first
         bytes32 key = keccak256(abi.encodePacked(intent.executorId, intent.actionId));
         _checkCircuitBreaker(key);
@@ -109,9 +128,16 @@ contract CapabilityIssuer is Events {
         _checkRateLimit(key);
 
         // Validate intent timing
+        if (intent.notBefore > intent.notAfter) {
+            revert InvalidInput("notBefore after notAfter");
+        }
         if (block.timestamp < intent.notBefore || block.timestamp > intent.notAfter) {
             revert IntentExpired();
         }
+        // The token may not outlive the intent's authorization window
+        if (expiresAt > intent.notAfter || expiresAt <= block.timestamp) {
+            revert InvalidInput("expiresAt outside intent window");
+        }
 
         // ER1: Validate that scaledLimitsHash comes from VagalBrake
         (bytes32 expectedScaledLimitsHash, bool brakeAllowed) = IVagalBrake(vagalBrake).previewBrake(intent);
@@ -134,8 +160,17 @@ contract CapabilityIssuer is Events {
             revert InvalidInput("scaledLimitsHash cannot be zero");
         }
 
-        // TODO: Check nonce uniqueness per planner
-        // For MVP, we skip this check
+        // Reject while the executor already holds the maximum number of active tokens
+        _pruneExpiredTokens(intent.executorId);
+        if (activeTokens[intent.executorId].length >= maxActivePerExecutor) {
+            revert TooManyActiveTokens(intent.executorId, maxActivePerExecutor);
+        }
+
+        // Each of a planner's intents is issued at most once
+        if (usedNonces[intent.planner][intent.nonce]) {
+            revert NonceAlreadyUsed();
+        }
+        usedNonces[intent.planner][intent.nonce] = true;
 
         // Mint new token
         tokenId = nextTokenId++;
@@ -143,12 +178,15 @@ contract CapabilityIssuer is Events {
             executorId: intent.executorId,
             actionId: intent.actionId,
             scaledLimitsHash: scaledLimitsHash,
-            issuedAt: uint64(block.timestamp),
-            expiresAt: intent.notAfter,
+            issuedAt: block.timestamp,
+            expiresAt: expiresAt,
             revoked: false,
-            issuer: msg.sender
+            issuer: msg.sender,
+            isEscape: intent.isEscape
         });
 
+        tokenOwner[tokenId] = intent.planner;
+
         // Add to active tokens list
         activeTokens[intent.executorId].push(tokenId);
 
@@ -158,7 +196,7 @@ contract CapabilityIssuer is Events {
             intent.executorId,
             intent.planner,
             intent.actionId,
-            intent.notAfter,
+            expiresAt,
             bytes32(0), // paramsHashSha256 - placeholder for T-5 full implementation
             bytes32(0), // paramsHashKeccak - placeholder for T-5 full implementation
             intent.preStateRoot, // preStateRootSha256 - simplified for now
@@ -203,7 +241,8 @@ contract CapabilityIssuer is Events {
         vagalBrake = _vagalBrake;
     }
 
-    /// @notice Check if a token is valid (not expired, not revoked)
+    /// @notice Check if a token is valid (not expired, not revoked, and an
+    ///         escape token if its executor is in SHUTDOWN)
     /// @param tokenId The token ID to check
     /// @return True if the token is valid
     function isValid(uint256 tokenId) external view returns (bool) {
@@ -211,6 +250,10 @@ contract CapabilityIssuer is Events {
         if (meta.issuedAt == 0) return false; // Token doesn't exist
         if (meta.revoked) return false; // Token revoked
         if (block.timestamp > meta.expiresAt) return false; // Token expired
+        if (!meta.isEscape && ansStateManager != address(0)) {
+            (uint8 state,,) = IANSStateManager(ansStateManager).getExecutorState(meta.executorId);
+            if (state == 2) return false; // Only escape tokens survive SHUTDOWN
+        }
         return true;
     }
 
@@ -221,6 +264,13 @@ contract CapabilityIssuer is Events {
         return activeTokens[executorId];
     }
 
+    /// @notice Get the planner owning a token
+    /// @param tokenId The token ID
+    /// @return The owner, or the zero address if the token does not exist
+    function ownerOf(uint256 tokenId) external view returns (address) {
+        return tokenOwner[tokenId];
+    }
+
     /// @notice Get token metadata
     /// @param tokenId The token ID
     /// @return The token metadata
@@ -242,6 +292,21 @@ contract CapabilityIssuer is Events {
         }
     }
 
+    /// @notice Internal function to drop expired tokens from the active tokens list
+    /// @param executorId The executor ID
+    function _pruneExpiredTokens(uint256 executorId) internal {
+        uint256[] storage tokens = activeTokens[executorId];
+        uint256 i = 0;
+        while (i < tokens.length) {
+            if (block.timestamp > tokenMeta[tokens[i]].expiresAt) {
+                tokens[i] = tokens[tokens.length - 1];
+                tokens.pop();
+            } else {
+                i++;
+            }
+        }
+    }
+
     /// @notice Internal function to check circuit breaker state
     /// @param key The rate limit key (keccak256(executorId, actionId))
     function _checkCircuitBreaker(bytes32 key) internal {
@@ -341,6 +406,27 @@ contract CapabilityIssuer is Events {
         circuitBreakerRecovery = recovery;
     }
 
+    /// @notice Set the maximum number of simultaneously-active tokens per executor
+    /// @param maxActive New cap on active tokens
+    function setMaxActivePerExecutor(uint256 maxActive) external {
+        require(msg.sender == owner, "Only owner can set active token cap");
+        maxActivePerExecutor = maxActive;
+    }
+
+    /// @notice Pause issuance for a single executor
+    /// @param executorId The executor ID
+    function pauseExecutor(uint256 executorId) external {
+        require(msg.sender == owner, "Only owner can pause executors");
+        executorPaused[executorId] = true;
+    }
+
+    /// @notice Resume issuance for a paused executor
+    /// @param executorId The executor ID
+    function unpauseExecutor(uint256 executorId) external {
+        require(msg.sender == owner, "Only owner can unpause executors");
+        executorPaused[executorId] = false;
+    }
+
     /// @notice Get circuit breaker state for an executor-action pair
     /// @param executorId The executor ID
     /// @param actionId The action ID
@@ -37,11 +37,20 @@ contract Events {
         uint256 updatedAt
     );
 
+    /// @notice Emitted when the ANS state transitions
+    event StateChanged(
+        uint8 indexed fromState,
+        uint8 indexed toState,
+        uint256 vti,
+        uint256 timestamp
+    );
+
     /// @notice Emitted when reflex arc triggers capability revocation
     event ReflexTriggered(
         uint256 indexed executorId,
         string reason,
         uint256 revokedCount,
-        uint256 triggeredAt
+        uint256 triggeredAt,
+        uint256[] revokedTokens
     );
 }
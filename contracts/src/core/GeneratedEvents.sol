@@ -12,4 +12,6 @@ event AEPPosted(uint256 indexed executorId, bytes32 stateRootSha256, bytes32 sta
 
 event VagalToneUpdated(uint256 indexed tone, uint8 indexed state, uint256 updatedAt);
 
-event ReflexTriggered(uint256 indexed executorId, string reason, uint256 revokedCount, uint256 triggeredAt);
+event StateChanged(uint8 indexed fromState, uint8 indexed toState, uint256 vti, uint256 timestamp);
+
+event ReflexTriggered(uint256 indexed executorId, string reason, uint256 revokedCount, uint256 triggeredAt, uint256[] revokedTokens);
@@ -28,6 +28,7 @@ struct Intent {
     uint256 maxEnergyJ;
     address planner;
     uint256 nonce;
+    bool isEscape;
 }
 
 struct TokenMeta {
@@ -39,6 +40,7 @@ struct TokenMeta {
     uint256 expiresAt;
     bool revoked;
     uint256 revokedAt;
+    bool isEscape;
 }
 
 struct Guard {
@@ -73,10 +75,13 @@ error NonceAlreadyUsed();
 error TokenNotFound(uint256 tokenId);
 error TokenAlreadyRevoked(uint256 tokenId);
 error UnauthorizedRevocation();
+error TooManyActiveTokens(uint256 executorId, uint256 maxActive);
+error ExecutorPaused(uint256 executorId);
 error ANSBlocked(string reason);
 error ANSLimitExceeded(string field, uint256 requested, uint256 allowed);
 error UnauthorizedAttestor();
 error InvalidEvidenceFormat();
+error InsufficientAttestations(uint256 provided, uint256 required);
 error RateLimited(string limiterId, uint256 retryAfter);
 error CircuitBreakerOpen(string breakerId, uint256 retryAfter);
 error CBORHashMismatch(bytes32 expectedSha256, bytes32 actualSha256, bytes32 expectedKeccak, bytes32 actualKeccak);
@@ -15,8 +15,9 @@ interface IANSStateManager {
 interface ICapabilityIssuer {
     function activeTokensOf(uint256 executorId) external view returns (uint256[] memory);
     function isValid(uint256 tokenId) external view returns (bool);
+    function ownerOf(uint256 tokenId) external view returns (address);
     function revoke(uint256 tokenId, uint8 reason) external;
-    function issueCapability(Types.Intent calldata intent, bytes32 scaledLimitsHash) external returns (uint256 tokenId);
+    function issueCapability(Types.Intent calldata intent, bytes32 scaledLimitsHash, uint256 expiresAt) external returns (uint256 tokenId);
 }
 
 /// @title AfferentInbox Interface
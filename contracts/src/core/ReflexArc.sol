@@ -163,7 +163,8 @@ contract ReflexArc is Events {
                 executorId,
                 reason,
                 revokedCount,
-                block.timestamp
+                block.timestamp,
+                actualRevokedTokens
             );
         }
     }
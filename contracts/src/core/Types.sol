@@ -4,19 +4,21 @@ pragma solidity ^0.8.24;
 /// @title Core Types for Vagus Protocol
 /// @notice Defines fundamental data structures used across the Vagus ecosystem
 library Types {
-    // Core Intent structure
+    // Core Intent structure; ABI-identical to Intent in GeneratedTypes.sol
+    // (spec/types.yml), which off-chain clients encode against
     struct Intent {
         uint256 executorId;
         bytes32 actionId;
         bytes params;
         bytes32 envelopeHash;
         bytes32 preStateRoot;
-        uint64 notBefore;
-        uint64 notAfter;
-        uint32 maxDurationMs;
-        uint32 maxEnergyJ;
+        uint256 notBefore;
+        uint256 notAfter;
+        uint256 maxDurationMs;
+        uint256 maxEnergyJ;
         address planner;
         uint256 nonce;
+        bool isEscape;
     }
 
     // Capability Token metadata
@@ -24,10 +26,11 @@ library Types {
         uint256 executorId;
         bytes32 actionId;
         bytes32 scaledLimitsHash;
-        uint64 issuedAt;
-        uint64 expiresAt;
+        uint256 issuedAt;
+        uint256 expiresAt;
         bool revoked;
         address issuer;
+        bool isEscape;
     }
 
     // Constants
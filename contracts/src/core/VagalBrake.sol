@@ -36,8 +36,14 @@ contract VagalBrake is Events {
 
     /// @notice Issue capability with vagal brake applied
     /// @param intent The intent to process
+    /// @param scaledLimitsHash Hash of the scaled limits, checked by the issuer
+    /// @param expiresAt Token expiry, no later than the intent's notAfter
     /// @return tokenId The issued capability token ID
-    function issueWithBrake(Types.Intent calldata intent) external returns (uint256 tokenId) {
+    function issueWithBrake(
+        Types.Intent calldata intent,
+        bytes32 scaledLimitsHash,
+        uint256 expiresAt
+    ) external returns (uint256 tokenId) {
         // Get guard information from ANS State Manager
         (uint256 scalingFactor, bool allowed) = IANSStateManager(ansStateManager).guardFor(intent.executorId, intent.actionId);
 
@@ -46,11 +52,12 @@ contract VagalBrake is Events {
             revert ANSBlocked("ANS:blocked");
         }
 
-        // Scale brakeable parameters and validate limits
-        bytes32 scaledLimitsHash = _scaleAndValidateIntent(intent, scalingFactor);
+        // Scale brakeable parameters and validate limits; the issuer checks the
+        // caller's scaledLimitsHash against the same computation
+        _scaleAndValidateIntent(intent, scalingFactor);
 
         // Issue capability through the issuer
-        tokenId = ICapabilityIssuer(capabilityIssuer).issueCapability(intent, scaledLimitsHash);
+        tokenId = ICapabilityIssuer(capabilityIssuer).issueCapability(intent, scaledLimitsHash, expiresAt);
 
         return tokenId;
     }
@@ -86,8 +93,8 @@ contract VagalBrake is Events {
         // For now, we assume the intent parameters are already validated and just hash them
 
         // Apply scaling to brakeable limits using safe arithmetic
-        uint256 scaledMaxDuration = (uint256(intent.maxDurationMs) * scalingFactor) / 10000;
-        uint256 scaledMaxEnergy = (uint256(intent.maxEnergyJ) * scalingFactor) / 10000;
+        uint256 scaledMaxDuration = (intent.maxDurationMs * scalingFactor) / 10000;
+        uint256 scaledMaxEnergy = (intent.maxEnergyJ * scalingFactor) / 10000;
 
         // Validate against absolute limits
         if (scaledMaxDuration > Types.MAX_DURATION_MS) {
@@ -112,8 +119,8 @@ contract VagalBrake is Events {
     /// @return scaledLimitsHash Hash of scaled limits
     function _scaleAndValidateIntentPreview(Types.Intent calldata intent, uint256 scalingFactor) internal pure returns (bytes32) {
         // Apply scaling to brakeable limits using safe arithmetic
-        uint256 scaledMaxDuration = (uint256(intent.maxDurationMs) * scalingFactor) / 10000;
-        uint256 scaledMaxEnergy = (uint256(intent.maxEnergyJ) * scalingFactor) / 10000;
+        uint256 scaledMaxDuration = (intent.maxDurationMs * scalingFactor) / 10000;
+        uint256 scaledMaxEnergy = (intent.maxEnergyJ * scalingFactor) / 10000;
 
         // For preview, we don't revert on limits - just return the hash
         return keccak256(abi.encodePacked(
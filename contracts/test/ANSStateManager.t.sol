@@ -116,4 +116,24 @@ contract ANSStateManagerTest is Test {
         assertEq(scalingFactor, 0); // 0%
         assertFalse(allowed);
     }
+
+    function testStateChangedReportsVtiInBasisPoints() public {
+        vm.recordLogs();
+        ans.updateTone(EXECUTOR_ID, 350000); // 35% - reading 1
+        ans.updateTone(EXECUTOR_ID, 350000); // 35% - reading 2
+        ans.updateTone(EXECUTOR_ID, 350000); // 35% - reading 3 -> DANGER
+
+        Vm.Log[] memory logs = vm.getRecordedLogs();
+        bytes32 stateChanged = keccak256("StateChanged(uint8,uint8,uint256,uint256)");
+        uint256 found;
+        for (uint256 i = 0; i < logs.length; i++) {
+            if (logs[i].topics[0] != stateChanged) continue;
+            (uint256 vti,) = abi.decode(logs[i].data, (uint256, uint256));
+            // A 35% danger tone is a VTI of 6500 bps, as on CosmWasm
+            assertEq(vti, 6500);
+            assertEq(uint256(logs[i].topics[2]), 1); // DANGER
+            found++;
+        }
+        assertEq(found, 1);
+    }
 }
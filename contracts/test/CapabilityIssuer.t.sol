@@ -19,7 +19,7 @@ contract CapabilityIssuerTest is Test {
     function setUp() public {
         inbox = new AfferentInbox();
         ans = new ANSStateManager();
-        issuer = new CapabilityIssuer(address(inbox), address(0)); // Will set vagalBrake later
+        issuer = new CapabilityIssuer(address(inbox), address(0), address(ans), address(0)); // Will set vagalBrake later
         brake = new VagalBrake(address(ans), address(issuer));
         issuer.setVagalBrake(address(brake));
     }
@@ -36,14 +36,15 @@ contract CapabilityIssuerTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         assertEq(tokenId, 1);
         assertTrue(issuer.isValid(tokenId));
@@ -54,6 +55,7 @@ contract CapabilityIssuerTest is Test {
         assertEq(meta.actionId, intent.actionId);
         assertEq(meta.scaledLimitsHash, scaledLimitsHash);
         assertEq(meta.issuer, address(this));
+        assertEq(issuer.ownerOf(tokenId), user);
 
         // Check active tokens
         uint256[] memory active = issuer.activeTokensOf(42);
@@ -74,14 +76,15 @@ contract CapabilityIssuerTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // Revoke it
         issuer.revoke(tokenId, 1);
@@ -105,7 +108,8 @@ contract CapabilityIssuerTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
@@ -113,7 +117,7 @@ contract CapabilityIssuerTest is Test {
         require(allowed, "Brake should allow this intent");
 
         vm.expectRevert(abi.encodeWithSignature("IntentExpired()"));
-        issuer.issueCapability(intent, scaledLimitsHash);
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
     }
 
     function testIsValidExpired() public {
@@ -128,18 +132,194 @@ contract CapabilityIssuerTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // Fast forward time
         vm.warp(block.timestamp + 2);
 
         assertFalse(issuer.isValid(tokenId));
     }
+
+    function testIssueCapabilityHonorsRequestedExpiry() public {
+        Types.Intent memory intent = Types.Intent({
+            executorId: 42,
+            actionId: keccak256("test_action"),
+            params: "",
+            envelopeHash: keccak256("envelope"),
+            preStateRoot: bytes32(0),
+            notBefore: uint64(block.timestamp),
+            notAfter: uint64(block.timestamp + 3600),
+            maxDurationMs: 1000,
+            maxEnergyJ: 500,
+            planner: user,
+            nonce: 1,
+            isEscape: false
+        });
+
+        (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
+        require(allowed, "Brake should allow this intent");
+
+        // A token may not outlive its intent
+        vm.expectRevert(abi.encodeWithSignature("InvalidInput(string)", "expiresAt outside intent window"));
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter + 1);
+
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, block.timestamp + 600);
+        assertEq(issuer.getTokenMeta(tokenId).expiresAt, block.timestamp + 600);
+
+        vm.warp(block.timestamp + 601);
+        assertFalse(issuer.isValid(tokenId));
+    }
+
+    function testOnlyEscapeTokensValidDuringShutdown() public {
+        Types.Intent memory intent = Types.Intent({
+            executorId: 42,
+            actionId: keccak256("test_action"),
+            params: "",
+            envelopeHash: keccak256("envelope"),
+            preStateRoot: bytes32(0),
+            notBefore: uint64(block.timestamp),
+            notAfter: uint64(block.timestamp + 3600),
+            maxDurationMs: 1000,
+            maxEnergyJ: 500,
+            planner: user,
+            nonce: 1,
+            isEscape: false
+        });
+        (bytes32 scaledLimitsHash,) = brake.previewBrake(intent);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+
+        intent.actionId = keccak256("retreat");
+        intent.nonce = 2;
+        intent.isEscape = true;
+        (bytes32 escapeLimitsHash,) = brake.previewBrake(intent);
+        uint256 escapeTokenId = issuer.issueCapability(intent, escapeLimitsHash, intent.notAfter);
+        assertTrue(issuer.getTokenMeta(escapeTokenId).isEscape);
+        assertFalse(issuer.getTokenMeta(tokenId).isEscape);
+
+        // Executor 42 enters SHUTDOWN
+        vm.mockCall(
+            address(ans),
+            abi.encodeWithSelector(ans.getExecutorState.selector, uint256(42)),
+            abi.encode(uint8(2), uint32(1_000_000), uint64(block.timestamp))
+        );
+
+        assertFalse(issuer.isValid(tokenId));
+        assertTrue(issuer.isValid(escapeTokenId));
+    }
+
+    function testPausedExecutorIsBlockedWhileOthersIssue() public {
+        Types.Intent memory intent = Types.Intent({
+            executorId: 42,
+            actionId: keccak256("test_action"),
+            params: "",
+            envelopeHash: keccak256("envelope"),
+            preStateRoot: bytes32(0),
+            notBefore: uint64(block.timestamp),
+            notAfter: uint64(block.timestamp + 3600),
+            maxDurationMs: 1000,
+            maxEnergyJ: 500,
+            planner: user,
+            nonce: 1,
+            isEscape: false
+        });
+        (bytes32 scaledLimitsHash,) = brake.previewBrake(intent);
+
+        // Only the owner may pause an executor
+        vm.prank(user);
+        vm.expectRevert("Only owner can pause executors");
+        issuer.pauseExecutor(42);
+
+        issuer.pauseExecutor(42);
+        assertTrue(issuer.executorPaused(42));
+        vm.expectRevert(abi.encodeWithSignature("ExecutorPaused(uint256)", uint256(42)));
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+
+        // Another executor keeps issuing normally
+        intent.executorId = 43;
+        (bytes32 otherLimitsHash,) = brake.previewBrake(intent);
+        uint256 tokenId = issuer.issueCapability(intent, otherLimitsHash, intent.notAfter);
+        assertTrue(issuer.isValid(tokenId));
+
+        issuer.unpauseExecutor(42);
+        intent.executorId = 42;
+        intent.nonce = 2;
+        (scaledLimitsHash,) = brake.previewBrake(intent);
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+        assertEq(issuer.activeTokensOf(42).length, 1);
+    }
+
+    function testNonceIsUsedOncePerPlanner() public {
+        Types.Intent memory intent = Types.Intent({
+            executorId: 42,
+            actionId: keccak256("test_action"),
+            params: "",
+            envelopeHash: keccak256("envelope"),
+            preStateRoot: bytes32(0),
+            notBefore: uint64(block.timestamp),
+            notAfter: uint64(block.timestamp + 3600),
+            maxDurationMs: 1000,
+            maxEnergyJ: 500,
+            planner: user,
+            nonce: 1,
+            isEscape: false
+        });
+        (bytes32 scaledLimitsHash,) = brake.previewBrake(intent);
+
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+        assertTrue(issuer.usedNonces(user, 1));
+
+        // Replaying the intent is rejected
+        vm.expectRevert(abi.encodeWithSignature("NonceAlreadyUsed()"));
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+
+        // The same nonce is free for another planner
+        intent.planner = address(0x456);
+        (scaledLimitsHash,) = brake.previewBrake(intent);
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+        assertEq(issuer.activeTokensOf(42).length, 2);
+    }
+
+    function testIssuanceWindowBoundaries() public {
+        Types.Intent memory intent = Types.Intent({
+            executorId: 42,
+            actionId: keccak256("test_action"),
+            params: "",
+            envelopeHash: keccak256("envelope"),
+            preStateRoot: bytes32(0),
+            notBefore: uint64(block.timestamp),
+            notAfter: uint64(block.timestamp + 3600),
+            maxDurationMs: 1000,
+            maxEnergyJ: 500,
+            planner: user,
+            nonce: 1,
+            isEscape: false
+        });
+        (bytes32 scaledLimitsHash,) = brake.previewBrake(intent);
+
+        // A token that is already expired
+        vm.expectRevert(abi.encodeWithSignature("InvalidInput(string)", "expiresAt outside intent window"));
+        issuer.issueCapability(intent, scaledLimitsHash, block.timestamp);
+
+        // An inverted authorization window
+        uint256 notBefore = intent.notBefore;
+        intent.notBefore = intent.notAfter + 1;
+        vm.expectRevert(abi.encodeWithSignature("InvalidInput(string)", "notBefore after notAfter"));
+        issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+        intent.notBefore = notBefore;
+
+        // Both ends of the valid range are accepted
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, block.timestamp + 1);
+        assertEq(issuer.getTokenMeta(tokenId).expiresAt, block.timestamp + 1);
+        intent.nonce = 2;
+        tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
+        assertEq(issuer.getTokenMeta(tokenId).expiresAt, intent.notAfter);
+    }
 }
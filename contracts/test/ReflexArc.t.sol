@@ -21,7 +21,7 @@ contract ReflexArcTest is Test {
     function setUp() public {
         inbox = new AfferentInbox();
         ans = new ANSStateManager();
-        issuer = new CapabilityIssuer(address(inbox), address(0)); // Will set vagalBrake later
+        issuer = new CapabilityIssuer(address(inbox), address(0), address(ans), address(0)); // Will set vagalBrake later
         brake = new VagalBrake(address(ans), address(issuer));
         issuer.setVagalBrake(address(brake));
         reflex = new ReflexArc(address(inbox), address(issuer));
@@ -46,14 +46,15 @@ contract ReflexArcTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: address(this),
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // The reflex contract owner is the deployer (address(this) in setUp)
         // So manualTrigger should work
@@ -82,19 +83,20 @@ contract ReflexArcTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: address(this),
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId1 = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId1 = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
         intent.nonce = 2;
         (scaledLimitsHash, allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId2 = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId2 = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // Verify tokens are active
         assertTrue(issuer.isValid(tokenId1));
@@ -131,14 +133,15 @@ contract ReflexArcTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: address(this),
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
                 // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // First trigger should work
         reflex.on_aep(999);
@@ -149,7 +152,7 @@ contract ReflexArcTest is Test {
         (scaledLimitsHash, allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId2 = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId2 = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // Advance time past cooldown
         vm.warp(block.timestamp + 31);
@@ -181,14 +184,15 @@ contract ReflexArcTest is Test {
             maxDurationMs: 1000,
             maxEnergyJ: 500,
             planner: address(this),
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
                 // Get the correct scaled limits hash from VagalBrake
         (bytes32 scaledLimitsHash, bool allowed) = brake.previewBrake(intent);
         require(allowed, "Brake should allow this intent");
 
-        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash);
+        uint256 tokenId = issuer.issueCapability(intent, scaledLimitsHash, intent.notAfter);
 
         // Analysis should not trigger reflex
         reflex.on_aep(42);
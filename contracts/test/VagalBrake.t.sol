@@ -20,7 +20,7 @@ contract VagalBrakeTest is Test {
     function setUp() public {
         inbox = new AfferentInbox();
         ans = new ANSStateManager();
-        issuer = new CapabilityIssuer(address(inbox), address(0)); // Will set vagalBrake later
+        issuer = new CapabilityIssuer(address(inbox), address(0), address(ans), address(0)); // Will set vagalBrake later
         brake = new VagalBrake(address(ans), address(issuer));
         issuer.setVagalBrake(address(brake));
     }
@@ -37,7 +37,8 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 2000, // Will be scaled to 2000 (no scaling in SAFE)
             maxEnergyJ: 800,     // Will be scaled to 800
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         (bytes32 limitsHash, bool allowed) = brake.previewBrake(intent);
@@ -62,7 +63,8 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 2000, // Will be scaled to 1200 (60%)
             maxEnergyJ: 800,     // Will be scaled to 480 (60%)
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         (bytes32 limitsHash, bool allowed) = brake.previewBrake(intent);
@@ -94,7 +96,8 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 2000,
             maxEnergyJ: 800,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         (bytes32 limitsHash, bool allowed) = brake.previewBrake(intent);
@@ -115,10 +118,12 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 2000,
             maxEnergyJ: 800,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
-        uint256 tokenId = brake.issueWithBrake(intent);
+        (bytes32 limitsHash, ) = brake.previewBrake(intent);
+        uint256 tokenId = brake.issueWithBrake(intent, limitsHash, intent.notAfter);
 
         assertEq(tokenId, 1);
         assertTrue(issuer.isValid(tokenId));
@@ -147,11 +152,12 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 2000,
             maxEnergyJ: 800,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         vm.expectRevert(abi.encodeWithSelector(VagalBrake.ANSBlocked.selector, "ANS:blocked"));
-        brake.issueWithBrake(intent);
+        brake.issueWithBrake(intent, bytes32(0), intent.notAfter);
     }
 
     function testLimitExceeded() public {
@@ -172,10 +178,11 @@ contract VagalBrakeTest is Test {
             maxDurationMs: 60000, // 60000 * 0.6 = 36000 > MAX_DURATION_MS (30000)
             maxEnergyJ: 800,
             planner: user,
-            nonce: 1
+            nonce: 1,
+            isEscape: false
         });
 
         vm.expectRevert(abi.encodeWithSelector(VagalBrake.ANSLimitExceeded.selector, "maxDurationMs", uint256(36000), uint256(30000)));
-        brake.issueWithBrake(intent);
+        brake.issueWithBrake(intent, bytes32(0), intent.notAfter);
     }
 }
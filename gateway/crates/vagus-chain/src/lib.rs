@@ -4,12 +4,16 @@
 //! Supports submitting AEP, issuing capabilities, revoking tokens, and subscribing to events.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use anyhow::Result;
 use thiserror::Error;
 
-pub use vagus_telemetry::AfferentEvidencePacket;
-pub use vagus_spec::{Intent, TokenMeta, ANSState, Guard, VagusError};
+pub use vagus_telemetry::{AfferentEvidencePacket, METRICS_SCHEMA_VERSION};
+pub use vagus_spec::{Intent, TokenMeta, ANSState, Guard, ScalingFactor, VagusError, Vti};
 
 /// Core chain client operations (dyn compatible)
 #[async_trait::async_trait]
@@ -28,39 +32,216 @@ pub trait ChainClient: Send + Sync {
     /// Revoke capability token
     async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()>;
 
-    /// Get current ANS guard for action
-    async fn get_guard(&self, action_id: &[u8; 32]) -> Result<Guard>;
+    /// Planner owning a capability token, or None if the token does not exist
+    async fn token_owner(&self, token_id: &str) -> Result<Option<String>>;
+
+    /// Whether a capability token exists and is neither revoked nor expired
+    async fn is_valid(&self, token_id: &str) -> Result<bool>;
+
+    /// Ids of the capability tokens an executor currently holds
+    async fn active_tokens_of(&self, executor_id: u64) -> Result<Vec<String>>;
+
+    /// Get the current ANS guard of an executor for an action
+    async fn get_guard(&self, executor_id: u64, action_id: &[u8; 32]) -> Result<Guard>;
 
     /// Get current ANS state
     async fn get_ans_state(&self) -> Result<ANSState>;
 
-    /// Update ANS tone and state
-    async fn update_tone(&self, vti: u64, suggested_state: ANSState) -> Result<()>;
-}
+    /// Update an executor's ANS tone and state. The Cosmos manager keeps a
+    /// single tone shared by every executor, so there any executor updates it.
+    async fn update_tone(&self, executor_id: u64, vti: Vti, suggested_state: ANSState) -> Result<()>;
 
-/// Event subscription capability (separate trait for dyn compatibility)
-#[async_trait::async_trait]
-pub trait EventSubscriber: Send + Sync {
     /// Subscribe to chain events
-    async fn subscribe_events<F>(&self, callback: F) -> Result<()>
-    where
-        F: Fn(Event) + Send + Sync + 'static;
+    async fn subscribe_events(&self, callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()>;
+
+    /// Fetch past events in an inclusive block range, optionally restricted to
+    /// the given event names (empty means all Vagus events)
+    async fn get_events_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        event_names: &[&str],
+    ) -> Result<Vec<Event>>;
+
+    /// Current block height; doubles as a readiness probe for the connection
+    async fn health(&self) -> Result<u64>;
+
+    /// Clone into a new boxed client sharing the same connection
+    fn clone_box(&self) -> Box<dyn ChainClient>;
+}
+
+impl Clone for Box<dyn ChainClient> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
 
 /// Chain types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ChainType {
     EVM,
     Cosmos,
 }
 
+impl FromStr for ChainType {
+    type Err = ChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "evm" => Ok(ChainType::EVM),
+            "cosmos" => Ok(ChainType::Cosmos),
+            _ => Err(ChainError::Config(format!("Unsupported chain type: {}", s))),
+        }
+    }
+}
+
 /// Configuration for chain clients
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChainConfig {
     pub chain_type: ChainType,
     pub rpc_url: String,
     pub contract_addresses: HashMap<String, String>,
     pub private_key: Option<String>,
+    /// EIP-1559 fee cap in wei (EVM only; provider estimate when unset)
+    pub max_fee_per_gas: Option<u64>,
+    /// EIP-1559 priority fee in wei (EVM only; provider estimate when unset)
+    pub max_priority_fee_per_gas: Option<u64>,
+    /// Factor applied to estimated gas limits (EVM only; 1.0 when unset)
+    pub gas_limit_multiplier: Option<f64>,
+    /// WebSocket endpoint (Cosmos only; `rpc_url` over ws at `/websocket` when unset)
+    pub ws_url: Option<String>,
+    /// Denom transaction fees are paid in (Cosmos only; `stake` when unset)
+    pub fee_denom: Option<String>,
+    /// Fee per unit of gas in `fee_denom` (Cosmos only; 0 when unset)
+    pub gas_price: Option<f64>,
+    /// Gas limit of every transaction (Cosmos only; 500000 when unset)
+    pub gas_limit: Option<u64>,
+}
+
+impl fmt::Debug for ChainConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainConfig")
+            .field("chain_type", &self.chain_type)
+            .field("rpc_url", &self.rpc_url)
+            .field("contract_addresses", &self.contract_addresses)
+            .field("private_key", &self.private_key.as_ref().map(|_| "<redacted>"))
+            .field("max_fee_per_gas", &self.max_fee_per_gas)
+            .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
+            .field("gas_limit_multiplier", &self.gas_limit_multiplier)
+            .field("ws_url", &self.ws_url)
+            .field("fee_denom", &self.fee_denom)
+            .field("gas_price", &self.gas_price)
+            .field("gas_limit", &self.gas_limit)
+            .finish()
+    }
+}
+
+/// `ChainConfig` as written in a TOML file, where the key may instead be
+/// referenced by the name of the environment variable holding it
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChainConfigFile {
+    chain_type: ChainType,
+    rpc_url: String,
+    #[serde(default)]
+    contract_addresses: HashMap<String, String>,
+    private_key: Option<String>,
+    private_key_env: Option<String>,
+    max_fee_per_gas: Option<u64>,
+    max_priority_fee_per_gas: Option<u64>,
+    gas_limit_multiplier: Option<f64>,
+    ws_url: Option<String>,
+    fee_denom: Option<String>,
+    gas_price: Option<f64>,
+    gas_limit: Option<u64>,
+}
+
+impl ChainConfig {
+    /// Load a config from a TOML file with the `ChainConfig` fields, plus an
+    /// optional `private_key_env` naming the variable to read the key from
+    pub fn from_toml(path: impl AsRef<Path>) -> Result<Self, ChainError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| ChainError::Config(format!("Cannot read {}: {}", path.display(), e)))?;
+        let file: ChainConfigFile = toml::from_str(&contents)
+            .map_err(|e| ChainError::Config(format!("Invalid chain config {}: {}", path.display(), e)))?;
+
+        Ok(ChainConfig {
+            chain_type: file.chain_type,
+            rpc_url: file.rpc_url,
+            contract_addresses: file.contract_addresses,
+            private_key: resolve_private_key(file.private_key, file.private_key_env)?,
+            max_fee_per_gas: file.max_fee_per_gas,
+            max_priority_fee_per_gas: file.max_priority_fee_per_gas,
+            gas_limit_multiplier: file.gas_limit_multiplier,
+            ws_url: file.ws_url,
+            fee_denom: file.fee_denom,
+            gas_price: file.gas_price,
+            gas_limit: file.gas_limit,
+        })
+    }
+
+    /// Read a config from `{prefix}_CHAIN_TYPE` (`evm` or `cosmos`),
+    /// `{prefix}_RPC_URL` and one `{prefix}_CONTRACT_<NAME>` per contract,
+    /// stored under the lowercased name. `PRIVATE_KEY` or `PRIVATE_KEY_ENV`,
+    /// `MAX_FEE_PER_GAS`, `MAX_PRIORITY_FEE_PER_GAS`, `GAS_LIMIT_MULTIPLIER`,
+    /// `WS_URL`, `FEE_DENOM`, `GAS_PRICE` and `GAS_LIMIT` under the same
+    /// prefix are optional.
+    pub fn from_env(prefix: &str) -> Result<Self, ChainError> {
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+        let required = |name: &str| {
+            var(name).ok_or_else(|| ChainError::Config(format!("{}_{} is not set", prefix, name)))
+        };
+        let contract_prefix = format!("{}_CONTRACT_", prefix);
+        let contract_addresses = std::env::vars()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(&contract_prefix)
+                    .map(|name| (name.to_lowercase(), value))
+            })
+            .collect();
+
+        Ok(ChainConfig {
+            chain_type: required("CHAIN_TYPE")?.parse()?,
+            rpc_url: required("RPC_URL")?,
+            contract_addresses,
+            private_key: resolve_private_key(var("PRIVATE_KEY"), var("PRIVATE_KEY_ENV"))?,
+            max_fee_per_gas: parse_env_var(prefix, "MAX_FEE_PER_GAS")?,
+            max_priority_fee_per_gas: parse_env_var(prefix, "MAX_PRIORITY_FEE_PER_GAS")?,
+            gas_limit_multiplier: parse_env_var(prefix, "GAS_LIMIT_MULTIPLIER")?,
+            ws_url: var("WS_URL"),
+            fee_denom: var("FEE_DENOM"),
+            gas_price: parse_env_var(prefix, "GAS_PRICE")?,
+            gas_limit: parse_env_var(prefix, "GAS_LIMIT")?,
+        })
+    }
+}
+
+/// `{prefix}_{name}` parsed as `T`, if set
+fn parse_env_var<T: FromStr>(prefix: &str, name: &str) -> Result<Option<T>, ChainError> {
+    let key = format!("{}_{}", prefix, name);
+    match std::env::var(&key) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| ChainError::Config(format!("Invalid {}: {}", key, value))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// The key given inline, or read from the environment variable it references
+fn resolve_private_key(
+    private_key: Option<String>,
+    private_key_env: Option<String>,
+) -> Result<Option<String>, ChainError> {
+    match (private_key, private_key_env) {
+        (Some(_), Some(_)) => Err(ChainError::Config(
+            "Set either a private key or a private key variable, not both".to_string(),
+        )),
+        (None, Some(name)) => std::env::var(&name)
+            .map(Some)
+            .map_err(|_| ChainError::Config(format!("Private key variable {} is not set", name))),
+        (private_key, None) => Ok(private_key),
+    }
 }
 
 /// Chain client factory
@@ -72,8 +253,10 @@ impl ChainClientFactory {
             ChainType::EVM => {
                 #[cfg(feature = "evm")]
                 {
-                    let client = EVMClient::new(config).await?;
-                    Ok(Box::new(client))
+                    match EvmTransport::from_url(&config.rpc_url)? {
+                        EvmTransport::Ws => Ok(Box::new(EVMClient::new(config).await?)),
+                        EvmTransport::Http => Ok(Box::new(EVMClient::new_http(config).await?)),
+                    }
                 }
                 #[cfg(not(feature = "evm"))]
                 {
@@ -95,6 +278,21 @@ impl ChainClientFactory {
     }
 }
 
+/// Tone of the EVM ANS manager for a VTI. The EVM manager takes danger in
+/// ppm (higher is more dangerous); the spec VTI is basis points, higher safer.
+pub fn evm_tone_from_vti(vti: Vti) -> u32 {
+    ((10_000 - vti.get()) * 100) as u32
+}
+
+/// VTI for a tone reported by the EVM ANS manager, rounded to the nearest
+/// basis point
+pub fn vti_from_evm_tone(tone_ppm: u64) -> Result<Vti, ChainError> {
+    if tone_ppm > 1_000_000 {
+        return Err(ChainError::Contract(format!("EVM tone out of range: {} ppm", tone_ppm)));
+    }
+    Vti::new(10_000 - (tone_ppm + 50) / 100).map_err(|e| ChainError::Contract(e.to_string()))
+}
+
 /// Unified event representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
@@ -112,27 +310,401 @@ pub struct Event {
 #[cfg(feature = "evm")]
 pub mod evm {
     use super::*;
+    use std::sync::Arc;
     use ethers::{
-        providers::{Provider, Ws},
+        providers::{Http, JsonRpcClient, Middleware, Provider, StreamExt, Ws},
         signers::{LocalWallet, Signer},
         middleware::SignerMiddleware,
-        contract::Contract,
-        types::{Address, U256, H256},
+        contract::{abigen, ContractCall, ContractError, EthEvent},
+        abi::{Detokenize, HumanReadableParser, ParamType, RawLog, Token},
+        types::{
+            transaction::eip2718::TypedTransaction, Address, Bytes, Filter, Log, TransactionReceipt,
+            H256, I256, U256, U64,
+        },
     };
+    use futures::Stream;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::time::Duration;
+    use super::Intent;
+
+    abigen!(
+        AfferentInboxContract,
+        r#"[
+            function postAEP(uint256 executorId, bytes32 stateRoot, bytes32 metricsHash, bytes signature) external
+        ]"#,
+    );
+
+    abigen!(
+        ANSStateManagerContract,
+        r#"[
+            function guardFor(uint256 executorId, bytes32 actionId) external view returns (uint256 scalingFactor, bool allowed)
+            function getCurrentState() external view returns (uint8)
+            function updateTone(uint256 executorId, uint32 tone) external
+        ]"#,
+    );
+
+    // `Intent` mirrors contracts/src/core/GeneratedTypes.sol (spec/types.yml);
+    // a unit test re-derives the ABI from that file to keep them in step
+    abigen!(
+        VagalBrakeContract,
+        r#"[
+            struct Intent { uint256 executorId; bytes32 actionId; bytes params; bytes32 envelopeHash; bytes32 preStateRoot; uint256 notBefore; uint256 notAfter; uint256 maxDurationMs; uint256 maxEnergyJ; address planner; uint256 nonce; bool isEscape; }
+            function issueWithBrake(Intent intent, bytes32 scaledLimitsHash, uint256 expiresAt) external returns (uint256 tokenId)
+        ]"#,
+    );
+
+    abigen!(
+        CapabilityIssuerContract,
+        r#"[
+            function ownerOf(uint256 tokenId) external view returns (address)
+            function isValid(uint256 tokenId) external view returns (bool)
+            function activeTokensOf(uint256 executorId) external view returns (uint256[])
+            function revoke(uint256 tokenId, uint8 reason) external
+        ]"#,
+    );
+
+    // Event shapes the core contracts emit (contracts/src/core/Events.sol)
+    abigen!(
+        VagusEvents,
+        r#"[
+            event CapabilityIssued(uint256 indexed tokenId, uint256 indexed executorId, address indexed planner, bytes32 actionId, uint256 expiresAt, bytes32 paramsHashSha256, bytes32 paramsHashKeccak, bytes32 preStateRootSha256, bytes32 preStateRootKeccak)
+            event CapabilityRevoked(uint256 indexed tokenId, uint8 reason)
+            event AEPPosted(uint256 indexed executorId, bytes32 stateRoot, bytes32 metricsHash)
+            event VagalToneUpdated(uint256 indexed tone, uint8 indexed state, uint256 updatedAt)
+            event ReflexTriggered(uint256 indexed executorId, string reason, uint256 revokedCount, uint256 triggeredAt, uint256[] revokedTokens)
+        ]"#,
+    );
+
+    type EVMMiddleware<P> = SignerMiddleware<Provider<P>, LocalWallet>;
+
+    /// Vagus contract events, matched by their topic0 signature
+    const EVENT_NAMES: [&str; 5] = [
+        "CapabilityIssued",
+        "CapabilityRevoked",
+        "AEPPosted",
+        "VagalToneUpdated",
+        "ReflexTriggered",
+    ];
+
+    fn event_signature(name: &str) -> Option<H256> {
+        match name {
+            "CapabilityIssued" => Some(CapabilityIssuedFilter::signature()),
+            "CapabilityRevoked" => Some(CapabilityRevokedFilter::signature()),
+            "AEPPosted" => Some(AeppostedFilter::signature()),
+            "VagalToneUpdated" => Some(VagalToneUpdatedFilter::signature()),
+            "ReflexTriggered" => Some(ReflexTriggeredFilter::signature()),
+            _ => None,
+        }
+    }
+
+    /// Poll interval for log filters on HTTP providers
+    const HTTP_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// RPC transport, selected from the URL scheme
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EvmTransport {
+        Ws,
+        Http,
+    }
+
+    impl EvmTransport {
+        pub fn from_url(rpc_url: &str) -> Result<Self, ChainError> {
+            let scheme = rpc_url.split("://").next().unwrap_or_default();
+            match scheme.to_ascii_lowercase().as_str() {
+                "ws" | "wss" => Ok(EvmTransport::Ws),
+                "http" | "https" => Ok(EvmTransport::Http),
+                _ => Err(ChainError::Config(format!("Unsupported EVM RPC URL: {}", rpc_url))),
+            }
+        }
+    }
+
+    /// Transport-specific log streaming: pubsub subscriptions over WebSocket,
+    /// filter polling over HTTP
+    #[async_trait::async_trait]
+    pub trait LogTransport: JsonRpcClient + Clone + 'static {
+        async fn stream_logs(
+            provider: &Provider<Self>,
+            rpc_url: &str,
+            filter: &Filter,
+            on_log: &(dyn Fn(Log) + Send + Sync),
+        ) -> Result<()>;
+    }
 
-    pub struct EVMClient {
-        provider: SignerMiddleware<Provider<Ws>, LocalWallet>,
+    #[async_trait::async_trait]
+    impl LogTransport for Ws {
+        async fn stream_logs(
+            _provider: &Provider<Self>,
+            rpc_url: &str,
+            filter: &Filter,
+            on_log: &(dyn Fn(Log) + Send + Sync),
+        ) -> Result<()> {
+            // Subscriptions get their own connection so it can be re-established
+            // without disturbing the client's request provider
+            let rpc_url = rpc_url.to_string();
+            let connect = move || {
+                let rpc_url = rpc_url.clone();
+                async move { Ok(WsLogSource(Provider::<Ws>::connect(rpc_url).await?)) }
+            };
+            stream_with_reconnect(connect, filter, on_log, ReconnectPolicy::default()).await
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LogTransport for Http {
+        async fn stream_logs(
+            provider: &Provider<Self>,
+            _rpc_url: &str,
+            filter: &Filter,
+            on_log: &(dyn Fn(Log) + Send + Sync),
+        ) -> Result<()> {
+            let mut stream = provider.watch(filter).await?.interval(HTTP_POLL_INTERVAL);
+            while let Some(log) = stream.next().await {
+                on_log(log);
+            }
+            Ok(())
+        }
+    }
+
+    /// Backoff and give-up bounds for re-establishing WebSocket subscriptions
+    #[derive(Debug, Clone, Copy)]
+    pub struct ReconnectPolicy {
+        pub initial_backoff: Duration,
+        pub max_backoff: Duration,
+        /// Consecutive failed connection attempts before giving up
+        pub max_attempts: u32,
+    }
+
+    impl Default for ReconnectPolicy {
+        fn default() -> Self {
+            Self {
+                initial_backoff: Duration::from_millis(500),
+                max_backoff: Duration::from_secs(30),
+                max_attempts: 10,
+            }
+        }
+    }
+
+    /// A connection that can replay past logs and stream new ones
+    #[async_trait::async_trait]
+    pub(crate) trait LogSource: Send + Sync {
+        /// Current head block number
+        async fn block_number(&self) -> Result<u64>;
+
+        /// Logs matching `filter` from `from_block` through the current head
+        async fn logs_since(&self, filter: &Filter, from_block: u64) -> Result<Vec<Log>>;
+
+        /// Stream new logs until the connection drops
+        async fn subscribe(&self, filter: &Filter) -> Result<Pin<Box<dyn Stream<Item = Log> + Send + '_>>>;
+    }
+
+    struct WsLogSource(Provider<Ws>);
+
+    #[async_trait::async_trait]
+    impl LogSource for WsLogSource {
+        async fn block_number(&self) -> Result<u64> {
+            Ok(self.0.get_block_number().await?.as_u64())
+        }
+
+        async fn logs_since(&self, filter: &Filter, from_block: u64) -> Result<Vec<Log>> {
+            Ok(self.0.get_logs(&filter.clone().from_block(from_block)).await?)
+        }
+
+        async fn subscribe(&self, filter: &Filter) -> Result<Pin<Box<dyn Stream<Item = Log> + Send + '_>>> {
+            Ok(Box::pin(self.0.subscribe_logs(filter).await?))
+        }
+    }
+
+    /// Forward a log unless it is at or before the last delivered position
+    fn deliver_log(log: Log, cursor: &mut Option<(u64, u64)>, on_log: &(dyn Fn(Log) + Send + Sync)) {
+        let position = log
+            .block_number
+            .zip(log.log_index)
+            .map(|(block, index)| (block.as_u64(), index.as_u64()));
+
+        if let (Some(position), Some(last)) = (position, *cursor) {
+            if position <= last {
+                return;
+            }
+        }
+        if position.is_some() {
+            *cursor = position;
+        }
+        on_log(log);
+    }
+
+    /// Stream logs, reconnecting with exponential backoff whenever the source
+    /// drops and backfilling from the last delivered block so no events are
+    /// missed across the gap. Until a log is delivered, backfills start after
+    /// the head seen by the first subscription. Gives up after `max_attempts`
+    /// consecutive failed connection attempts.
+    pub(crate) async fn stream_with_reconnect<S, C, Fut>(
+        mut connect: C,
+        filter: &Filter,
+        on_log: &(dyn Fn(Log) + Send + Sync),
+        policy: ReconnectPolicy,
+    ) -> Result<()>
+    where
+        S: LogSource,
+        C: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<S>> + Send,
+    {
+        let mut cursor: Option<(u64, u64)> = None;
+        // First block the stream is responsible for, fixed at the first subscription
+        let mut start_block: Option<u64> = None;
+        let mut backoff = policy.initial_backoff;
+        let mut failures = 0u32;
+
+        loop {
+            let source = match connect().await {
+                Ok(source) => {
+                    failures = 0;
+                    backoff = policy.initial_backoff;
+                    source
+                }
+                Err(e) => {
+                    failures += 1;
+                    if failures >= policy.max_attempts {
+                        return Err(e.context(format!(
+                            "EVM log subscription failed to reconnect after {} attempts",
+                            failures
+                        )));
+                    }
+                    tracing::warn!("EVM log subscription connect failed ({}), retrying in {:?}", e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(policy.max_backoff);
+                    continue;
+                }
+            };
+
+            // Subscribe before backfilling so logs landing in between are
+            // buffered on the stream rather than lost; duplicates are skipped
+            match source.subscribe(filter).await {
+                Ok(mut stream) => {
+                    let from_block = match (cursor, start_block) {
+                        (Some((last_block, _)), _) => Ok(last_block),
+                        (None, Some(start)) => Ok(start),
+                        (None, None) => source.block_number().await.map(|head| head + 1),
+                    };
+                    let backfill = match from_block {
+                        Ok(from_block) => {
+                            start_block.get_or_insert(from_block);
+                            source.logs_since(filter, from_block).await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match backfill {
+                        Ok(logs) => {
+                            for log in logs {
+                                deliver_log(log, &mut cursor, on_log);
+                            }
+                            while let Some(log) = stream.next().await {
+                                deliver_log(log, &mut cursor, on_log);
+                            }
+                            tracing::warn!("EVM log subscription dropped, reconnecting");
+                        }
+                        Err(e) => tracing::warn!("EVM log backfill failed ({}), reconnecting", e),
+                    }
+                }
+                Err(e) => tracing::warn!("EVM log subscription failed ({}), reconnecting", e),
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(policy.max_backoff);
+        }
+    }
+
+    /// Fee and gas limit settings applied to every transaction the client sends
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub(crate) struct TxFees {
+        pub max_fee_per_gas: Option<U256>,
+        pub max_priority_fee_per_gas: Option<U256>,
+        pub gas_limit_multiplier: f64,
+    }
+
+    impl TxFees {
+        pub(crate) fn from_config(config: &ChainConfig) -> Result<Self, ChainError> {
+            let gas_limit_multiplier = config.gas_limit_multiplier.unwrap_or(1.0);
+            if !gas_limit_multiplier.is_finite() || gas_limit_multiplier < 1.0 {
+                return Err(ChainError::Config(format!(
+                    "gas_limit_multiplier must be at least 1.0, got {}",
+                    gas_limit_multiplier
+                )));
+            }
+            if let (Some(max_fee), Some(priority_fee)) =
+                (config.max_fee_per_gas, config.max_priority_fee_per_gas)
+            {
+                if priority_fee > max_fee {
+                    return Err(ChainError::Config(format!(
+                        "max_priority_fee_per_gas ({}) exceeds max_fee_per_gas ({})",
+                        priority_fee, max_fee
+                    )));
+                }
+            }
+
+            Ok(Self {
+                max_fee_per_gas: config.max_fee_per_gas.map(U256::from),
+                max_priority_fee_per_gas: config.max_priority_fee_per_gas.map(U256::from),
+                gas_limit_multiplier,
+            })
+        }
+
+        /// Set the gas limit from an estimate and pin any configured fee caps;
+        /// unset fees are left for the provider to fill in
+        pub(crate) fn apply(&self, tx: &mut TypedTransaction, estimated_gas: U256) {
+            // Scale in thousandths to stay in integer arithmetic
+            let per_mille = U256::from((self.gas_limit_multiplier * 1000.0).round() as u64);
+            tx.set_gas(estimated_gas * per_mille / 1000);
+
+            if let TypedTransaction::Eip1559(inner) = tx {
+                if let Some(max_fee) = self.max_fee_per_gas {
+                    inner.max_fee_per_gas = Some(max_fee);
+                }
+                if let Some(priority_fee) = self.max_priority_fee_per_gas {
+                    inner.max_priority_fee_per_gas = Some(priority_fee);
+                }
+            } else if let Some(max_fee) = self.max_fee_per_gas {
+                tx.set_gas_price(max_fee);
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct EVMClient<P: LogTransport = Ws> {
+        provider: Arc<EVMMiddleware<P>>,
+        rpc_url: String,
         contract_addresses: HashMap<String, Address>,
+        fees: TxFees,
     }
 
-    impl EVMClient {
+    impl EVMClient<Ws> {
+        /// Connect over WebSocket
         pub async fn new(config: ChainConfig) -> Result<Self> {
             let provider = Provider::<Ws>::connect(&config.rpc_url).await?;
+            Self::with_provider(provider, config).await
+        }
+    }
+
+    impl EVMClient<Http> {
+        /// Connect over HTTP; event subscriptions fall back to polling
+        pub async fn new_http(config: ChainConfig) -> Result<Self> {
+            let provider = Provider::<Http>::try_from(config.rpc_url.as_str())?;
+            Self::with_provider(provider, config).await
+        }
+    }
+
+    impl<P: LogTransport> EVMClient<P> {
+        async fn with_provider(provider: Provider<P>, config: ChainConfig) -> Result<Self> {
+            let fees = TxFees::from_config(&config)?;
             let wallet = config.private_key
                 .ok_or_else(|| anyhow::anyhow!("Private key required for EVM client"))?
                 .parse::<LocalWallet>()?;
 
-            let provider = SignerMiddleware::new(provider, wallet);
+            let chain_id = provider.get_chainid().await?;
+            let provider = Arc::new(SignerMiddleware::new(
+                provider,
+                wallet.with_chain_id(chain_id.as_u64()),
+            ));
 
             let mut contract_addresses = HashMap::new();
             for (name, addr_str) in config.contract_addresses {
@@ -142,16 +714,217 @@ pub mod evm {
 
             Ok(Self {
                 provider,
+                rpc_url: config.rpc_url,
                 contract_addresses,
+                fees,
             })
         }
+
+        fn contract_address(&self, name: &str) -> Result<Address, ChainError> {
+            self.contract_addresses
+                .get(name)
+                .copied()
+                .ok_or_else(|| ChainError::Config(format!("Missing contract address: {}", name)))
+        }
+    }
+
+    /// Spec integers are cosmwasm `Uint256`s; convert via their big-endian bytes
+    fn u256_from(be_bytes: [u8; 32]) -> U256 {
+        U256::from_big_endian(&be_bytes)
+    }
+
+    fn bytes32_from(value: &[u8], field: &str) -> Result<[u8; 32], ChainError> {
+        value
+            .try_into()
+            .map_err(|_| ChainError::Config(format!("{} must be 32 bytes, got {}", field, value.len())))
+    }
+
+    /// Decode the Solidity `uint8` state (0=SAFE, 1=DANGER, 2=SHUTDOWN)
+    fn ans_state_from(value: u8) -> Result<ANSState, ChainError> {
+        match value {
+            0 => Ok(ANSState::SAFE),
+            1 => Ok(ANSState::DANGER),
+            2 => Ok(ANSState::SHUTDOWN),
+            other => Err(ChainError::Contract(format!("Invalid ANS state value: {}", other))),
+        }
+    }
+
+    /// Convert a spec `Intent` into the Solidity `Intent` tuple
+    pub(crate) fn evm_intent(intent: &Intent) -> Result<vagal_brake_contract::Intent, ChainError> {
+        Ok(vagal_brake_contract::Intent {
+            executor_id: u256_from(intent.executorId.to_be_bytes()),
+            action_id: bytes32_from(&intent.actionId, "actionId")?,
+            params: Bytes::from(intent.params.to_vec()),
+            envelope_hash: bytes32_from(&intent.envelopeHash, "envelopeHash")?,
+            pre_state_root: bytes32_from(&intent.preStateRoot, "preStateRoot")?,
+            not_before: u256_from(intent.notBefore.to_be_bytes()),
+            not_after: u256_from(intent.notAfter.to_be_bytes()),
+            max_duration_ms: u256_from(intent.maxDurationMs.to_be_bytes()),
+            max_energy_j: u256_from(intent.maxEnergyJ.to_be_bytes()),
+            planner: intent
+                .planner
+                .parse()
+                .map_err(|_| ChainError::Config(format!("Invalid planner address: {}", intent.planner)))?,
+            nonce: u256_from(intent.nonce.to_be_bytes()),
+            is_escape: intent.isEscape,
+        })
+    }
+
+    /// Map a contract call failure to a `ChainError`, decoding revert data
+    fn contract_error<M: Middleware>(err: ContractError<M>) -> ChainError {
+        match err.as_revert() {
+            Some(data) => ChainError::from_revert_data(data),
+            None => ChainError::Rpc(err.to_string()),
+        }
+    }
+
+    /// Render a decoded ABI value the way it appears in Solidity
+    fn abi_value(token: &Token) -> String {
+        match token {
+            Token::Uint(value) => value.to_string(),
+            Token::Int(value) => I256::from_raw(*value).to_string(),
+            Token::Address(address) => format!("{:?}", address),
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+            // Comma-separated, as CosmWasm attributes carry arrays
+            Token::Array(items) | Token::FixedArray(items) => {
+                items.iter().map(abi_value).collect::<Vec<_>>().join(",")
+            }
+            other => other.to_string(),
+        }
+    }
+
+    impl ChainError {
+        /// Decode EVM revert data into `ChainError::Revert`. `Error(string)`,
+        /// `Panic(uint256)` and the portable Vagus errors get a readable reason;
+        /// other selectors are kept with no reason
+        pub fn from_revert_data(data: &[u8]) -> Self {
+            let Some(selector) = data.get(..4).and_then(|s| <[u8; 4]>::try_from(s).ok()) else {
+                return ChainError::Revert { selector: [0u8; 4], reason: None };
+            };
+            let args = &data[4..];
+
+            let reason = if selector == ERROR_STRING_SELECTOR {
+                match ethers::abi::decode(&[ParamType::String], args).ok().as_deref() {
+                    Some([Token::String(reason)]) => Some(reason.clone()),
+                    _ => None,
+                }
+            } else if selector == PANIC_SELECTOR {
+                match ethers::abi::decode(&[ParamType::Uint(256)], args).ok().as_deref() {
+                    Some([Token::Uint(code)]) => Some(format!("Panic(0x{:02x})", code)),
+                    _ => None,
+                }
+            } else {
+                SPEC_ERRORS
+                    .iter()
+                    .find(|(signature, _)| spec_selector(signature) == selector)
+                    .and_then(|(signature, _)| {
+                        let error = HumanReadableParser::parse_error(&format!("error {}", signature)).ok()?;
+                        let tokens = error.decode(args).ok()?;
+                        let args: Vec<String> = tokens.iter().map(abi_value).collect();
+                        Some(format!("{}({})", error.name, args.join(", ")))
+                    })
+            };
+
+            ChainError::Revert { selector, reason }
+        }
+    }
+
+    /// Convert a raw log into the chain-agnostic event shape
+    pub fn event_from_log(log: Log) -> Event {
+        let event_name = match log.topics.first() {
+            Some(topic) => EVENT_NAMES
+                .iter()
+                .find(|name| event_signature(name) == Some(*topic))
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("{:?}", topic)),
+            None => "Anonymous".to_string(),
+        };
+
+        let mut data = HashMap::new();
+        data.insert(
+            "data".to_string(),
+            serde_json::Value::String(format!("0x{}", hex::encode(&log.data))),
+        );
+        // Decoded parameters under their spec/events.yml keys, as CosmWasm
+        // attributes carry them
+        let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+        if let Ok(decoded) = VAGUSEVENTS_ABI
+            .event(&event_name)
+            .and_then(|event| event.parse_log(raw))
+        {
+            for param in decoded.params {
+                data.insert(param.name, serde_json::Value::String(abi_value(&param.value)));
+            }
+        }
+
+        Event {
+            chain_type: ChainType::EVM,
+            contract_address: format!("{:?}", log.address),
+            event_name,
+            topics: log.topics.iter().map(|t| format!("{:?}", t)).collect(),
+            data,
+            block_number: log.block_number.map(|n| n.as_u64()).unwrap_or_default(),
+            transaction_hash: log.transaction_hash.map(|h| format!("{:?}", h)).unwrap_or_default(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
+        }
+    }
+
+    /// Estimate gas, apply the fee settings, send a contract transaction and
+    /// wait for it to be mined successfully
+    async fn send_and_confirm<P: LogTransport, D: Detokenize>(
+        mut call: ContractCall<EVMMiddleware<P>, D>,
+        fees: &TxFees,
+    ) -> Result<TransactionReceipt, ChainError> {
+        let estimated_gas = call.estimate_gas().await.map_err(contract_error)?;
+        fees.apply(&mut call.tx, estimated_gas);
+
+        let pending_tx = call.send().await.map_err(contract_error)?;
+        let tx_hash = *pending_tx;
+        let receipt = pending_tx
+            .await
+            .map_err(|e| ChainError::Rpc(e.to_string()))?
+            .ok_or_else(|| ChainError::Rpc(format!("Transaction {:?} dropped", tx_hash)))?;
+
+        if receipt.status == Some(U64::zero()) {
+            // Receipts carry no revert data; replay the call on the parent block to recover it
+            let parent = receipt.block_number.unwrap_or_default().saturating_sub(U64::one());
+            return Err(match call.block(parent).call().await {
+                Err(err) => contract_error(err),
+                Ok(_) => ChainError::Revert {
+                    selector: [0u8; 4],
+                    reason: Some(format!("Transaction {:?} reverted", tx_hash)),
+                },
+            });
+        }
+        Ok(receipt)
     }
 
     #[async_trait::async_trait]
-    impl ChainClient for EVMClient {
+    impl<P: LogTransport> ChainClient for EVMClient<P> {
         async fn submit_aep(&self, aep: &AfferentEvidencePacket) -> Result<String> {
-            // Implementation would call AfferentInbox.postAEP
-            todo!("Implement EVM AEP submission")
+            aep.check_schema_version()
+                .map_err(|e| ChainError::Config(e.to_string()))?;
+
+            let inbox = AfferentInboxContract::new(
+                self.contract_address("afferent_inbox")?,
+                self.provider.clone(),
+            );
+            let signature = Bytes::from(aep.attestation.clone().unwrap_or_default());
+
+            let call = inbox.post_aep(
+                U256::from(aep.executor_id),
+                aep.state_root,
+                aep.metrics_hash,
+                signature,
+            );
+            let receipt = send_and_confirm(call, &self.fees).await?;
+
+            tracing::info!(
+                "Submitted AEP for executor {}: {:?}",
+                aep.executor_id,
+                receipt.transaction_hash
+            );
+            Ok(format!("{:?}", receipt.transaction_hash))
         }
 
         async fn issue_with_brake(
@@ -160,39 +933,183 @@ pub mod evm {
             scaled_limits_hash: &[u8; 32],
             expires_at: u64,
         ) -> Result<String> {
-            // Implementation would call VagalBrake.issueWithBrake
-            todo!("Implement EVM capability issuance")
+            let brake = VagalBrakeContract::new(
+                self.contract_address("vagal_brake")?,
+                self.provider.clone(),
+            );
+
+            let call = brake.issue_with_brake(
+                evm_intent(intent)?,
+                *scaled_limits_hash,
+                U256::from(expires_at),
+            );
+            let receipt = send_and_confirm(call, &self.fees).await?;
+
+            // Prefer the issued token id; fall back to the tx hash if the event is missing
+            let issued_topic = CapabilityIssuedFilter::signature();
+            let token_id = receipt
+                .logs
+                .iter()
+                .find(|log| log.topics.len() > 1 && log.topics[0] == issued_topic)
+                .map(|log| U256::from_big_endian(log.topics[1].as_bytes()));
+
+            match token_id {
+                Some(token_id) => {
+                    tracing::info!("Issued capability token {} in {:?}", token_id, receipt.transaction_hash);
+                    Ok(token_id.to_string())
+                }
+                None => Ok(format!("{:?}", receipt.transaction_hash)),
+            }
         }
 
         async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()> {
-            // Implementation would call CapabilityIssuer.revoke
-            todo!("Implement EVM capability revocation")
+            let token = U256::from_dec_str(token_id)
+                .map_err(|_| ChainError::Config(format!("Invalid token id: {}", token_id)))?;
+            let issuer = CapabilityIssuerContract::new(
+                self.contract_address("capability_issuer")?,
+                self.provider.clone(),
+            );
+
+            let receipt = send_and_confirm(issuer.revoke(token, reason), &self.fees).await?;
+            tracing::info!("Revoked capability token {} in {:?}", token_id, receipt.transaction_hash);
+            Ok(())
+        }
+
+        async fn token_owner(&self, token_id: &str) -> Result<Option<String>> {
+            let token_id = U256::from_dec_str(token_id)
+                .map_err(|_| ChainError::Config(format!("Invalid token id: {}", token_id)))?;
+            let issuer = CapabilityIssuerContract::new(
+                self.contract_address("capability_issuer")?,
+                self.provider.clone(),
+            );
+
+            // Unknown tokens report the zero address rather than reverting
+            let owner = issuer.owner_of(token_id).call().await.map_err(contract_error)?;
+            Ok((owner != Address::zero()).then(|| format!("{:?}", owner)))
         }
 
-        async fn get_guard(&self, action_id: &[u8; 32]) -> Result<Guard> {
-            // Implementation would call ANSStateManager.guardFor
-            todo!("Implement EVM guard query")
+        async fn is_valid(&self, token_id: &str) -> Result<bool> {
+            let token_id = U256::from_dec_str(token_id)
+                .map_err(|_| ChainError::Config(format!("Invalid token id: {}", token_id)))?;
+            let issuer = CapabilityIssuerContract::new(
+                self.contract_address("capability_issuer")?,
+                self.provider.clone(),
+            );
+            Ok(issuer.is_valid(token_id).call().await.map_err(contract_error)?)
+        }
+
+        async fn active_tokens_of(&self, executor_id: u64) -> Result<Vec<String>> {
+            let issuer = CapabilityIssuerContract::new(
+                self.contract_address("capability_issuer")?,
+                self.provider.clone(),
+            );
+            let token_ids = issuer
+                .active_tokens_of(U256::from(executor_id))
+                .call()
+                .await
+                .map_err(contract_error)?;
+            Ok(token_ids.iter().map(U256::to_string).collect())
+        }
+
+        async fn get_guard(&self, executor_id: u64, action_id: &[u8; 32]) -> Result<Guard> {
+            let ans = ANSStateManagerContract::new(
+                self.contract_address("ans_state_manager")?,
+                self.provider.clone(),
+            );
+
+            let (scaling_factor, allowed) = ans
+                .guard_for(U256::from(executor_id), *action_id)
+                .call()
+                .await
+                .map_err(contract_error)?;
+            let scaling_factor: u128 = scaling_factor.try_into().map_err(|_| {
+                ChainError::Contract(format!("Scaling factor out of range: {}", scaling_factor))
+            })?;
+
+            Ok(Guard {
+                scalingFactor: scaling_factor.into(),
+                allowed,
+            })
         }
 
         async fn get_ans_state(&self) -> Result<ANSState> {
-            // Implementation would query ANSStateManager.currentState
-            todo!("Implement EVM ANS state query")
+            let ans = ANSStateManagerContract::new(
+                self.contract_address("ans_state_manager")?,
+                self.provider.clone(),
+            );
+
+            let state = ans.get_current_state().call().await.map_err(contract_error)?;
+            Ok(ans_state_from(state)?)
         }
 
-        async fn update_tone(&self, vti: u64, suggested_state: ANSState) -> Result<()> {
-            // Implementation would call ANSStateManager.updateTone
-            todo!("Implement EVM tone update")
+        async fn update_tone(&self, executor_id: u64, vti: Vti, _suggested_state: ANSState) -> Result<()> {
+            // The EVM manager derives the state from the tone with its own
+            // hysteresis; it takes no suggested state
+            let ans = ANSStateManagerContract::new(
+                self.contract_address("ans_state_manager")?,
+                self.provider.clone(),
+            );
+
+            let call = ans.update_tone(U256::from(executor_id), evm_tone_from_vti(vti));
+            let receipt = send_and_confirm(call, &self.fees).await?;
+            tracing::info!(
+                "Updated tone of executor {} to {} in {:?}",
+                executor_id,
+                vti,
+                receipt.transaction_hash
+            );
+            Ok(())
         }
-    }
 
-    #[async_trait::async_trait]
-    impl EventSubscriber for EVMClient {
-        async fn subscribe_events<F>(&self, _callback: F) -> Result<()>
-        where
-            F: Fn(Event) + Send + Sync + 'static,
-        {
-            // Implementation would subscribe to contract events
-            todo!("Implement EVM event subscription")
+        async fn subscribe_events(&self, callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            let addresses: Vec<Address> = self.contract_addresses.values().copied().collect();
+            let filter = Filter::new().address(addresses);
+
+            P::stream_logs(self.provider.inner(), &self.rpc_url, &filter, &|log| callback(event_from_log(log))).await
+        }
+
+        async fn get_events_in_range(
+            &self,
+            from_block: u64,
+            to_block: u64,
+            event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            let signatures = event_names
+                .iter()
+                .map(|name| {
+                    event_signature(name)
+                        .ok_or_else(|| ChainError::Config(format!("Unknown event: {}", name)))
+                })
+                .collect::<Result<Vec<H256>, ChainError>>()?;
+
+            let addresses: Vec<Address> = self.contract_addresses.values().copied().collect();
+            let mut filter = Filter::new()
+                .address(addresses)
+                .from_block(from_block)
+                .to_block(to_block);
+            if !signatures.is_empty() {
+                filter = filter.topic0(signatures);
+            }
+
+            let logs = self
+                .provider
+                .get_logs(&filter)
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            Ok(logs.into_iter().map(event_from_log).collect())
+        }
+
+        async fn health(&self) -> Result<u64> {
+            let block_number = self
+                .provider
+                .get_block_number()
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            Ok(block_number.as_u64())
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
         }
     }
 }
@@ -202,102 +1119,885 @@ pub mod evm {
 pub mod cosmos {
     use super::*;
     use cosmrs::{
-        tx::{Msg, SignDoc, SignerInfo},
+        cosmwasm::MsgExecuteContract,
         crypto::secp256k1::SigningKey,
-        AccountId,
+        proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+        proto::cosmwasm::wasm::v1::{QuerySmartContractStateRequest, QuerySmartContractStateResponse},
+        proto::traits::Message,
+        tendermint::{abci, chain},
+        tx::{Body, Fee, Msg, SignDoc, SignerInfo},
+        AccountId, Coin,
     };
-    use tendermint_rpc::{Client, HttpClient, WebSocketClient, WebSocketClientUrl};
+    use cosmwasm_std::{Binary, Uint256};
+    use serde::de::DeserializeOwned;
+    use tendermint_rpc::{query::Query, Client, HttpClient, Order, WebSocketClientUrl};
     use std::str::FromStr;
+    use std::sync::Arc;
     use url::Url;
+    use vagus_spec::CapabilityRevocationReason;
+
+    /// Gas limit for contract executions; the contracts do no unbounded work
+    const DEFAULT_GAS_LIMIT: u64 = 500_000;
+
+    /// Fee denom of local wasmd nodes, which accept zero-fee transactions
+    const DEFAULT_FEE_DENOM: &str = "stake";
+
+    /// Path of the CometBFT WebSocket endpoint
+    const WEBSOCKET_PATH: &str = "/websocket";
+
+    /// Page size for `ActiveTokensOf`; the capability issuer caps pages at 100
+    const ACTIVE_TOKENS_PAGE_LIMIT: u32 = 100;
+
+    /// Page size for `tx_search`; CometBFT caps pages at 100
+    const TX_SEARCH_PAGE_SIZE: u8 = 100;
+
+    const ACCOUNT_QUERY_PATH: &str = "/cosmos.auth.v1beta1.Query/Account";
+    const SMART_QUERY_PATH: &str = "/cosmwasm.wasm.v1.Query/SmartContractState";
+
+    /// Fee and gas limit settings applied to every transaction the client sends
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct TxFee {
+        pub denom: String,
+        pub gas_price: f64,
+        pub gas_limit: u64,
+    }
+
+    impl TxFee {
+        pub(crate) fn from_config(config: &ChainConfig) -> Result<Self, ChainError> {
+            let gas_price = config.gas_price.unwrap_or(0.0);
+            if !gas_price.is_finite() || gas_price < 0.0 {
+                return Err(ChainError::Config(format!(
+                    "gas_price must be a non-negative number, got {}",
+                    gas_price
+                )));
+            }
+            let gas_limit = config.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT);
+            if gas_limit == 0 {
+                return Err(ChainError::Config("gas_limit must be positive".to_string()));
+            }
+
+            let fee = Self {
+                denom: config.fee_denom.clone().unwrap_or_else(|| DEFAULT_FEE_DENOM.to_string()),
+                gas_price,
+                gas_limit,
+            };
+            // Reject a malformed denom up front rather than on the first transaction
+            fee.fee()?;
+            Ok(fee)
+        }
+
+        /// Fee paying for the whole gas limit at the gas price, rounded up
+        pub(crate) fn fee(&self) -> Result<Fee, ChainError> {
+            let amount = (self.gas_limit as f64 * self.gas_price).ceil() as u128;
+            let coin = Coin::new(amount, &self.denom)
+                .map_err(|e| ChainError::Config(format!("Invalid fee denom {}: {}", self.denom, e)))?;
+            Ok(Fee::from_amount_and_gas(coin, self.gas_limit))
+        }
+    }
+
+    /// WebSocket endpoint of the node: the configured one, or the RPC URL
+    /// over ws(s) at the CometBFT `/websocket` path
+    pub(crate) fn websocket_url(config: &ChainConfig) -> Result<WebSocketClientUrl, ChainError> {
+        let ws_url = match &config.ws_url {
+            Some(ws_url) => ws_url.clone(),
+            None => {
+                let mut url = Url::parse(&config.rpc_url)
+                    .map_err(|e| ChainError::Config(format!("Invalid RPC URL {}: {}", config.rpc_url, e)))?;
+                let scheme = match url.scheme() {
+                    "http" => "ws",
+                    "https" => "wss",
+                    other => other,
+                }
+                .to_string();
+                url.set_scheme(&scheme)
+                    .map_err(|_| ChainError::Config(format!("No WebSocket URL for {}", config.rpc_url)))?;
+                if url.path() == "/" {
+                    url.set_path(WEBSOCKET_PATH);
+                }
+                url.to_string()
+            }
+        };
+        WebSocketClientUrl::from_str(&ws_url)
+            .map_err(|e| ChainError::Config(format!("Invalid WebSocket URL {}: {}", ws_url, e)))
+    }
 
+    #[derive(Clone)]
     pub struct CosmosClient {
         rpc_client: HttpClient,
-        ws_client: WebSocketClient,
-        signer: SigningKey,
-        account_id: AccountId,
+        /// Endpoint event subscriptions connect to
+        pub(crate) ws_url: WebSocketClientUrl,
+        signer: Arc<SigningKey>,
+        pub(crate) account_id: AccountId,
         contract_addresses: HashMap<String, String>,
+        pub(crate) fee: TxFee,
     }
 
     impl CosmosClient {
         pub async fn new(config: ChainConfig) -> Result<Self> {
             let rpc_url = Url::parse(&config.rpc_url)?;
             let rpc_client = HttpClient::new(rpc_url)?;
-            let ws_url = WebSocketClientUrl::from_str(&config.rpc_url)?;
-            let (ws_client, _) = WebSocketClient::new(ws_url).await?;
+            let ws_url = websocket_url(&config)?;
+            let fee = TxFee::from_config(&config)?;
 
             let private_key_hex = config.private_key
                 .ok_or_else(|| anyhow::anyhow!("Private key required for Cosmos client"))?;
             let private_key_bytes = hex::decode(private_key_hex.trim_start_matches("0x"))?;
-            let signer = SigningKey::from_slice(&private_key_bytes)?;
+            let signer = Arc::new(
+                SigningKey::from_slice(&private_key_bytes).map_err(|e| ChainError::Config(e.to_string()))?,
+            );
 
-            let account_id = signer.public_key().account_id("cosmos")?;
+            // Sign with the bech32 prefix of the chain the contracts live on
+            let prefix = config
+                .contract_addresses
+                .values()
+                .find_map(|address| AccountId::from_str(address).ok())
+                .map(|address| address.prefix().to_string())
+                .unwrap_or_else(|| "cosmos".to_string());
+            let account_id = signer
+                .public_key()
+                .account_id(&prefix)
+                .map_err(|e| ChainError::Config(e.to_string()))?;
 
             Ok(Self {
                 rpc_client,
-                ws_client,
+                ws_url,
                 signer,
                 account_id,
                 contract_addresses: config.contract_addresses,
+                fee,
             })
         }
-    }
 
-    #[async_trait::async_trait]
-    impl ChainClient for CosmosClient {
-        async fn submit_aep(&self, aep: &AfferentEvidencePacket) -> Result<String> {
-            // Implementation would submit PostAEP message to AfferentInbox contract
-            todo!("Implement Cosmos AEP submission")
+        fn contract_address(&self, name: &str) -> Result<&str, ChainError> {
+            self.contract_addresses
+                .get(name)
+                .map(String::as_str)
+                .ok_or_else(|| ChainError::Config(format!("Missing contract address: {}", name)))
         }
 
-        async fn issue_with_brake(
+        async fn abci_query<Req: Message, Resp: Message + Default>(
             &self,
-            intent: &Intent,
-            scaled_limits_hash: &[u8; 32],
-            expires_at: u64,
-        ) -> Result<String> {
-            // Implementation would submit IssueWithBrake message to VagalBrake contract
-            todo!("Implement Cosmos capability issuance")
+            path: &str,
+            request: Req,
+        ) -> Result<Resp, ChainError> {
+            let response = self
+                .rpc_client
+                .abci_query(Some(path.to_string()), request.encode_to_vec(), None, false)
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            if response.code.is_err() {
+                return Err(ChainError::from_cosmwasm_error(&response.log));
+            }
+            Resp::decode(response.value.as_slice()).map_err(|e| ChainError::Rpc(e.to_string()))
         }
 
-        async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()> {
-            // Implementation would submit Revoke message to CapabilityIssuer contract
-            todo!("Implement Cosmos capability revocation")
+        /// Run a `QuerySmart` request against the named contract
+        async fn query_smart<Q: Serialize, R: DeserializeOwned>(
+            &self,
+            contract: &str,
+            query: &Q,
+        ) -> Result<R, ChainError> {
+            let request = smart_query_request(self.contract_address(contract)?, query)?;
+            let response: QuerySmartContractStateResponse =
+                self.abci_query(SMART_QUERY_PATH, request).await?;
+            Ok(serde_json::from_slice(&response.data)?)
         }
 
-        async fn get_guard(&self, action_id: &[u8; 32]) -> Result<Guard> {
-            // Implementation would query ANSStateManager contract
-            todo!("Implement Cosmos guard query")
+        /// Account number and next sequence of the signing account
+        async fn account_info(&self) -> Result<(u64, u64), ChainError> {
+            let response: QueryAccountResponse = self
+                .abci_query(
+                    ACCOUNT_QUERY_PATH,
+                    QueryAccountRequest { address: self.account_id.to_string() },
+                )
+                .await?;
+            let account = response
+                .account
+                .ok_or_else(|| ChainError::Config(format!("Account {} not found", self.account_id)))?;
+            let account = BaseAccount::decode(account.value.as_slice())
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            Ok((account.account_number, account.sequence))
         }
 
-        async fn get_ans_state(&self) -> Result<ANSState> {
-            // Implementation would query ANSStateManager contract
-            todo!("Implement Cosmos ANS state query")
-        }
+        /// Sign and broadcast a `MsgExecuteContract`, waiting for it to commit.
+        /// Returns the tx hash and the wasm event attributes it emitted.
+        async fn execute<M: Serialize>(
+            &self,
+            contract: &str,
+            msg: &M,
+        ) -> Result<(String, Vec<(String, String)>), ChainError> {
+            let contract_address = self.contract_address(contract)?;
+            let status = self
+                .rpc_client
+                .status()
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            let (account_number, sequence) = self.account_info().await?;
 
-        async fn update_tone(&self, vti: u64, suggested_state: ANSState) -> Result<()> {
-            // Implementation would submit UpdateTone message to ANSStateManager contract
-            todo!("Implement Cosmos tone update")
-        }
-    }
+            let tx_bytes = build_execute_tx(
+                &self.signer,
+                &self.account_id,
+                contract_address,
+                msg,
+                &status.node_info.network,
+                account_number,
+                sequence,
+                &self.fee,
+            )?;
 
-    #[async_trait::async_trait]
-    impl EventSubscriber for CosmosClient {
-        async fn subscribe_events<F>(&self, _callback: F) -> Result<()>
-        where
-            F: Fn(Event) + Send + Sync + 'static,
-        {
-            // Implementation would subscribe to contract events via WebSocket
-            todo!("Implement Cosmos event subscription")
+            let response = self
+                .rpc_client
+                .broadcast_tx_commit(tx_bytes)
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            if response.check_tx.code.is_err() {
+                return Err(ChainError::from_cosmwasm_error(&response.check_tx.log));
+            }
+            if response.tx_result.code.is_err() {
+                return Err(ChainError::from_cosmwasm_error(&response.tx_result.log));
+            }
+
+            let attributes = response
+                .tx_result
+                .events
+                .iter()
+                .filter(|event| event.kind == "wasm")
+                .flat_map(|event| event.attributes.iter())
+                .map(|attribute| (attribute.key.to_string(), attribute.value.to_string()))
+                .collect();
+            Ok((response.hash.to_string(), attributes))
         }
     }
-}
 
-// Re-export clients for easier importing
-#[cfg(feature = "evm")]
-pub use evm::EVMClient;
+    /// Build the signed bytes of a single-message `MsgExecuteContract` tx
+    pub(crate) fn build_execute_tx<M: Serialize>(
+        signer: &SigningKey,
+        sender: &AccountId,
+        contract: &str,
+        msg: &M,
+        chain_id: &chain::Id,
+        account_number: u64,
+        sequence: u64,
+        fee: &TxFee,
+    ) -> Result<Vec<u8>, ChainError> {
+        let execute = MsgExecuteContract {
+            sender: sender.clone(),
+            contract: AccountId::from_str(contract)
+                .map_err(|e| ChainError::Config(format!("Invalid contract address {}: {}", contract, e)))?,
+            msg: serde_json::to_vec(msg)?,
+            funds: vec![],
+        };
+        let body = Body::new(
+            vec![execute
+                .to_any()
+                .map_err(|e| ChainError::Contract(format!("Failed to encode message: {}", e)))?],
+            "",
+            0u32,
+        );
 
-#[cfg(feature = "cosmos")]
-pub use cosmos::CosmosClient;
+        let auth_info =
+            SignerInfo::single_direct(Some(signer.public_key()), sequence).auth_info(fee.fee()?);
+
+        SignDoc::new(&body, &auth_info, chain_id, account_number)
+            .and_then(|sign_doc| sign_doc.sign(signer))
+            .and_then(|raw| raw.to_bytes())
+            .map_err(|e| ChainError::Contract(format!("Failed to sign tx: {}", e)))
+    }
+
+    /// Build the `QuerySmart` request for a JSON contract query
+    pub(crate) fn smart_query_request<Q: Serialize>(
+        contract: &str,
+        query: &Q,
+    ) -> Result<QuerySmartContractStateRequest, ChainError> {
+        Ok(QuerySmartContractStateRequest {
+            address: contract.to_string(),
+            query_data: serde_json::to_vec(query)?,
+        })
+    }
+
+    /// Spec integers are `Uint256`; the contract messages take `u64`
+    fn u64_from(value: Uint256, field: &str) -> Result<u64, ChainError> {
+        let be_bytes = value.to_be_bytes();
+        if be_bytes[..24].iter().any(|b| *b != 0) {
+            return Err(ChainError::Config(format!("{} out of range", field)));
+        }
+        Ok(u64::from_be_bytes(be_bytes[24..].try_into().expect("8 bytes")))
+    }
+
+    /// Map the EVM revocation reason code onto the spec enum
+    fn revocation_reason_from(reason: u8) -> Result<CapabilityRevocationReason, ChainError> {
+        match reason {
+            0 => Ok(CapabilityRevocationReason::OWNER_REVOCATION),
+            1 => Ok(CapabilityRevocationReason::REFLEX_TRIGGER),
+            2 => Ok(CapabilityRevocationReason::EXPIRATION),
+            other => Err(ChainError::Config(format!("Invalid revocation reason: {}", other))),
+        }
+    }
+
+    /// Convert the contract events of one transaction into the chain-agnostic
+    /// shape. Custom `wasm-<Name>` events keep their spec name; plain `wasm`
+    /// events are named by their `action` attribute. Events from contracts
+    /// other than `contracts`, or not in `event_names` when it is non-empty,
+    /// are skipped.
+    /// spec/events.yml name of the event a contract reports through its
+    /// `wasm` event's `action`
+    fn spec_event_name(action: &str) -> Option<&'static str> {
+        match action {
+            "issue" => Some("CapabilityIssued"),
+            "revoke" => Some("CapabilityRevoked"),
+            "post_aep" => Some("AEPPosted"),
+            "update_tone" => Some("VagalToneUpdated"),
+            _ => None,
+        }
+    }
+
+    /// spec/events.yml key of a snake_case attribute, e.g. `token_id` -> `tokenId`
+    fn camel_case(key: &str) -> String {
+        let mut words = key.split('_');
+        let mut camel = words.next().unwrap_or_default().to_string();
+        for word in words {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                camel.extend(first.to_uppercase());
+                camel.push_str(chars.as_str());
+            }
+        }
+        camel
+    }
+
+    pub(crate) fn events_from_tx(
+        tx_hash: &str,
+        height: u64,
+        tx_events: &[abci::Event],
+        contracts: &[&str],
+        event_names: &[&str],
+    ) -> Vec<Event> {
+        tx_events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| {
+                let attribute = |key: &str| {
+                    event
+                        .attributes
+                        .iter()
+                        .find(|attribute| attribute.key == key)
+                        .map(|attribute| attribute.value.to_string())
+                };
+                // Actions of the spec events are renamed, with their keys
+                let (event_name, spec_keys) = match event.kind.strip_prefix("wasm-") {
+                    Some(name) => (name.to_string(), false),
+                    None if event.kind == "wasm" => {
+                        let action = attribute("action")?;
+                        match spec_event_name(&action) {
+                            Some(name) => (name.to_string(), true),
+                            None => (action, false),
+                        }
+                    }
+                    None => return None,
+                };
+                let contract_address = attribute("_contract_address")?;
+                if !contracts.contains(&contract_address.as_str())
+                    || (!event_names.is_empty() && !event_names.contains(&event_name.as_str()))
+                {
+                    return None;
+                }
+
+                let data = event
+                    .attributes
+                    .iter()
+                    .filter(|attribute| attribute.key != "_contract_address")
+                    .map(|attribute| {
+                        let key = if spec_keys {
+                            camel_case(&attribute.key)
+                        } else {
+                            attribute.key.to_string()
+                        };
+                        (key, serde_json::Value::String(attribute.value.to_string()))
+                    })
+                    .collect();
+                Some(Event {
+                    chain_type: ChainType::Cosmos,
+                    contract_address,
+                    event_name,
+                    topics: vec![],
+                    data,
+                    block_number: height,
+                    transaction_hash: tx_hash.to_string(),
+                    log_index: index as u64,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn post_aep_msg(aep: &AfferentEvidencePacket) -> afferent_inbox::ExecuteMsg {
+        // The gateway commits a single state root; post it in both slots. The
+        // metrics hashes are cross-checked against the CBOR by the inbox.
+        afferent_inbox::ExecuteMsg::PostAEP {
+            executor_id: aep.executor_id,
+            state_root_sha256: Binary::from(aep.state_root.to_vec()),
+            state_root_keccak: Binary::from(aep.state_root.to_vec()),
+            metrics_hash_sha256: Binary::from(aep.metrics_hash_sha256.to_vec()),
+            metrics_hash_keccak: Binary::from(aep.metrics_hash.to_vec()),
+            attestation: Binary::from(aep.attestation.clone().unwrap_or_default()),
+            co_attestations: vec![],
+            metrics_cbor: Binary::from(aep.metrics_cbor.clone()),
+        }
+    }
+
+    pub(crate) fn issue_with_brake_msg(
+        intent: &Intent,
+        scaled_limits_hash: &[u8; 32],
+        expires_at: u64,
+    ) -> Result<vagal_brake::ExecuteMsg, ChainError> {
+        Ok(vagal_brake::ExecuteMsg::IssueWithBrake {
+            intent_executor_id: u64_from(intent.executorId, "executorId")?,
+            intent_action_id: intent.actionId.clone(),
+            intent_params: intent.params.clone(),
+            intent_envelope_hash: intent.envelopeHash.clone(),
+            intent_pre_state_root: intent.preStateRoot.clone(),
+            intent_not_before: u64_from(intent.notBefore, "notBefore")?,
+            intent_not_after: u64_from(intent.notAfter, "notAfter")?,
+            intent_max_duration_ms: u64_from(intent.maxDurationMs, "maxDurationMs")?,
+            intent_max_energy_j: u64_from(intent.maxEnergyJ, "maxEnergyJ")?,
+            intent_planner: intent.planner.clone(),
+            intent_nonce: u64_from(intent.nonce, "nonce")?,
+            intent_is_escape: intent.isEscape,
+            scaled_limits_hash: Binary::from(scaled_limits_hash.to_vec()),
+            expires_at,
+        })
+    }
+
+    /// Token id issued by a `vagal_brake` transaction. A rejected issuance
+    /// still commits, so the brake can count it towards the issuer's circuit
+    /// breaker; it is surfaced as the issuer's rejection, or the raw error
+    pub(crate) fn issued_token_id(
+        tx_hash: &str,
+        attributes: &[(String, String)],
+    ) -> Result<String, ChainError> {
+        let attribute = |name: &str| {
+            attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        if let Some(token_id) = attribute("token_id") {
+            return Ok(token_id.to_string());
+        }
+        match attribute("rejected").or_else(|| attribute("error")) {
+            Some(reason) => Err(ChainError::from_cosmwasm_error(reason)),
+            None => Err(ChainError::Contract(format!(
+                "transaction {} issued no capability token",
+                tx_hash
+            ))),
+        }
+    }
+
+    pub(crate) fn revoke_msg(token_id: &str, reason: u8) -> Result<capability_issuer::ExecuteMsg, ChainError> {
+        Ok(capability_issuer::ExecuteMsg::Revoke {
+            token_id: token_id.to_string(),
+            reason: revocation_reason_from(reason)?,
+        })
+    }
+
+    pub(crate) fn guard_for_query(executor_id: u64, action_id: &[u8; 32]) -> ans_state_manager::QueryMsg {
+        ans_state_manager::QueryMsg::GuardFor {
+            action_id: Binary::from(action_id.to_vec()),
+            executor_id: Some(executor_id),
+        }
+    }
+
+    pub(crate) fn update_tone_msg(vti: Vti, suggested_state: ANSState) -> ans_state_manager::ExecuteMsg {
+        ans_state_manager::ExecuteMsg::UpdateTone {
+            vti: vti.get(),
+            suggested: suggested_state,
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for CosmosClient {
+        async fn submit_aep(&self, aep: &AfferentEvidencePacket) -> Result<String> {
+            aep.check_schema_version()
+                .map_err(|e| ChainError::Config(e.to_string()))?;
+
+            let (tx_hash, _) = self.execute("afferent_inbox", &post_aep_msg(aep)).await?;
+            tracing::info!("Submitted AEP for executor {}: {}", aep.executor_id, tx_hash);
+            Ok(tx_hash)
+        }
+
+        async fn issue_with_brake(
+            &self,
+            intent: &Intent,
+            scaled_limits_hash: &[u8; 32],
+            expires_at: u64,
+        ) -> Result<String> {
+            let msg = issue_with_brake_msg(intent, scaled_limits_hash, expires_at)?;
+            let (tx_hash, attributes) = self.execute("vagal_brake", &msg).await?;
+
+            let token_id = issued_token_id(&tx_hash, &attributes)?;
+            tracing::info!("Issued capability token {} in {}", token_id, tx_hash);
+            Ok(token_id)
+        }
+
+        async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()> {
+            let (tx_hash, _) = self
+                .execute("capability_issuer", &revoke_msg(token_id, reason)?)
+                .await?;
+            tracing::info!("Revoked capability token {} in {}", token_id, tx_hash);
+            Ok(())
+        }
+
+        async fn token_owner(&self, token_id: &str) -> Result<Option<String>> {
+            let response: capability_issuer::TokenOwnerResponse = self
+                .query_smart(
+                    "capability_issuer",
+                    &capability_issuer::QueryMsg::TokenOwner {
+                        token_id: token_id.to_string(),
+                    },
+                )
+                .await?;
+            Ok(response.owner)
+        }
+
+        async fn is_valid(&self, token_id: &str) -> Result<bool> {
+            let response: capability_issuer::IsValidResponse = self
+                .query_smart(
+                    "capability_issuer",
+                    &capability_issuer::QueryMsg::IsValid {
+                        token_id: token_id.to_string(),
+                    },
+                )
+                .await?;
+            Ok(response.valid)
+        }
+
+        async fn active_tokens_of(&self, executor_id: u64) -> Result<Vec<String>> {
+            let mut token_ids = Vec::new();
+            loop {
+                let response: capability_issuer::ActiveTokensOfResponse = self
+                    .query_smart(
+                        "capability_issuer",
+                        &capability_issuer::QueryMsg::ActiveTokensOf {
+                            executor_id,
+                            start_after: token_ids.last().cloned(),
+                            limit: Some(ACTIVE_TOKENS_PAGE_LIMIT),
+                        },
+                    )
+                    .await?;
+                let last_page = response.token_ids.len() < ACTIVE_TOKENS_PAGE_LIMIT as usize;
+                token_ids.extend(response.token_ids);
+                if last_page {
+                    return Ok(token_ids);
+                }
+            }
+        }
+
+        async fn get_guard(&self, executor_id: u64, action_id: &[u8; 32]) -> Result<Guard> {
+            let response: ans_state_manager::GuardForResponse = self
+                .query_smart("ans_state_manager", &guard_for_query(executor_id, action_id))
+                .await?;
+            Ok(response.guard)
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            let response: ans_state_manager::CurrentStateResponse = self
+                .query_smart("ans_state_manager", &ans_state_manager::QueryMsg::CurrentState {})
+                .await?;
+            Ok(response.state)
+        }
+
+        async fn update_tone(&self, _executor_id: u64, vti: Vti, suggested_state: ANSState) -> Result<()> {
+            // The Cosmos manager's tone is not keyed by executor
+            let (tx_hash, _) = self
+                .execute("ans_state_manager", &update_tone_msg(vti, suggested_state))
+                .await?;
+            tracing::info!("Updated tone to {} in {}", vti, tx_hash);
+            Ok(())
+        }
+
+        async fn subscribe_events(&self, _callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            // Implementation would subscribe to contract events via WebSocket
+            todo!("Implement Cosmos event subscription over {}", self.ws_url)
+        }
+
+        async fn get_events_in_range(
+            &self,
+            from_block: u64,
+            to_block: u64,
+            event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            let query = Query::gte("tx.height", from_block).and_lte("tx.height", to_block);
+            let contracts: Vec<&str> = self.contract_addresses.values().map(String::as_str).collect();
+
+            let mut events = Vec::new();
+            let mut seen = 0usize;
+            for page in 1u32.. {
+                let response = self
+                    .rpc_client
+                    .tx_search(query.clone(), false, page, TX_SEARCH_PAGE_SIZE, Order::Ascending)
+                    .await
+                    .map_err(|e| ChainError::Rpc(e.to_string()))?;
+                seen += response.txs.len();
+                let last_page = response.txs.len() < TX_SEARCH_PAGE_SIZE as usize
+                    || seen >= response.total_count as usize;
+
+                for tx in response.txs {
+                    events.extend(events_from_tx(
+                        &tx.hash.to_string(),
+                        tx.height.value(),
+                        &tx.tx_result.events,
+                        &contracts,
+                        event_names,
+                    ));
+                }
+                if last_page {
+                    break;
+                }
+            }
+            Ok(events)
+        }
+
+        async fn health(&self) -> Result<u64> {
+            let status = self
+                .rpc_client
+                .status()
+                .await
+                .map_err(|e| ChainError::Rpc(e.to_string()))?;
+            Ok(status.sync_info.latest_block_height.value())
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
+}
+
+/// In-memory client for exercising chain consumers without a live chain
+#[cfg(feature = "mock")]
+pub mod mock {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    type Subscriber = Box<dyn Fn(Event) + Send + Sync + 'static>;
+
+    /// A state-changing call received by a `MockChainClient`
+    #[derive(Debug, Clone, PartialEq)]
+    #[allow(clippy::large_enum_variant)]
+    pub enum MockCall {
+        SubmitAep { executor_id: u64, metrics_hash: [u8; 32] },
+        IssueWithBrake { intent: Intent, expires_at: u64, token_id: String },
+        RevokeCapability { token_id: String, reason: u8 },
+        UpdateTone { executor_id: u64, vti: Vti, suggested_state: ANSState },
+    }
+
+    /// Validity bookkeeping for an issued token
+    struct MockToken {
+        /// Big-endian executor id, as carried by the intent
+        executor_id: [u8; 32],
+        expires_at: u64,
+        revoked: bool,
+    }
+
+    impl MockToken {
+        fn is_valid(&self) -> bool {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default();
+            !self.revoked && self.expires_at > now
+        }
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        ans_state: Option<ANSState>,
+        next_token_id: u64,
+        owners: HashMap<String, String>,
+        tokens: HashMap<String, MockToken>,
+        calls: Vec<MockCall>,
+        history: Vec<Event>,
+        subscribers: Vec<Subscriber>,
+    }
+
+    /// Chain client that records calls and replays injected events.
+    /// Clones share the same state, like clones of a real client share a connection.
+    #[derive(Clone, Default)]
+    pub struct MockChainClient {
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockChainClient {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Set the ANS state reported by `get_ans_state`
+        pub fn set_ans_state(&self, state: ANSState) {
+            self.state.lock().unwrap().ans_state = Some(state);
+        }
+
+        /// Inject an event: it joins the history and is delivered to current subscribers
+        pub fn emit(&self, event: Event) {
+            let mut state = self.state.lock().unwrap();
+            for subscriber in &state.subscribers {
+                subscriber(event.clone());
+            }
+            state.history.push(event);
+        }
+
+        /// Drop all subscriber callbacks, as a closed connection would
+        pub fn unsubscribe_all(&self) {
+            self.state.lock().unwrap().subscribers.clear();
+        }
+
+        /// State-changing calls received so far, in order
+        pub fn calls(&self) -> Vec<MockCall> {
+            self.state.lock().unwrap().calls.clone()
+        }
+
+        fn record(&self, call: MockCall) {
+            self.state.lock().unwrap().calls.push(call);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for MockChainClient {
+        async fn submit_aep(&self, aep: &AfferentEvidencePacket) -> Result<String> {
+            self.record(MockCall::SubmitAep {
+                executor_id: aep.executor_id,
+                metrics_hash: aep.metrics_hash,
+            });
+            Ok(format!("0x{}", hex::encode(aep.metrics_hash)))
+        }
+
+        async fn issue_with_brake(
+            &self,
+            intent: &Intent,
+            _scaled_limits_hash: &[u8; 32],
+            expires_at: u64,
+        ) -> Result<String> {
+            let token_id = {
+                let mut state = self.state.lock().unwrap();
+                state.next_token_id += 1;
+                let token_id = state.next_token_id.to_string();
+                state.owners.insert(token_id.clone(), intent.planner.clone());
+                state.tokens.insert(
+                    token_id.clone(),
+                    MockToken {
+                        executor_id: intent.executorId.to_be_bytes(),
+                        expires_at,
+                        revoked: false,
+                    },
+                );
+                token_id
+            };
+            self.record(MockCall::IssueWithBrake {
+                intent: intent.clone(),
+                expires_at,
+                token_id: token_id.clone(),
+            });
+            Ok(token_id)
+        }
+
+        async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()> {
+            if let Some(token) = self.state.lock().unwrap().tokens.get_mut(token_id) {
+                token.revoked = true;
+            }
+            self.record(MockCall::RevokeCapability {
+                token_id: token_id.to_string(),
+                reason,
+            });
+            Ok(())
+        }
+
+        async fn token_owner(&self, token_id: &str) -> Result<Option<String>> {
+            Ok(self.state.lock().unwrap().owners.get(token_id).cloned())
+        }
+
+        async fn is_valid(&self, token_id: &str) -> Result<bool> {
+            let state = self.state.lock().unwrap();
+            Ok(state.tokens.get(token_id).is_some_and(MockToken::is_valid))
+        }
+
+        async fn active_tokens_of(&self, executor_id: u64) -> Result<Vec<String>> {
+            let mut executor = [0u8; 32];
+            executor[24..].copy_from_slice(&executor_id.to_be_bytes());
+            let state = self.state.lock().unwrap();
+            let mut token_ids: Vec<u64> = state
+                .tokens
+                .iter()
+                .filter(|(_, token)| token.executor_id == executor && token.is_valid())
+                .filter_map(|(token_id, _)| token_id.parse().ok())
+                .collect();
+            token_ids.sort_unstable();
+            Ok(token_ids.iter().map(u64::to_string).collect())
+        }
+
+        async fn get_guard(&self, _executor_id: u64, _action_id: &[u8; 32]) -> Result<Guard> {
+            Ok(Guard::with_scaling_factor(ScalingFactor::FULL))
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            Ok(self.state.lock().unwrap().ans_state.clone().unwrap_or(ANSState::SAFE))
+        }
+
+        async fn update_tone(&self, executor_id: u64, vti: Vti, suggested_state: ANSState) -> Result<()> {
+            self.record(MockCall::UpdateTone {
+                executor_id,
+                vti,
+                suggested_state: suggested_state.clone(),
+            });
+            self.set_ans_state(suggested_state);
+            Ok(())
+        }
+
+        /// Replays the event history to `callback`, then keeps it subscribed
+        /// to future `emit`s until `unsubscribe_all`
+        async fn subscribe_events(&self, callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            let mut state = self.state.lock().unwrap();
+            for event in &state.history {
+                callback(event.clone());
+            }
+            state.subscribers.push(callback);
+            Ok(())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            from_block: u64,
+            to_block: u64,
+            event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            let state = self.state.lock().unwrap();
+            Ok(state
+                .history
+                .iter()
+                .filter(|event| (from_block..=to_block).contains(&event.block_number))
+                .filter(|event| event_names.is_empty() || event_names.contains(&event.event_name.as_str()))
+                .cloned()
+                .collect())
+        }
+
+        async fn health(&self) -> Result<u64> {
+            let state = self.state.lock().unwrap();
+            Ok(state.history.iter().map(|event| event.block_number).max().unwrap_or_default())
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
+}
+
+// Re-export clients for easier importing
+#[cfg(feature = "evm")]
+pub use evm::{EVMClient, EvmTransport, ReconnectPolicy};
+
+#[cfg(feature = "cosmos")]
+pub use cosmos::CosmosClient;
+
+#[cfg(feature = "mock")]
+pub use mock::{MockCall, MockChainClient};
 
 /// Error types
 #[derive(Error, Debug)]
@@ -311,9 +2011,1140 @@ pub enum ChainError {
     #[error("Contract error: {0}")]
     Contract(String),
 
+    #[error(
+        "Contract reverted (selector 0x{}): {}",
+        hex::encode(.selector),
+        .reason.as_deref().unwrap_or("unknown reason")
+    )]
+    Revert {
+        selector: [u8; 4],
+        reason: Option<String>,
+    },
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
     #[error("Generic error: {0}")]
     Generic(#[from] anyhow::Error),
 }
+
+/// Selector of Solidity's `Error(string)` revert
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of Solidity's `Panic(uint256)` revert
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Portable errors from `spec/errors.yml`: EVM signature and the message of
+/// the matching CosmWasm `VagusError`
+const SPEC_ERRORS: [(&str, &str); 22] = [
+    ("StateChangeTooFrequent()", "State change attempted too soon after previous change"),
+    ("InvalidToneValue(uint256)", "Tone value outside valid range (0-10000)"),
+    ("IntentExpired()", "Intent execution time window has expired"),
+    ("InvalidPreState()", "Pre-execution state root mismatch"),
+    ("NonceAlreadyUsed()", "Intent nonce has already been used"),
+    ("TokenNotFound(uint256)", "Capability token does not exist"),
+    ("TokenAlreadyRevoked(uint256)", "Capability token is already revoked"),
+    ("UnauthorizedRevocation()", "Caller not authorized to revoke this token"),
+    (
+        "TooManyActiveTokens(uint256,uint256)",
+        "Executor already holds the maximum number of active capability tokens",
+    ),
+    ("ExecutorPaused(uint256)", "Executor is paused and cannot be issued capability tokens"),
+    ("ANSBlocked(string)", "Execution blocked by ANS shutdown state"),
+    ("ANSLimitExceeded(string,uint256,uint256)", "Scaled parameter exceeds ANS limits"),
+    ("UnauthorizedAttestor()", "Caller not authorized to post evidence"),
+    ("InvalidEvidenceFormat()", "Evidence packet format is invalid"),
+    (
+        "InsufficientAttestations(uint256,uint256)",
+        "Evidence packet lacks the required number of distinct attestations",
+    ),
+    ("RateLimited(string,uint256)", "Request rate exceeds configured limits"),
+    ("CircuitBreakerOpen(string,uint256)", "Circuit breaker is in open state, blocking requests"),
+    (
+        "CBORHashMismatch(bytes32,bytes32,bytes32,bytes32)",
+        "CBOR normalized input produces different hashes across stacks",
+    ),
+    ("StateMismatch(bytes32,bytes32)", "Pre-execution state root does not match AfferentInbox latest"),
+    ("TTLExpired(uint256,uint256)", "Time-to-live has expired"),
+    ("Unauthorized()", "Caller not authorized for this operation"),
+    ("InvalidInput(string)", "Input parameters are invalid"),
+];
+
+/// 4-byte EVM selector of an error signature
+fn spec_selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+impl ChainError {
+    /// Map a CosmWasm `VagusError` onto the selector of its EVM counterpart.
+    /// Accepts either the raw log text or a JSON error body carrying it in a
+    /// `message`, `log` or `raw_log` field
+    pub fn from_cosmwasm_error(raw: &str) -> Self {
+        let message = serde_json::from_str::<serde_json::Value>(raw)
+            .ok()
+            .and_then(|body| {
+                ["message", "log", "raw_log"]
+                    .iter()
+                    .find_map(|key| body.get(key).and_then(|v| v.as_str()).map(str::to_string))
+            })
+            .unwrap_or_else(|| raw.to_string());
+
+        match SPEC_ERRORS.iter().find(|(_, text)| message.contains(text)) {
+            Some((signature, text)) => {
+                let name = signature.split('(').next().unwrap_or(signature);
+                ChainError::Revert {
+                    selector: spec_selector(signature),
+                    reason: Some(format!("{}: {}", name, text)),
+                }
+            }
+            None => ChainError::Revert { selector: [0u8; 4], reason: Some(message) },
+        }
+    }
+
+    /// Whether this is a revert with the named spec error, e.g. `"TokenNotFound"`
+    pub fn is_spec_error(&self, name: &str) -> bool {
+        let ChainError::Revert { selector, .. } = self else {
+            return false;
+        };
+        SPEC_ERRORS.iter().any(|(signature, _)| {
+            signature.split('(').next() == Some(name) && spec_selector(signature) == *selector
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Client that replays a single event to every subscriber
+    #[derive(Clone)]
+    struct EchoClient;
+
+    #[async_trait::async_trait]
+    impl ChainClient for EchoClient {
+        async fn submit_aep(&self, _aep: &AfferentEvidencePacket) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn issue_with_brake(
+            &self,
+            _intent: &Intent,
+            _scaled_limits_hash: &[u8; 32],
+            _expires_at: u64,
+        ) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn revoke_capability(&self, _token_id: &str, _reason: u8) -> Result<()> {
+            Ok(())
+        }
+
+        async fn token_owner(&self, _token_id: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn is_valid(&self, _token_id: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn active_tokens_of(&self, _executor_id: u64) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_guard(&self, _executor_id: u64, _action_id: &[u8; 32]) -> Result<Guard> {
+            Ok(Guard {
+                scalingFactor: 0u128.into(),
+                allowed: false,
+            })
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            Ok(ANSState::SAFE)
+        }
+
+        async fn update_tone(&self, _executor_id: u64, _vti: Vti, _suggested_state: ANSState) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_events(&self, callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            callback(Event {
+                chain_type: ChainType::EVM,
+                contract_address: "0x0".to_string(),
+                event_name: "ReflexTriggered".to_string(),
+                topics: vec![],
+                data: HashMap::new(),
+                block_number: 1,
+                transaction_hash: "0x1".to_string(),
+                log_index: 0,
+            });
+            Ok(())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            _from_block: u64,
+            _to_block: u64,
+            _event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn health(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_events_through_trait_object() {
+        let client: Box<dyn ChainClient> = Box::new(EchoClient);
+        let received = Arc::new(AtomicUsize::new(0));
+
+        let counter = received.clone();
+        client
+            .subscribe_events(Box::new(move |event: Event| {
+                assert_eq!(event.event_name, "ReflexTriggered");
+                counter.fetch_add(1, Ordering::SeqCst);
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_chain_config_round_trips_through_toml() {
+        let config = ChainConfig {
+            chain_type: ChainType::EVM,
+            rpc_url: "ws://localhost:8545".to_string(),
+            contract_addresses: HashMap::from([
+                ("afferent_inbox".to_string(), "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string()),
+                ("vagal_brake".to_string(), "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512".to_string()),
+            ]),
+            private_key: Some("0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".to_string()),
+            max_fee_per_gas: Some(50_000_000_000),
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: Some(1.25),
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
+        };
+        let path = std::env::temp_dir().join(format!("vagus-chain-config-{}.toml", std::process::id()));
+        std::fs::write(&path, toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(ChainConfig::from_toml(&path).unwrap(), config);
+
+        // The key may live in the environment instead of the file
+        std::env::set_var("VAGUS_TEST_TOML_SIGNER", "0xfeed");
+        std::fs::write(
+            &path,
+            "chain_type = \"Cosmos\"\n\
+             rpc_url = \"http://localhost:26657\"\n\
+             private_key_env = \"VAGUS_TEST_TOML_SIGNER\"\n",
+        )
+        .unwrap();
+        let loaded = ChainConfig::from_toml(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.chain_type, ChainType::Cosmos);
+        assert_eq!(loaded.private_key.as_deref(), Some("0xfeed"));
+        assert!(loaded.contract_addresses.is_empty());
+    }
+
+    #[test]
+    fn test_chain_config_debug_redacts_private_key() {
+        let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let config = ChainConfig {
+            chain_type: ChainType::EVM,
+            rpc_url: "http://localhost:8545".to_string(),
+            contract_addresses: HashMap::from([("vagal_brake".to_string(), "0xbrake".to_string())]),
+            private_key: Some(private_key.to_string()),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
+        };
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains(&private_key[2..]));
+        assert!(debug.contains(r#"private_key: Some("<redacted>")"#));
+        assert!(debug.contains("http://localhost:8545"));
+        assert!(debug.contains("0xbrake"));
+
+        // Pretty-printed as well
+        assert!(!format!("{:#?}", config).contains(&private_key[2..]));
+    }
+
+    #[test]
+    fn test_chain_config_from_env_prefix() {
+        let prefix = "VAGUS_TEST_COSMOS";
+        for (name, value) in [
+            ("CHAIN_TYPE", "cosmos"),
+            ("RPC_URL", "http://localhost:26657"),
+            ("CONTRACT_ANS_STATE_MANAGER", "wasm1ans"),
+            ("CONTRACT_VAGAL_BRAKE", "wasm1brake"),
+            ("PRIVATE_KEY_ENV", "VAGUS_TEST_COSMOS_SIGNER"),
+            ("GAS_LIMIT_MULTIPLIER", "1.5"),
+        ] {
+            std::env::set_var(format!("{}_{}", prefix, name), value);
+        }
+        std::env::set_var("VAGUS_TEST_COSMOS_SIGNER", "deadbeef");
+
+        let config = ChainConfig::from_env(prefix).unwrap();
+        assert_eq!(config.chain_type, ChainType::Cosmos);
+        assert_eq!(config.rpc_url, "http://localhost:26657");
+        assert_eq!(
+            config.contract_addresses,
+            HashMap::from([
+                ("ans_state_manager".to_string(), "wasm1ans".to_string()),
+                ("vagal_brake".to_string(), "wasm1brake".to_string()),
+            ])
+        );
+        assert_eq!(config.private_key.as_deref(), Some("deadbeef"));
+        assert_eq!(config.max_fee_per_gas, None);
+        assert_eq!(config.gas_limit_multiplier, Some(1.5));
+
+        std::env::set_var(format!("{}_MAX_FEE_PER_GAS", prefix), "lots");
+        let err = ChainConfig::from_env(prefix).unwrap_err();
+        assert_eq!(err.to_string(), "Configuration error: Invalid VAGUS_TEST_COSMOS_MAX_FEE_PER_GAS: lots");
+
+        std::env::remove_var(format!("{}_RPC_URL", prefix));
+        let err = ChainConfig::from_env(prefix).unwrap_err();
+        assert_eq!(err.to_string(), "Configuration error: VAGUS_TEST_COSMOS_RPC_URL is not set");
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_evm_transport_from_url_scheme() {
+        assert_eq!(EvmTransport::from_url("ws://localhost:8545").unwrap(), EvmTransport::Ws);
+        assert_eq!(EvmTransport::from_url("wss://rpc.example.org").unwrap(), EvmTransport::Ws);
+        assert_eq!(EvmTransport::from_url("http://localhost:8545").unwrap(), EvmTransport::Http);
+        assert_eq!(EvmTransport::from_url("HTTPS://rpc.example.org").unwrap(), EvmTransport::Http);
+        assert!(matches!(
+            EvmTransport::from_url("localhost:8545"),
+            Err(ChainError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_boxed_client_clone_is_usable() {
+        let client: Box<dyn ChainClient> = Box::new(EchoClient);
+        let subscriber = client.clone();
+        let received = Arc::new(AtomicUsize::new(0));
+
+        let counter = received.clone();
+        let subscription = tokio::spawn(async move {
+            subscriber
+                .subscribe_events(Box::new(move |_event: Event| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }))
+                .await
+        });
+
+        assert_eq!(client.get_ans_state().await.unwrap(), ANSState::SAFE);
+        subscription.await.unwrap().unwrap();
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_evm_tone_converts_to_and_from_vti() {
+        assert_eq!(evm_tone_from_vti(Vti::new(10_000).unwrap()), 0);
+        assert_eq!(evm_tone_from_vti(Vti::new(7_000).unwrap()), 300_000);
+        assert_eq!(evm_tone_from_vti(Vti::new(0).unwrap()), 1_000_000);
+
+        assert_eq!(vti_from_evm_tone(300_000).unwrap().get(), 7_000);
+        assert_eq!(vti_from_evm_tone(350_049).unwrap().get(), 6_500);
+        assert_eq!(vti_from_evm_tone(1_000_000).unwrap().get(), 0);
+        assert!(vti_from_evm_tone(1_000_001).is_err());
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_evm_log_carries_spec_event_keys() {
+        use ethers::contract::EthEvent;
+        use ethers::types::{Log, H256, U256};
+
+        let log = Log {
+            topics: vec![
+                evm::VagalToneUpdatedFilter::signature(),
+                H256::from_low_u64_be(4000),
+                H256::from_low_u64_be(2),
+            ],
+            data: ethers::abi::encode(&[ethers::abi::Token::Uint(U256::from(42))]).into(),
+            ..Default::default()
+        };
+
+        let event = evm::event_from_log(log);
+        assert_eq!(event.event_name, "VagalToneUpdated");
+        assert_eq!(event.data["tone"], "4000");
+        assert_eq!(event.data["state"], "2");
+        assert_eq!(event.data["updatedAt"], "42");
+        assert!(event.data.contains_key("data"));
+    }
+
+    /// Canonical signature of an event as declared in Events.sol
+    #[cfg(feature = "evm")]
+    fn events_sol_signature(name: &str) -> String {
+        let events = include_str!("../../../../contracts/src/core/Events.sol");
+        let start = events
+            .find(&format!("event {}(", name))
+            .unwrap_or_else(|| panic!("{} in Events.sol", name));
+        let declaration = &events[start..];
+        let params = &declaration[declaration.find('(').unwrap() + 1..declaration.find(')').unwrap()];
+        let types: Vec<&str> = params
+            .split(',')
+            .filter_map(|param| param.split_whitespace().next())
+            .collect();
+        format!("{}({})", name, types.join(","))
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_evm_logs_decode_with_deployed_event_signatures() {
+        use ethers::abi::Token;
+        use ethers::types::{Log, H256, U256};
+
+        let topic = |name: &str| H256::from_slice(&Keccak256::digest(events_sol_signature(name)));
+        assert_eq!(events_sol_signature("CapabilityRevoked"), "CapabilityRevoked(uint256,uint8)");
+
+        let revoked = evm::event_from_log(Log {
+            topics: vec![topic("CapabilityRevoked"), H256::from_low_u64_be(9)],
+            data: ethers::abi::encode(&[Token::Uint(U256::from(1))]).into(),
+            ..Default::default()
+        });
+        assert_eq!(revoked.event_name, "CapabilityRevoked");
+        assert_eq!(revoked.data["tokenId"], "9");
+        assert_eq!(revoked.data["reason"], "1");
+
+        let posted = evm::event_from_log(Log {
+            topics: vec![topic("AEPPosted"), H256::from_low_u64_be(3)],
+            data: ethers::abi::encode(&[
+                Token::FixedBytes(vec![0xaa; 32]),
+                Token::FixedBytes(vec![0xbb; 32]),
+            ])
+            .into(),
+            ..Default::default()
+        });
+        assert_eq!(posted.event_name, "AEPPosted");
+        assert_eq!(posted.data["executorId"], "3");
+        assert_eq!(posted.data["stateRoot"], format!("0x{}", "aa".repeat(32)));
+        assert_eq!(posted.data["metricsHash"], format!("0x{}", "bb".repeat(32)));
+
+        // Every relayed event name resolves to the deployed signature
+        for name in ["CapabilityIssued", "CapabilityRevoked", "AEPPosted", "VagalToneUpdated", "ReflexTriggered"] {
+            let log = Log { topics: vec![topic(name)], ..Default::default() };
+            assert_eq!(evm::event_from_log(log).event_name, name);
+        }
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_evm_reflex_log_carries_revoked_tokens() {
+        use ethers::abi::Token;
+        use ethers::contract::EthEvent;
+        use ethers::types::{Log, H256, U256};
+
+        let log = Log {
+            topics: vec![evm::ReflexTriggeredFilter::signature(), H256::from_low_u64_be(2)],
+            data: ethers::abi::encode(&[
+                Token::String("danger_detected".to_string()),
+                Token::Uint(U256::from(2)),
+                Token::Uint(U256::from(42)),
+                Token::Array(vec![Token::Uint(U256::from(4)), Token::Uint(U256::from(5))]),
+            ])
+            .into(),
+            ..Default::default()
+        };
+
+        let event = evm::event_from_log(log);
+        assert_eq!(event.event_name, "ReflexTriggered");
+        assert_eq!(event.data["executorId"], "2");
+        assert_eq!(event.data["revokedCount"], "2");
+        assert_eq!(event.data["revokedTokens"], "4,5");
+    }
+
+    /// `issueWithBrake` inputs as declared over the generated `Intent` struct
+    #[cfg(feature = "evm")]
+    fn generated_issue_with_brake_inputs() -> Vec<ethers::abi::ParamType> {
+        let generated = include_str!("../../../../contracts/src/core/GeneratedTypes.sol");
+        let start = generated.find("struct Intent {").expect("Intent in GeneratedTypes.sol");
+        let end = start + generated[start..].find('}').unwrap();
+        let intent_struct = generated[start..=end].split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let abi = ethers::abi::AbiParser::default()
+            .parse(&[
+                intent_struct.as_str(),
+                "function issueWithBrake(Intent intent, bytes32 scaledLimitsHash, uint256 expiresAt) external returns (uint256 tokenId)",
+            ])
+            .unwrap();
+        abi.function("issueWithBrake")
+            .unwrap()
+            .inputs
+            .iter()
+            .map(|param| param.kind.clone())
+            .collect()
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_issue_with_brake_binding_matches_generated_types() {
+        let bound: Vec<_> = evm::VAGALBRAKECONTRACT_ABI
+            .function("issueWithBrake")
+            .unwrap()
+            .inputs
+            .iter()
+            .map(|param| param.kind.clone())
+            .collect();
+        assert_eq!(bound, generated_issue_with_brake_inputs());
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_issue_with_brake_encodes_every_argument() {
+        use ethers::abi::{AbiEncode, Token};
+        use ethers::types::U256;
+
+        let intent = Intent {
+            executorId: 7u64.into(),
+            actionId: vec![1u8; 32].into(),
+            params: vec![0xca, 0xfe].into(),
+            envelopeHash: vec![2u8; 32].into(),
+            preStateRoot: vec![3u8; 32].into(),
+            notBefore: 1_700_000_000u64.into(),
+            notAfter: 1_700_003_600u64.into(),
+            maxDurationMs: 2000u64.into(),
+            maxEnergyJ: 500u64.into(),
+            planner: "0x0000000000000000000000000000000000000123".to_string(),
+            nonce: 9u64.into(),
+            isEscape: true,
+        };
+        let calldata = evm::IssueWithBrakeCall {
+            intent: evm::evm_intent(&intent).unwrap(),
+            scaled_limits_hash: [0xab; 32],
+            expires_at: U256::from(1_700_000_600u64),
+        }
+        .encode();
+
+        assert_eq!(
+            calldata[..4],
+            ethers::utils::id(
+                "issueWithBrake((uint256,bytes32,bytes,bytes32,bytes32,uint256,uint256,uint256,uint256,address,uint256,bool),bytes32,uint256)"
+            )
+        );
+        let tokens = ethers::abi::decode(&generated_issue_with_brake_inputs(), &calldata[4..]).unwrap();
+        let [Token::Tuple(fields), scaled_limits_hash, expires_at] = tokens.as_slice() else {
+            panic!("unexpected tokens: {:?}", tokens);
+        };
+        assert_eq!(fields[0], Token::Uint(U256::from(7u64)));
+        assert_eq!(fields[2], Token::Bytes(vec![0xca, 0xfe]));
+        assert_eq!(fields[6], Token::Uint(U256::from(1_700_003_600u64)));
+        assert_eq!(fields[10], Token::Uint(U256::from(9u64)));
+        assert_eq!(fields[11], Token::Bool(true));
+        assert_eq!(*scaled_limits_hash, Token::FixedBytes(vec![0xab; 32]));
+        assert_eq!(*expires_at, Token::Uint(U256::from(1_700_000_600u64)));
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_ans_blocked_revert_decodes_reason() {
+        use ethers::abi::Token;
+
+        let selector = spec_selector("ANSBlocked(string)");
+        assert_eq!(selector, ethers::utils::id("ANSBlocked(string)"));
+
+        let mut data = selector.to_vec();
+        data.extend(ethers::abi::encode(&[Token::String("VagalBrake validation failed".to_string())]));
+
+        let err = ChainError::from_revert_data(&data);
+        assert!(matches!(
+            &err,
+            ChainError::Revert { selector: s, reason: Some(reason) }
+                if *s == selector && reason == "ANSBlocked(VagalBrake validation failed)"
+        ));
+        assert!(err.to_string().contains("ANSBlocked(VagalBrake validation failed)"));
+
+        // Plain require() messages and unknown custom errors
+        let mut data = ERROR_STRING_SELECTOR.to_vec();
+        data.extend(ethers::abi::encode(&[Token::String("Token does not exist".to_string())]));
+        assert!(matches!(
+            ChainError::from_revert_data(&data),
+            ChainError::Revert { reason: Some(reason), .. } if reason == "Token does not exist"
+        ));
+        assert!(matches!(
+            ChainError::from_revert_data(&[0xde, 0xad, 0xbe, 0xef]),
+            ChainError::Revert { selector: [0xde, 0xad, 0xbe, 0xef], reason: None }
+        ));
+    }
+
+    #[cfg(feature = "evm")]
+    #[test]
+    fn test_tx_fees_populate_eip1559_fields() {
+        use ethers::types::{transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, U256};
+
+        let mut config = ChainConfig {
+            chain_type: ChainType::EVM,
+            rpc_url: "http://localhost:8545".to_string(),
+            contract_addresses: HashMap::new(),
+            private_key: None,
+            max_fee_per_gas: Some(50_000_000_000),
+            max_priority_fee_per_gas: Some(2_000_000_000),
+            gas_limit_multiplier: Some(1.25),
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
+        };
+        let fees = evm::TxFees::from_config(&config).unwrap();
+
+        let mut tx = TypedTransaction::Eip1559(Eip1559TransactionRequest::new());
+        fees.apply(&mut tx, U256::from(80_000u64));
+        match &tx {
+            TypedTransaction::Eip1559(inner) => {
+                assert_eq!(inner.gas, Some(U256::from(100_000u64)));
+                assert_eq!(inner.max_fee_per_gas, Some(U256::from(50_000_000_000u64)));
+                assert_eq!(inner.max_priority_fee_per_gas, Some(U256::from(2_000_000_000u64)));
+            }
+            other => panic!("unexpected transaction type: {:?}", other),
+        }
+
+        // Unset fees are left for the provider and the estimate is used as-is
+        config.max_fee_per_gas = None;
+        config.max_priority_fee_per_gas = None;
+        config.gas_limit_multiplier = None;
+        let mut tx = TypedTransaction::Eip1559(Eip1559TransactionRequest::new());
+        evm::TxFees::from_config(&config).unwrap().apply(&mut tx, U256::from(80_000u64));
+        assert_eq!(tx.gas(), Some(&U256::from(80_000u64)));
+        assert_eq!(tx.gas_price(), None);
+
+        config.gas_limit_multiplier = Some(0.5);
+        assert!(evm::TxFees::from_config(&config).is_err());
+        config.gas_limit_multiplier = None;
+        config.max_fee_per_gas = Some(1);
+        config.max_priority_fee_per_gas = Some(2);
+        assert!(evm::TxFees::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_cosmwasm_vagus_error_maps_to_evm_selector() {
+        let raw_log = format!(
+            "failed to execute message; message index: 0: {}: execute wasm contract failed",
+            VagusError::ANSBlocked
+        );
+        let body = serde_json::json!({ "code": 5, "raw_log": raw_log }).to_string();
+
+        let err = ChainError::from_cosmwasm_error(&body);
+        assert!(matches!(
+            &err,
+            ChainError::Revert { selector, reason: Some(reason) }
+                if *selector == spec_selector("ANSBlocked(string)") && reason.starts_with("ANSBlocked")
+        ));
+
+        // Every portable VagusError is recognized
+        for vagus_error in [
+            VagusError::StateChangeTooFrequent,
+            VagusError::IntentExpired,
+            VagusError::TokenAlreadyRevoked,
+            VagusError::TooManyActiveTokens,
+            VagusError::ANSLimitExceeded,
+            VagusError::InsufficientAttestations,
+            VagusError::RateLimited,
+            VagusError::StateMismatch,
+            VagusError::Unauthorized,
+            VagusError::InvalidInput,
+        ] {
+            assert!(matches!(
+                ChainError::from_cosmwasm_error(&vagus_error.to_string()),
+                ChainError::Revert { selector, .. } if selector != [0u8; 4]
+            ));
+        }
+
+        assert!(matches!(
+            ChainError::from_cosmwasm_error("out of gas"),
+            ChainError::Revert { selector: [0, 0, 0, 0], reason: Some(_) }
+        ));
+
+        let err = ChainError::from_cosmwasm_error(&VagusError::TokenNotFound.to_string());
+        assert!(err.is_spec_error("TokenNotFound"));
+        assert!(!err.is_spec_error("TokenAlreadyRevoked"));
+        assert!(!ChainError::Rpc("TokenNotFound".to_string()).is_spec_error("TokenNotFound"));
+    }
+}
+
+#[cfg(all(test, feature = "evm"))]
+mod evm_reconnect_tests {
+    use super::evm::*;
+    use anyhow::Result;
+    use ethers::types::{Filter, Log, U256, U64};
+    use futures::Stream;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// One socket lifetime: logs available via backfill, then the live stream
+    /// which ends (the socket drops) once exhausted
+    struct MockSession {
+        head: u64,
+        history: Vec<Log>,
+        live: Vec<Log>,
+    }
+
+    struct MockLogSource(MockSession);
+
+    #[async_trait::async_trait]
+    impl LogSource for MockLogSource {
+        async fn block_number(&self) -> Result<u64> {
+            Ok(self.0.head)
+        }
+
+        async fn logs_since(&self, _filter: &Filter, from_block: u64) -> Result<Vec<Log>> {
+            Ok(self
+                .0
+                .history
+                .iter()
+                .filter(|log| log.block_number.unwrap().as_u64() >= from_block)
+                .cloned()
+                .collect())
+        }
+
+        async fn subscribe(&self, _filter: &Filter) -> Result<Pin<Box<dyn Stream<Item = Log> + Send + '_>>> {
+            Ok(Box::pin(futures::stream::iter(self.0.live.clone())))
+        }
+    }
+
+    fn log_at(block: u64, index: u64) -> Log {
+        Log {
+            block_number: Some(U64::from(block)),
+            log_index: Some(U256::from(index)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_backfills_events_across_gap() {
+        // Socket 1 drops after block 2, the node is briefly unreachable, and
+        // socket 2 comes back after blocks 3 were already mined
+        let sessions = Arc::new(Mutex::new(VecDeque::from([
+            Some(MockSession {
+                head: 0,
+                history: vec![],
+                live: vec![log_at(1, 0), log_at(2, 0)],
+            }),
+            None,
+            Some(MockSession {
+                head: 3,
+                history: vec![log_at(2, 0), log_at(3, 0), log_at(3, 1)],
+                live: vec![log_at(3, 1), log_at(4, 0)],
+            }),
+        ])));
+
+        // Gives up once the node stays down, but only after delivering every
+        // event exactly once, including those mined during the gap
+        let (result, delivered) = stream_sessions(sessions).await;
+        assert!(result.is_err());
+        assert_eq!(delivered, vec![(1, 0), (2, 0), (3, 0), (3, 1), (4, 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_backfills_gap_before_first_event() {
+        // Socket 1 subscribes at block 5 and drops before any event; blocks
+        // 6 and 7 are mined before socket 2 comes back
+        let sessions = VecDeque::from([
+            Some(MockSession {
+                head: 5,
+                history: vec![log_at(5, 0)],
+                live: vec![],
+            }),
+            Some(MockSession {
+                head: 7,
+                history: vec![log_at(5, 0), log_at(6, 0), log_at(7, 0)],
+                live: vec![log_at(8, 0)],
+            }),
+        ]);
+
+        // Block 5 predates the first subscription and is not replayed
+        let (_, delivered) = stream_sessions(Arc::new(Mutex::new(sessions))).await;
+        assert_eq!(delivered, vec![(6, 0), (7, 0), (8, 0)]);
+    }
+
+    /// Stream from `sessions` (None = connection refused) until they run out,
+    /// returning the result and the (block, index) of every delivered log
+    async fn stream_sessions(
+        sessions: Arc<Mutex<VecDeque<Option<MockSession>>>>,
+    ) -> (Result<()>, Vec<(u64, u64)>) {
+        let connect = move || {
+            let next = sessions.lock().unwrap().pop_front().flatten();
+            async move { next.map(MockLogSource).ok_or_else(|| anyhow::anyhow!("connection refused")) }
+        };
+
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let sink = delivered.clone();
+        let on_log = move |log: Log| {
+            sink.lock()
+                .unwrap()
+                .push((log.block_number.unwrap().as_u64(), log.log_index.unwrap().as_u64()));
+        };
+
+        let policy = ReconnectPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            max_attempts: 3,
+        };
+        let result = stream_with_reconnect(connect, &Filter::new(), &on_log, policy).await;
+        let delivered = delivered.lock().unwrap().clone();
+        (result, delivered)
+    }
+}
+
+#[cfg(all(test, feature = "cosmos"))]
+mod cosmos_tests {
+    use super::cosmos::*;
+    use super::*;
+    use cosmrs::{
+        cosmwasm::MsgExecuteContract, crypto::secp256k1::SigningKey, tx::Msg, AccountId, Tx,
+    };
+    use cosmwasm_std::{Binary, Uint256};
+
+    fn test_intent(executor_id: Uint256) -> Intent {
+        Intent {
+            executorId: executor_id,
+            actionId: Binary::from(vec![1u8; 32]),
+            params: Binary::from(vec![]),
+            envelopeHash: Binary::from(vec![2u8; 32]),
+            preStateRoot: Binary::from(vec![3u8; 32]),
+            notBefore: Uint256::from(1000u64),
+            notAfter: Uint256::from(2000u64),
+            maxDurationMs: Uint256::from(5000u64),
+            maxEnergyJ: Uint256::from(100u64),
+            planner: "wasm1planner".to_string(),
+            nonce: Uint256::from(7u64),
+            isEscape: false,
+        }
+    }
+
+    #[test]
+    fn test_execute_tx_is_well_formed() {
+        let signer = SigningKey::random();
+        let sender = signer.public_key().account_id("wasm").unwrap();
+        let contract = AccountId::new("wasm", &[9u8; 32]).unwrap();
+        let msg = update_tone_msg(Vti::new(6500).unwrap(), ANSState::DANGER);
+
+        let tx_bytes = build_execute_tx(
+            &signer,
+            &sender,
+            &contract.to_string(),
+            &msg,
+            &"vagus-test".parse().unwrap(),
+            3,
+            7,
+            &TxFee { denom: "uwasm".to_string(), gas_price: 0.025, gas_limit: 300_000 },
+        )
+        .unwrap();
+
+        let tx = Tx::from_bytes(&tx_bytes).unwrap();
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 7);
+        assert_eq!(tx.auth_info.fee.gas_limit, 300_000);
+        assert_eq!(tx.auth_info.fee.amount[0].amount, 7_500);
+        assert_eq!(tx.auth_info.fee.amount[0].denom.to_string(), "uwasm");
+        assert_eq!(tx.body.messages.len(), 1);
+
+        let execute = MsgExecuteContract::from_any(&tx.body.messages[0]).unwrap();
+        assert_eq!(execute.sender, sender);
+        assert_eq!(execute.contract, contract);
+        assert!(execute.funds.is_empty());
+        assert_eq!(execute.msg, serde_json::to_vec(&msg).unwrap());
+        let body: serde_json::Value = serde_json::from_slice(&execute.msg).unwrap();
+        assert_eq!(body["update_tone"]["vti"], 6500);
+    }
+
+    fn cosmos_config() -> ChainConfig {
+        ChainConfig {
+            chain_type: ChainType::Cosmos,
+            rpc_url: "http://localhost:26657".to_string(),
+            contract_addresses: HashMap::from([(
+                "vagal_brake".to_string(),
+                AccountId::new("wasm", &[9u8; 32]).unwrap().to_string(),
+            )]),
+            private_key: Some(format!("0x{}", "11".repeat(32))),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            ws_url: None,
+            fee_denom: Some("uwasm".to_string()),
+            gas_price: Some(0.025),
+            gas_limit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_from_node_shaped_config() {
+        // The node serves HTTP RPC and its WebSocket on the same port
+        let client = CosmosClient::new(cosmos_config()).await.unwrap();
+        assert_eq!(client.ws_url.to_string(), "ws://localhost:26657/websocket");
+        assert_eq!(client.account_id.prefix(), "wasm");
+        assert_eq!(
+            client.fee,
+            TxFee { denom: "uwasm".to_string(), gas_price: 0.025, gas_limit: 500_000 }
+        );
+
+        // A separately configured WebSocket endpoint is used as given
+        let client = CosmosClient::new(ChainConfig {
+            rpc_url: "https://rpc.example.com".to_string(),
+            ws_url: Some("wss://ws.example.com/websocket".to_string()),
+            gas_limit: Some(250_000),
+            ..cosmos_config()
+        })
+        .await
+        .unwrap();
+        assert_eq!(client.ws_url.to_string(), "wss://ws.example.com/websocket");
+        assert_eq!(client.fee.gas_limit, 250_000);
+
+        // Without one, https maps to wss
+        let config = ChainConfig { rpc_url: "https://rpc.example.com".to_string(), ..cosmos_config() };
+        assert_eq!(websocket_url(&config).unwrap().to_string(), "wss://rpc.example.com/websocket");
+
+        for config in [
+            ChainConfig { gas_price: Some(-1.0), ..cosmos_config() },
+            ChainConfig { gas_limit: Some(0), ..cosmos_config() },
+            ChainConfig { fee_denom: Some("1bad".to_string()), ..cosmos_config() },
+        ] {
+            assert!(CosmosClient::new(config).await.is_err());
+        }
+    }
+
+    #[test]
+    fn test_smart_query_request_encodes_contract_query() {
+        let request =
+            smart_query_request("wasm1ans", &ans_state_manager::QueryMsg::CurrentState {}).unwrap();
+        assert_eq!(request.address, "wasm1ans");
+        assert_eq!(request.query_data, br#"{"current_state":{}}"#.to_vec());
+    }
+
+    #[test]
+    fn test_contract_messages_from_client_arguments() {
+        let aep = AfferentEvidencePacket {
+            executor_id: 42,
+            state_root: [1u8; 32],
+            metrics_hash: [2u8; 32],
+            metrics_hash_sha256: [3u8; 32],
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
+            metrics_cbor: vec![4u8; 8],
+            attestation: None,
+            timestamp: 0,
+        };
+        match post_aep_msg(&aep) {
+            afferent_inbox::ExecuteMsg::PostAEP {
+                executor_id,
+                state_root_keccak,
+                metrics_hash_sha256,
+                metrics_hash_keccak,
+                metrics_cbor,
+                ..
+            } => {
+                assert_eq!(executor_id, 42);
+                assert_eq!(state_root_keccak.to_vec(), vec![1u8; 32]);
+                assert_eq!(metrics_hash_sha256.to_vec(), vec![3u8; 32]);
+                assert_eq!(metrics_hash_keccak.to_vec(), vec![2u8; 32]);
+                assert_eq!(metrics_cbor.to_vec(), vec![4u8; 8]);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        match issue_with_brake_msg(&test_intent(Uint256::from(42u64)), &[4u8; 32], 3000).unwrap() {
+            vagal_brake::ExecuteMsg::IssueWithBrake { intent_executor_id, intent_nonce, expires_at, .. } => {
+                assert_eq!(intent_executor_id, 42);
+                assert_eq!(intent_nonce, 7);
+                assert_eq!(expires_at, 3000);
+            }
+        }
+        assert!(matches!(
+            issue_with_brake_msg(&test_intent(Uint256::MAX), &[4u8; 32], 3000),
+            Err(ChainError::Config(_))
+        ));
+
+        assert!(matches!(
+            revoke_msg("5", 1).unwrap(),
+            capability_issuer::ExecuteMsg::Revoke {
+                reason: vagus_spec::CapabilityRevocationReason::REFLEX_TRIGGER,
+                ..
+            }
+        ));
+        assert!(revoke_msg("5", 3).is_err());
+
+        // Guards are read for the caller's executor, not a default one
+        match guard_for_query(42, &[5u8; 32]) {
+            ans_state_manager::QueryMsg::GuardFor { action_id, executor_id } => {
+                assert_eq!(action_id.to_vec(), vec![5u8; 32]);
+                assert_eq!(executor_id, Some(42));
+            }
+            other => panic!("unexpected query: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_issued_token_id_requires_token_attribute() {
+        let attributes = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+
+        let issued = attributes(&[("action", "issue_with_brake"), ("token_id", "5")]);
+        assert_eq!(issued_token_id("ABCD", &issued).unwrap(), "5");
+
+        let rejected = attributes(&[
+            ("action", "issue_failed"),
+            ("error", "codespace: wasm, code: 5"),
+            ("action", "record_issue_failure"),
+            ("rejected", "Request rate exceeds configured limits"),
+        ]);
+        assert!(issued_token_id("ABCD", &rejected)
+            .unwrap_err()
+            .is_spec_error("RateLimited"));
+
+        // Not counted by the breaker: only the brake's error remains
+        let failed = attributes(&[("action", "issue_failed"), ("error", "codespace: wasm, code: 5")]);
+        assert!(matches!(
+            issued_token_id("ABCD", &failed),
+            Err(ChainError::Revert { reason: Some(reason), .. }) if reason.contains("code: 5")
+        ));
+
+        assert!(matches!(
+            issued_token_id("ABCD", &attributes(&[("action", "issue_with_brake")])),
+            Err(ChainError::Contract(_))
+        ));
+    }
+
+    #[test]
+    fn test_events_from_tx_keeps_named_events_of_known_contracts() {
+        use cosmrs::tendermint::abci::Event as AbciEvent;
+
+        let tx_events = [
+            AbciEvent::new("message", [("action", "/cosmwasm.wasm.v1.MsgExecuteContract")]),
+            AbciEvent::new(
+                "wasm",
+                [("_contract_address", "wasm1reflex"), ("action", "reflex_triggered"), ("executor_id", "1")],
+            ),
+            AbciEvent::new(
+                "wasm-StateChanged",
+                [("_contract_address", "wasm1ans"), ("fromState", "0"), ("toState", "1")],
+            ),
+            AbciEvent::new("wasm-StateChanged", [("_contract_address", "wasm1other"), ("toState", "2")]),
+        ];
+        let contracts = ["wasm1reflex", "wasm1ans"];
+
+        let events = events_from_tx("ABCD", 12, &tx_events, &contracts, &[]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_name, "reflex_triggered");
+        assert_eq!(events[0].data["executor_id"], "1");
+        assert_eq!(events[1].event_name, "StateChanged");
+        assert_eq!(events[1].contract_address, "wasm1ans");
+        assert_eq!(events[1].data["toState"], "1");
+        assert!(!events[1].data.contains_key("_contract_address"));
+        assert_eq!((events[1].block_number, events[1].log_index), (12, 2));
+        assert_eq!(events[1].transaction_hash, "ABCD");
+
+        let events = events_from_tx("ABCD", 12, &tx_events, &contracts, &["StateChanged"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_name, "StateChanged");
+    }
+
+    #[test]
+    fn test_events_from_tx_names_spec_actions() {
+        use cosmrs::tendermint::abci::Event as AbciEvent;
+
+        let tx_events = [
+            AbciEvent::new(
+                "wasm",
+                [("_contract_address", "wasm1issuer"), ("action", "revoke"), ("token_id", "5"), ("reason", "REFLEX_TRIGGER")],
+            ),
+            AbciEvent::new(
+                "wasm",
+                [("_contract_address", "wasm1ans"), ("action", "update_tone"), ("tone", "6500"), ("updated_at", "9")],
+            ),
+        ];
+        let contracts = ["wasm1issuer", "wasm1ans"];
+
+        let events =
+            events_from_tx("ABCD", 12, &tx_events, &contracts, &["CapabilityRevoked", "VagalToneUpdated"]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_name, "CapabilityRevoked");
+        assert_eq!(events[0].data["tokenId"], "5");
+        assert_eq!(events[0].data["reason"], "REFLEX_TRIGGER");
+        assert_eq!(events[1].event_name, "VagalToneUpdated");
+        assert_eq!(events[1].data["tone"], "6500");
+        assert_eq!(events[1].data["updatedAt"], "9");
+    }
+
+    #[test]
+    fn test_gateway_aep_passes_inbox_metrics_cross_check() {
+        use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+        use vagus_telemetry::{SensorReading, TelemetryWindow};
+
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        window
+            .add_reading(SensorReading {
+                sensor_id: "dist_1".to_string(),
+                sensor_type: "human_distance".to_string(),
+                value: 312.5,
+                unit: "mm".to_string(),
+                timestamp: 1500,
+            })
+            .unwrap();
+        let metrics = window.compute_metrics();
+        let aep = AfferentEvidencePacket {
+            executor_id: 42,
+            state_root: metrics.hash(),
+            metrics_hash: metrics.hash(),
+            metrics_hash_sha256: metrics.hash_sha256(),
+            metrics_schema_version: metrics.metrics_schema_version,
+            metrics_cbor: metrics.to_canonical_cbor(),
+            attestation: None,
+            timestamp: 2000,
+        };
+        assert!(aep.verify_against(&metrics));
+
+        let mut deps = mock_dependencies();
+        afferent_inbox::instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            afferent_inbox::InstantiateMsg {
+                authorized_attestors: vec!["gateway".to_string()],
+                min_attestations: None,
+                activation_delay: None,
+            },
+        )
+        .unwrap();
+        let post = |deps: cosmwasm_std::DepsMut, aep: &AfferentEvidencePacket| {
+            afferent_inbox::execute(deps, mock_env(), mock_info("gateway", &[]), post_aep_msg(aep))
+        };
+
+        post(deps.as_mut(), &aep).unwrap();
+
+        // Bytes that no longer match the committed hashes are refused
+        let tampered = AfferentEvidencePacket {
+            metrics_cbor: WindowMetrics {
+                min_human_distance: Some(1000.0),
+                ..metrics.clone()
+            }
+            .to_canonical_cbor(),
+            ..aep.clone()
+        };
+        assert!(matches!(
+            post(deps.as_mut(), &tampered),
+            Err(vagus_spec::VagusError::CBORHashMismatch)
+        ));
+    }
+}
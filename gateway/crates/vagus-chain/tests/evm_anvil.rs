@@ -0,0 +1,302 @@
+//! EVM client tests against a local Anvil node.
+//!
+//! Requires the `anvil` binary on PATH:
+//! `cargo test -p vagus-chain --features evm -- --ignored`
+
+#![cfg(feature = "evm")]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Middleware, Provider, Ws},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionRequest, H256},
+    utils::{keccak256, Anvil, AnvilInstance},
+};
+use vagus_chain::{
+    ANSState, AfferentEvidencePacket, ChainClient, ChainClientFactory, ChainConfig, ChainType,
+    EVMClient, Vti, METRICS_SCHEMA_VERSION,
+};
+
+type TestClient = SignerMiddleware<Provider<Ws>, LocalWallet>;
+
+/// Init code for a stub contract whose runtime is a single STOP, so any
+/// call (e.g. `postAEP`) succeeds without touching state.
+const ACCEPT_ALL_INIT_CODE: &str = "6001600c60003960016000f300";
+
+/// Init code for a stub ANS manager pinned to one state. The runtime answers
+/// selector-only calls (`getCurrentState()`) with `state` and anything longer
+/// (`guardFor(uint256,bytes32)`) with `(scaling, allowed)`.
+fn ans_stub_init_code(state: u8, scaling: u16, allowed: bool) -> String {
+    format!(
+        "6022600c60003960226000f3\
+         3660041460175761{:04x}60005260{:02x}60205260406000f3\
+         5b60{:02x}60005260206000f3",
+        scaling, allowed as u8, state
+    )
+}
+
+/// Init code for a stub whose runtime emits a data-less log with the given
+/// topic0 on every call.
+fn emitter_init_code(topic0: H256) -> String {
+    format!("6027600c60003960276000f37f{}60006000a100", hex::encode(topic0))
+}
+
+async fn deployer(anvil: &AnvilInstance) -> Arc<TestClient> {
+    let provider = Provider::<Ws>::connect(anvil.ws_endpoint()).await.unwrap();
+    let wallet: LocalWallet = anvil.keys()[0].clone().into();
+    Arc::new(SignerMiddleware::new(
+        provider,
+        wallet.with_chain_id(anvil.chain_id()),
+    ))
+}
+
+async fn deploy_stub(anvil: &AnvilInstance, init_code: &str) -> Address {
+    let client = deployer(anvil).await;
+    let tx = TransactionRequest::new().data(Bytes::from(hex::decode(init_code).unwrap()));
+    let receipt = client
+        .send_transaction(tx, None)
+        .await
+        .unwrap()
+        .await
+        .unwrap()
+        .unwrap();
+    receipt.contract_address.unwrap()
+}
+
+fn chain_config(anvil: &AnvilInstance, contracts: &[(&str, Address)]) -> ChainConfig {
+    let contract_addresses: HashMap<String, String> = contracts
+        .iter()
+        .map(|(name, addr)| (name.to_string(), format!("{:?}", addr)))
+        .collect();
+
+    ChainConfig {
+        chain_type: ChainType::EVM,
+        rpc_url: anvil.ws_endpoint(),
+        contract_addresses,
+        private_key: Some(hex::encode(anvil.keys()[0].to_bytes())),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        gas_limit_multiplier: None,
+        ws_url: None,
+        fee_denom: None,
+        gas_price: None,
+        gas_limit: None,
+    }
+}
+
+async fn evm_client(anvil: &AnvilInstance, contracts: &[(&str, Address)]) -> EVMClient {
+    EVMClient::new(chain_config(anvil, contracts)).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_submit_aep_returns_tx_hash() {
+    let anvil = Anvil::new().spawn();
+    let inbox = deploy_stub(&anvil, ACCEPT_ALL_INIT_CODE).await;
+    let client = evm_client(&anvil, &[("afferent_inbox", inbox)]).await;
+
+    let aep = AfferentEvidencePacket {
+        executor_id: 1,
+        state_root: [1u8; 32],
+        metrics_hash: [2u8; 32],
+        metrics_hash_sha256: [3u8; 32],
+        metrics_schema_version: METRICS_SCHEMA_VERSION,
+        metrics_cbor: vec![],
+        attestation: None,
+        timestamp: 0,
+    };
+
+    let tx_hash = client.submit_aep(&aep).await.unwrap();
+    assert!(!tx_hash.is_empty());
+    assert!(tx_hash.starts_with("0x"));
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_submit_aep_applies_fee_config() {
+    let anvil = Anvil::new().spawn();
+    let inbox = deploy_stub(&anvil, ACCEPT_ALL_INIT_CODE).await;
+    let client = EVMClient::new(ChainConfig {
+        max_fee_per_gas: Some(50_000_000_000),
+        max_priority_fee_per_gas: Some(2_000_000_000),
+        gas_limit_multiplier: Some(1.5),
+        ..chain_config(&anvil, &[("afferent_inbox", inbox)])
+    })
+    .await
+    .unwrap();
+
+    let aep = AfferentEvidencePacket {
+        executor_id: 1,
+        state_root: [1u8; 32],
+        metrics_hash: [2u8; 32],
+        metrics_hash_sha256: [3u8; 32],
+        metrics_schema_version: METRICS_SCHEMA_VERSION,
+        metrics_cbor: vec![],
+        attestation: None,
+        timestamp: 0,
+    };
+    let tx_hash: H256 = client.submit_aep(&aep).await.unwrap().parse().unwrap();
+
+    let provider = deployer(&anvil).await;
+    let tx = provider.get_transaction(tx_hash).await.unwrap().unwrap();
+    let receipt = provider.get_transaction_receipt(tx_hash).await.unwrap().unwrap();
+
+    assert_eq!(tx.max_fee_per_gas, Some(50_000_000_000u64.into()));
+    assert_eq!(tx.max_priority_fee_per_gas, Some(2_000_000_000u64.into()));
+    // The stub's gas use is exactly the estimate, so the limit is 1.5x what was used
+    assert_eq!(tx.gas, receipt.gas_used.unwrap() * 3 / 2);
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_get_guard_and_state_per_ans_state() {
+    let anvil = Anvil::new().spawn();
+
+    let cases = [
+        (ANSState::SAFE, 0u8, 10000u16, true),
+        (ANSState::DANGER, 1, 5000, true),
+        (ANSState::SHUTDOWN, 2, 0, false),
+    ];
+    for (expected, state, scaling, allowed) in cases {
+        let ans = deploy_stub(&anvil, &ans_stub_init_code(state, scaling, allowed)).await;
+        let client = evm_client(&anvil, &[("ans_state_manager", ans)]).await;
+
+        assert_eq!(client.get_ans_state().await.unwrap(), expected);
+
+        let guard = client.get_guard(0, &[7u8; 32]).await.unwrap();
+        assert_eq!(guard.scalingFactor.to_string(), scaling.to_string());
+        assert_eq!(guard.allowed, allowed);
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_get_ans_state_rejects_out_of_range_value() {
+    let anvil = Anvil::new().spawn();
+    let ans = deploy_stub(&anvil, &ans_stub_init_code(3, 0, false)).await;
+    let client = evm_client(&anvil, &[("ans_state_manager", ans)]).await;
+
+    let err = client.get_ans_state().await.unwrap_err();
+    assert!(err.to_string().contains("Invalid ANS state value: 3"));
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_factory_builds_http_and_ws_clients() {
+    let anvil = Anvil::new().spawn();
+    let ans = deploy_stub(&anvil, &ans_stub_init_code(1, 5000, true)).await;
+
+    for rpc_url in [anvil.endpoint(), anvil.ws_endpoint()] {
+        let client = ChainClientFactory::create_client(ChainConfig {
+            chain_type: ChainType::EVM,
+            rpc_url: rpc_url.clone(),
+            contract_addresses: HashMap::from([(
+                "ans_state_manager".to_string(),
+                format!("{:?}", ans),
+            )]),
+            private_key: Some(hex::encode(anvil.keys()[0].to_bytes())),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
+        })
+        .await
+        .unwrap_or_else(|e| panic!("failed to build client for {rpc_url}: {e}"));
+
+        assert_eq!(client.get_ans_state().await.unwrap(), ANSState::DANGER, "{rpc_url}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_get_events_in_range_filters_by_block_and_name() {
+    let anvil = Anvil::new().spawn();
+    let revoked_topic = H256::from(keccak256("CapabilityRevoked(uint256,uint256,uint8,uint256)"));
+    let issuer = deploy_stub(&anvil, &emitter_init_code(revoked_topic)).await;
+    let client = evm_client(&anvil, &[("capability_issuer", issuer)]).await;
+
+    // Three calls, each mined in its own block and emitting one event
+    let sender = deployer(&anvil).await;
+    let mut blocks = Vec::new();
+    for _ in 0..3 {
+        let receipt = sender
+            .send_transaction(TransactionRequest::new().to(issuer), None)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+        blocks.push(receipt.block_number.unwrap().as_u64());
+    }
+
+    let events = client
+        .get_events_in_range(blocks[0], blocks[1], &["CapabilityRevoked"])
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.event_name == "CapabilityRevoked"));
+    assert_eq!(events[0].block_number, blocks[0]);
+    assert_eq!(events[1].block_number, blocks[1]);
+
+    // All events by default, none when filtering for another name
+    let all = client.get_events_in_range(blocks[0], blocks[2], &[]).await.unwrap();
+    assert_eq!(all.len(), 3);
+    let issued = client
+        .get_events_in_range(blocks[0], blocks[2], &["CapabilityIssued"])
+        .await
+        .unwrap();
+    assert!(issued.is_empty());
+
+    assert!(client.get_events_in_range(blocks[0], blocks[2], &["NoSuchEvent"]).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_health_reports_advancing_block_height() {
+    let anvil = Anvil::new().spawn();
+
+    for rpc_url in [anvil.endpoint(), anvil.ws_endpoint()] {
+        let client = ChainClientFactory::create_client(ChainConfig {
+            rpc_url: rpc_url.clone(),
+            ..chain_config(&anvil, &[])
+        })
+        .await
+        .unwrap();
+
+        let before = client.health().await.unwrap();
+        // Each deployment is mined into its own block
+        deploy_stub(&anvil, ACCEPT_ALL_INIT_CODE).await;
+        let after = client.health().await.unwrap();
+        assert!(after > before, "{rpc_url}: {after} <= {before}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_update_tone_sends_transaction() {
+    let anvil = Anvil::new().spawn();
+    let ans = deploy_stub(&anvil, ACCEPT_ALL_INIT_CODE).await;
+    let client = evm_client(&anvil, &[("ans_state_manager", ans)]).await;
+
+    client
+        .update_tone(0, Vti::new(7000).unwrap(), ANSState::DANGER)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_revoke_sends_transaction() {
+    let anvil = Anvil::new().spawn();
+    let issuer = deploy_stub(&anvil, ACCEPT_ALL_INIT_CODE).await;
+    let client = evm_client(&anvil, &[("capability_issuer", issuer)]).await;
+
+    client.revoke_capability("42", 1).await.unwrap();
+    assert!(client.revoke_capability("not-a-token", 1).await.is_err());
+}
@@ -53,6 +53,22 @@ pub struct EvidenceMessage {
     pub timestamp: u64,
 }
 
+/// Capability token attestation, signed by a trusted issuer so executors
+/// can validate the token without chain access
+#[derive(Debug, Clone, Serialize, Deserialize, Eip712, EthAbiType)]
+#[eip712(
+    name = "VagusCapabilityToken",
+    version = "1"
+)]
+pub struct TokenMessage {
+    pub token_id: U256,
+    pub executor_id: U256,
+    pub action_id: [u8; 32],
+    pub scaled_limits_hash: [u8; 32],
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
 /// Signed message wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedMessage<T> {
@@ -174,6 +190,55 @@ impl VagusCrypto {
         Ok(Address::zero())
     }
 
+    /// Sign a capability token for offline validation
+    pub async fn sign_token(
+        &self,
+        token: TokenMessage,
+        private_key: &str,
+    ) -> Result<SignedMessage<TokenMessage>, CryptoError> {
+        let wallet = private_key
+            .parse::<LocalWallet>()
+            .map_err(|e| CryptoError::InvalidAddress(e.to_string()))?;
+
+        let digest = self.typed_digest(&token)?;
+
+        let signature = wallet
+            .sign_message(&digest)
+            .await
+            .map_err(|e| CryptoError::SigningError(e.to_string()))?;
+
+        Ok(SignedMessage {
+            message: token,
+            signature: signature.to_vec(),
+        })
+    }
+
+    /// Recover the signer of a capability token
+    pub fn verify_token_signature(
+        &self,
+        signed_token: &SignedMessage<TokenMessage>,
+    ) -> Result<Address, CryptoError> {
+        let digest = self.typed_digest(&signed_token.message)?;
+
+        let signature = ethers::types::Signature::try_from(signed_token.signature.as_slice())
+            .map_err(|e| CryptoError::InvalidSignature(e.to_string()))?;
+
+        // sign_message applies the EIP-191 prefix, so recover from the raw digest bytes
+        signature
+            .recover(digest.to_vec())
+            .map_err(|e| CryptoError::VerificationError(e.to_string()))
+    }
+
+    /// Compute the EIP-712 digest of a message under our domain
+    fn typed_digest<T: Eip712>(&self, message: &T) -> Result<[u8; 32], CryptoError> {
+        let domain_separator = self.domain.separator();
+        let struct_hash = message
+            .struct_hash()
+            .map_err(|e| CryptoError::SigningError(e.to_string()))?;
+        let digest_input = [b"\x19\x01", domain_separator.as_slice(), struct_hash.as_slice()].concat();
+        Ok(ethers::utils::keccak256(&digest_input))
+    }
+
     /// Verify capability token validity by checking signature and timing
     pub fn verify_capability_token(
         &self,
@@ -285,7 +350,7 @@ mod tests {
 /// Deterministic CBOR encoding for cross-chain consistency
 pub mod cbor {
     use super::*;
-    use sha3::{Digest, Sha3_256};
+    use sha3::{Digest, Keccak256};
     use sha2::Sha256;
 
     /// Encode data to deterministic CBOR bytes
@@ -305,7 +370,7 @@ pub mod cbor {
 
     /// Compute Keccak256 hash of CBOR bytes
     pub fn hash_keccak(cbor_bytes: &[u8]) -> [u8; 32] {
-        let mut hasher = Sha3_256::new();
+        let mut hasher = Keccak256::new();
         hasher.update(cbor_bytes);
         let result = hasher.finalize();
         result.into()
@@ -12,6 +12,19 @@ pub trait ControlBarrierFunction: Send + Sync {
     /// Returns a SafetyGuard indicating if the pose is allowed and any scaling needed
     async fn guard(&self, setpoint: &Pose, sensor_data: &SensorData) -> anyhow::Result<SafetyGuard>;
 
+    /// Check every waypoint of a trajectory, returning one SafetyGuard per setpoint
+    async fn guard_trajectory(
+        &self,
+        setpoints: &[Pose],
+        sensor_data: &SensorData,
+    ) -> anyhow::Result<Vec<SafetyGuard>> {
+        let mut guards = Vec::with_capacity(setpoints.len());
+        for setpoint in setpoints {
+            guards.push(self.guard(setpoint, sensor_data).await?);
+        }
+        Ok(guards)
+    }
+
     /// Update CBF parameters based on current conditions
     async fn update_parameters(&mut self, conditions: &SafetyConditions) -> anyhow::Result<()>;
 }
@@ -20,6 +33,8 @@ pub trait ControlBarrierFunction: Send + Sync {
 #[derive(Debug, Clone)]
 pub struct SensorData {
     pub human_distances: Vec<f64>, // Distances to humans in mm
+    pub human_positions: Vec<[f64; 3]>, // Located humans in the setpoint frame, mm
+    pub position: Option<[f64; 3]>, // Executor's current position in the setpoint frame, mm
     pub temperatures: Vec<f64>,    // Temperatures in °C
     pub velocities: Vec<f64>,      // Current velocities in m/s
     pub jerks: Vec<f64>,          // Current jerks in m/s²
@@ -34,12 +49,56 @@ pub struct SafetyConditions {
     pub vti_value: f64,          // Current VTI value
 }
 
+/// Policy for combining the local CBF decision with the chain-provided guard
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GuardCombinationPolicy {
+    /// Apply the stricter (lower) of the two scaling factors
+    #[default]
+    MostConservative,
+    /// Blend the two scaling factors, `local_weight` in [0, 1] going to the CBF
+    Weighted { local_weight: f64 },
+}
+
+impl GuardCombinationPolicy {
+    /// Combine a local CBF guard with the chain guard into the effective guard.
+    /// A block from either side always blocks, regardless of policy.
+    pub fn combine(&self, local: &SafetyGuard, chain: &SafetyGuard) -> SafetyGuard {
+        if !local.allowed || !chain.allowed {
+            let reason = if !local.allowed { &local.reason } else { &chain.reason };
+            return SafetyGuard {
+                allowed: false,
+                scaling_factor: 0.0,
+                reason: reason.clone(),
+            };
+        }
+
+        match *self {
+            GuardCombinationPolicy::MostConservative => {
+                if chain.scaling_factor < local.scaling_factor {
+                    chain.clone()
+                } else {
+                    local.clone()
+                }
+            }
+            GuardCombinationPolicy::Weighted { local_weight } => {
+                let w = local_weight.clamp(0.0, 1.0);
+                SafetyGuard {
+                    allowed: true,
+                    scaling_factor: w * local.scaling_factor + (1.0 - w) * chain.scaling_factor,
+                    reason: local.reason.clone().or_else(|| chain.reason.clone()),
+                }
+            }
+        }
+    }
+}
+
 /// Basic CBF implementation (placeholder)
 pub struct BasicCBF {
     max_human_distance: f64,
     max_temperature: f64,
     max_velocity: f64,
     max_jerk: f64,
+    waypoint_interval: f64,
 }
 
 impl BasicCBF {
@@ -49,6 +108,7 @@ impl BasicCBF {
             max_temperature: 80.0,     // 80°C max temperature
             max_velocity: 2.0,         // 2 m/s max velocity
             max_jerk: 5.0,            // 5 m/s² max jerk
+            waypoint_interval: DEFAULT_WAYPOINT_INTERVAL,
         }
     }
 
@@ -63,20 +123,87 @@ impl BasicCBF {
             max_temperature,
             max_velocity,
             max_jerk,
+            waypoint_interval: DEFAULT_WAYPOINT_INTERVAL,
+        }
+    }
+
+    /// Set the time between trajectory waypoints in seconds, used to derive
+    /// velocity and jerk from the pose deltas
+    pub fn set_waypoint_interval(&mut self, waypoint_interval: f64) {
+        self.waypoint_interval = waypoint_interval;
+    }
+
+    /// Clearance the setpoint has to keep: the nearest raw human distance or,
+    /// with located humans, the nearest one the setpoint closes in on. Humans
+    /// the executor moves away from don't count, so it can always retreat.
+    fn governing_clearance(&self, setpoint: &Pose, sensor_data: &SensorData) -> f64 {
+        if sensor_data.human_positions.is_empty() {
+            return sensor_data.human_distances.iter().fold(f64::INFINITY, |a, &b| a.min(b));
         }
+
+        sensor_data
+            .human_positions
+            .iter()
+            .filter_map(|human| {
+                let clearance = distance(&setpoint.position, human);
+                let approaching = match &sensor_data.position {
+                    Some(position) => clearance < distance(position, human),
+                    // Without the current position a retreat can't be told apart
+                    None => true,
+                };
+                approaching.then_some(clearance)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Default time between trajectory waypoints in seconds (10 Hz)
+const DEFAULT_WAYPOINT_INTERVAL: f64 = 0.1;
+
+/// Fraction of each limit over which motion slows down to a stop
+const SLOWDOWN_BAND: f64 = 0.25;
+
+// Scaling for a value that must stay below `limit`: 1.0 until it enters the
+// slowdown band, then linearly down to 0.0 at the limit
+fn upper_margin_factor(value: f64, limit: f64) -> f64 {
+    if value >= limit {
+        return 0.0;
+    }
+    ((limit - value) / (limit * SLOWDOWN_BAND)).clamp(0.0, 1.0)
+}
+
+// Scaling for a distance that must stay above `limit`, mirroring
+// `upper_margin_factor`
+fn lower_margin_factor(value: f64, limit: f64) -> f64 {
+    if value <= limit {
+        return 0.0;
     }
+    ((value - limit) / (limit * SLOWDOWN_BAND)).clamp(0.0, 1.0)
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
 }
 
 #[async_trait]
 impl ControlBarrierFunction for BasicCBF {
     async fn guard(&self, setpoint: &Pose, sensor_data: &SensorData) -> anyhow::Result<SafetyGuard> {
-        // Check human safety
-        let min_human_dist = sensor_data.human_distances.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-        if min_human_dist < self.max_human_distance {
+        // Check human safety: against the setpoint when humans are located,
+        // otherwise against the raw distance readings
+        let clearance = self.governing_clearance(setpoint, sensor_data);
+        if clearance < self.max_human_distance {
+            let reason = if sensor_data.human_positions.is_empty() {
+                "Human too close".to_string()
+            } else {
+                format!(
+                    "Setpoint approaches human: {:.0}mm clearance, {:.0}mm required",
+                    clearance, self.max_human_distance
+                )
+            };
             return Ok(SafetyGuard {
                 allowed: false,
                 scaling_factor: 0.0,
-                reason: Some("Human too close".to_string()),
+                reason: Some(reason),
             });
         }
 
@@ -110,14 +237,84 @@ impl ControlBarrierFunction for BasicCBF {
             });
         }
 
-        // All checks passed
+        // All checks passed; slow down by the tightest remaining margin
+        let margins = [
+            (lower_margin_factor(clearance, self.max_human_distance), "Human clearance"),
+            (upper_margin_factor(max_vel, self.max_velocity), "Velocity"),
+            (upper_margin_factor(max_jerk, self.max_jerk), "Jerk"),
+        ];
+        let (scaling_factor, limiting) = margins
+            .into_iter()
+            .fold((1.0, None), |(factor, limiting), (margin, constraint)| {
+                if margin < factor {
+                    (margin, Some(constraint))
+                } else {
+                    (factor, limiting)
+                }
+            });
+
         Ok(SafetyGuard {
             allowed: true,
-            scaling_factor: 1.0,
-            reason: None,
+            scaling_factor,
+            reason: limiting.map(|constraint| format!("{} near limit", constraint)),
         })
     }
 
+    async fn guard_trajectory(
+        &self,
+        setpoints: &[Pose],
+        sensor_data: &SensorData,
+    ) -> anyhow::Result<Vec<SafetyGuard>> {
+        let mut guards = Vec::with_capacity(setpoints.len());
+        for (i, setpoint) in setpoints.iter().enumerate() {
+            // Each waypoint is a move from the one before it
+            let mut waypoint_data = sensor_data.clone();
+            if i > 0 {
+                waypoint_data.position = Some(setpoints[i - 1].position);
+            }
+            let mut guard = self.guard(setpoint, &waypoint_data).await?;
+
+            // Velocity and jerk the path itself demands between waypoints, in
+            // m/s and m/s³ from positions in mm
+            let dt = self.waypoint_interval;
+            let position = |back: usize| setpoints[i - back].position;
+            let mut derived = Vec::new();
+            if i >= 1 {
+                let velocity = distance(&position(0), &position(1)) / 1000.0 / dt;
+                derived.push((velocity, self.max_velocity, "Waypoint velocity"));
+            }
+            if i >= 3 {
+                let third_difference: [f64; 3] = std::array::from_fn(|axis| {
+                    position(0)[axis] - 3.0 * position(1)[axis] + 3.0 * position(2)[axis]
+                        - position(3)[axis]
+                });
+                let jerk = distance(&third_difference, &[0.0; 3]) / 1000.0 / dt.powi(3);
+                derived.push((jerk, self.max_jerk, "Waypoint jerk"));
+            }
+
+            for (value, limit, constraint) in derived {
+                if !guard.allowed {
+                    break;
+                }
+                if value > limit {
+                    guard = SafetyGuard {
+                        allowed: false,
+                        scaling_factor: 0.0,
+                        reason: Some(format!("{} too high", constraint)),
+                    };
+                } else if upper_margin_factor(value, limit) < guard.scaling_factor {
+                    guard = SafetyGuard {
+                        allowed: true,
+                        scaling_factor: upper_margin_factor(value, limit),
+                        reason: Some(format!("{} near limit", constraint)),
+                    };
+                }
+            }
+            guards.push(guard);
+        }
+        Ok(guards)
+    }
+
     async fn update_parameters(&mut self, conditions: &SafetyConditions) -> anyhow::Result<()> {
         // Adjust limits based on ANS state
         match conditions.ans_state.as_str() {
@@ -157,6 +354,8 @@ mod tests {
 
         let sensor_data = SensorData {
             human_distances: vec![500.0, 600.0], // Safe distances
+            human_positions: vec![],
+            position: None,
             temperatures: vec![50.0, 60.0],      // Safe temperatures
             velocities: vec![1.0, 1.5],          // Safe velocities
             jerks: vec![2.0, 3.0],              // Safe jerks
@@ -179,6 +378,8 @@ mod tests {
 
         let sensor_data = SensorData {
             human_distances: vec![200.0, 600.0], // One human too close
+            human_positions: vec![],
+            position: None,
             temperatures: vec![50.0, 60.0],
             velocities: vec![1.0, 1.5],
             jerks: vec![2.0, 3.0],
@@ -190,4 +391,183 @@ mod tests {
         assert_eq!(guard.scaling_factor, 0.0);
         assert_eq!(guard.reason, Some("Human too close".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_setpoint_direction_decides_human_clearance() {
+        let cbf = BasicCBF::new();
+        let pose = |x| Pose {
+            position: [x, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+
+        // Executor at the origin with a human 250mm ahead: already too close,
+        // but backing off to 275mm still gains clearance
+        let sensor_data = SensorData {
+            human_distances: vec![250.0],
+            human_positions: vec![[250.0, 0.0, 0.0]],
+            position: Some([0.0, 0.0, 0.0]),
+            temperatures: vec![50.0],
+            velocities: vec![1.0],
+            jerks: vec![2.0],
+            battery_level: Some(80.0),
+        };
+
+        let retreat = cbf.guard(&pose(-25.0), &sensor_data).await.unwrap();
+        assert!(retreat.allowed);
+        assert_eq!(retreat.scaling_factor, 1.0);
+
+        let approach = cbf.guard(&pose(100.0), &sensor_data).await.unwrap();
+        assert!(!approach.allowed);
+        assert_eq!(approach.scaling_factor, 0.0);
+        assert_eq!(
+            approach.reason,
+            Some("Setpoint approaches human: 150mm clearance, 300mm required".to_string())
+        );
+
+        // Approaching while keeping the minimum clearance is fine
+        let sensor_data = SensorData {
+            human_positions: vec![[1000.0, 0.0, 0.0]],
+            ..sensor_data
+        };
+        assert!(cbf.guard(&pose(100.0), &sensor_data).await.unwrap().allowed);
+
+        // Without the current position a retreat can't be recognised
+        let sensor_data = SensorData {
+            human_positions: vec![[250.0, 0.0, 0.0]],
+            position: None,
+            ..sensor_data
+        };
+        assert!(!cbf.guard(&pose(-25.0), &sensor_data).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_margins_scale_continuously() {
+        let cbf = BasicCBF::new();
+        let setpoint = Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let sensor_data = |human_distance: f64, velocity: f64, jerk: f64| SensorData {
+            human_distances: vec![human_distance],
+            human_positions: vec![],
+            position: None,
+            temperatures: vec![50.0],
+            velocities: vec![velocity],
+            jerks: vec![jerk],
+            battery_level: Some(80.0),
+        };
+        let scaling = |guard: SafetyGuard| {
+            assert!(guard.allowed);
+            (guard.scaling_factor, guard.reason)
+        };
+
+        // Clearance slows the executor linearly over the last 75mm above 300mm
+        let clearances = [(400.0, 1.0), (375.0, 1.0), (356.25, 0.75), (337.5, 0.5), (300.0, 0.0)];
+        for (human_distance, expected) in clearances {
+            let guard = cbf.guard(&setpoint, &sensor_data(human_distance, 1.0, 2.0)).await.unwrap();
+            assert!((scaling(guard).0 - expected).abs() < 1e-9, "{} mm", human_distance);
+        }
+        let guard = cbf.guard(&setpoint, &sensor_data(337.5, 1.0, 2.0)).await.unwrap();
+        assert_eq!(guard.reason, Some("Human clearance near limit".to_string()));
+
+        // Velocity and jerk margins do the same; the tightest one wins
+        let guard = cbf.guard(&setpoint, &sensor_data(337.5, 1.9, 2.0)).await.unwrap();
+        let (factor, reason) = scaling(guard);
+        assert!((factor - 0.2).abs() < 1e-9);
+        assert_eq!(reason, Some("Velocity near limit".to_string()));
+        let guard = cbf.guard(&setpoint, &sensor_data(500.0, 1.0, 4.5)).await.unwrap();
+        let (factor, reason) = scaling(guard);
+        assert!((factor - 0.4).abs() < 1e-9);
+        assert_eq!(reason, Some("Jerk near limit".to_string()));
+
+        // Approaching a located human scales by the setpoint's clearance
+        let sensor_data = SensorData {
+            human_positions: vec![[437.5, 0.0, 0.0]],
+            position: Some([0.0, 0.0, 0.0]),
+            ..sensor_data(1000.0, 1.0, 2.0)
+        };
+        let approach = Pose {
+            position: [100.0, 0.0, 0.0],
+            ..setpoint
+        };
+        let (factor, _) = scaling(cbf.guard(&approach, &sensor_data).await.unwrap());
+        assert!((factor - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_trajectory_enforces_velocity_between_waypoints() {
+        let cbf = BasicCBF::new();
+        let waypoint = |x| Pose {
+            position: [x, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let sensor_data = SensorData {
+            human_distances: vec![1000.0],
+            human_positions: vec![],
+            position: None,
+            temperatures: vec![50.0],
+            velocities: vec![0.5],
+            jerks: vec![1.0],
+            battery_level: Some(80.0),
+        };
+
+        // 100mm then 500mm in 0.1s steps: 1 m/s, then 5 m/s over a 2 m/s limit
+        let path = [waypoint(0.0), waypoint(100.0), waypoint(600.0)];
+        for setpoint in &path {
+            assert!(cbf.guard(setpoint, &sensor_data).await.unwrap().allowed);
+        }
+
+        let guards = cbf.guard_trajectory(&path, &sensor_data).await.unwrap();
+        assert_eq!(guards.len(), 3);
+        assert!(guards[0].allowed && guards[1].allowed);
+        assert_eq!(guards[1].scaling_factor, 1.0);
+        assert!(!guards[2].allowed);
+        assert_eq!(guards[2].reason, Some("Waypoint velocity too high".to_string()));
+
+        // The same path spread over a second per step stays within limits
+        let mut cbf = cbf;
+        cbf.set_waypoint_interval(1.0);
+        let guards = cbf.guard_trajectory(&path, &sensor_data).await.unwrap();
+        assert!(guards.iter().all(|guard| guard.allowed));
+    }
+
+    fn scaled(scaling_factor: f64, reason: &str) -> SafetyGuard {
+        SafetyGuard {
+            allowed: true,
+            scaling_factor,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_most_conservative_takes_stricter_scaling() {
+        let local = scaled(0.5, "CBF");
+        let chain = scaled(0.3, "ANS guard");
+
+        let effective = GuardCombinationPolicy::default().combine(&local, &chain);
+        assert!(effective.allowed);
+        assert_eq!(effective.scaling_factor, 0.3);
+        assert_eq!(effective.reason, Some("ANS guard".to_string()));
+
+        // Order of arguments doesn't change the outcome
+        let effective = GuardCombinationPolicy::MostConservative.combine(&chain, &local);
+        assert_eq!(effective.scaling_factor, 0.3);
+    }
+
+    #[test]
+    fn test_weighted_policy_blends_and_blocks() {
+        let policy = GuardCombinationPolicy::Weighted { local_weight: 0.5 };
+        let effective = policy.combine(&scaled(0.5, "CBF"), &scaled(0.3, "ANS guard"));
+        assert!((effective.scaling_factor - 0.4).abs() < 1e-9);
+
+        let blocked = SafetyGuard {
+            allowed: false,
+            scaling_factor: 0.0,
+            reason: Some("SHUTDOWN".to_string()),
+        };
+        let effective = policy.combine(&scaled(1.0, "CBF"), &blocked);
+        assert!(!effective.allowed);
+        assert_eq!(effective.scaling_factor, 0.0);
+        assert_eq!(effective.reason, Some("SHUTDOWN".to_string()));
+    }
 }
@@ -5,8 +5,21 @@
 use anyhow::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use vagus_telemetry::{SensorReading, TelemetryWindow, WindowMetrics, VagalToneIndicator};
+use tokio::sync::{mpsc, RwLock};
+use tracing::warn;
+use vagus_telemetry::{
+    SensorIssue, SensorReading, SensorRegistry, TelemetryWindow, WindowMetrics, VagalToneIndicator,
+};
+
+/// What to do with readings that fail sensor registry validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidReadingPolicy {
+    /// Log and record the issue, keeping the reading in the window
+    #[default]
+    Warn,
+    /// Reject the reading with an error
+    Reject,
+}
 
 /// Telemetry collector for aggregating sensor data
 #[derive(Clone)]
@@ -15,44 +28,139 @@ pub struct TelemetryCollector {
     windows: Arc<RwLock<HashMap<u64, TelemetryWindow>>>,
     /// Window duration in milliseconds
     window_duration_ms: u64,
+    /// Known sensor types and their aggregation rules
+    registry: Arc<SensorRegistry>,
+    /// Handling of unknown types and unit mismatches
+    invalid_policy: InvalidReadingPolicy,
+    /// Issues flagged under the `Warn` policy
+    flagged: Arc<RwLock<Vec<SensorIssue>>>,
+    /// Latest accepted reading timestamp per executor and sensor id
+    last_seen: Arc<RwLock<HashMap<u64, HashMap<String, u64>>>>,
+    /// Receives the finalized metrics of each window closed by a rollover
+    window_sender: Option<mpsc::Sender<WindowMetrics>>,
+    /// Cap on readings retained per window
+    max_readings: Option<usize>,
 }
 
 impl TelemetryCollector {
     /// Create a new telemetry collector
     pub fn new(window_duration_ms: u64) -> Self {
+        Self::with_registry(window_duration_ms, SensorRegistry::default(), InvalidReadingPolicy::default())
+    }
+
+    /// Create a collector with a custom sensor registry and validation policy
+    pub fn with_registry(
+        window_duration_ms: u64,
+        registry: SensorRegistry,
+        invalid_policy: InvalidReadingPolicy,
+    ) -> Self {
         Self {
             windows: Arc::new(RwLock::new(HashMap::new())),
             window_duration_ms,
+            registry: Arc::new(registry),
+            invalid_policy,
+            flagged: Arc::new(RwLock::new(Vec::new())),
+            last_seen: Arc::new(RwLock::new(HashMap::new())),
+            window_sender: None,
+            max_readings: None,
         }
     }
 
+    /// Cap the readings each window keeps in memory; see `TelemetryWindow::set_max_readings`
+    pub fn set_max_readings(&mut self, max_readings: usize) {
+        self.max_readings = Some(max_readings);
+    }
+
+    fn new_window(&self, executor_id: u64, timestamp: u64) -> TelemetryWindow {
+        let window_start = timestamp / self.window_duration_ms * self.window_duration_ms;
+        let window_end = window_start + self.window_duration_ms;
+
+        let mut window = TelemetryWindow::new(executor_id, window_start, window_end);
+        if let Some(max_readings) = self.max_readings {
+            window.set_max_readings(max_readings);
+        }
+        window
+    }
+
+    /// Emit the finalized metrics of each window when a newer reading rolls it over
+    pub fn set_window_sender(&mut self, sender: mpsc::Sender<WindowMetrics>) {
+        self.window_sender = Some(sender);
+    }
+
+    /// Sensor issues flagged so far under the `Warn` policy
+    pub async fn flagged_readings(&self) -> Vec<SensorIssue> {
+        self.flagged.read().await.clone()
+    }
+
     /// Add a sensor reading to the appropriate window
     pub async fn add_reading(&self, executor_id: u64, reading: SensorReading) -> Result<()> {
-        let mut windows = self.windows.write().await;
+        if let Err(issue) = self.registry.validate(&reading) {
+            match self.invalid_policy {
+                InvalidReadingPolicy::Reject => return Err(issue.into()),
+                InvalidReadingPolicy::Warn => {
+                    warn!("Executor {}: {}", executor_id, issue);
+                    self.flagged.write().await.push(issue);
+                }
+            }
+        }
 
-        let window = windows.entry(executor_id).or_insert_with(|| {
-            let window_start = reading.timestamp / self.window_duration_ms * self.window_duration_ms;
-            let window_end = window_start + self.window_duration_ms;
+        let mut windows = self.windows.write().await;
 
-            TelemetryWindow::new(executor_id, window_start, window_end)
-        });
+        let window = windows
+            .entry(executor_id)
+            .or_insert_with(|| self.new_window(executor_id, reading.timestamp));
 
         // Check if we need to start a new window
+        let mut finalized = None;
         if reading.timestamp >= window.window_end {
-            let new_window_start = reading.timestamp / self.window_duration_ms * self.window_duration_ms;
-            let new_window_end = new_window_start + self.window_duration_ms;
+            finalized = Some(window.compute_metrics_with(&self.registry));
+            *window = self.new_window(executor_id, reading.timestamp);
+        }
+
+        let (sensor_id, timestamp) = (reading.sensor_id.clone(), reading.timestamp);
+        let added = window.add_reading(reading);
+        drop(windows);
 
-            *window = TelemetryWindow::new(executor_id, new_window_start, new_window_end);
+        // Sent without holding the windows lock, so a slow consumer never
+        // blocks readings for other executors
+        if let (Some(metrics), Some(sender)) = (finalized, &self.window_sender) {
+            if sender.send(metrics).await.is_err() {
+                warn!("Executor {}: window metrics receiver dropped", executor_id);
+            }
         }
+        added?;
 
-        window.add_reading(reading);
+        let mut last_seen = self.last_seen.write().await;
+        let seen = last_seen.entry(executor_id).or_default().entry(sensor_id).or_insert(timestamp);
+        *seen = (*seen).max(timestamp);
         Ok(())
     }
 
+    /// Sensors of an executor whose latest reading is more than `max_gap_ms`
+    /// older than `now_ms`. A quiet safety-critical sensor is not the same as
+    /// a safe reading, so callers should escalate rather than trust the window.
+    pub async fn stale_sensors(
+        &self,
+        executor_id: u64,
+        now_ms: u64,
+        max_gap_ms: u64,
+    ) -> Vec<String> {
+        let last_seen = self.last_seen.read().await;
+        let mut stale: Vec<String> = last_seen
+            .get(&executor_id)
+            .into_iter()
+            .flatten()
+            .filter(|(_, &timestamp)| now_ms.saturating_sub(timestamp) > max_gap_ms)
+            .map(|(sensor_id, _)| sensor_id.clone())
+            .collect();
+        stale.sort();
+        stale
+    }
+
     /// Get current window metrics for an executor
     pub async fn get_current_metrics(&self, executor_id: u64) -> Result<Option<WindowMetrics>> {
         let windows = self.windows.read().await;
-        Ok(windows.get(&executor_id).map(|window| window.compute_metrics()))
+        Ok(windows.get(&executor_id).map(|window| window.compute_metrics_with(&self.registry)))
     }
 
     /// Get current window for an executor
@@ -247,6 +355,60 @@ mod tests {
         assert_eq!(metrics.min_human_distance, Some(300.0));
     }
 
+    #[tokio::test]
+    async fn test_rollover_emits_finalized_window_metrics() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        let mut collector = TelemetryCollector::new(1000);
+        collector.set_window_sender(sender);
+        let reading = |value: f64, timestamp: u64| SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_distance".to_string(),
+            value,
+            unit: "mm".to_string(),
+            timestamp,
+        };
+
+        collector.add_reading(42, reading(600.0, 200)).await.unwrap();
+        collector.add_reading(42, reading(450.0, 700)).await.unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        // Crossing into 1000..2000 closes 0..1000
+        collector.add_reading(42, reading(900.0, 1100)).await.unwrap();
+        collector.add_reading(42, reading(800.0, 1300)).await.unwrap();
+
+        let finalized = receiver.try_recv().unwrap();
+        assert_eq!(finalized.executor_id, 42);
+        assert_eq!((finalized.window_start, finalized.window_end), (0, 1000));
+        assert_eq!(finalized.min_human_distance, Some(450.0));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quiet_sensor_is_reported_stale() {
+        let collector = TelemetryCollector::new(1000);
+        let reading = |sensor_id: &str, timestamp: u64| SensorReading {
+            sensor_id: sensor_id.to_string(),
+            sensor_type: "human_distance".to_string(),
+            value: 800.0,
+            unit: "mm".to_string(),
+            timestamp,
+        };
+
+        // The rear sensor stops reporting after its first reading
+        collector.add_reading(42, reading("lidar_rear", 1000)).await.unwrap();
+        for timestamp in [1000, 1500, 2000, 2500] {
+            collector.add_reading(42, reading("lidar_front", timestamp)).await.unwrap();
+        }
+
+        assert!(collector.stale_sensors(42, 1200, 500).await.is_empty());
+        assert_eq!(collector.stale_sensors(42, 2600, 500).await, vec!["lidar_rear".to_string()]);
+        assert_eq!(
+            collector.stale_sensors(42, 3100, 500).await,
+            vec!["lidar_front".to_string(), "lidar_rear".to_string()]
+        );
+        assert!(collector.stale_sensors(7, 3100, 500).await.is_empty());
+    }
+
     #[test]
     fn test_mock_sensor_generator() {
         let mut generator = MockSensorDataGenerator::new(42);
@@ -260,4 +422,34 @@ mod tests {
             .collect();
         assert_eq!(types.len(), 4); // Should have 4 different types
     }
+
+    #[tokio::test]
+    async fn test_misspelled_sensor_type_is_flagged() {
+        let reading = SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_dist".to_string(),
+            value: 150.0,
+            unit: "mm".to_string(),
+            timestamp: 1000,
+        };
+
+        // Default policy keeps going but records the issue
+        let collector = TelemetryCollector::new(1000);
+        collector.add_reading(42, reading.clone()).await.unwrap();
+        let flagged = collector.flagged_readings().await;
+        assert_eq!(flagged.len(), 1);
+        assert!(matches!(
+            &flagged[0],
+            SensorIssue::UnknownType { sensor_type, .. } if sensor_type == "human_dist"
+        ));
+
+        // Strict policy rejects the reading outright
+        let strict = TelemetryCollector::with_registry(
+            1000,
+            SensorRegistry::default(),
+            InvalidReadingPolicy::Reject,
+        );
+        assert!(strict.add_reading(42, reading).await.is_err());
+        assert!(strict.get_current_window(42).await.unwrap().is_none());
+    }
 }
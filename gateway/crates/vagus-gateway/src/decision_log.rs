@@ -0,0 +1,133 @@
+//! Decision Log
+//!
+//! Append-only record of the gateway's safety decisions and their inputs, so
+//! operators can reconstruct what it saw and did after an incident. Memory is
+//! bounded to the most recent records; an optional file keeps the full
+//! history as JSON lines.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use vagus_telemetry::{Pose, SafetyGuard};
+
+/// Default number of records kept in memory
+pub const DEFAULT_DECISION_LOG_CAPACITY: usize = 1024;
+
+/// A safety decision and the inputs it was made from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Decision {
+    /// Local VTI computed from the current telemetry window
+    Vti { value: f64, contributions: HashMap<String, f64> },
+    /// Local CBF verdict for a setpoint
+    Cbf { setpoint: Pose, guard: SafetyGuard },
+    /// CBF verdict combined with the chain guard
+    EffectiveGuard { chain: SafetyGuard, effective: SafetyGuard },
+    /// Evidence packet built for submission
+    EvidenceSubmitted { vti: f64, metrics_hash: [u8; 32], timestamp: u64 },
+}
+
+/// A logged decision with when and for whom it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// Wall-clock time of the decision in milliseconds
+    pub logged_at_ms: u64,
+    pub executor_id: u64,
+    pub decision: Decision,
+}
+
+struct LogState {
+    capacity: usize,
+    records: VecDeque<DecisionRecord>,
+    path: Option<PathBuf>,
+}
+
+/// Bounded decision log shared between the gateway's tasks
+#[derive(Clone)]
+pub struct DecisionLog {
+    state: Arc<RwLock<LogState>>,
+}
+
+impl DecisionLog {
+    /// In-memory log keeping the last `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self::with_path(capacity, None)
+    }
+
+    /// Log that also appends every record to `path`
+    pub fn persisted(capacity: usize, path: PathBuf) -> Self {
+        Self::with_path(capacity, Some(path))
+    }
+
+    fn with_path(capacity: usize, path: Option<PathBuf>) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(LogState {
+                capacity: capacity.max(1),
+                records: VecDeque::new(),
+                path,
+            })),
+        }
+    }
+
+    /// Append a decision
+    pub async fn record(&self, executor_id: u64, decision: Decision) -> Result<()> {
+        let record = DecisionRecord {
+            logged_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            executor_id,
+            decision,
+        };
+
+        let mut state = self.state.write().await;
+        if let Some(path) = &state.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        }
+
+        state.records.push_back(record);
+        while state.records.len() > state.capacity {
+            state.records.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Records still in memory, oldest first
+    pub async fn records(&self) -> Vec<DecisionRecord> {
+        self.state.read().await.records.iter().cloned().collect()
+    }
+
+    /// Records logged at or after `since_ms`, oldest first
+    pub async fn records_since(&self, since_ms: u64) -> Vec<DecisionRecord> {
+        self.state
+            .read()
+            .await
+            .records
+            .iter()
+            .filter(|record| record.logged_at_ms >= since_ms)
+            .cloned()
+            .collect()
+    }
+
+    /// Export the in-memory records as JSON lines
+    pub async fn export_json_lines(&self) -> Result<String> {
+        let mut out = String::new();
+        for record in self.state.read().await.records.iter() {
+            out.push_str(&serde_json::to_string(record)?);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+impl Default for DecisionLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_DECISION_LOG_CAPACITY)
+    }
+}
@@ -3,8 +3,33 @@
 //! Monitors blockchain events related to capability tokens and ANS state changes.
 
 use anyhow::Result;
-use ethers::types::Address;
+use ethers::contract::{abigen, EthEvent, EthLogDecode};
+use ethers::providers::{Middleware, Provider, StreamExt, Ws};
+use ethers::types::{Address, Filter, Log};
+use std::sync::Arc;
 use tokio::sync::mpsc;
+use tracing::warn;
+
+// Event shapes the core contracts emit (contracts/src/core/Events.sol)
+abigen!(
+    CoreEvents,
+    r#"[
+        event CapabilityIssued(uint256 indexed tokenId, uint256 indexed executorId, address indexed planner, bytes32 actionId, uint256 expiresAt, bytes32 paramsHashSha256, bytes32 paramsHashKeccak, bytes32 preStateRootSha256, bytes32 preStateRootKeccak)
+        event CapabilityRevoked(uint256 indexed tokenId, uint8 reason)
+        event AEPPosted(uint256 indexed executorId, bytes32 stateRoot, bytes32 metricsHash)
+        event VagalToneUpdated(uint256 indexed tone, uint8 indexed state, uint256 updatedAt)
+        event StateChanged(uint8 indexed fromState, uint8 indexed toState, uint256 vti, uint256 timestamp)
+        event ReflexTriggered(uint256 indexed executorId, string reason, uint256 revokedCount, uint256 triggeredAt, uint256[] revokedTokens)
+    ]"#,
+);
+
+// Per-executor state of the EVM ANS manager (contracts/src/core/ANSStateManager.sol)
+abigen!(
+    ExecutorStates,
+    r#"[
+        function getExecutorState(uint256 executorId) external view returns (uint8 state, uint32 tone, uint64 updatedAt)
+    ]"#,
+);
 
 /// Events that the gateway needs to monitor
 #[derive(Debug, Clone)]
@@ -33,16 +58,69 @@ pub enum GatewayEvent {
         tone: ethers::types::U256,
         state: u8,
     },
+    /// ANS state transition of some executor
+    StateChanged {
+        from_state: u8,
+        to_state: u8,
+    },
+    /// This gateway's executor state, read from the ANS manager after a
+    /// transition; `tone_ppm` is the manager's danger tone in ppm
+    ExecutorState {
+        state: u8,
+        tone_ppm: u32,
+    },
     /// Reflex triggered
     ReflexTriggered {
         executor_id: ethers::types::U256,
-        reason: [u8; 32],
-        revoked_tokens: Vec<ethers::types::U256>,
+        reason: String,
+        revoked_count: ethers::types::U256,
     },
 }
 
+impl GatewayEvent {
+    /// Decode a contract log, matching its topic0 against the event signatures
+    pub fn from_log(log: &Log) -> Result<Self> {
+        let event = match CoreEventsEvents::decode_log(&log.clone().into())? {
+            CoreEventsEvents::CapabilityIssuedFilter(e) => GatewayEvent::CapabilityIssued {
+                token_id: e.token_id,
+                executor_id: e.executor_id,
+                action_id: e.action_id,
+                // The EVM side commits to keccak hashes
+                params_hash: e.params_hash_keccak,
+                expires_at: u64::try_from(e.expires_at).unwrap_or(u64::MAX),
+            },
+            CoreEventsEvents::CapabilityRevokedFilter(e) => GatewayEvent::CapabilityRevoked {
+                token_id: e.token_id,
+                reason: e.reason,
+            },
+            CoreEventsEvents::AeppostedFilter(e) => GatewayEvent::AepPosted {
+                executor_id: e.executor_id,
+                state_root: e.state_root,
+                metrics_hash: e.metrics_hash,
+            },
+            CoreEventsEvents::VagalToneUpdatedFilter(e) => GatewayEvent::VagalToneUpdated {
+                tone: e.tone,
+                state: e.state,
+            },
+            CoreEventsEvents::StateChangedFilter(e) => GatewayEvent::StateChanged {
+                from_state: e.from_state,
+                to_state: e.to_state,
+            },
+            CoreEventsEvents::ReflexTriggeredFilter(e) => GatewayEvent::ReflexTriggered {
+                executor_id: e.executor_id,
+                reason: e.reason,
+                revoked_count: e.revoked_count,
+            },
+        };
+        Ok(event)
+    }
+}
+
 /// Event watcher that monitors blockchain events
 pub struct EventWatcher {
+    ws_url: String,
+    executor_id: u64,
+    backfill_from_block: u64,
     afferent_inbox_address: Address,
     ans_state_manager_address: Address,
     capability_issuer_address: Address,
@@ -50,18 +128,19 @@ pub struct EventWatcher {
 }
 
 impl EventWatcher {
-    /// Create a new event watcher
+    /// Create a new event watcher; the connection is made once watching starts
     pub async fn new(
-        _ws_url: &str,
+        ws_url: &str,
+        executor_id: u64,
         afferent_inbox_address: Address,
         ans_state_manager_address: Address,
         capability_issuer_address: Address,
         reflex_arc_address: Address,
     ) -> Result<Self> {
-        // Note: In production, we would connect to WebSocket here
-        // For MVP, this is a placeholder that doesn't actually connect
-
         Ok(Self {
+            ws_url: ws_url.to_string(),
+            executor_id,
+            backfill_from_block: 0,
             afferent_inbox_address,
             ans_state_manager_address,
             capability_issuer_address,
@@ -69,22 +148,89 @@ impl EventWatcher {
         })
     }
 
-    /// Start watching events and send them through the channel
+    /// First block replayed on startup; defaults to genesis
+    pub fn set_backfill_from(&mut self, block: u64) {
+        self.backfill_from_block = block;
+    }
+
+    /// Log filter for the Vagus events of the watched contracts
+    fn filter(&self) -> Filter {
+        Filter::new()
+            .address(vec![
+                self.afferent_inbox_address,
+                self.ans_state_manager_address,
+                self.capability_issuer_address,
+                self.reflex_arc_address,
+            ])
+            .topic0(vec![
+                CapabilityIssuedFilter::signature(),
+                CapabilityRevokedFilter::signature(),
+                AeppostedFilter::signature(),
+                VagalToneUpdatedFilter::signature(),
+                StateChangedFilter::signature(),
+                ReflexTriggeredFilter::signature(),
+            ])
+    }
+
+    /// Start watching events and send them through the channel, replaying
+    /// past events first so the gateway starts from the chain's view. Returns
+    /// once the receiving side is dropped; a closed subscription is an error.
     pub async fn start_watching(
         self,
-        _event_sender: mpsc::UnboundedSender<GatewayEvent>,
+        event_sender: mpsc::UnboundedSender<GatewayEvent>,
     ) -> Result<()> {
-        // TODO: Implement actual event watching with ethers WebSocket provider
-        // For MVP, this is a placeholder that just runs indefinitely
+        let provider = Provider::<Ws>::connect(&self.ws_url).await?;
+        let ans = ExecutorStates::new(self.ans_state_manager_address, Arc::new(provider.clone()));
 
-        // In production, this would:
-        // 1. Connect to WebSocket
-        // 2. Set up event filters for all relevant contracts
-        // 3. Parse incoming events and send them through the channel
+        // Subscribe before backfilling so nothing emitted in between is missed
+        let mut logs = provider.subscribe_logs(&self.filter()).await?;
+        let head = provider.get_block_number().await?.as_u64();
+        let backfill = self
+            .filter()
+            .from_block(self.backfill_from_block)
+            .to_block(head);
+        for log in provider.get_logs(&backfill).await? {
+            if !self.forward(&ans, &log, &event_sender).await {
+                return Ok(());
+            }
+        }
 
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-            // TODO: Check for new events and send them
+        while let Some(log) = logs.next().await {
+            // Already delivered by the backfill
+            if log.block_number.is_some_and(|block| block.as_u64() <= head) {
+                continue;
+            }
+            if !self.forward(&ans, &log, &event_sender).await {
+                return Ok(());
+            }
+        }
+
+        Err(anyhow::anyhow!("Event subscription to {} closed", self.ws_url))
+    }
+
+    // Decode and send a log; false once the receiver is gone. `StateChanged`
+    // names no executor, so this executor's own state is sent in its place.
+    async fn forward(
+        &self,
+        ans: &ExecutorStates<Provider<Ws>>,
+        log: &Log,
+        event_sender: &mpsc::UnboundedSender<GatewayEvent>,
+    ) -> bool {
+        let event = match GatewayEvent::from_log(log) {
+            Ok(GatewayEvent::StateChanged { .. }) => ans
+                .get_executor_state(self.executor_id.into())
+                .call()
+                .await
+                .map(|(state, tone_ppm, _)| GatewayEvent::ExecutorState { state, tone_ppm })
+                .map_err(Into::into),
+            event => event,
+        };
+        match event {
+            Ok(event) => event_sender.send(event).is_ok(),
+            Err(e) => {
+                warn!("Skipping log {:?}: {:?}", log.transaction_hash, e);
+                true
+            }
         }
     }
 }
@@ -92,12 +238,13 @@ impl EventWatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ethers::types::Address;
+    use ethers::types::{H256, U256};
 
     #[tokio::test]
     async fn test_event_watcher_creation() {
         let watcher = EventWatcher::new(
             "ws://localhost:8545",
+            42,
             Address::zero(),
             Address::zero(),
             Address::zero(),
@@ -108,4 +255,54 @@ mod tests {
 
         assert_eq!(watcher.afferent_inbox_address, Address::zero());
     }
+
+    #[test]
+    fn test_logs_decode_by_topic() {
+        let word = |value: u64| H256::from_low_u64_be(value);
+        let log = |topics: Vec<H256>, data: Vec<u8>| Log {
+            topics,
+            data: data.into(),
+            ..Default::default()
+        };
+
+        let tone = log(
+            vec![VagalToneUpdatedFilter::signature(), word(4000), word(1)],
+            word(1_700_000_000).as_bytes().to_vec(),
+        );
+        match GatewayEvent::from_log(&tone).unwrap() {
+            GatewayEvent::VagalToneUpdated { tone, state } => {
+                assert_eq!(tone, U256::from(4000));
+                assert_eq!(state, 1);
+            }
+            other => panic!("expected VagalToneUpdated, got {:?}", other),
+        }
+
+        let aep = log(
+            vec![AeppostedFilter::signature(), word(7)],
+            [[1u8; 32], [2u8; 32]].concat(),
+        );
+        match GatewayEvent::from_log(&aep).unwrap() {
+            GatewayEvent::AepPosted { executor_id, state_root, metrics_hash } => {
+                assert_eq!(executor_id, U256::from(7));
+                assert_eq!(state_root, [1u8; 32]);
+                assert_eq!(metrics_hash, [2u8; 32]);
+            }
+            other => panic!("expected AepPosted, got {:?}", other),
+        }
+
+        let state_changed = log(
+            vec![StateChangedFilter::signature(), word(0), word(2)],
+            [word(1000).as_bytes(), word(1_700_000_000).as_bytes()].concat(),
+        );
+        match GatewayEvent::from_log(&state_changed).unwrap() {
+            GatewayEvent::StateChanged { from_state, to_state } => {
+                assert_eq!((from_state, to_state), (0, 2));
+            }
+            other => panic!("expected StateChanged, got {:?}", other),
+        }
+
+        // Logs of other events are rejected rather than misread
+        let unknown = log(vec![H256::repeat_byte(0xab), word(4000), word(1)], vec![0u8; 32]);
+        assert!(GatewayEvent::from_log(&unknown).is_err());
+    }
 }
@@ -0,0 +1,54 @@
+//! Jittered Timers
+//!
+//! Gateways started together (e.g. after a fleet reboot) would otherwise tick
+//! in lockstep and hit the chain at the same moment. Each tick is delayed by
+//! a random extra amount so their schedules drift apart.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+/// Periodic timer firing every `interval_ms` plus up to `max_jitter_ms`
+pub struct JitteredTimer<R = StdRng> {
+    interval_ms: u64,
+    max_jitter_ms: u64,
+    rng: R,
+}
+
+impl JitteredTimer<StdRng> {
+    /// Create a timer; a fixed seed makes the jitter reproducible
+    pub fn new(interval_ms: u64, max_jitter_ms: u64, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self::with_rng(interval_ms, max_jitter_ms, rng)
+    }
+}
+
+impl<R: Rng> JitteredTimer<R> {
+    /// Create a timer drawing jitter from the given RNG
+    pub fn with_rng(interval_ms: u64, max_jitter_ms: u64, rng: R) -> Self {
+        Self {
+            interval_ms,
+            max_jitter_ms,
+            rng,
+        }
+    }
+
+    /// Delay until the next tick
+    pub fn next_delay(&mut self) -> Duration {
+        let jitter = if self.max_jitter_ms == 0 {
+            0
+        } else {
+            self.rng.gen_range(0..=self.max_jitter_ms)
+        };
+        Duration::from_millis(self.interval_ms + jitter)
+    }
+
+    /// Sleep until the next tick
+    pub async fn tick(&mut self) {
+        let delay = self.next_delay();
+        tokio::time::sleep(delay).await;
+    }
+}
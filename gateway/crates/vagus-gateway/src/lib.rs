@@ -5,7 +5,9 @@
 
 pub mod cbf;
 pub mod collector;
+pub mod decision_log;
 pub mod event_watcher;
+pub mod jitter;
 pub mod manager;
 pub mod token_manager;
 
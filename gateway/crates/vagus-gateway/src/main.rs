@@ -11,6 +11,7 @@ use vagus_chain::{ChainClient, ChainClientFactory, ChainConfig, ChainType};
 use vagus_crypto::VagusDomain;
 use vagus_gateway::manager::GatewayConfig;
 use vagus_gateway::VagusGateway;
+use vagus_health::{HealthAggregator, HealthStatus};
 
 #[derive(Parser)]
 #[command(name = "vagus-gateway")]
@@ -59,6 +60,10 @@ enum Commands {
         /// Evidence submission interval in milliseconds
         #[arg(long, default_value = "5000")]
         evidence_interval_ms: u64,
+
+        /// Port serving the gateway's health as JSON
+        #[arg(long)]
+        health_port: Option<u16>,
     },
     /// Run with simulated sensors for testing
     #[command(name = "sim")]
@@ -111,6 +116,7 @@ async fn main() -> Result<()> {
             reflex_arc,
             window_duration_ms,
             evidence_interval_ms,
+            health_port,
         } => {
             let chain_type = match chain.as_str() {
                 "evm" => vagus_chain::ChainType::EVM,
@@ -132,6 +138,7 @@ async fn main() -> Result<()> {
                 window_duration_ms,
                 evidence_interval_ms,
                 false,
+                health_port,
             ).await
         }
         Commands::Simulate {
@@ -163,6 +170,7 @@ async fn main() -> Result<()> {
                 1000,
                 5000,
                 true,
+                None,
             ).await
         }
     }
@@ -256,6 +264,7 @@ async fn start_mock_sensors(gateway: VagusGateway, executor_id: u64) -> Result<(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_multichain_gateway(
     executor_id: u64,
     chain_type: ChainType,
@@ -264,19 +273,38 @@ async fn run_multichain_gateway(
     window_duration_ms: u64,
     evidence_interval_ms: u64,
     simulation_mode: bool,
+    health_port: Option<u16>,
 ) -> Result<()> {
     println!("Starting Vagus Gateway with chain type: {:?}", chain_type);
 
+    let health = HealthAggregator::new();
+    if let Some(port) = health_port {
+        let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+        println!("Serving health on port {}", port);
+        tokio::spawn(health.clone().serve(listener));
+    }
+
     // Create chain client configuration
     let chain_config = ChainConfig {
         chain_type,
         rpc_url: rpc_url.clone(),
         contract_addresses,
         private_key: Some("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string()), // Default anvil key
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        gas_limit_multiplier: None,
+        ws_url: None,
+        fee_denom: None,
+        gas_price: None,
+        gas_limit: None,
     };
 
     // Create chain client
     let chain_client = ChainClientFactory::create_client(chain_config).await?;
+    match chain_client.health().await {
+        Ok(_) => health.report("chain", HealthStatus::Healthy, None).await,
+        Err(e) => health.report("chain", HealthStatus::Unhealthy, Some(e.to_string())).await,
+    }
 
     // Create crypto utilities (for now, still using EVM domain)
     let crypto_domain = VagusDomain {
@@ -3,15 +3,21 @@
 //! Main gateway implementation that coordinates all components.
 
 use anyhow::Result;
-use ethers::types::Address;
+use ethers::types::{Address, U256};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{info, warn, error};
 
-use crate::cbf::{ControlBarrierFunction, BasicCBF, SafetyConditions};
+use crate::cbf::{ControlBarrierFunction, BasicCBF, GuardCombinationPolicy, SafetyConditions};
 use crate::collector::TelemetryCollector;
+use crate::decision_log::{Decision, DecisionLog};
 use crate::event_watcher::{EventWatcher, GatewayEvent};
-use crate::token_manager::TokenManager;
+use crate::jitter::JitteredTimer;
+use crate::token_manager::{CapabilityToken, TokenManager};
+use vagus_chain::ChainClient;
 use vagus_crypto::VagusCrypto;
 use vagus_telemetry::{AfferentEvidencePacket, SensorReading, VagalToneIndicator};
 
@@ -26,15 +32,42 @@ pub struct GatewayConfig {
     pub reflex_arc_address: Address,
     pub window_duration_ms: u64,
     pub evidence_submission_interval_ms: u64,
+    /// Maximum random delay added to each evidence submission
+    pub evidence_jitter_ms: u64,
+    /// Maximum random delay added to each telemetry cleanup
+    pub cleanup_jitter_ms: u64,
+    /// Seed for timer jitter; drawn from entropy when unset
+    pub jitter_seed: Option<u64>,
+    /// Warn about capability tokens expiring within this many seconds
+    pub token_expiry_warning_secs: u64,
 }
 
+/// Interval between telemetry window cleanups
+const CLEANUP_INTERVAL_MS: u64 = 100;
+
+/// Interval between checks for capability tokens nearing expiry
+const TOKEN_EXPIRY_CHECK_INTERVAL_MS: u64 = 1000;
+
+/// Guard scaling the ANS state manager applies in DANGER (6000 bps)
+const DANGER_SCALING: f64 = 0.6;
+
 /// Main Vagus Gateway implementation
 pub struct VagusGateway {
     config: GatewayConfig,
     crypto: VagusCrypto,
     token_manager: TokenManager,
+    token_store: Option<PathBuf>,
+    /// Tokens already reported as nearing expiry
+    warned_expiring: HashSet<U256>,
     telemetry_collector: TelemetryCollector,
     cbf: Box<dyn ControlBarrierFunction>,
+    guard_policy: GuardCombinationPolicy,
+    /// Guard the ANS state manager applies to this executor, as of its last
+    /// reported state
+    chain_guard: vagus_telemetry::SafetyGuard,
+    decision_log: DecisionLog,
+    chain_client: Option<Box<dyn ChainClient>>,
+    evidence_submitted: Arc<AtomicU64>,
     event_sender: Option<mpsc::UnboundedSender<GatewayEvent>>,
     event_receiver: Option<mpsc::UnboundedReceiver<GatewayEvent>>,
 }
@@ -51,8 +84,20 @@ impl VagusGateway {
             config,
             crypto,
             token_manager: TokenManager::new(cloned_crypto),
+            token_store: None,
+            warned_expiring: HashSet::new(),
             telemetry_collector: TelemetryCollector::new(window_duration),
             cbf: Box::new(BasicCBF::new()),
+            guard_policy: GuardCombinationPolicy::default(),
+            // Executors start out SAFE
+            chain_guard: vagus_telemetry::SafetyGuard {
+                allowed: true,
+                scaling_factor: 1.0,
+                reason: None,
+            },
+            decision_log: DecisionLog::default(),
+            chain_client: None,
+            evidence_submitted: Arc::new(AtomicU64::new(0)),
             event_sender: Some(event_sender),
             event_receiver: Some(event_receiver),
         }
@@ -65,6 +110,7 @@ impl VagusGateway {
         // Start event watcher
         let event_watcher = EventWatcher::new(
             &self.config.websocket_url,
+            self.config.executor_id,
             self.config.afferent_inbox_address,
             self.config.ans_state_manager_address,
             self.config.capability_issuer_address,
@@ -82,6 +128,31 @@ impl VagusGateway {
         self.start_telemetry_loop().await?;
         self.start_evidence_submission_loop().await?;
 
+        // The gateway itself moves into the event loop
+        let mut event_receiver = self.event_receiver.take().unwrap();
+        let mut expiry_check = tokio::time::interval(std::time::Duration::from_millis(
+            TOKEN_EXPIRY_CHECK_INTERVAL_MS,
+        ));
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = event_receiver.recv() => {
+                        let Some(event) = event else { break };
+                        if let Err(e) = self.handle_event(event).await {
+                            warn!("Failed to handle gateway event: {:?}", e);
+                        }
+                    }
+                    _ = expiry_check.tick() => {
+                        let now = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs();
+                        self.warn_expiring_tokens(now);
+                    }
+                }
+            }
+        });
+
         info!("Vagus Gateway started successfully");
         Ok(())
     }
@@ -95,9 +166,17 @@ impl VagusGateway {
 
     /// Get current VTI
     pub async fn get_current_vti(&self) -> Result<Option<VagalToneIndicator>> {
-        self.telemetry_collector
+        let vti = self.telemetry_collector
             .compute_vti(self.config.executor_id)
-            .await
+            .await?;
+
+        if let Some(vti) = &vti {
+            self.log_decision(Decision::Vti {
+                value: vti.value,
+                contributions: vti.contributions.clone(),
+            }).await;
+        }
+        Ok(vti)
     }
 
     /// Check if an action is allowed by the CBF
@@ -105,24 +184,169 @@ impl VagusGateway {
         // Get current sensor data (simplified - in production this would query actual sensors)
         let sensor_data = crate::cbf::SensorData {
             human_distances: vec![500.0], // Mock data
+            human_positions: vec![],
+            position: None,
             temperatures: vec![60.0],
             velocities: vec![1.0],
             jerks: vec![0.5],
             battery_level: Some(75.0),
         };
 
-        self.cbf.guard(setpoint, &sensor_data).await
-            .map_err(Into::into)
+        let guard = self.cbf.guard(setpoint, &sensor_data).await?;
+        self.log_decision(Decision::Cbf {
+            setpoint: setpoint.clone(),
+            guard: guard.clone(),
+        }).await;
+        Ok(guard)
+    }
+
+    /// Veto a motion locally before execution: the capability token must be
+    /// valid, and the setpoint is then guarded by the CBF combined with the
+    /// chain's guard for this executor
+    pub async fn authorize_execution(
+        &self,
+        token_id: U256,
+        setpoint: &vagus_telemetry::Pose,
+    ) -> Result<vagus_telemetry::SafetyGuard> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        if !self.token_manager.is_token_valid(token_id, now) {
+            return Ok(vagus_telemetry::SafetyGuard {
+                allowed: false,
+                scaling_factor: 0.0,
+                reason: Some(format!("Capability token {} is not valid", token_id)),
+            });
+        }
+
+        self.check_effective_guard(setpoint, &self.chain_guard).await
+    }
+
+    /// Set how the local CBF decision is combined with the chain guard
+    pub fn set_guard_policy(&mut self, policy: GuardCombinationPolicy) {
+        self.guard_policy = policy;
+    }
+
+    /// Check an action against both the local CBF and the chain-provided guard,
+    /// returning the effective guard under the configured policy
+    pub async fn check_effective_guard(
+        &self,
+        setpoint: &vagus_telemetry::Pose,
+        chain_guard: &vagus_telemetry::SafetyGuard,
+    ) -> Result<vagus_telemetry::SafetyGuard> {
+        let local_guard = self.check_safety_guard(setpoint).await?;
+        let effective = self.guard_policy.combine(&local_guard, chain_guard);
+        self.log_decision(Decision::EffectiveGuard {
+            chain: chain_guard.clone(),
+            effective: effective.clone(),
+        }).await;
+        Ok(effective)
+    }
+
+    /// Replace the decision log, e.g. with one persisted to disk
+    pub fn set_decision_log(&mut self, log: DecisionLog) {
+        self.decision_log = log;
+    }
+
+    /// Log of the safety decisions made so far
+    pub fn decision_log(&self) -> &DecisionLog {
+        &self.decision_log
+    }
+
+    /// Submit evidence through `client`; without one, evidence is only logged
+    pub fn set_chain_client(&mut self, client: Box<dyn ChainClient>) {
+        self.chain_client = Some(client);
+    }
+
+    /// Persist the token view to `path` after every capability event,
+    /// restoring the snapshot already there so a restarted gateway doesn't
+    /// start empty. Backfilled events then reconcile it with the chain.
+    pub fn set_token_store(&mut self, path: PathBuf) -> Result<()> {
+        if path.exists() {
+            let restored = self.token_manager.load(&path)?;
+            info!("Restored {} capability tokens from {}", restored, path.display());
+        }
+        self.token_store = Some(path);
+        Ok(())
+    }
+
+    /// Warn once about each token of this executor expiring within the
+    /// configured horizon, returning the newly reported token ids
+    pub fn warn_expiring_tokens(&mut self, now: u64) -> Vec<U256> {
+        let expiring = self.token_manager.tokens_expiring_within(
+            self.config.executor_id.into(),
+            now,
+            self.config.token_expiry_warning_secs,
+        );
+
+        let mut reported = Vec::new();
+        for token in expiring {
+            if self.warned_expiring.insert(token.token_id) {
+                warn!(
+                    "Capability token {} for action 0x{} expires in {}s",
+                    token.token_id,
+                    ethers::utils::hex::encode(token.action_id),
+                    token.expires_at - now
+                );
+                reported.push(token.token_id);
+            }
+        }
+        reported
+    }
+
+    fn save_tokens(&self) {
+        if let Some(path) = &self.token_store {
+            // The in-memory view stays authoritative if the disk write fails
+            if let Err(e) = self.token_manager.save(path) {
+                warn!("Failed to save capability tokens: {:?}", e);
+            }
+        }
+    }
+
+    /// Locally tracked capability tokens of this executor
+    pub fn token_manager(&self) -> &TokenManager {
+        &self.token_manager
+    }
+
+    /// Number of evidence packets the chain has accepted
+    pub fn evidence_submitted(&self) -> u64 {
+        self.evidence_submitted.load(Ordering::Relaxed)
+    }
+
+    async fn log_decision(&self, decision: Decision) {
+        // A failing log must never block a safety decision
+        if let Err(e) = self.decision_log.record(self.config.executor_id, decision).await {
+            warn!("Failed to record decision: {:?}", e);
+        }
+    }
+
+    fn evidence_timer(&self) -> JitteredTimer {
+        JitteredTimer::new(
+            self.config.evidence_submission_interval_ms,
+            self.config.evidence_jitter_ms,
+            self.config.jitter_seed,
+        )
+    }
+
+    fn cleanup_timer(&self) -> JitteredTimer {
+        // Offset the seed so the two timers don't draw identical jitter
+        JitteredTimer::new(
+            CLEANUP_INTERVAL_MS,
+            self.config.cleanup_jitter_ms,
+            self.config.jitter_seed.map(|seed| seed.wrapping_add(1)),
+        )
     }
 
     /// Start telemetry collection loop
     async fn start_telemetry_loop(&self) -> Result<()> {
         let collector = Arc::new(self.telemetry_collector.clone());
         let _executor_id = self.config.executor_id;
+        let mut timer = self.cleanup_timer();
 
         tokio::spawn(async move {
             loop {
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                timer.tick().await;
 
                 // Cleanup old windows periodically
                 let current_time = std::time::SystemTime::now()
@@ -143,14 +367,25 @@ impl VagusGateway {
     async fn start_evidence_submission_loop(&self) -> Result<()> {
         let collector = Arc::new(self.telemetry_collector.clone());
         let crypto = self.crypto.clone();
+        let decision_log = self.decision_log.clone();
+        let chain_client = self.chain_client.as_ref().map(|client| client.clone_box());
+        let submitted = self.evidence_submitted.clone();
         let executor_id = self.config.executor_id;
-        let interval = self.config.evidence_submission_interval_ms;
+        let mut timer = self.evidence_timer();
 
         tokio::spawn(async move {
             loop {
-                tokio::time::sleep(tokio::time::Duration::from_millis(interval)).await;
-
-                if let Err(e) = Self::submit_evidence(&collector, &crypto, executor_id).await {
+                timer.tick().await;
+
+                // A failed submission is retried with fresh evidence next tick
+                if let Err(e) = Self::submit_evidence(
+                    &collector,
+                    &crypto,
+                    &decision_log,
+                    chain_client.as_deref(),
+                    &submitted,
+                    executor_id,
+                ).await {
                     warn!("Failed to submit evidence: {:?}", e);
                 }
             }
@@ -163,6 +398,9 @@ impl VagusGateway {
     async fn submit_evidence(
         collector: &TelemetryCollector,
         crypto: &VagusCrypto,
+        decision_log: &DecisionLog,
+        chain_client: Option<&dyn ChainClient>,
+        submitted: &AtomicU64,
         executor_id: u64,
     ) -> Result<()> {
         // Get current metrics
@@ -177,8 +415,10 @@ impl VagusGateway {
         // Create state root (simplified - in production this would be a Merkle root)
         let state_root = metrics.hash();
 
-        // Create metrics hash
-        let metrics_hash = metrics.hash();
+        // Commit to the canonical metrics bytes under both hash functions
+        let metrics_cbor = metrics.to_canonical_cbor();
+        let metrics_hash = vagus_crypto::cbor::hash_keccak(&metrics_cbor);
+        let metrics_hash_sha256 = vagus_crypto::cbor::hash_sha256(&metrics_cbor);
 
         // Create AEP
         let timestamp = std::time::SystemTime::now()
@@ -190,19 +430,103 @@ impl VagusGateway {
             executor_id,
             state_root,
             metrics_hash,
+            metrics_hash_sha256,
+            metrics_schema_version: metrics.metrics_schema_version,
+            metrics_cbor,
             attestation: None, // TODO: Add signature
             timestamp,
         };
 
-        info!("Submitting AEP for executor {}: VTI={:.3}", executor_id, vti.value);
+        if !aep.verify_against(&metrics) {
+            return Err(anyhow::anyhow!(
+                "AEP for executor {} does not commit to its metrics",
+                executor_id
+            ));
+        }
 
-        // TODO: Submit to blockchain via contract call
-        // For now, just log the evidence
+        match chain_client {
+            Some(client) => {
+                let tx_hash = client.submit_aep(&aep).await?;
+                submitted.fetch_add(1, Ordering::Relaxed);
+                info!("Submitted AEP for executor {}: VTI={:.3}, tx {}", executor_id, vti.value, tx_hash);
+            }
+            None => info!("Built AEP for executor {}: VTI={:.3} (no chain client)", executor_id, vti.value),
+        }
+        if let Err(e) = decision_log
+            .record(executor_id, Decision::EvidenceSubmitted {
+                vti: vti.value,
+                metrics_hash,
+                timestamp,
+            })
+            .await
+        {
+            warn!("Failed to record decision: {:?}", e);
+        }
 
         Ok(())
     }
 
-    // TODO: Implement event handling when GatewayEvent types are finalized
+    /// React to a chain event: capability events keep the local token view in
+    /// step with the chain, this executor's ANS state retunes the CBF limits
+    pub async fn handle_event(&mut self, event: GatewayEvent) -> Result<()> {
+        match event {
+            GatewayEvent::CapabilityIssued { token_id, executor_id, action_id, expires_at, .. } => {
+                if executor_id != self.config.executor_id.into() {
+                    return Ok(());
+                }
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                self.token_manager.add_token(CapabilityToken {
+                    token_id,
+                    executor_id,
+                    action_id,
+                    // Not part of the issuance event; only signed tokens carry it
+                    scaled_limits_hash: [0u8; 32],
+                    // When the gateway learned of the token
+                    issued_at: now,
+                    expires_at,
+                    revoked: false,
+                });
+                self.save_tokens();
+                Ok(())
+            }
+            GatewayEvent::CapabilityRevoked { token_id, .. } => {
+                // Tokens of other executors aren't tracked
+                if self.token_manager.revoke_token(token_id) {
+                    self.save_tokens();
+                }
+                Ok(())
+            }
+            GatewayEvent::ExecutorState { state, tone_ppm } => {
+                let (ans_state, scaling_factor) = match state {
+                    0 => ("SAFE", 1.0),
+                    1 => ("DANGER", DANGER_SCALING),
+                    2 => ("SHUTDOWN", 0.0),
+                    other => return Err(anyhow::anyhow!("Unknown ANS state {}", other)),
+                };
+                let vti = vagus_chain::vti_from_evm_tone(tone_ppm.into())?;
+                let conditions = SafetyConditions {
+                    ans_state: ans_state.to_string(),
+                    scaling_factor,
+                    vti_value: vti.get() as f64 / 10_000.0,
+                };
+
+                info!("ANS state {} (VTI {}), updating CBF limits", ans_state, vti);
+                self.chain_guard = vagus_telemetry::SafetyGuard {
+                    allowed: scaling_factor > 0.0,
+                    scaling_factor,
+                    reason: (ans_state != "SAFE").then(|| format!("ANS state {}", ans_state)),
+                };
+                self.cbf.update_parameters(&conditions).await
+            }
+            // `VagalToneUpdated` and `StateChanged` name no executor, so they
+            // may belong to another one; the watcher resolves transitions
+            // into `ExecutorState`
+            _ => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -221,6 +545,10 @@ mod tests {
             reflex_arc_address: Address::random(),
             window_duration_ms: 1000,
             evidence_submission_interval_ms: 5000,
+            evidence_jitter_ms: 0,
+            cleanup_jitter_ms: 0,
+            jitter_seed: None,
+            token_expiry_warning_secs: 60,
         }
     }
 
@@ -263,4 +591,350 @@ mod tests {
         let vti = gateway.get_current_vti().await.unwrap().unwrap();
         assert!(vti.value >= 0.0 && vti.value <= 1.0);
     }
+
+    #[tokio::test]
+    async fn test_decision_log_captures_dangerous_sequence() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        // Require more clearance than the 500mm the CBF currently sees
+        gateway.cbf = Box::new(BasicCBF::with_limits(1000.0, 80.0, 2.0, 5.0));
+
+        let distance = |value| vagus_telemetry::SensorReading {
+            sensor_id: "lidar".to_string(),
+            sensor_type: "human_distance".to_string(),
+            value,
+            unit: "mm".to_string(),
+            timestamp: 1000,
+        };
+
+        // A human approaches: VTI rises
+        gateway.add_sensor_reading(distance(2000.0)).await.unwrap();
+        gateway.get_current_vti().await.unwrap();
+        gateway.add_sensor_reading(distance(200.0)).await.unwrap();
+        gateway.get_current_vti().await.unwrap();
+
+        // The CBF blocks the next move even though the chain allows it
+        let setpoint = vagus_telemetry::Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let chain_guard = vagus_telemetry::SafetyGuard {
+            allowed: true,
+            scaling_factor: 1.0,
+            reason: None,
+        };
+        let effective = gateway.check_effective_guard(&setpoint, &chain_guard).await.unwrap();
+        assert!(!effective.allowed);
+
+        VagusGateway::submit_evidence(
+            &gateway.telemetry_collector,
+            &gateway.crypto,
+            &gateway.decision_log,
+            None,
+            &gateway.evidence_submitted,
+            42,
+        ).await.unwrap();
+
+        let records = gateway.decision_log().records().await;
+        assert_eq!(records.len(), 5);
+        assert!(records.iter().all(|r| r.executor_id == 42));
+
+        let (first_vti, second_vti) = match (&records[0].decision, &records[1].decision) {
+            (Decision::Vti { value: a, .. }, Decision::Vti { value: b, .. }) => (*a, *b),
+            other => panic!("expected two VTI decisions, got {:?}", other),
+        };
+        assert!(second_vti > first_vti);
+
+        assert!(matches!(&records[2].decision, Decision::Cbf { guard, .. } if !guard.allowed));
+        assert!(matches!(
+            &records[3].decision,
+            Decision::EffectiveGuard { chain, effective } if chain.allowed && !effective.allowed
+        ));
+        match &records[4].decision {
+            Decision::EvidenceSubmitted { vti, .. } => assert_eq!(*vti, second_vti),
+            other => panic!("expected an evidence submission, got {:?}", other),
+        }
+
+        let exported = gateway.decision_log().export_json_lines().await.unwrap();
+        assert_eq!(exported.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_jitter_offsets_gateways_with_different_seeds() {
+        let gateway_with_seed = |seed| {
+            let config = GatewayConfig {
+                evidence_jitter_ms: 1000,
+                jitter_seed: Some(seed),
+                ..create_test_config()
+            };
+            VagusGateway::new(config, create_test_crypto())
+        };
+
+        // Submission times relative to start, for the first few ticks
+        let schedule = |gateway: &VagusGateway| {
+            let mut timer = gateway.evidence_timer();
+            let mut elapsed = 0;
+            (0..5)
+                .map(|_| {
+                    let delay = timer.next_delay().as_millis() as u64;
+                    assert!((5000..=6000).contains(&delay));
+                    elapsed += delay;
+                    elapsed
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = schedule(&gateway_with_seed(1));
+        let second = schedule(&gateway_with_seed(2));
+        assert!(first.iter().zip(&second).all(|(a, b)| a != b));
+
+        // The same seed reproduces the same schedule
+        assert_eq!(first, schedule(&gateway_with_seed(1)));
+
+        // Without jitter every gateway submits exactly on the interval
+        let unjittered = VagusGateway::new(create_test_config(), create_test_crypto());
+        assert_eq!(
+            unjittered.evidence_timer().next_delay(),
+            std::time::Duration::from_millis(5000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_danger_tone_event_tightens_cbf_limits() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        let setpoint = vagus_telemetry::Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let tone_event = |tone_ppm: u32, state| GatewayEvent::ExecutorState { tone_ppm, state };
+
+        // The mock executor moves at 1 m/s, well within the SAFE 2 m/s limit
+        let guard = gateway.check_safety_guard(&setpoint).await.unwrap();
+        assert_eq!(guard.scaling_factor, 1.0);
+
+        // Another executor's SHUTDOWN leaves this gateway's limits alone
+        gateway
+            .handle_event(GatewayEvent::VagalToneUpdated { tone: 900_000.into(), state: 2 })
+            .await
+            .unwrap();
+        gateway
+            .handle_event(GatewayEvent::StateChanged { from_state: 1, to_state: 2 })
+            .await
+            .unwrap();
+        assert_eq!(gateway.check_safety_guard(&setpoint).await.unwrap().scaling_factor, 1.0);
+
+        // DANGER cuts the limit to 1.2 m/s, so the same motion slows down
+        gateway.handle_event(tone_event(600_000, 1)).await.unwrap();
+        let guard = gateway.check_safety_guard(&setpoint).await.unwrap();
+        assert!(guard.allowed);
+        assert!((guard.scaling_factor - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(guard.reason, Some("Velocity near limit".to_string()));
+
+        gateway.handle_event(tone_event(900_000, 2)).await.unwrap();
+        let guard = gateway.check_safety_guard(&setpoint).await.unwrap();
+        assert!(!guard.allowed);
+        assert_eq!(guard.reason, Some("Velocity too high".to_string()));
+
+        gateway.handle_event(tone_event(100_000, 0)).await.unwrap();
+        assert_eq!(gateway.check_safety_guard(&setpoint).await.unwrap().scaling_factor, 1.0);
+
+        assert!(gateway.handle_event(tone_event(100_000, 3)).await.is_err());
+        assert!(gateway.handle_event(tone_event(1_000_001, 0)).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_evidence_is_submitted_once_per_interval() {
+        use vagus_chain::{MockCall, MockChainClient};
+
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        let chain = MockChainClient::new();
+        gateway.set_chain_client(Box::new(chain.clone()));
+        gateway
+            .add_sensor_reading(vagus_telemetry::SensorReading {
+                sensor_id: "lidar".to_string(),
+                sensor_type: "human_distance".to_string(),
+                value: 800.0,
+                unit: "mm".to_string(),
+                timestamp: 1000,
+            })
+            .await
+            .unwrap();
+        let metrics_hash = gateway
+            .telemetry_collector
+            .get_current_metrics(42)
+            .await
+            .unwrap()
+            .unwrap()
+            .hash();
+
+        // Three 5s intervals elapse
+        gateway.start_evidence_submission_loop().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(15_500)).await;
+
+        let calls = chain.calls();
+        assert_eq!(calls.len(), 3);
+        assert!(calls.iter().all(|call| *call
+            == MockCall::SubmitAep {
+                executor_id: 42,
+                metrics_hash,
+            }));
+        assert_eq!(gateway.evidence_submitted(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_capability_events_update_token_view() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        let issued = |token_id: u64, executor_id: u64| GatewayEvent::CapabilityIssued {
+            token_id: token_id.into(),
+            executor_id: executor_id.into(),
+            action_id: [1u8; 32],
+            params_hash: [0u8; 32],
+            expires_at: 2000,
+        };
+
+        gateway.handle_event(issued(1, 42)).await.unwrap();
+        // Another executor's token stays out of this gateway's view
+        gateway.handle_event(issued(2, 7)).await.unwrap();
+        assert!(gateway.token_manager().is_token_valid(1.into(), 1500));
+        assert!(!gateway.token_manager().is_token_valid(2.into(), 1500));
+        assert_eq!(gateway.token_manager().get_token_count(42.into()), 1);
+
+        gateway
+            .handle_event(GatewayEvent::CapabilityRevoked {
+                token_id: 1.into(),
+                reason: 1,
+            })
+            .await
+            .unwrap();
+        assert!(!gateway.token_manager().is_token_valid(1.into(), 1500));
+    }
+
+    #[tokio::test]
+    async fn test_expiring_token_is_reported_once() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        gateway
+            .handle_event(GatewayEvent::CapabilityIssued {
+                token_id: 1.into(),
+                executor_id: 42.into(),
+                action_id: [1u8; 32],
+                params_hash: [0u8; 32],
+                expires_at: 2000,
+            })
+            .await
+            .unwrap();
+
+        // Outside the 60s horizon, then inside it
+        assert!(gateway.warn_expiring_tokens(1900).is_empty());
+        assert_eq!(gateway.warn_expiring_tokens(1950), vec![U256::from(1)]);
+        assert!(gateway.warn_expiring_tokens(1960).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_authorize_execution_needs_valid_token_and_safe_pose() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        let setpoint = vagus_telemetry::Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        for token_id in [1u64, 2] {
+            gateway
+                .handle_event(GatewayEvent::CapabilityIssued {
+                    token_id: token_id.into(),
+                    executor_id: 42.into(),
+                    action_id: [1u8; 32],
+                    params_hash: [0u8; 32],
+                    expires_at: now + 3600,
+                })
+                .await
+                .unwrap();
+        }
+
+        // Valid token and a pose the CBF accepts
+        let guard = gateway.authorize_execution(1.into(), &setpoint).await.unwrap();
+        assert!(guard.allowed);
+        assert_eq!(guard.scaling_factor, 1.0);
+
+        // A revoked or unknown token is vetoed before the CBF runs
+        gateway
+            .handle_event(GatewayEvent::CapabilityRevoked {
+                token_id: 1.into(),
+                reason: 1,
+            })
+            .await
+            .unwrap();
+        for token_id in [1u64, 3] {
+            let guard = gateway.authorize_execution(token_id.into(), &setpoint).await.unwrap();
+            assert!(!guard.allowed);
+            assert_eq!(
+                guard.reason,
+                Some(format!("Capability token {} is not valid", token_id))
+            );
+        }
+
+        // A valid token doesn't override the CBF: require more clearance than
+        // the 500mm the CBF currently sees
+        gateway.cbf = Box::new(BasicCBF::with_limits(1000.0, 80.0, 2.0, 5.0));
+        let guard = gateway.authorize_execution(2.into(), &setpoint).await.unwrap();
+        assert!(!guard.allowed);
+        assert_eq!(guard.reason, Some("Human too close".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_execution_combines_cbf_with_chain_guard() {
+        let mut gateway = VagusGateway::new(create_test_config(), create_test_crypto());
+        let setpoint = vagus_telemetry::Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        gateway
+            .handle_event(GatewayEvent::CapabilityIssued {
+                token_id: 1.into(),
+                executor_id: 42.into(),
+                action_id: [1u8; 32],
+                params_hash: [0u8; 32],
+                expires_at: now + 3600,
+            })
+            .await
+            .unwrap();
+
+        // DANGER: the CBF scales the 1 m/s motion by 2/3 against its 1.2 m/s
+        // limit, the chain guard scales by 0.6, and the tighter one wins
+        gateway
+            .handle_event(GatewayEvent::ExecutorState { state: 1, tone_ppm: 600_000 })
+            .await
+            .unwrap();
+        let guard = gateway.authorize_execution(1.into(), &setpoint).await.unwrap();
+        assert!(guard.allowed);
+        assert_eq!(guard.scaling_factor, DANGER_SCALING);
+        assert_eq!(guard.reason, Some("ANS state DANGER".to_string()));
+
+        gateway.set_guard_policy(GuardCombinationPolicy::Weighted { local_weight: 0.5 });
+        let guard = gateway.authorize_execution(1.into(), &setpoint).await.unwrap();
+        assert!((guard.scaling_factor - (2.0 / 3.0 + DANGER_SCALING) / 2.0).abs() < 1e-9);
+
+        // Both decisions are logged along with the effective one
+        let records = gateway.decision_log().records().await;
+        assert!(matches!(
+            &records[records.len() - 1].decision,
+            Decision::EffectiveGuard { chain, effective }
+                if chain.scaling_factor == DANGER_SCALING && effective.allowed
+        ));
+        assert!(matches!(&records[records.len() - 2].decision, Decision::Cbf { guard, .. } if guard.allowed));
+
+        // SHUTDOWN blocks whatever the policy
+        gateway
+            .handle_event(GatewayEvent::ExecutorState { state: 2, tone_ppm: 900_000 })
+            .await
+            .unwrap();
+        let guard = gateway.authorize_execution(1.into(), &setpoint).await.unwrap();
+        assert!(!guard.allowed);
+        assert_eq!(guard.scaling_factor, 0.0);
+    }
 }
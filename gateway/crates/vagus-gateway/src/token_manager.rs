@@ -2,12 +2,15 @@
 //!
 //! Tracks active capability tokens for each executor and validates them locally.
 
+use anyhow::Result;
 use ethers::types::{Address, U256};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use vagus_crypto::VagusCrypto;
+use std::path::Path;
+use vagus_crypto::{CryptoError, SignedMessage, TokenMessage, VagusCrypto};
 
 /// Capability token information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapabilityToken {
     pub token_id: U256,
     pub executor_id: U256,
@@ -18,12 +21,30 @@ pub struct CapabilityToken {
     pub revoked: bool,
 }
 
+impl From<&TokenMessage> for CapabilityToken {
+    fn from(message: &TokenMessage) -> Self {
+        Self {
+            token_id: message.token_id,
+            executor_id: message.executor_id,
+            action_id: message.action_id,
+            scaled_limits_hash: message.scaled_limits_hash,
+            issued_at: message.issued_at,
+            expires_at: message.expires_at,
+            revoked: false,
+        }
+    }
+}
+
 /// Token manager for tracking active capabilities
 pub struct TokenManager {
     /// Active tokens per executor
     active_tokens: HashMap<U256, Vec<CapabilityToken>>,
+    /// Unrevoked token ids per (executor, action)
+    action_index: HashMap<(U256, [u8; 32]), Vec<U256>>,
     /// Crypto utilities for validation
     crypto: VagusCrypto,
+    /// Signer trusted to attest tokens for offline validation
+    trusted_signer: Option<Address>,
 }
 
 impl TokenManager {
@@ -31,13 +52,76 @@ impl TokenManager {
     pub fn new(crypto: VagusCrypto) -> Self {
         Self {
             active_tokens: HashMap::new(),
+            action_index: HashMap::new(),
             crypto,
+            trusted_signer: None,
+        }
+    }
+
+    /// Set the signer whose token attestations are accepted offline
+    pub fn set_trusted_signer(&mut self, signer: Address) {
+        self.trusted_signer = Some(signer);
+    }
+
+    /// Add a capability token from a signed attestation
+    pub fn add_signed_token(&mut self, signed_token: &SignedMessage<TokenMessage>) -> Result<(), CryptoError> {
+        let signer = self.crypto.verify_token_signature(signed_token)?;
+        if self.trusted_signer != Some(signer) {
+            return Err(CryptoError::InvalidSignature(format!(
+                "token {} signed by untrusted signer {:?}",
+                signed_token.message.token_id, signer
+            )));
         }
+
+        self.add_token(CapabilityToken::from(&signed_token.message));
+        Ok(())
+    }
+
+    /// Validate a signed token without chain access: the signature must come
+    /// from the trusted signer, the token must be within its validity window,
+    /// and it must not have been revoked locally
+    pub fn validate_offline(&self, signed_token: &SignedMessage<TokenMessage>, current_time: u64) -> bool {
+        let Some(trusted_signer) = self.trusted_signer else {
+            return false;
+        };
+
+        match self.crypto.verify_token_signature(signed_token) {
+            Ok(signer) if signer == trusted_signer => {}
+            _ => return false,
+        }
+
+        let token = &signed_token.message;
+        if current_time < token.issued_at || current_time > token.expires_at {
+            return false;
+        }
+
+        let locally_revoked = self
+            .active_tokens
+            .get(&token.executor_id)
+            .and_then(|tokens| tokens.iter().find(|t| t.token_id == token.token_id))
+            .map(|t| t.revoked)
+            .unwrap_or(false);
+
+        !locally_revoked
     }
 
-    /// Add a new capability token
+    /// Add a new capability token. A token already tracked is kept as is, so
+    /// replaying its issuance after a restart doesn't undo a local revocation.
     pub fn add_token(&mut self, token: CapabilityToken) {
         let executor_id = token.executor_id;
+        let known = self
+            .active_tokens
+            .get(&executor_id)
+            .is_some_and(|tokens| tokens.iter().any(|t| t.token_id == token.token_id));
+        if known {
+            return;
+        }
+        if !token.revoked {
+            self.action_index
+                .entry((executor_id, token.action_id))
+                .or_default()
+                .push(token.token_id);
+        }
         self.active_tokens
             .entry(executor_id)
             .or_insert_with(Vec::new)
@@ -49,12 +133,40 @@ impl TokenManager {
         for (_executor_id, tokens) in &mut self.active_tokens {
             if let Some(pos) = tokens.iter().position(|t| t.token_id == token_id) {
                 tokens[pos].revoked = true;
+                let key = (tokens[pos].executor_id, tokens[pos].action_id);
+                self.unindex(key, |id| id == token_id);
                 return true;
             }
         }
         false
     }
 
+    /// A valid token authorizing `action_id` for the executor, if any
+    pub fn valid_token_for_action(
+        &self,
+        executor_id: U256,
+        action_id: [u8; 32],
+        now: u64,
+    ) -> Option<U256> {
+        let token_ids = self.action_index.get(&(executor_id, action_id))?;
+        let tokens = self.active_tokens.get(&executor_id)?;
+        token_ids.iter().copied().find(|token_id| {
+            tokens
+                .iter()
+                .any(|t| t.token_id == *token_id && !t.revoked && now <= t.expires_at)
+        })
+    }
+
+    // Drop token ids matching `remove` from one action index entry
+    fn unindex(&mut self, key: (U256, [u8; 32]), remove: impl Fn(U256) -> bool) {
+        if let Some(token_ids) = self.action_index.get_mut(&key) {
+            token_ids.retain(|id| !remove(*id));
+            if token_ids.is_empty() {
+                self.action_index.remove(&key);
+            }
+        }
+    }
+
     /// Check if a token is valid (not expired, not revoked)
     pub fn is_token_valid(&self, token_id: U256, current_time: u64) -> bool {
         for (_executor_id, tokens) in &self.active_tokens {
@@ -78,6 +190,24 @@ impl TokenManager {
             .unwrap_or_default()
     }
 
+    /// Valid tokens of an executor that expire within `horizon` seconds of
+    /// `now`, soonest first
+    pub fn tokens_expiring_within(
+        &self,
+        executor_id: U256,
+        now: u64,
+        horizon: u64,
+    ) -> Vec<&CapabilityToken> {
+        let deadline = now.saturating_add(horizon);
+        let mut expiring: Vec<&CapabilityToken> = self
+            .get_active_tokens(executor_id, now)
+            .into_iter()
+            .filter(|token| token.expires_at <= deadline)
+            .collect();
+        expiring.sort_by_key(|token| token.expires_at);
+        expiring
+    }
+
     /// Validate scaling limits hash for a token
     pub fn validate_scaling_limits(
         &self,
@@ -94,14 +224,46 @@ impl TokenManager {
 
     /// Clean up expired tokens
     pub fn cleanup_expired(&mut self, current_time: u64) {
+        let mut expired = Vec::new();
         for (_executor_id, tokens) in &mut self.active_tokens {
-            tokens.retain(|token| current_time <= token.expires_at);
+            tokens.retain(|token| {
+                let keep = current_time <= token.expires_at;
+                if !keep {
+                    expired.push((token.executor_id, token.action_id, token.token_id));
+                }
+                keep
+            });
+        }
+        for (executor_id, action_id, token_id) in expired {
+            self.unindex((executor_id, action_id), |id| id == token_id);
         }
 
         // Remove empty executor entries
         self.active_tokens.retain(|_executor_id, tokens| !tokens.is_empty());
     }
 
+    /// Snapshot every tracked token to `path` as JSON. The file is replaced
+    /// atomically, so a crash mid-write leaves the previous snapshot intact.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tokens: Vec<&CapabilityToken> = self.active_tokens.values().flatten().collect();
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(&tokens)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Restore tokens from a `save` snapshot, returning how many were added.
+    /// The snapshot only bridges the restart; replaying capability events
+    /// from before it reconciles the view with the chain.
+    pub fn load(&mut self, path: &Path) -> Result<usize> {
+        let tokens: Vec<CapabilityToken> = serde_json::from_slice(&std::fs::read(path)?)?;
+        let count = tokens.len();
+        for token in tokens {
+            self.add_token(token);
+        }
+        Ok(count)
+    }
+
     /// Get token count per executor
     pub fn get_token_count(&self, executor_id: U256) -> usize {
         self.active_tokens
@@ -118,6 +280,7 @@ impl TokenManager {
 mod tests {
     use super::*;
     use vagus_crypto::VagusDomain;
+    use ethers::signers::{LocalWallet, Signer};
     use ethers::types::Address;
 
     fn create_test_crypto() -> VagusCrypto {
@@ -207,4 +370,155 @@ mod tests {
         assert_eq!(active.len(), 1);
         assert_eq!(active[0].token_id, 2.into());
     }
+
+    #[test]
+    fn test_valid_token_for_action() {
+        let mut manager = TokenManager::new(create_test_crypto());
+        let token = |token_id: u64, action: u8, expires_at: u64| CapabilityToken {
+            token_id: token_id.into(),
+            executor_id: 42.into(),
+            action_id: [action; 32],
+            scaled_limits_hash: [0u8; 32],
+            issued_at: 1000,
+            expires_at,
+            revoked: false,
+        };
+        manager.add_token(token(1, 1, 1500));
+        manager.add_token(token(2, 1, 3000));
+        manager.add_token(token(3, 2, 3000));
+
+        // Hits are scoped to the executor and the action
+        assert_eq!(manager.valid_token_for_action(42.into(), [1u8; 32], 1200), Some(1.into()));
+        assert_eq!(manager.valid_token_for_action(42.into(), [2u8; 32], 1200), Some(3.into()));
+        assert_eq!(manager.valid_token_for_action(7.into(), [1u8; 32], 1200), None);
+        assert_eq!(manager.valid_token_for_action(42.into(), [9u8; 32], 1200), None);
+
+        // Revoked tokens don't authorize
+        assert!(manager.revoke_token(3.into()));
+        assert_eq!(manager.valid_token_for_action(42.into(), [2u8; 32], 1200), None);
+        assert!(!manager.action_index.contains_key(&(42.into(), [2u8; 32])));
+
+        // An expired token falls through to the next valid one, then none
+        assert_eq!(manager.valid_token_for_action(42.into(), [1u8; 32], 2000), Some(2.into()));
+        manager.cleanup_expired(2000);
+        assert_eq!(manager.action_index[&(42.into(), [1u8; 32])], vec![U256::from(2)]);
+        assert_eq!(manager.valid_token_for_action(42.into(), [1u8; 32], 3500), None);
+        manager.cleanup_expired(3500);
+        assert!(manager.action_index.is_empty());
+    }
+
+    #[test]
+    fn test_tokens_expiring_within_horizon() {
+        let mut manager = TokenManager::new(create_test_crypto());
+        for (token_id, executor_id, expires_at) in
+            [(1u64, 42u64, 1300), (2, 42, 1100), (3, 42, 5000), (4, 42, 900), (5, 7, 1100)]
+        {
+            manager.add_token(CapabilityToken {
+                token_id: token_id.into(),
+                executor_id: executor_id.into(),
+                action_id: [1u8; 32],
+                scaled_limits_hash: [0u8; 32],
+                issued_at: 500,
+                expires_at,
+                revoked: false,
+            });
+        }
+        manager.add_token(CapabilityToken {
+            token_id: 6.into(),
+            revoked: true,
+            ..manager.get_active_tokens(42.into(), 1000)[0].clone()
+        });
+
+        let expiring: Vec<U256> = manager
+            .tokens_expiring_within(42.into(), 1000, 300)
+            .iter()
+            .map(|token| token.token_id)
+            .collect();
+        // Already expired, far off, revoked and other executors' tokens are left out
+        assert_eq!(expiring, vec![U256::from(2), U256::from(1)]);
+
+        assert!(manager.tokens_expiring_within(42.into(), 1000, 50).is_empty());
+        assert_eq!(manager.tokens_expiring_within(42.into(), 1000, 4000).len(), 3);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_keeps_validity() {
+        let path = std::env::temp_dir()
+            .join(format!("vagus-token-snapshot-{}.json", std::process::id()));
+        let token = |token_id: u64, action: u8, expires_at: u64| CapabilityToken {
+            token_id: token_id.into(),
+            executor_id: 42.into(),
+            action_id: [action; 32],
+            scaled_limits_hash: [action; 32],
+            issued_at: 1000,
+            expires_at,
+            revoked: false,
+        };
+
+        let mut manager = TokenManager::new(create_test_crypto());
+        manager.add_token(token(1, 1, 3000));
+        manager.add_token(token(2, 2, 1500));
+        manager.add_token(token(3, 3, 3000));
+        assert!(manager.revoke_token(3.into()));
+        manager.save(&path).unwrap();
+
+        let mut restored = TokenManager::new(create_test_crypto());
+        assert_eq!(restored.load(&path).unwrap(), 3);
+        std::fs::remove_file(&path).unwrap();
+
+        for now in [1200, 2000, 3500] {
+            for id in 1..=3u64 {
+                assert_eq!(
+                    restored.is_token_valid(id.into(), now),
+                    manager.is_token_valid(id.into(), now)
+                );
+                assert_eq!(
+                    restored.valid_token_for_action(42.into(), [id as u8; 32], now),
+                    manager.valid_token_for_action(42.into(), [id as u8; 32], now)
+                );
+            }
+        }
+        assert!(restored.validate_scaling_limits(1.into(), &[1u8; 32]));
+
+        // Replaying the issuance of a revoked token doesn't revive it
+        restored.add_token(token(3, 3, 3000));
+        assert!(!restored.is_token_valid(3.into(), 1200));
+        assert_eq!(restored.get_token_count(42.into()), 3);
+    }
+
+    #[tokio::test]
+    async fn test_signed_token_validates_offline() {
+        let issuer_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let issuer_wallet: LocalWallet = issuer_key.parse().unwrap();
+
+        let token = TokenMessage {
+            token_id: 7.into(),
+            executor_id: 42.into(),
+            action_id: [1u8; 32],
+            scaled_limits_hash: [2u8; 32],
+            issued_at: 1000,
+            expires_at: 2000,
+        };
+
+        // Issuer signs the token alongside the on-chain issuance
+        let signed = create_test_crypto().sign_token(token, issuer_key).await.unwrap();
+
+        // Gateway with no chain client: validation relies only on the signature
+        let mut manager = TokenManager::new(create_test_crypto());
+        manager.set_trusted_signer(issuer_wallet.address());
+        manager.add_signed_token(&signed).unwrap();
+
+        assert!(manager.validate_offline(&signed, 1500));
+        assert!(!manager.validate_offline(&signed, 2500));
+
+        // Tampered limits no longer match the signature
+        let mut tampered = signed.clone();
+        tampered.message.scaled_limits_hash = [9u8; 32];
+        assert!(!manager.validate_offline(&tampered, 1500));
+        assert!(manager.add_signed_token(&tampered).is_err());
+
+        // Local revocation overrides a still-valid signature
+        assert!(manager.revoke_token(7.into()));
+        assert!(!manager.validate_offline(&signed, 1500));
+    }
 }
@@ -0,0 +1,90 @@
+//! Event watcher tests against a local Anvil node.
+//!
+//! Requires the `anvil` binary on PATH:
+//! `cargo test -p vagus-gateway -- --ignored`
+
+use std::time::Duration;
+
+use ethers::{
+    contract::EthEvent,
+    middleware::SignerMiddleware,
+    providers::{Middleware, Provider, Ws},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionRequest, H256, U256},
+    utils::{hex, Anvil, AnvilInstance},
+};
+use tokio::sync::mpsc;
+use vagus_gateway::event_watcher::{EventWatcher, GatewayEvent, VagalToneUpdatedFilter};
+
+/// Init code for a stub whose runtime emits
+/// `VagalToneUpdated(tone = 4000, state = 1, updatedAt = 42)` on every call.
+fn tone_emitter_init_code(topic0: H256) -> String {
+    format!(
+        "6031600c60003960316000f3\
+         602a6000526001610fa07f{}60206000a300",
+        hex::encode(topic0)
+    )
+}
+
+async fn send(anvil: &AnvilInstance, tx: TransactionRequest) -> Option<Address> {
+    let provider = Provider::<Ws>::connect(anvil.ws_endpoint()).await.unwrap();
+    let wallet: LocalWallet = anvil.keys()[0].clone().into();
+    let client = SignerMiddleware::new(provider, wallet.with_chain_id(anvil.chain_id()));
+    let receipt = client
+        .send_transaction(tx, None)
+        .await
+        .unwrap()
+        .await
+        .unwrap()
+        .unwrap();
+    receipt.contract_address
+}
+
+#[tokio::test]
+#[ignore = "requires anvil"]
+async fn test_past_and_new_tone_updates_reach_the_gateway() {
+    let anvil = Anvil::new().spawn();
+    let init_code = tone_emitter_init_code(VagalToneUpdatedFilter::signature());
+    let ans = send(
+        &anvil,
+        TransactionRequest::new().data(Bytes::from(hex::decode(init_code).unwrap())),
+    )
+    .await
+    .unwrap();
+    // Emitted before the gateway starts: delivered by the backfill
+    send(&anvil, TransactionRequest::new().to(ans)).await;
+
+    let watcher = EventWatcher::new(
+        &anvil.ws_endpoint(),
+        1,
+        Address::random(),
+        ans,
+        Address::random(),
+        Address::random(),
+    )
+    .await
+    .unwrap();
+    let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+    tokio::spawn(watcher.start_watching(event_sender));
+    // Let the subscription install before emitting
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    send(&anvil, TransactionRequest::new().to(ans)).await;
+
+    for _ in 0..2 {
+        let event = tokio::time::timeout(Duration::from_secs(10), event_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            GatewayEvent::VagalToneUpdated { tone, state } => {
+                assert_eq!(tone, U256::from(4000));
+                assert_eq!(state, 1);
+            }
+            other => panic!("expected VagalToneUpdated, got {:?}", other),
+        }
+    }
+    // Each emission arrives exactly once
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(event_receiver.try_recv().is_err());
+}
@@ -0,0 +1,227 @@
+//! Vagus Health
+//!
+//! Uniform health reporting for the gateway, oracle and relayer. Each service
+//! reports the state of its components to a `HealthAggregator` and exposes
+//! the aggregate as JSON, so one monitoring check covers all of them.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+/// Health of a component, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    /// Working with reduced capability (e.g. one of several chains unreachable)
+    Degraded,
+    Unhealthy,
+}
+
+/// Health of a single component of a service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    /// Component name (e.g. "evm_chain", "event_subscription")
+    pub component: String,
+    pub status: HealthStatus,
+    /// Last time the component was healthy (Unix timestamp in milliseconds)
+    pub last_ok: Option<u64>,
+    /// Human-readable cause of a degraded or unhealthy status
+    pub detail: Option<String>,
+}
+
+/// Aggregate health of a service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Worst status among the components; healthy when there are none
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+    /// Service counters by name (e.g. "unknown_events")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, u64>,
+}
+
+impl HealthReport {
+    /// Aggregate component statuses into a report
+    pub fn from_components(components: Vec<ComponentHealth>) -> Self {
+        let status = components
+            .iter()
+            .map(|component| component.status)
+            .max()
+            .unwrap_or(HealthStatus::Healthy);
+        Self {
+            status,
+            components,
+            metrics: BTreeMap::new(),
+        }
+    }
+}
+
+/// Reads the current value of a counter
+type MetricSource = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// Latest health of each component of a service, shared between its tasks
+#[derive(Clone, Default)]
+pub struct HealthAggregator {
+    components: Arc<RwLock<BTreeMap<String, ComponentHealth>>>,
+    metrics: Arc<RwLock<BTreeMap<String, MetricSource>>>,
+}
+
+impl HealthAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a component's current status. `last_ok` advances when it is
+    /// healthy and is kept from earlier reports otherwise.
+    pub async fn report(&self, component: &str, status: HealthStatus, detail: Option<String>) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let mut components = self.components.write().await;
+        let last_ok = if status == HealthStatus::Healthy {
+            Some(now)
+        } else {
+            components.get(component).and_then(|previous| previous.last_ok)
+        };
+        components.insert(
+            component.to_string(),
+            ComponentHealth {
+                component: component.to_string(),
+                status,
+                last_ok,
+                detail,
+            },
+        );
+    }
+
+    /// Include a counter in every report under `name`, read when the report
+    /// is taken so it is never stale
+    pub async fn register_metric(&self, name: &str, read: impl Fn() -> u64 + Send + Sync + 'static) {
+        self.metrics.write().await.insert(name.to_string(), Arc::new(read));
+    }
+
+    /// Current aggregate health, components and metrics sorted by name
+    pub async fn snapshot(&self) -> HealthReport {
+        let mut report =
+            HealthReport::from_components(self.components.read().await.values().cloned().collect());
+        report.metrics = self
+            .metrics
+            .read()
+            .await
+            .iter()
+            .map(|(name, read)| (name.clone(), read()))
+            .collect();
+        report
+    }
+
+    /// Answer every HTTP request on `listener` with the current report; an
+    /// unhealthy service answers 503 so plain HTTP checks catch it
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (mut stream, _) = listener.accept().await?;
+            let aggregator = self.clone();
+            tokio::spawn(async move {
+                // The request itself is irrelevant; drain what was sent
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+
+                let report = aggregator.snapshot().await;
+                let status_line = match report.status {
+                    HealthStatus::Unhealthy => "503 Service Unavailable",
+                    _ => "200 OK",
+                };
+                let body = serde_json::to_string(&report).unwrap_or_default();
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status_line,
+                    body.len(),
+                    body
+                );
+                if let Err(e) = stream.write_all(response.as_bytes()).await {
+                    tracing::warn!("Failed to write health response: {}", e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &str, status: HealthStatus) -> ComponentHealth {
+        ComponentHealth {
+            component: name.to_string(),
+            status,
+            last_ok: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_status_is_worst_component() {
+        let report = HealthReport::from_components(vec![
+            component("evm_chain", HealthStatus::Healthy),
+            component("cosmos_chain", HealthStatus::Degraded),
+            component("event_subscription", HealthStatus::Healthy),
+        ]);
+        assert_eq!(report.status, HealthStatus::Degraded);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["components"][1]["component"], "cosmos_chain");
+        assert_eq!(json["components"][1]["status"], "degraded");
+        assert!(json["components"][1]["last_ok"].is_null());
+        assert!(json.get("metrics").is_none());
+        assert_eq!(serde_json::from_value::<HealthReport>(json).unwrap(), report);
+
+        let mut components = report.components;
+        components.push(component("target_chain", HealthStatus::Unhealthy));
+        assert_eq!(HealthReport::from_components(components).status, HealthStatus::Unhealthy);
+
+        assert_eq!(HealthReport::from_components(vec![]).status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_last_ok_survives_failures() {
+        let health = HealthAggregator::new();
+        health.report("evm_chain", HealthStatus::Healthy, None).await;
+        let last_ok = health.snapshot().await.components[0].last_ok;
+        assert!(last_ok.is_some());
+
+        health
+            .report("evm_chain", HealthStatus::Unhealthy, Some("connection refused".to_string()))
+            .await;
+        let report = health.snapshot().await;
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert_eq!(report.components[0].last_ok, last_ok);
+        assert_eq!(report.components[0].detail.as_deref(), Some("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_read_at_snapshot() {
+        let health = HealthAggregator::new();
+        let count = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let source = count.clone();
+        health
+            .register_metric("unknown_events", move || {
+                source.load(std::sync::atomic::Ordering::Relaxed)
+            })
+            .await;
+
+        count.store(3, std::sync::atomic::Ordering::Relaxed);
+        let report = health.snapshot().await;
+        assert_eq!(report.metrics.get("unknown_events"), Some(&3));
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["metrics"]["unknown_events"], 3);
+    }
+}
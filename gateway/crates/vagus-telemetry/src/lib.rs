@@ -4,7 +4,26 @@
 //! used in the afferent evidence processing pipeline.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Version of the metrics layout committed by `WindowMetrics::hash`. Bump it
+/// whenever the hashed fields or their encoding change; 0 marks metrics
+/// produced before versioning.
+pub const METRICS_SCHEMA_VERSION: u32 = 5;
+
+/// Fixed-point scale of metric values in `WindowMetrics::to_canonical_cbor` (thousandths)
+const METRIC_FIXED_POINT_SCALE: f64 = 1000.0;
+
+/// Percentile of human distance readings reported as `p5_human_distance`
+pub const HUMAN_DISTANCE_PERCENTILE: f64 = 5.0;
+
+/// Percentile of temperature readings reported as `p95_temperature`
+pub const TEMPERATURE_PERCENTILE: f64 = 95.0;
+
+/// Readings of a sensor type needed before its percentile is reported; with
+/// fewer, the percentile would land on the extreme reading anyway
+pub const MIN_PERCENTILE_SAMPLES: usize = 20;
 
 /// Telemetry data point from a single sensor
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,11 +51,60 @@ pub struct TelemetryWindow {
     pub window_end: u64,
     /// Sensor readings in this window
     pub readings: Vec<SensorReading>,
+    /// Readings rejected by `add_reading`
+    #[serde(default)]
+    pub dropped_readings: u64,
+    /// Cap on retained readings; unset keeps every reading
+    #[serde(default)]
+    pub max_readings: Option<usize>,
+    /// Running aggregates of readings evicted by the cap, per sensor type
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub evicted: BTreeMap<String, EvictedAggregate>,
+}
+
+/// Running aggregate of readings evicted from a capped window, enough to
+/// fold them into any `Aggregation` without keeping the readings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvictedAggregate {
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    pub count: usize,
+    /// Value and timestamp of the most recent evicted reading
+    pub latest: f64,
+    pub latest_timestamp: u64,
+}
+
+impl EvictedAggregate {
+    fn from_reading(reading: &SensorReading) -> Self {
+        Self {
+            min: reading.value,
+            max: reading.value,
+            sum: reading.value,
+            count: 1,
+            latest: reading.value,
+            latest_timestamp: reading.timestamp,
+        }
+    }
+
+    fn combine(&mut self, other: &EvictedAggregate) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+        if other.latest_timestamp >= self.latest_timestamp {
+            self.latest = other.latest;
+            self.latest_timestamp = other.latest_timestamp;
+        }
+    }
 }
 
 /// Aggregated metrics from a telemetry window
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowMetrics {
+    /// Schema version the metrics were computed under
+    #[serde(default)]
+    pub metrics_schema_version: u32,
     /// Executor ID
     pub executor_id: u64,
     /// Window start time
@@ -53,6 +121,175 @@ pub struct WindowMetrics {
     pub max_jerk: Option<f64>,
     /// Battery level remaining (0-100%)
     pub battery_level: Option<f64>,
+    /// 5th percentile human distance (mm), robust to single spurious readings;
+    /// `None` below `MIN_PERCENTILE_SAMPLES` readings
+    #[serde(default)]
+    pub p5_human_distance: Option<f64>,
+    /// 95th percentile temperature (°C); `None` below `MIN_PERCENTILE_SAMPLES` readings
+    #[serde(default)]
+    pub p95_temperature: Option<f64>,
+    /// Maximum contact force (N)
+    #[serde(default)]
+    pub max_force: Option<f64>,
+    /// Maximum acceleration (m/s²)
+    #[serde(default)]
+    pub max_acceleration: Option<f64>,
+    /// Minimum clearance to a protected proximity zone (mm)
+    #[serde(default)]
+    pub min_zone_clearance: Option<f64>,
+    /// Aggregates for sensor types registered beyond the built-in set
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_metrics: BTreeMap<String, f64>,
+}
+
+/// How readings of one sensor type are folded into a window aggregate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    Min,
+    Max,
+    Mean,
+    /// Most recent reading in the window
+    Latest,
+}
+
+/// Registered sensor type: expected unit and aggregation rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorTypeSpec {
+    pub unit: String,
+    pub aggregation: Aggregation,
+}
+
+/// Problem found when validating a reading against the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SensorIssue {
+    /// Sensor type is not registered (e.g. a typo like "human_dist")
+    UnknownType { sensor_id: String, sensor_type: String },
+    /// Sensor type is known but reported in an unexpected unit
+    UnitMismatch {
+        sensor_id: String,
+        sensor_type: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for SensorIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SensorIssue::UnknownType { sensor_id, sensor_type } => {
+                write!(f, "unknown sensor type '{}' from sensor {}", sensor_type, sensor_id)
+            }
+            SensorIssue::UnitMismatch { sensor_id, sensor_type, expected, found } => write!(
+                f,
+                "sensor {} reported {} in '{}', expected '{}'",
+                sensor_id, sensor_type, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SensorIssue {}
+
+/// Evidence whose metrics hash was computed under a different schema version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaVersionMismatch {
+    pub expected: u32,
+    pub found: u32,
+}
+
+impl fmt::Display for SchemaVersionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "metrics schema version {} does not match supported version {}",
+            self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for SchemaVersionMismatch {}
+
+/// Reading refused by `TelemetryWindow::add_reading`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadingRejected {
+    /// Value is NaN or infinite and would poison metric comparisons
+    NonFinite { sensor_id: String, value: f64 },
+    /// Timestamp predates the start of the window
+    BeforeWindowStart {
+        sensor_id: String,
+        timestamp: u64,
+        window_start: u64,
+    },
+}
+
+impl fmt::Display for ReadingRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadingRejected::NonFinite { sensor_id, value } => {
+                write!(f, "sensor {} reported non-finite value {}", sensor_id, value)
+            }
+            ReadingRejected::BeforeWindowStart { sensor_id, timestamp, window_start } => write!(
+                f,
+                "reading from sensor {} at {} predates window start {}",
+                sensor_id, timestamp, window_start
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReadingRejected {}
+
+/// Windows that `TelemetryWindow::merge` refuses to combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMergeError {
+    /// Windows belong to different executors
+    ExecutorMismatch { expected: u64, found: u64 },
+    /// Window time ranges don't overlap
+    Disjoint {
+        window: (u64, u64),
+        other: (u64, u64),
+    },
+}
+
+impl fmt::Display for WindowMergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowMergeError::ExecutorMismatch { expected, found } => write!(
+                f,
+                "cannot merge telemetry of executor {} into window of executor {}",
+                found, expected
+            ),
+            WindowMergeError::Disjoint { window, other } => write!(
+                f,
+                "window {}..{} does not overlap window {}..{}",
+                other.0, other.1, window.0, window.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WindowMergeError {}
+
+/// Orientation quaternion that cannot be normalized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidOrientation {
+    pub orientation: [f64; 4],
+}
+
+impl fmt::Display for InvalidOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "orientation {:?} has zero or non-finite norm", self.orientation)
+    }
+}
+
+impl std::error::Error for InvalidOrientation {}
+
+/// Registry of known sensor types, extensible via config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SensorRegistry {
+    types: HashMap<String, SensorTypeSpec>,
 }
 
 /// Afferent Evidence Packet (AEP) ready for blockchain submission
@@ -62,14 +299,26 @@ pub struct AfferentEvidencePacket {
     pub executor_id: u64,
     /// Current state root hash
     pub state_root: [u8; 32],
-    /// Metrics hash for this evidence packet
+    /// Keccak-256 of `metrics_cbor`; the metrics commitment on every chain
     pub metrics_hash: [u8; 32],
+    /// SHA-256 of `metrics_cbor`, paired with `metrics_hash` on CosmWasm
+    #[serde(default)]
+    pub metrics_hash_sha256: [u8; 32],
+    /// Canonical CBOR of the committed metrics, so either hash can be recomputed
+    #[serde(default)]
+    pub metrics_cbor: Vec<u8>,
+    /// Schema version the metrics hash was computed under
+    #[serde(default)]
+    pub metrics_schema_version: u32,
     /// Attestation signature (if available)
     pub attestation: Option<Vec<u8>>,
     /// Timestamp when evidence was generated
     pub timestamp: u64,
 }
 
+/// Contribution recorded when a window has no metric the VTI can use
+pub const INSUFFICIENT_DATA: &str = "insufficient_data";
+
 /// Vagal Tone Indicator (VTI) computation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VagalToneIndicator {
@@ -81,6 +330,57 @@ pub struct VagalToneIndicator {
     pub timestamp: u64,
 }
 
+/// Metric weights and danger thresholds used to compute a VTI
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VtiWeights {
+    pub human_distance: f64,
+    pub temperature: f64,
+    pub energy: f64,
+    pub jerk: f64,
+    pub force: f64,
+    pub acceleration: f64,
+    pub zone_clearance: f64,
+    /// Human distance below which danger rises (mm)
+    pub human_distance_danger_mm: f64,
+    /// Temperature above which danger rises (°C)
+    pub temperature_danger_celsius: f64,
+    /// Rise above the temperature threshold that counts as full danger (°C)
+    pub temperature_span_celsius: f64,
+    /// Energy consumption that counts as full danger (J)
+    pub max_energy_joules: f64,
+    /// Jerk that counts as full danger (mm/s²)
+    pub max_jerk: f64,
+    /// Contact force that counts as full danger (N)
+    pub max_force_newtons: f64,
+    /// Acceleration that counts as full danger (m/s²)
+    pub max_acceleration: f64,
+    /// Zone clearance below which danger rises (mm)
+    pub zone_clearance_danger_mm: f64,
+}
+
+impl Default for VtiWeights {
+    fn default() -> Self {
+        Self {
+            human_distance: 0.4,
+            temperature: 0.2,
+            energy: 0.2,
+            jerk: 0.2,
+            force: 0.3,
+            acceleration: 0.1,
+            zone_clearance: 0.3,
+            human_distance_danger_mm: 500.0,
+            temperature_danger_celsius: 80.0,
+            temperature_span_celsius: 20.0,
+            max_energy_joules: 1000.0,
+            max_jerk: 2000.0,
+            max_force_newtons: 150.0,
+            max_acceleration: 10.0,
+            zone_clearance_danger_mm: 500.0,
+        }
+    }
+}
+
 /// Pose representation for robotic systems
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pose {
@@ -101,6 +401,63 @@ pub struct SafetyGuard {
     pub reason: Option<String>,
 }
 
+impl SensorRegistry {
+    /// Create an empty registry
+    pub fn empty() -> Self {
+        Self { types: HashMap::new() }
+    }
+
+    /// Register (or override) a sensor type
+    pub fn register(&mut self, sensor_type: &str, unit: &str, aggregation: Aggregation) {
+        self.types.insert(
+            sensor_type.to_string(),
+            SensorTypeSpec {
+                unit: unit.to_string(),
+                aggregation,
+            },
+        );
+    }
+
+    /// Look up a sensor type
+    pub fn get(&self, sensor_type: &str) -> Option<&SensorTypeSpec> {
+        self.types.get(sensor_type)
+    }
+
+    /// Check a reading's type and unit against the registry
+    pub fn validate(&self, reading: &SensorReading) -> Result<(), SensorIssue> {
+        let spec = self.get(&reading.sensor_type).ok_or_else(|| SensorIssue::UnknownType {
+            sensor_id: reading.sensor_id.clone(),
+            sensor_type: reading.sensor_type.clone(),
+        })?;
+
+        if spec.unit != reading.unit {
+            return Err(SensorIssue::UnitMismatch {
+                sensor_id: reading.sensor_id.clone(),
+                sensor_type: reading.sensor_type.clone(),
+                expected: spec.unit.clone(),
+                found: reading.unit.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for SensorRegistry {
+    /// Built-in sensor types feeding `WindowMetrics` and the VTI
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("human_distance", "mm", Aggregation::Min);
+        registry.register("temperature", "celsius", Aggregation::Max);
+        registry.register("energy_consumption", "joules", Aggregation::Mean);
+        registry.register("jerk", "m/s²", Aggregation::Max);
+        registry.register("battery_level", "%", Aggregation::Latest);
+        registry.register("force", "N", Aggregation::Max);
+        registry.register("acceleration", "m/s²", Aggregation::Max);
+        registry.register("proximity_zone", "mm", Aggregation::Min);
+        registry
+    }
+}
+
 impl TelemetryWindow {
     /// Create a new telemetry window
     pub fn new(executor_id: u64, window_start: u64, window_end: u64) -> Self {
@@ -109,95 +466,360 @@ impl TelemetryWindow {
             window_start,
             window_end,
             readings: Vec::new(),
+            dropped_readings: 0,
+            max_readings: None,
+            evicted: BTreeMap::new(),
         }
     }
 
-    /// Add a sensor reading to this window
-    pub fn add_reading(&mut self, reading: SensorReading) {
-        self.readings.push(reading);
+    /// Cap the readings kept in memory. Beyond the cap the oldest readings
+    /// are evicted into per-type running aggregates: min, max, mean and
+    /// latest stay exact, but percentiles only see the retained readings.
+    pub fn set_max_readings(&mut self, max_readings: usize) {
+        self.max_readings = Some(max_readings);
+        self.evict_overflow();
+    }
+
+    fn evict_overflow(&mut self) {
+        let Some(max_readings) = self.max_readings else {
+            return;
+        };
+        let overflow = self.readings.len().saturating_sub(max_readings);
+        for reading in self.readings.drain(..overflow) {
+            let aggregate = EvictedAggregate::from_reading(&reading);
+            self.evicted
+                .entry(reading.sensor_type)
+                .and_modify(|evicted| evicted.combine(&aggregate))
+                .or_insert(aggregate);
+        }
     }
 
-    /// Compute aggregated metrics for this window
+    /// Add a sensor reading to this window. Non-finite values and readings
+    /// older than the window start are rejected and counted as dropped.
+    pub fn add_reading(&mut self, reading: SensorReading) -> Result<(), ReadingRejected> {
+        let rejected = if !reading.value.is_finite() {
+            ReadingRejected::NonFinite {
+                sensor_id: reading.sensor_id,
+                value: reading.value,
+            }
+        } else if reading.timestamp < self.window_start {
+            ReadingRejected::BeforeWindowStart {
+                sensor_id: reading.sensor_id,
+                timestamp: reading.timestamp,
+                window_start: self.window_start,
+            }
+        } else {
+            self.readings.push(reading);
+            self.evict_overflow();
+            return Ok(());
+        };
+
+        self.dropped_readings += 1;
+        Err(rejected)
+    }
+
+    /// Merge another source's window for the same executor and an
+    /// overlapping time range. Readings are kept in timestamp order and the
+    /// bounds widen to cover both windows.
+    pub fn merge(&mut self, other: TelemetryWindow) -> Result<(), WindowMergeError> {
+        if other.executor_id != self.executor_id {
+            return Err(WindowMergeError::ExecutorMismatch {
+                expected: self.executor_id,
+                found: other.executor_id,
+            });
+        }
+        if other.window_start >= self.window_end || self.window_start >= other.window_end {
+            return Err(WindowMergeError::Disjoint {
+                window: (self.window_start, self.window_end),
+                other: (other.window_start, other.window_end),
+            });
+        }
+
+        self.window_start = self.window_start.min(other.window_start);
+        self.window_end = self.window_end.max(other.window_end);
+        self.readings.extend(other.readings);
+        self.readings.sort_by_key(|reading| reading.timestamp);
+        self.dropped_readings += other.dropped_readings;
+        for (sensor_type, aggregate) in other.evicted {
+            self.evicted
+                .entry(sensor_type)
+                .and_modify(|evicted| evicted.combine(&aggregate))
+                .or_insert(aggregate);
+        }
+        self.evict_overflow();
+        Ok(())
+    }
+
+    /// Compute aggregated metrics for this window using the built-in sensor types
     pub fn compute_metrics(&self) -> WindowMetrics {
-        let mut min_human_distance = None;
-        let mut max_temperature = None;
-        let mut energy_readings = Vec::new();
-        let mut max_jerk = None;
-        let mut battery_level = None;
+        self.compute_metrics_with(&SensorRegistry::default())
+    }
+
+    /// Compute aggregated metrics, folding each registered sensor type by its
+    /// aggregation rule. Unregistered types are skipped here; callers should
+    /// flag them on ingest via `SensorRegistry::validate`.
+    pub fn compute_metrics_with(&self, registry: &SensorRegistry) -> WindowMetrics {
+        // sensor type -> (running aggregate, reading count, latest timestamp)
+        let mut aggregates: BTreeMap<String, (f64, usize, u64)> = BTreeMap::new();
+
+        // Readings evicted by the cap seed the aggregates
+        for (sensor_type, evicted) in &self.evicted {
+            let Some(spec) = registry.get(sensor_type) else {
+                continue;
+            };
+            let value = match spec.aggregation {
+                Aggregation::Min => evicted.min,
+                Aggregation::Max => evicted.max,
+                Aggregation::Mean => evicted.sum,
+                Aggregation::Latest => evicted.latest,
+            };
+            let seed = (value, evicted.count, evicted.latest_timestamp);
+            aggregates.insert(sensor_type.clone(), seed);
+        }
 
         for reading in &self.readings {
-            match reading.sensor_type.as_str() {
-                "human_distance" => {
-                    if min_human_distance.is_none() || reading.value < min_human_distance.unwrap() {
-                        min_human_distance = Some(reading.value);
-                    }
-                }
-                "temperature" => {
-                    if max_temperature.is_none() || reading.value > max_temperature.unwrap() {
-                        max_temperature = Some(reading.value);
-                    }
-                }
-                "energy_consumption" => {
-                    energy_readings.push(reading.value);
-                }
-                "jerk" => {
-                    if max_jerk.is_none() || reading.value > max_jerk.unwrap() {
-                        max_jerk = Some(reading.value);
-                    }
-                }
-                "battery_level" => {
-                    battery_level = Some(reading.value);
-                }
-                _ => {} // Ignore unknown sensor types
-            }
+            let Some(spec) = registry.get(&reading.sensor_type) else {
+                continue;
+            };
+
+            let entry = aggregates
+                .entry(reading.sensor_type.clone())
+                .or_insert((reading.value, 0, reading.timestamp));
+            entry.0 = match spec.aggregation {
+                Aggregation::Min => entry.0.min(reading.value),
+                Aggregation::Max => entry.0.max(reading.value),
+                Aggregation::Mean if entry.1 == 0 => reading.value,
+                Aggregation::Mean => entry.0 + reading.value,
+                Aggregation::Latest if reading.timestamp < entry.2 => entry.0,
+                Aggregation::Latest => reading.value,
+            };
+            entry.1 += 1;
+            entry.2 = entry.2.max(reading.timestamp);
         }
 
-        let avg_energy_consumption = if energy_readings.is_empty() {
-            None
-        } else {
-            Some(energy_readings.iter().sum::<f64>() / energy_readings.len() as f64)
-        };
+        let mut values: BTreeMap<String, f64> = aggregates
+            .into_iter()
+            .map(|(sensor_type, (value, count, _))| {
+                let value = match registry.get(&sensor_type).map(|spec| spec.aggregation) {
+                    Some(Aggregation::Mean) => value / count as f64,
+                    _ => value,
+                };
+                (sensor_type, value)
+            })
+            .collect();
 
         WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
             executor_id: self.executor_id,
             window_start: self.window_start,
             window_end: self.window_end,
+            min_human_distance: values.remove("human_distance"),
+            max_temperature: values.remove("temperature"),
+            avg_energy_consumption: values.remove("energy_consumption"),
+            max_jerk: values.remove("jerk"),
+            battery_level: values.remove("battery_level"),
+            p5_human_distance: self.percentile(
+                registry,
+                "human_distance",
+                HUMAN_DISTANCE_PERCENTILE,
+            ),
+            p95_temperature: self.percentile(registry, "temperature", TEMPERATURE_PERCENTILE),
+            max_force: values.remove("force"),
+            max_acceleration: values.remove("acceleration"),
+            min_zone_clearance: values.remove("proximity_zone"),
+            extra_metrics: values,
+        }
+    }
+
+    /// Nearest-rank percentile of the retained readings of a registered
+    /// sensor type, or `None` with fewer than `MIN_PERCENTILE_SAMPLES` readings
+    fn percentile(&self, registry: &SensorRegistry, sensor_type: &str, pct: f64) -> Option<f64> {
+        registry.get(sensor_type)?;
+
+        let mut values: Vec<f64> = self
+            .readings
+            .iter()
+            .filter(|reading| reading.sensor_type == sensor_type)
+            .map(|reading| reading.value)
+            .collect();
+        if values.len() < MIN_PERCENTILE_SAMPLES {
+            return None;
+        }
+
+        values.sort_by(f64::total_cmp);
+        let rank = (pct / 100.0 * (values.len() - 1) as f64).round() as usize;
+        values.get(rank.min(values.len() - 1)).copied()
+    }
+}
+
+impl Pose {
+    /// Same pose with a unit orientation quaternion. Zero-norm and
+    /// non-finite quaternions have no direction to keep and are rejected.
+    pub fn normalized(&self) -> Result<Pose, InvalidOrientation> {
+        let norm = self.orientation_norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err(InvalidOrientation { orientation: self.orientation });
+        }
+
+        Ok(Pose {
+            position: self.position,
+            orientation: self.orientation.map(|component| component / norm),
+        })
+    }
+
+    /// Whether the orientation quaternion has unit norm within `eps`
+    pub fn is_unit(&self, eps: f64) -> bool {
+        (self.orientation_norm() - 1.0).abs() <= eps
+    }
+
+    /// Euclidean distance between the two positions, ignoring orientation
+    pub fn distance_to(&self, other: &Pose) -> f64 {
+        self.position
+            .iter()
+            .zip(&other.position)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn orientation_norm(&self) -> f64 {
+        self.orientation.iter().map(|component| component * component).sum::<f64>().sqrt()
+    }
+}
+
+impl WindowMetrics {
+    /// Compute hash of the metrics for commitment: Keccak-256 of
+    /// `to_canonical_cbor`, so it can be reproduced from the submitted bytes
+    pub fn hash(&self) -> [u8; 32] {
+        vagus_crypto::cbor::hash_keccak(&self.to_canonical_cbor())
+    }
+
+    /// SHA-256 of `to_canonical_cbor`; the CosmWasm inbox checks it against
+    /// `hash` over the same submitted bytes
+    pub fn hash_sha256(&self) -> [u8; 32] {
+        vagus_crypto::cbor::hash_sha256(&self.to_canonical_cbor())
+    }
+
+    /// Deterministic CBOR encoding of the metrics, as submitted alongside the
+    /// AEP for the `afferent_inbox` hash cross-check
+    pub fn to_canonical_cbor(&self) -> Vec<u8> {
+        let commitment = MetricsCommitment {
+            metrics_schema_version: self.metrics_schema_version,
+            executor_id: self.executor_id,
+            window_start: self.window_start,
+            window_end: self.window_end,
+            metrics: self.builtin_metrics().map(|metric| metric.map(to_fixed_point)),
+            extra_metrics: self
+                .extra_metrics
+                .iter()
+                .map(|(sensor_type, value)| (sensor_type.clone(), to_fixed_point(*value)))
+                .collect(),
+        };
+        vagus_crypto::cbor::encode_deterministic(&commitment)
+            .expect("metrics commitment always encodes to CBOR")
+    }
+
+    /// Decode metrics from `to_canonical_cbor` bytes. Values come back at the
+    /// committed fixed-point precision, so they re-hash to the same value.
+    pub fn from_canonical_cbor(bytes: &[u8]) -> Result<Self, serde_cbor::Error> {
+        let commitment: MetricsCommitment = serde_cbor::from_slice(bytes)?;
+        let [
             min_human_distance,
             max_temperature,
             avg_energy_consumption,
             max_jerk,
             battery_level,
-        }
+            p5_human_distance,
+            p95_temperature,
+            max_force,
+            max_acceleration,
+            min_zone_clearance,
+        ] = commitment.metrics.map(|metric| metric.map(from_fixed_point));
+
+        Ok(Self {
+            metrics_schema_version: commitment.metrics_schema_version,
+            executor_id: commitment.executor_id,
+            window_start: commitment.window_start,
+            window_end: commitment.window_end,
+            min_human_distance,
+            max_temperature,
+            avg_energy_consumption,
+            max_jerk,
+            battery_level,
+            p5_human_distance,
+            p95_temperature,
+            max_force,
+            max_acceleration,
+            min_zone_clearance,
+            extra_metrics: commitment
+                .extra_metrics
+                .into_iter()
+                .map(|(sensor_type, value)| (sensor_type, from_fixed_point(value)))
+                .collect(),
+        })
+    }
+
+    /// Built-in metrics in commitment order
+    fn builtin_metrics(&self) -> [Option<f64>; 10] {
+        [
+            self.min_human_distance,
+            self.max_temperature,
+            self.avg_energy_consumption,
+            self.max_jerk,
+            self.battery_level,
+            self.p5_human_distance,
+            self.p95_temperature,
+            self.max_force,
+            self.max_acceleration,
+            self.min_zone_clearance,
+        ]
     }
 }
 
-impl WindowMetrics {
-    /// Compute hash of the metrics for commitment
-    pub fn hash(&self) -> [u8; 32] {
-        use sha3::{Digest, Sha3_256};
+/// Committed form of `WindowMetrics`. Metrics are fixed point so the encoding
+/// doesn't depend on float formatting; a missing metric encodes as null.
+#[derive(Serialize, Deserialize)]
+struct MetricsCommitment {
+    metrics_schema_version: u32,
+    executor_id: u64,
+    window_start: u64,
+    window_end: u64,
+    metrics: [Option<i64>; 10],
+    extra_metrics: BTreeMap<String, i64>,
+}
 
-        let mut hasher = Sha3_256::new();
-        hasher.update(self.executor_id.to_be_bytes());
-        hasher.update(self.window_start.to_be_bytes());
-        hasher.update(self.window_end.to_be_bytes());
+// Values beyond the i64 range saturate and NaN encodes as zero
+fn to_fixed_point(value: f64) -> i64 {
+    (value * METRIC_FIXED_POINT_SCALE).round() as i64
+}
 
-        if let Some(dist) = self.min_human_distance {
-            hasher.update((dist as u64).to_be_bytes());
-        }
-        if let Some(temp) = self.max_temperature {
-            hasher.update((temp as u64).to_be_bytes());
-        }
-        if let Some(energy) = self.avg_energy_consumption {
-            hasher.update((energy as u64).to_be_bytes());
-        }
-        if let Some(jerk) = self.max_jerk {
-            hasher.update((jerk as u64).to_be_bytes());
-        }
-        if let Some(battery) = self.battery_level {
-            hasher.update((battery as u64).to_be_bytes());
+fn from_fixed_point(value: i64) -> f64 {
+    value as f64 / METRIC_FIXED_POINT_SCALE
+}
+
+impl AfferentEvidencePacket {
+    /// Reject packets whose metrics hash was computed under another schema
+    /// version; their hashes are not comparable with ours
+    pub fn check_schema_version(&self) -> Result<(), SchemaVersionMismatch> {
+        if self.metrics_schema_version == METRICS_SCHEMA_VERSION {
+            Ok(())
+        } else {
+            Err(SchemaVersionMismatch {
+                expected: METRICS_SCHEMA_VERSION,
+                found: self.metrics_schema_version,
+            })
         }
+    }
 
-        hasher.finalize().into()
+    /// Whether this packet commits to `metrics`: same executor and schema
+    /// version, and metrics bytes and hashes matching the recomputed ones
+    pub fn verify_against(&self, metrics: &WindowMetrics) -> bool {
+        let cbor = metrics.to_canonical_cbor();
+        self.executor_id == metrics.executor_id
+            && self.metrics_schema_version == metrics.metrics_schema_version
+            && self.metrics_cbor == cbor
+            && self.metrics_hash == vagus_crypto::cbor::hash_keccak(&cbor)
+            && self.metrics_hash_sha256 == vagus_crypto::cbor::hash_sha256(&cbor)
     }
 }
 
@@ -214,59 +836,125 @@ impl VagalToneIndicator {
         }
     }
 
-    /// Compute VTI from window metrics using a simple weighted formula
+    /// Compute VTI from window metrics with the default `VtiWeights`
     pub fn from_metrics(metrics: &WindowMetrics) -> Self {
+        Self::from_metrics_with_weights(metrics, &VtiWeights::default())
+    }
+
+    /// Compute VTI from window metrics as a weighted mean of per-metric danger.
+    /// Human distance and temperature use their percentiles when the window
+    /// had enough readings, so a single spurious spike doesn't trip danger.
+    pub fn from_metrics_with_weights(metrics: &WindowMetrics, weights: &VtiWeights) -> Self {
         let mut vti = Self::new();
         let mut total_weight = 0.0;
 
         // Human distance contribution (lower distance = higher danger)
-        if let Some(dist) = metrics.min_human_distance {
-            let dist_contrib = if dist < 500.0 {
-                1.0 - (dist / 500.0).min(1.0) // Danger when < 500mm
+        if let Some(dist) = metrics.p5_human_distance.or(metrics.min_human_distance) {
+            let danger_mm = weights.human_distance_danger_mm;
+            let dist_contrib = if dist < danger_mm {
+                1.0 - (dist / danger_mm).min(1.0)
             } else {
                 0.0
             };
             vti.contributions.insert("human_distance".to_string(), dist_contrib);
-            vti.value += dist_contrib * 0.4; // 40% weight
-            total_weight += 0.4;
+            vti.value += dist_contrib * weights.human_distance;
+            total_weight += weights.human_distance;
         }
 
         // Temperature contribution
-        if let Some(temp) = metrics.max_temperature {
-            let temp_contrib = if temp > 80.0 {
-                ((temp - 80.0) / 20.0).min(1.0) // Danger when > 80°C
+        if let Some(temp) = metrics.p95_temperature.or(metrics.max_temperature) {
+            let danger_celsius = weights.temperature_danger_celsius;
+            let temp_contrib = if temp > danger_celsius {
+                ((temp - danger_celsius) / weights.temperature_span_celsius).min(1.0)
             } else {
                 0.0
             };
             vti.contributions.insert("temperature".to_string(), temp_contrib);
-            vti.value += temp_contrib * 0.2; // 20% weight
-            total_weight += 0.2;
+            vti.value += temp_contrib * weights.temperature;
+            total_weight += weights.temperature;
         }
 
         // Energy consumption contribution (higher = more dangerous)
         if let Some(energy) = metrics.avg_energy_consumption {
-            let energy_contrib = (energy / 1000.0).min(1.0); // Normalize to 1000J max
+            let energy_contrib = (energy / weights.max_energy_joules).min(1.0);
             vti.contributions.insert("energy".to_string(), energy_contrib);
-            vti.value += energy_contrib * 0.2; // 20% weight
-            total_weight += 0.2;
+            vti.value += energy_contrib * weights.energy;
+            total_weight += weights.energy;
         }
 
         // Jerk contribution (sudden movements are dangerous)
         if let Some(jerk) = metrics.max_jerk {
-            let jerk_contrib = (jerk / 2000.0).min(1.0); // Normalize to 2000 mm/s² max
+            let jerk_contrib = (jerk / weights.max_jerk).min(1.0);
             vti.contributions.insert("jerk".to_string(), jerk_contrib);
-            vti.value += jerk_contrib * 0.2; // 20% weight
-            total_weight += 0.2;
+            vti.value += jerk_contrib * weights.jerk;
+            total_weight += weights.jerk;
+        }
+
+        // Force contribution (collaborative contact with a person or fixture)
+        if let Some(force) = metrics.max_force {
+            let force_contrib = (force / weights.max_force_newtons).min(1.0);
+            vti.contributions.insert("force".to_string(), force_contrib);
+            vti.value += force_contrib * weights.force;
+            total_weight += weights.force;
+        }
+
+        // Acceleration contribution
+        if let Some(acceleration) = metrics.max_acceleration {
+            let acceleration_contrib = (acceleration / weights.max_acceleration).min(1.0);
+            vti.contributions.insert("acceleration".to_string(), acceleration_contrib);
+            vti.value += acceleration_contrib * weights.acceleration;
+            total_weight += weights.acceleration;
+        }
+
+        // Zone clearance contribution (lower clearance = higher danger)
+        if let Some(clearance) = metrics.min_zone_clearance {
+            let danger_mm = weights.zone_clearance_danger_mm;
+            let zone_contrib = if clearance < danger_mm {
+                1.0 - (clearance / danger_mm).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            vti.contributions.insert("zone_clearance".to_string(), zone_contrib);
+            vti.value += zone_contrib * weights.zone_clearance;
+            total_weight += weights.zone_clearance;
         }
 
-        // Normalize by total weight
-        if total_weight > 0.0 {
-            vti.value /= total_weight;
+        // With no usable metric there is nothing to show the executor is
+        // safe, so report the most dangerous value instead of 0.0
+        if total_weight == 0.0 {
+            vti.contributions.insert(INSUFFICIENT_DATA.to_string(), 1.0);
+            vti.value = 1.0;
+            return vti;
         }
 
-        vti.value = vti.value.min(1.0); // Clamp to [0, 1]
+        vti.value /= total_weight;
+
+        // Clamp to [0, 1]; a NaN from a faulty sensor counts as dangerous
+        vti.value = if vti.value.is_nan() { 1.0 } else { vti.value.clamp(0.0, 1.0) };
         vti
     }
+
+    /// Whether the VTI was computed without any usable metric
+    pub fn is_insufficient_data(&self) -> bool {
+        self.contributions.contains_key(INSUFFICIENT_DATA)
+    }
+
+    /// Effective VTI at `now_ms`. Decay raises the risk: the margin to the
+    /// most dangerous value (1.0) halves every `half_life_ms` since the VTI
+    /// was computed, so a tone built from data that has dried up trends
+    /// toward caution instead of vouching for the executor indefinitely.
+    pub fn decayed(&self, now_ms: u64, half_life_ms: u64) -> f64 {
+        let age_ms = now_ms.saturating_sub(self.timestamp);
+        if age_ms == 0 {
+            return self.value;
+        }
+        if half_life_ms == 0 {
+            return 1.0;
+        }
+
+        let remaining = 0.5f64.powf(age_ms as f64 / half_life_ms as f64);
+        1.0 - (1.0 - self.value) * remaining
+    }
 }
 
 #[cfg(test)]
@@ -283,7 +971,7 @@ mod tests {
             value: 300.0,
             unit: "mm".to_string(),
             timestamp: 1500,
-        });
+        }).unwrap();
 
         window.add_reading(SensorReading {
             sensor_id: "temp_1".to_string(),
@@ -291,7 +979,7 @@ mod tests {
             value: 85.0,
             unit: "celsius".to_string(),
             timestamp: 1500,
-        });
+        }).unwrap();
 
         let metrics = window.compute_metrics();
 
@@ -299,9 +987,111 @@ mod tests {
         assert_eq!(metrics.max_temperature, Some(85.0));
     }
 
+    #[test]
+    fn test_window_rejects_non_finite_and_stale_readings() {
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        let reading = |value: f64, timestamp: u64| SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_distance".to_string(),
+            value,
+            unit: "mm".to_string(),
+            timestamp,
+        };
+
+        window.add_reading(reading(300.0, 1500)).unwrap();
+        assert!(matches!(
+            window.add_reading(reading(f64::NAN, 1600)),
+            Err(ReadingRejected::NonFinite { .. })
+        ));
+        assert_eq!(
+            window.add_reading(reading(100.0, 900)),
+            Err(ReadingRejected::BeforeWindowStart {
+                sensor_id: "dist_1".to_string(),
+                timestamp: 900,
+                window_start: 1000,
+            })
+        );
+        window.add_reading(reading(250.0, 1700)).unwrap();
+
+        assert_eq!(window.readings.len(), 2);
+        assert_eq!(window.dropped_readings, 2);
+        assert_eq!(window.compute_metrics().min_human_distance, Some(250.0));
+    }
+
+    #[test]
+    fn test_capped_window_evicts_oldest_and_keeps_aggregates() {
+        let mut window = TelemetryWindow::new(42, 0, 100_000);
+        window.set_max_readings(50);
+        let reading = |sensor_type: &str, unit: &str, value: f64, timestamp: u64| SensorReading {
+            sensor_id: format!("{}_1", sensor_type),
+            sensor_type: sensor_type.to_string(),
+            value,
+            unit: unit.to_string(),
+            timestamp,
+        };
+
+        // The closest approach and the hottest reading come first and get evicted
+        window.add_reading(reading("human_distance", "mm", 120.0, 0)).unwrap();
+        window.add_reading(reading("temperature", "celsius", 90.0, 0)).unwrap();
+        for i in 1..=1000u64 {
+            let value = 500.0 + (i % 10) as f64;
+            window.add_reading(reading("human_distance", "mm", value, i)).unwrap();
+            window.add_reading(reading("energy_consumption", "joules", 100.0, i)).unwrap();
+            window.add_reading(reading("battery_level", "%", 90.0 - i as f64 / 100.0, i)).unwrap();
+        }
+
+        assert_eq!(window.readings.len(), 50);
+        assert_eq!(window.readings.first().unwrap().timestamp, 984);
+        assert_eq!(window.evicted["human_distance"].count, 985);
+
+        let metrics = window.compute_metrics();
+        assert_eq!(metrics.min_human_distance, Some(120.0));
+        assert_eq!(metrics.max_temperature, Some(90.0));
+        assert_eq!(metrics.avg_energy_consumption, Some(100.0));
+        assert_eq!(metrics.battery_level, Some(80.0));
+    }
+
+    #[test]
+    fn test_merge_combines_partial_windows() {
+        let reading = |sensor_type: &str, unit: &str, value: f64, timestamp: u64| SensorReading {
+            sensor_id: format!("{}_1", sensor_type),
+            sensor_type: sensor_type.to_string(),
+            value,
+            unit: unit.to_string(),
+            timestamp,
+        };
+        let mut vision = TelemetryWindow::new(42, 1000, 2000);
+        vision.add_reading(reading("human_distance", "mm", 600.0, 1200)).unwrap();
+        vision.add_reading(reading("battery_level", "%", 80.0, 1900)).unwrap();
+        let mut drive = TelemetryWindow::new(42, 1500, 2500);
+        drive.add_reading(reading("human_distance", "mm", 450.0, 1600)).unwrap();
+        drive.add_reading(reading("temperature", "celsius", 55.0, 1700)).unwrap();
+        drive.add_reading(reading("battery_level", "%", 79.0, 2100)).unwrap();
+
+        vision.merge(drive).unwrap();
+        assert_eq!((vision.window_start, vision.window_end), (1000, 2500));
+        assert_eq!(vision.readings.len(), 5);
+
+        let metrics = vision.compute_metrics();
+        assert_eq!(metrics.min_human_distance, Some(450.0));
+        assert_eq!(metrics.max_temperature, Some(55.0));
+        // Latest is decided by timestamp, not by source order
+        assert_eq!(metrics.battery_level, Some(79.0));
+
+        let other_executor = TelemetryWindow::new(7, 1000, 2000);
+        assert_eq!(
+            vision.merge(other_executor),
+            Err(WindowMergeError::ExecutorMismatch { expected: 42, found: 7 })
+        );
+        let later = TelemetryWindow::new(42, 2500, 3500);
+        assert!(matches!(vision.merge(later), Err(WindowMergeError::Disjoint { .. })));
+        assert_eq!(vision.readings.len(), 5);
+    }
+
     #[test]
     fn test_vti_computation() {
         let metrics = WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
             executor_id: 42,
             window_start: 1000,
             window_end: 2000,
@@ -310,6 +1100,12 @@ mod tests {
             avg_energy_consumption: Some(500.0), // Medium energy
             max_jerk: Some(1000.0),          // Medium jerk
             battery_level: Some(50.0),
+            p5_human_distance: None,
+            p95_temperature: None,
+            max_force: None,
+            max_acceleration: None,
+            min_zone_clearance: None,
+            extra_metrics: BTreeMap::new(),
         };
 
         let vti = VagalToneIndicator::from_metrics(&metrics);
@@ -319,5 +1115,443 @@ mod tests {
         assert!(vti.contributions.contains_key("temperature"));
         assert!(vti.contributions.contains_key("energy"));
         assert!(vti.contributions.contains_key("jerk"));
+        assert!(!vti.is_insufficient_data());
+    }
+
+    #[test]
+    fn test_cobot_sensor_types_aggregate_and_contribute() {
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        let reading = |sensor_type: &str, unit: &str, value: f64, timestamp: u64| SensorReading {
+            sensor_id: format!("{}_1", sensor_type),
+            sensor_type: sensor_type.to_string(),
+            value,
+            unit: unit.to_string(),
+            timestamp,
+        };
+        for (force, acceleration, zone, timestamp) in
+            [(40.0, 2.0, 900.0, 1100), (75.0, 5.0, 250.0, 1200), (60.0, 3.0, 400.0, 1300)]
+        {
+            window.add_reading(reading("force", "N", force, timestamp)).unwrap();
+            window.add_reading(reading("acceleration", "m/s²", acceleration, timestamp)).unwrap();
+            window.add_reading(reading("proximity_zone", "mm", zone, timestamp)).unwrap();
+        }
+
+        let metrics = window.compute_metrics();
+        assert_eq!(metrics.max_force, Some(75.0));
+        assert_eq!(metrics.max_acceleration, Some(5.0));
+        assert_eq!(metrics.min_zone_clearance, Some(250.0));
+        assert!(metrics.extra_metrics.is_empty());
+
+        let vti = VagalToneIndicator::from_metrics(&metrics);
+        assert!(!vti.is_insufficient_data());
+        assert_eq!(vti.contributions.get("force"), Some(&0.5));
+        assert_eq!(vti.contributions.get("acceleration"), Some(&0.5));
+        assert_eq!(vti.contributions.get("zone_clearance"), Some(&0.5));
+        assert!((vti.value - 0.5).abs() < 1e-9);
+
+        // The new metrics are committed in the hash
+        let no_force = WindowMetrics {
+            max_force: None,
+            ..metrics.clone()
+        };
+        assert_ne!(metrics.hash(), no_force.hash());
+    }
+
+    #[test]
+    fn test_vti_weights_change_the_result() {
+        let metrics = WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
+            executor_id: 42,
+            window_start: 1000,
+            window_end: 2000,
+            min_human_distance: Some(250.0), // contributes 0.5
+            max_temperature: None,
+            avg_energy_consumption: None,
+            max_jerk: Some(2000.0), // contributes 1.0
+            battery_level: None,
+            p5_human_distance: None,
+            p95_temperature: None,
+            max_force: None,
+            max_acceleration: None,
+            min_zone_clearance: None,
+            extra_metrics: BTreeMap::new(),
+        };
+
+        let default = VagalToneIndicator::from_metrics(&metrics);
+        assert_eq!(
+            default.value,
+            VagalToneIndicator::from_metrics_with_weights(&metrics, &VtiWeights::default()).value
+        );
+        assert!((default.value - 2.0 / 3.0).abs() < 1e-9);
+
+        let distance_heavy = VtiWeights {
+            human_distance: 0.8,
+            jerk: 0.2,
+            ..VtiWeights::default()
+        };
+        let vti = VagalToneIndicator::from_metrics_with_weights(&metrics, &distance_heavy);
+        assert!((vti.value - 0.6).abs() < 1e-9);
+
+        // Thresholds are tunable as well
+        let tolerant = VtiWeights {
+            human_distance_danger_mm: 250.0,
+            max_jerk: 4000.0,
+            ..VtiWeights::default()
+        };
+        let vti = VagalToneIndicator::from_metrics_with_weights(&metrics, &tolerant);
+        assert_eq!(vti.contributions.get("human_distance"), Some(&0.0));
+        assert!((vti.value - 1.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decayed_vti_trends_toward_danger() {
+        let vti = VagalToneIndicator {
+            value: 0.2,
+            contributions: HashMap::new(),
+            timestamp: 10_000,
+        };
+        let half_life = 5_000;
+
+        assert_eq!(vti.decayed(10_000, half_life), 0.2);
+        // A clock behind the computation time doesn't lower the risk
+        assert_eq!(vti.decayed(9_000, half_life), 0.2);
+        assert!((vti.decayed(15_000, half_life) - 0.6).abs() < 1e-9);
+        assert!((vti.decayed(20_000, half_life) - 0.8).abs() < 1e-9);
+        assert!(vti.decayed(60_000, half_life) > 0.999);
+        assert!(vti.decayed(60_000, half_life) <= 1.0);
+
+        assert_eq!(vti.decayed(10_001, 0), 1.0);
+    }
+
+    #[test]
+    fn test_vti_without_metrics_is_not_safe() {
+        let metrics = WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
+            executor_id: 42,
+            window_start: 1000,
+            window_end: 2000,
+            min_human_distance: None,
+            max_temperature: None,
+            avg_energy_consumption: None,
+            max_jerk: None,
+            battery_level: None,
+            p5_human_distance: None,
+            p95_temperature: None,
+            max_force: None,
+            max_acceleration: None,
+            min_zone_clearance: None,
+            extra_metrics: BTreeMap::new(),
+        };
+
+        let vti = VagalToneIndicator::from_metrics(&metrics);
+
+        assert!(vti.is_insufficient_data());
+        assert_eq!(vti.value, 1.0);
+
+        // A NaN reading is clamped to the dangerous end as well
+        let vti = VagalToneIndicator::from_metrics(&WindowMetrics {
+            max_jerk: Some(f64::NAN),
+            ..metrics
+        });
+        assert!(!vti.is_insufficient_data());
+        assert_eq!(vti.value, 1.0);
+    }
+
+    #[test]
+    fn test_percentiles_ignore_single_outlier() {
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        let reading = |sensor_type: &str, unit: &str, value: f64, timestamp: u64| SensorReading {
+            sensor_id: format!("{}_1", sensor_type),
+            sensor_type: sensor_type.to_string(),
+            value,
+            unit: unit.to_string(),
+            timestamp,
+        };
+        for i in 0..MIN_PERCENTILE_SAMPLES as u64 - 1 {
+            window.add_reading(reading("human_distance", "mm", 800.0, 1000 + i)).unwrap();
+            window.add_reading(reading("temperature", "celsius", 40.0, 1000 + i)).unwrap();
+        }
+        // A reflection and a glitching thermistor
+        window.add_reading(reading("human_distance", "mm", 50.0, 1500)).unwrap();
+        window.add_reading(reading("temperature", "celsius", 150.0, 1500)).unwrap();
+
+        let metrics = window.compute_metrics();
+        assert_eq!(metrics.min_human_distance, Some(50.0));
+        assert_eq!(metrics.max_temperature, Some(150.0));
+        assert_eq!(metrics.p5_human_distance, Some(800.0));
+        assert_eq!(metrics.p95_temperature, Some(40.0));
+
+        let vti = VagalToneIndicator::from_metrics(&metrics);
+        assert_eq!(vti.contributions.get("human_distance"), Some(&0.0));
+        assert_eq!(vti.contributions.get("temperature"), Some(&0.0));
+
+        // Too few samples for a percentile: the extremes decide
+        let extremes = WindowMetrics {
+            p5_human_distance: None,
+            p95_temperature: None,
+            ..metrics
+        };
+        let vti = VagalToneIndicator::from_metrics(&extremes);
+        assert_eq!(vti.contributions.get("human_distance"), Some(&0.9));
+        assert_eq!(vti.contributions.get("temperature"), Some(&1.0));
+    }
+
+    #[test]
+    fn test_registry_flags_misspelled_sensor_type() {
+        let registry = SensorRegistry::default();
+        let reading = SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_dist".to_string(),
+            value: 120.0,
+            unit: "mm".to_string(),
+            timestamp: 1500,
+        };
+
+        assert_eq!(
+            registry.validate(&reading),
+            Err(SensorIssue::UnknownType {
+                sensor_id: "dist_1".to_string(),
+                sensor_type: "human_dist".to_string(),
+            })
+        );
+
+        let wrong_unit = SensorReading {
+            sensor_type: "human_distance".to_string(),
+            unit: "cm".to_string(),
+            ..reading
+        };
+        assert!(matches!(
+            registry.validate(&wrong_unit),
+            Err(SensorIssue::UnitMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registered_sensor_type_is_aggregated() {
+        let mut registry = SensorRegistry::default();
+        registry.register("motor_current", "amps", Aggregation::Max);
+
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        for (value, timestamp) in [(3.5, 1100), (7.25, 1200), (5.0, 1300)] {
+            let reading = SensorReading {
+                sensor_id: "motor_1".to_string(),
+                sensor_type: "motor_current".to_string(),
+                value,
+                unit: "amps".to_string(),
+                timestamp,
+            };
+            assert!(registry.validate(&reading).is_ok());
+            window.add_reading(reading).unwrap();
+        }
+
+        let metrics = window.compute_metrics_with(&registry);
+        assert_eq!(metrics.extra_metrics.get("motor_current"), Some(&7.25));
+
+        // The built-in registry doesn't know the type
+        assert!(window.compute_metrics().extra_metrics.is_empty());
+    }
+
+    #[test]
+    fn test_schema_version_is_committed_in_hash() {
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        window.add_reading(SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_distance".to_string(),
+            value: 300.0,
+            unit: "mm".to_string(),
+            timestamp: 1500,
+        }).unwrap();
+
+        let current = window.compute_metrics();
+        assert_eq!(current.metrics_schema_version, METRICS_SCHEMA_VERSION);
+
+        let older = WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION - 1,
+            ..current.clone()
+        };
+        assert_ne!(current.hash(), older.hash());
+
+        let aep = AfferentEvidencePacket {
+            executor_id: 42,
+            state_root: older.hash(),
+            metrics_hash: older.hash(),
+            metrics_hash_sha256: older.hash_sha256(),
+            metrics_cbor: older.to_canonical_cbor(),
+            metrics_schema_version: older.metrics_schema_version,
+            attestation: None,
+            timestamp: 2000,
+        };
+        assert_eq!(
+            aep.check_schema_version(),
+            Err(SchemaVersionMismatch {
+                expected: METRICS_SCHEMA_VERSION,
+                found: METRICS_SCHEMA_VERSION - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_hash_encodes_metrics_in_fixed_point() {
+        let metrics = WindowMetrics {
+            metrics_schema_version: METRICS_SCHEMA_VERSION,
+            executor_id: 42,
+            window_start: 1000,
+            window_end: 2000,
+            min_human_distance: Some(300.4),
+            max_temperature: None,
+            avg_energy_consumption: None,
+            max_jerk: None,
+            battery_level: None,
+            p5_human_distance: None,
+            p95_temperature: None,
+            max_force: None,
+            max_acceleration: None,
+            min_zone_clearance: None,
+            extra_metrics: BTreeMap::new(),
+        };
+        let with = |min_human_distance, max_temperature| WindowMetrics {
+            min_human_distance,
+            max_temperature,
+            ..metrics.clone()
+        };
+
+        // Fractions no longer truncate away
+        assert_ne!(metrics.hash(), with(Some(300.6), None).hash());
+        assert_eq!(metrics.hash(), with(Some(300.4000001), None).hash());
+        assert_ne!(metrics.hash(), with(Some(-300.4), None).hash());
+
+        // A missing metric differs from a zero reading, and metrics can't
+        // shift into each other's slot
+        assert_ne!(metrics.hash(), with(Some(300.4), Some(0.0)).hash());
+        assert_ne!(with(None, Some(0.0)).hash(), with(Some(0.0), None).hash());
+
+        // Extra metric names are length-prefixed
+        let extra = |entries: &[(&str, f64)]| WindowMetrics {
+            extra_metrics: entries.iter().map(|(name, value)| (name.to_string(), *value)).collect(),
+            ..metrics.clone()
+        };
+        assert_ne!(
+            extra(&[("ab", 1.0), ("c", 2.0)]).hash(),
+            extra(&[("a", 1.0), ("bc", 2.0)]).hash()
+        );
+        assert_ne!(extra(&[]).hash(), extra(&[("a", 0.0)]).hash());
+    }
+
+    #[test]
+    fn test_canonical_cbor_round_trips_to_the_same_hash() {
+        use sha2::Sha256;
+        use sha3::{Digest, Keccak256};
+
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        for (sensor_type, unit, value) in [
+            ("human_distance", "mm", 312.3456),
+            ("temperature", "celsius", 41.5),
+            ("force", "N", 12.0),
+        ] {
+            window.add_reading(SensorReading {
+                sensor_id: format!("{}_1", sensor_type),
+                sensor_type: sensor_type.to_string(),
+                value,
+                unit: unit.to_string(),
+                timestamp: 1500,
+            }).unwrap();
+        }
+        let mut metrics = window.compute_metrics();
+        metrics.extra_metrics.insert("motor_current".to_string(), 7.25);
+
+        let bytes = metrics.to_canonical_cbor();
+        assert_eq!(bytes, metrics.to_canonical_cbor());
+        assert_eq!(metrics.hash(), <[u8; 32]>::from(Keccak256::digest(&bytes)));
+        assert_eq!(metrics.hash_sha256(), <[u8; 32]>::from(Sha256::digest(&bytes)));
+
+        let decoded = WindowMetrics::from_canonical_cbor(&bytes).unwrap();
+        assert_eq!(decoded.hash(), metrics.hash());
+        assert_eq!(decoded.to_canonical_cbor(), bytes);
+        assert_eq!(decoded.min_human_distance, Some(312.346));
+        assert_eq!(decoded.max_temperature, Some(41.5));
+        assert_eq!(decoded.battery_level, None);
+        assert_eq!(decoded.extra_metrics.get("motor_current"), Some(&7.25));
+
+        assert!(WindowMetrics::from_canonical_cbor(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_pose_normalization_and_distance() {
+        let pose = Pose {
+            position: [0.0, 0.0, 0.0],
+            orientation: [2.0, 0.0, 0.0, 2.0],
+        };
+        assert!(!pose.is_unit(1e-9));
+
+        let normalized = pose.normalized().unwrap();
+        assert!(normalized.is_unit(1e-9));
+        let half_sqrt2 = std::f64::consts::FRAC_1_SQRT_2;
+        let expected = [half_sqrt2, 0.0, 0.0, half_sqrt2];
+        for (component, expected) in normalized.orientation.iter().zip(expected) {
+            assert!((component - expected).abs() < 1e-12);
+        }
+        assert_eq!(normalized.position, pose.position);
+
+        let zero = Pose {
+            orientation: [0.0; 4],
+            ..pose.clone()
+        };
+        assert_eq!(
+            zero.normalized().unwrap_err(),
+            InvalidOrientation { orientation: [0.0; 4] }
+        );
+        assert!(!zero.is_unit(1e-9));
+
+        let other = Pose {
+            position: [300.0, 400.0, 0.0],
+            ..normalized.clone()
+        };
+        assert_eq!(pose.distance_to(&other), 500.0);
+        assert_eq!(other.distance_to(&pose), 500.0);
+        assert_eq!(pose.distance_to(&normalized), 0.0);
+    }
+
+    #[test]
+    fn test_verify_against_recomputes_metrics_hash() {
+        let mut window = TelemetryWindow::new(42, 1000, 2000);
+        window.add_reading(SensorReading {
+            sensor_id: "dist_1".to_string(),
+            sensor_type: "human_distance".to_string(),
+            value: 300.0,
+            unit: "mm".to_string(),
+            timestamp: 1500,
+        }).unwrap();
+        let metrics = window.compute_metrics();
+
+        let aep = AfferentEvidencePacket {
+            executor_id: 42,
+            state_root: metrics.hash(),
+            metrics_hash: metrics.hash(),
+            metrics_hash_sha256: metrics.hash_sha256(),
+            metrics_cbor: metrics.to_canonical_cbor(),
+            metrics_schema_version: metrics.metrics_schema_version,
+            attestation: None,
+            timestamp: 2000,
+        };
+        assert!(aep.verify_against(&metrics));
+
+        let closer = WindowMetrics {
+            min_human_distance: Some(100.0),
+            ..metrics.clone()
+        };
+        assert!(!aep.verify_against(&closer));
+
+        let other_executor = AfferentEvidencePacket {
+            executor_id: 7,
+            ..aep.clone()
+        };
+        assert!(!other_executor.verify_against(&metrics));
+
+        // Both hashes must be over the submitted bytes
+        let mismatched_sha256 = AfferentEvidencePacket {
+            metrics_hash_sha256: metrics.hash(),
+            ..aep.clone()
+        };
+        assert!(!mismatched_sha256.verify_against(&metrics));
     }
 }
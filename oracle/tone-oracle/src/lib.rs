@@ -26,10 +26,96 @@ pub struct VtiResult {
     pub suggested_state: String, // "SAFE", "DANGER", "SHUTDOWN"
 }
 
+/// How samples are weighted by age when averaging the window
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WindowDecay {
+    /// Every sample counts equally
+    #[default]
+    Flat,
+    /// Weight falls linearly from 1 for the newest sample to 0 at `horizon_ms` older
+    Linear { horizon_ms: u64 },
+    /// Weight halves for every `half_life_ms` a sample is older than the newest
+    Exponential { half_life_ms: u64 },
+}
+
+impl WindowDecay {
+    /// Weight of a sample `age_ms` older than the newest in the window
+    pub fn weight(&self, age_ms: u64) -> f64 {
+        match *self {
+            WindowDecay::Flat => 1.0,
+            WindowDecay::Linear { horizon_ms } => {
+                if horizon_ms == 0 {
+                    return if age_ms == 0 { 1.0 } else { 0.0 };
+                }
+                (1.0 - age_ms as f64 / horizon_ms as f64).max(0.0)
+            }
+            WindowDecay::Exponential { half_life_ms } => {
+                if half_life_ms == 0 {
+                    return if age_ms == 0 { 1.0 } else { 0.0 };
+                }
+                0.5f64.powf(age_ms as f64 / half_life_ms as f64)
+            }
+        }
+    }
+}
+
+/// Limit on how fast the reported VTI may move between windows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlewLimit {
+    /// Largest change in basis points from one window to the next
+    pub max_step: u64,
+    /// Consecutive windows a rise may be clamped before the raw VTI passes
+    /// through, so a real escalation is delayed by at most this many windows
+    pub max_clamped_windows: u32,
+}
+
+/// Rate-of-change filter keeping a single outlier from swinging the VTI
+#[derive(Debug, Clone)]
+pub struct VtiSlewLimiter {
+    limit: SlewLimit,
+    last: Option<u64>,
+    clamped_windows: u32,
+}
+
+impl VtiSlewLimiter {
+    pub fn new(limit: SlewLimit) -> Self {
+        Self {
+            limit,
+            last: None,
+            clamped_windows: 0,
+        }
+    }
+
+    /// Limit a raw VTI against the previously reported one
+    pub fn apply(&mut self, raw: u64) -> u64 {
+        let limited = match self.last {
+            None => raw,
+            Some(last) if raw > last.saturating_add(self.limit.max_step) => {
+                if self.clamped_windows < self.limit.max_clamped_windows {
+                    self.clamped_windows += 1;
+                    last + self.limit.max_step
+                } else {
+                    // A rise this persistent is not an outlier
+                    self.clamped_windows = 0;
+                    raw
+                }
+            }
+            Some(last) => {
+                self.clamped_windows = 0;
+                raw.max(last.saturating_sub(self.limit.max_step))
+            }
+        };
+        self.last = Some(limited);
+        limited
+    }
+}
+
 /// Configuration for VTI computation
 #[derive(Debug, Clone)]
 pub struct VtiConfig {
     pub window_size: usize, // Number of metrics to keep for averaging
+    pub decay: WindowDecay, // Age weighting applied within the window
+    pub slew_limit: Option<SlewLimit>, // Unset = VTI follows each window directly
     pub safe_threshold: f64,
     pub danger_threshold: f64,
     pub shutdown_threshold: f64,
@@ -40,6 +126,8 @@ impl Default for VtiConfig {
     fn default() -> Self {
         Self {
             window_size: 10,
+            decay: WindowDecay::Flat,
+            slew_limit: None,
             safe_threshold: 3000.0,    // 30%
             danger_threshold: 7000.0,  // 70%
             shutdown_threshold: 9000.0, // 90%
@@ -77,24 +165,34 @@ impl VtiCalculator {
             return None;
         }
 
-        // Compute average metrics
+        // Compute weighted average metrics, with ages relative to the newest sample
+        let newest = self
+            .metrics_window
+            .iter()
+            .map(|metrics| metrics.timestamp_ms)
+            .max()
+            .unwrap_or(0);
+
+        let mut total_weight = 0.0;
         let mut total_distance = 0.0;
         let mut total_temp = 0.0;
         let mut total_energy = 0.0;
         let mut total_jerk = 0.0;
 
         for metrics in &self.metrics_window {
-            total_distance += metrics.human_distance_mm;
-            total_temp += metrics.temperature_celsius;
-            total_energy += metrics.energy_consumption_j;
-            total_jerk += metrics.jerk_m_s3;
+            let weight = self.config.decay.weight(newest - metrics.timestamp_ms);
+            total_weight += weight;
+            total_distance += metrics.human_distance_mm * weight;
+            total_temp += metrics.temperature_celsius * weight;
+            total_energy += metrics.energy_consumption_j * weight;
+            total_jerk += metrics.jerk_m_s3 * weight;
         }
 
-        let count = self.metrics_window.len() as f64;
-        let avg_distance = total_distance / count;
-        let avg_temp = total_temp / count;
-        let avg_energy = total_energy / count;
-        let avg_jerk = total_jerk / count;
+        // The newest sample always has weight 1, so this never divides by zero
+        let avg_distance = total_distance / total_weight;
+        let avg_temp = total_temp / total_weight;
+        let avg_energy = total_energy / total_weight;
+        let avg_jerk = total_jerk / total_weight;
 
         // Simple VTI calculation (MVP)
         // Higher risk factors increase VTI:
@@ -129,28 +227,33 @@ impl VtiCalculator {
         // Convert to basis points (0-10000)
         let vti_value = (clamped_risk * 100.0) as u64;
 
-        // Determine suggested state with hysteresis
-        let suggested_state = if vti_value >= (self.config.shutdown_threshold as u64) {
+        Some(VtiResult {
+            vti_value,
+            suggested_state: self.config.suggested_state(vti_value).to_string(),
+        })
+    }
+}
+
+impl VtiConfig {
+    /// Determine suggested state with hysteresis
+    pub fn suggested_state(&self, vti_value: u64) -> &'static str {
+        if vti_value >= (self.shutdown_threshold as u64) {
             "SHUTDOWN"
-        } else if vti_value >= (self.config.danger_threshold as u64) {
+        } else if vti_value >= (self.danger_threshold as u64) {
             "DANGER"
-        } else if vti_value <= ((self.config.safe_threshold - self.config.hysteresis_margin) as u64) {
+        } else if vti_value <= ((self.safe_threshold - self.hysteresis_margin) as u64) {
             "SAFE"
         } else {
             // Stay in current state for hysteresis
             "UNKNOWN" // Will be resolved by ANS contract
-        };
-
-        Some(VtiResult {
-            vti_value,
-            suggested_state: suggested_state.to_string(),
-        })
+        }
     }
 }
 
 /// Oracle service state
 pub struct ToneOracle {
     calculator: VtiCalculator,
+    slew_limiter: Option<VtiSlewLimiter>,
     config: VtiConfig,
     blockchain: Option<BlockchainOracle>,
 }
@@ -160,6 +263,7 @@ impl ToneOracle {
     pub fn new(config: VtiConfig) -> Self {
         Self {
             calculator: VtiCalculator::new(config.clone()),
+            slew_limiter: config.slew_limit.map(VtiSlewLimiter::new),
             config,
             blockchain: None,
         }
@@ -174,6 +278,7 @@ impl ToneOracle {
 
         Ok(Self {
             calculator: VtiCalculator::new(config.clone()),
+            slew_limiter: config.slew_limit.map(VtiSlewLimiter::new),
             config,
             blockchain,
         })
@@ -183,7 +288,12 @@ impl ToneOracle {
     pub async fn process_metrics(&mut self, metrics: SensorMetrics) -> Result<Option<VtiResult>, Box<dyn std::error::Error>> {
         self.calculator.add_metrics(metrics);
 
-        if let Some(result) = self.calculator.compute_vti() {
+        if let Some(mut result) = self.calculator.compute_vti() {
+            if let Some(limiter) = &mut self.slew_limiter {
+                result.vti_value = limiter.apply(result.vti_value);
+                result.suggested_state = self.config.suggested_state(result.vti_value).to_string();
+            }
+
             // If blockchain integration is enabled, update the contract
             if let Some(blockchain) = &self.blockchain {
                 if result.suggested_state != "UNKNOWN" {
@@ -208,13 +318,23 @@ impl ToneOracle {
 }
 
 /// Blockchain configuration for the oracle
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct BlockchainConfig {
     pub rpc_url: String,
     pub private_key: String,
     pub ans_state_manager_address: Address,
 }
 
+impl std::fmt::Debug for BlockchainConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockchainConfig")
+            .field("rpc_url", &self.rpc_url)
+            .field("private_key", &"<redacted>")
+            .field("ans_state_manager_address", &self.ans_state_manager_address)
+            .finish()
+    }
+}
+
 /// Blockchain integration for the oracle
 pub struct BlockchainOracle {
     provider: Provider<Http>,
@@ -3,22 +3,27 @@
 //! Provides HTTP interface for sensor metrics submission and VTI computation.
 
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use clap::{Parser, Subcommand};
-use ethers::types::Address;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::Mutex;
+use std::{collections::HashMap, future::Future, net::SocketAddr, sync::Arc};
+use tokio::{net::TcpListener, sync::Mutex};
+use tokio_util::task::TaskTracker;
 use tower_http::cors::CorsLayer;
-use tracing_subscriber;
 
 use tone_oracle::{BlockchainConfig, SensorMetrics, ToneOracle, VtiConfig, VtiResult};
 use vagus_chain::{ChainClient, ChainClientFactory, ChainConfig, ChainType};
+use vagus_health::{HealthAggregator, HealthReport, HealthStatus};
+
+mod rate_limit;
+
+use rate_limit::RateLimiter;
 
 /// HTTP request for submitting sensor metrics
 #[derive(Debug, Deserialize)]
@@ -31,19 +36,74 @@ struct SubmitMetricsRequest {
     timestamp_ms: Option<u64>,
 }
 
+impl SubmitMetricsRequest {
+    /// Reject non-finite or physically implausible readings before they reach
+    /// the VTI window
+    fn validate(&self, bounds: &MetricBounds) -> Result<(), String> {
+        check_range("human_distance_mm", self.human_distance_mm, 0.0, bounds.max_human_distance_mm)?;
+        check_range(
+            "temperature_celsius",
+            self.temperature_celsius,
+            bounds.min_temperature_celsius,
+            bounds.max_temperature_celsius,
+        )?;
+        check_range("energy_consumption_j", self.energy_consumption_j, 0.0, bounds.max_energy_consumption_j)?;
+        check_range("jerk_m_s3", self.jerk_m_s3, 0.0, bounds.max_jerk_m_s3)
+    }
+}
+
+fn check_range(field: &str, value: f64, min: f64, max: f64) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err(format!("{} must be a finite number, got {}", field, value));
+    }
+    if !(min..=max).contains(&value) {
+        return Err(format!("{} must be within [{}, {}], got {}", field, min, max, value));
+    }
+    Ok(())
+}
+
+/// Plausible physical ranges for submitted sensor metrics
+#[derive(Debug, Clone, Copy, clap::Args)]
+struct MetricBounds {
+    /// Largest accepted human distance in millimetres
+    #[arg(long, default_value_t = 100_000.0)]
+    max_human_distance_mm: f64,
+
+    /// Lowest accepted temperature in degrees Celsius
+    #[arg(long, default_value_t = -50.0, allow_negative_numbers = true)]
+    min_temperature_celsius: f64,
+
+    /// Highest accepted temperature in degrees Celsius
+    #[arg(long, default_value_t = 200.0)]
+    max_temperature_celsius: f64,
+
+    /// Largest accepted energy consumption in joules
+    #[arg(long, default_value_t = 1_000_000.0)]
+    max_energy_consumption_j: f64,
+
+    /// Largest accepted jerk in m/s^3
+    #[arg(long, default_value_t = 1_000.0)]
+    max_jerk_m_s3: f64,
+}
+
 /// HTTP response for VTI computation
 #[derive(Debug, Serialize)]
 struct VtiResponse {
     success: bool,
     vti_result: Option<VtiResult>,
+    /// Tone update pushed to the chains, or only planned in dry-run mode
+    chain_update: Option<ChainUpdate>,
     error: Option<String>,
 }
 
-/// HTTP response for health check
+/// ANS tone update decided for one VTI result
 #[derive(Debug, Serialize)]
-struct HealthResponse {
-    status: String,
-    version: String,
+struct ChainUpdate {
+    /// True when the update was computed but not sent
+    dry_run: bool,
+    vti_value: u64,
+    state: String,
+    chains: Vec<ChainType>,
 }
 
 /// Application state
@@ -51,6 +111,16 @@ struct HealthResponse {
 struct AppState {
     oracle: Arc<Mutex<ToneOracle>>,
     chain_clients: HashMap<ChainType, Arc<dyn ChainClient>>,
+    health: HealthAggregator,
+    /// Chain updates in flight; drained before the server exits
+    chain_updates: TaskTracker,
+    /// Key required on every route except `/health`; unset leaves them open
+    api_key: Option<Arc<str>>,
+    /// Per-source request budget for every route except `/health`
+    rate_limiter: Arc<RateLimiter>,
+    metric_bounds: MetricBounds,
+    /// Compute and report tone updates without sending them
+    dry_run: bool,
 }
 
 /// CLI arguments
@@ -65,31 +135,53 @@ struct Args {
 #[derive(Subcommand)]
 enum Commands {
     /// Start the oracle server
-    Serve {
-        /// Port to listen on
-        #[arg(long, default_value = "3000")]
-        port: u16,
-
-        /// Enable EVM chain integration
-        #[arg(long)]
-        evm_rpc: Option<String>,
-
-        /// Enable Cosmos chain integration
-        #[arg(long)]
-        cosmos_rpc: Option<String>,
-
-        /// Private key for blockchain transactions
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: Option<String>,
-
-        /// ANS State Manager contract addresses (chain_name=address)
-        #[arg(long, value_parser = parse_contract_addresses)]
-        ans_state_managers: Vec<(String, String)>,
-
-        /// Other contract addresses (chain_name=contract_name=address)
-        #[arg(long, value_parser = parse_contract_addresses)]
-        contracts: Vec<(String, String, String)>,
-    },
+    Serve(ServeArgs),
+}
+
+#[derive(clap::Args)]
+struct ServeArgs {
+    /// Port to listen on
+    #[arg(long, default_value = "3000")]
+    port: u16,
+
+    /// Enable EVM chain integration
+    #[arg(long)]
+    evm_rpc: Option<String>,
+
+    /// Enable Cosmos chain integration
+    #[arg(long)]
+    cosmos_rpc: Option<String>,
+
+    /// Private key for blockchain transactions
+    #[arg(long, env = "PRIVATE_KEY", hide_env_values = true)]
+    private_key: Option<String>,
+
+    /// API key clients must send as a bearer token or `x-api-key` header
+    #[arg(long, env = "TONE_ORACLE_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Requests per second each source address may sustain
+    #[arg(long, default_value = "10", value_parser = parse_positive_rate)]
+    rate_limit_per_second: f64,
+
+    /// Requests a source address may send in a burst
+    #[arg(long, default_value = "20")]
+    rate_limit_burst: u32,
+
+    #[command(flatten)]
+    metric_bounds: MetricBounds,
+
+    /// Compute VTI and report the intended ANS updates without sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// ANS State Manager contract addresses (chain_name=address)
+    #[arg(long, value_parser = parse_contract_addresses)]
+    ans_state_managers: Vec<(String, String)>,
+
+    /// Other contract addresses (chain_name=contract_name=address)
+    #[arg(long, value_parser = parse_contract_addresses)]
+    contracts: Vec<(String, String, String)>,
 }
 
 fn parse_contract_addresses(s: &str) -> Result<(String, String), String> {
@@ -101,6 +193,15 @@ fn parse_contract_addresses(s: &str) -> Result<(String, String), String> {
     }
 }
 
+fn parse_positive_rate(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if rate.is_finite() && rate > 0.0 {
+        Ok(rate)
+    } else {
+        Err(format!("rate must be a positive number, got {}", s))
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -109,32 +210,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
     match args.command {
-        Commands::Serve {
-            port,
-            evm_rpc,
-            cosmos_rpc,
-            private_key,
-            ans_state_managers,
-            contracts,
-        } => {
-            run_server(port, evm_rpc, cosmos_rpc, private_key, ans_state_managers, contracts).await
-        }
+        Commands::Serve(serve_args) => run_server(serve_args).await,
     }
 }
 
-async fn run_server(
-    port: u16,
-    evm_rpc: Option<String>,
-    cosmos_rpc: Option<String>,
-    private_key: Option<String>,
-    ans_state_managers: Vec<(String, String)>,
-    contracts: Vec<(String, String, String)>,
-) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_server(args: ServeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let ServeArgs {
+        port,
+        evm_rpc,
+        cosmos_rpc,
+        private_key,
+        api_key,
+        rate_limit_per_second,
+        rate_limit_burst,
+        metric_bounds,
+        dry_run,
+        ans_state_managers,
+        contracts,
+    } = args;
+
     // Create VTI configuration
     let config = VtiConfig::default();
 
-    // Create oracle - for now use legacy blockchain config if EVM is enabled
-    let oracle = if let (Some(rpc_url), Some(private_key), Some(ans_addr)) = (
+    // Create oracle - for now use legacy blockchain config if EVM is enabled.
+    // Its updates bypass the chain clients, so dry runs leave it off
+    let oracle = if let (false, Some(rpc_url), Some(private_key), Some(ans_addr)) = (
+        dry_run,
         evm_rpc.as_ref(),
         private_key.as_ref(),
         ans_state_managers.iter().find(|(chain, _)| chain == "evm").map(|(_, addr)| addr)
@@ -153,6 +254,10 @@ async fn run_server(
         ToneOracle::new(config)
     };
 
+    // The oracle serves VTI without chains, so an unreachable chain only degrades it
+    let health = HealthAggregator::new();
+    health.report("tone_oracle", HealthStatus::Healthy, None).await;
+
     // Create chain clients
     let mut chain_clients = HashMap::new();
 
@@ -176,16 +281,31 @@ async fn run_server(
             chain_type: ChainType::EVM,
             rpc_url,
             contract_addresses,
-            private_key,
+            private_key: Some(private_key),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
         };
 
         match ChainClientFactory::create_client(chain_config).await {
-            Ok(client) => {
-                chain_clients.insert(ChainType::EVM, Arc::from(client) as Arc<dyn ChainClient>);
-                tracing::info!("EVM chain client initialized");
-            }
+            Ok(client) => match client.health().await {
+                Ok(height) => {
+                    chain_clients.insert(ChainType::EVM, Arc::from(client) as Arc<dyn ChainClient>);
+                    tracing::info!("EVM chain client initialized at block {}", height);
+                    health.report("evm_chain", HealthStatus::Healthy, None).await;
+                }
+                Err(e) => {
+                    tracing::warn!("EVM chain client is not reachable: {}", e);
+                    health.report("evm_chain", HealthStatus::Degraded, Some(e.to_string())).await;
+                }
+            },
             Err(e) => {
                 tracing::warn!("Failed to create EVM chain client: {}", e);
+                health.report("evm_chain", HealthStatus::Degraded, Some(e.to_string())).await;
             }
         }
     }
@@ -210,16 +330,31 @@ async fn run_server(
             chain_type: ChainType::Cosmos,
             rpc_url,
             contract_addresses,
-            private_key,
+            private_key: Some(private_key),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_limit_multiplier: None,
+            ws_url: None,
+            fee_denom: None,
+            gas_price: None,
+            gas_limit: None,
         };
 
         match ChainClientFactory::create_client(chain_config).await {
-            Ok(client) => {
-                chain_clients.insert(ChainType::Cosmos, Arc::from(client) as Arc<dyn ChainClient>);
-                tracing::info!("Cosmos chain client initialized");
-            }
+            Ok(client) => match client.health().await {
+                Ok(height) => {
+                    chain_clients.insert(ChainType::Cosmos, Arc::from(client) as Arc<dyn ChainClient>);
+                    tracing::info!("Cosmos chain client initialized at block {}", height);
+                    health.report("cosmos_chain", HealthStatus::Healthy, None).await;
+                }
+                Err(e) => {
+                    tracing::warn!("Cosmos chain client is not reachable: {}", e);
+                    health.report("cosmos_chain", HealthStatus::Degraded, Some(e.to_string())).await;
+                }
+            },
             Err(e) => {
                 tracing::warn!("Failed to create Cosmos chain client: {}", e);
+                health.report("cosmos_chain", HealthStatus::Degraded, Some(e.to_string())).await;
             }
         }
     }
@@ -227,38 +362,172 @@ async fn run_server(
     let state = AppState {
         oracle: Arc::new(Mutex::new(oracle)),
         chain_clients,
+        health,
+        chain_updates: TaskTracker::new(),
+        api_key: api_key.map(Arc::from),
+        rate_limiter: Arc::new(RateLimiter::new(rate_limit_per_second, rate_limit_burst)),
+        metric_bounds,
+        dry_run,
     };
-
-    // Build router
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .route("/vti", post(submit_metrics))
-        .layer(CorsLayer::permissive())
-        .with_state(state);
+    if dry_run {
+        tracing::info!("Dry run: ANS tone updates are computed but never sent");
+    }
+    if state.api_key.is_none() {
+        tracing::warn!("No API key configured; anyone who can reach the port can submit metrics");
+    }
 
     // Start server
     let addr = format!("0.0.0.0:{}", port);
     tracing::info!("Tone Oracle listening on {}", addr);
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    let listener = TcpListener::bind(&addr).await?;
+    serve(listener, state, shutdown_signal()).await?;
 
+    tracing::info!("Tone Oracle stopped");
     Ok(())
 }
 
+fn router(state: AppState) -> Router {
+    let authenticated = Router::new()
+        .route("/vti", post(submit_metrics))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_api_key))
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit));
+
+    Router::new()
+        .route("/health", get(health_check))
+        .merge(authenticated)
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+/// Answer 429 once the calling address has spent its request budget. Runs
+/// before authentication so rejected keys count against the budget too
+async fn rate_limit(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match state.rate_limiter.acquire(peer.ip()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limit exceeded for {}", peer.ip());
+            let retry_after = HeaderValue::from(retry_after.as_secs_f64().ceil() as u64);
+            (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, retry_after)]).into_response()
+        }
+    }
+}
+
+/// Reject requests that do not carry the configured API key
+async fn require_api_key(State(state): State<AppState>, request: Request, next: Next) -> Result<Response, StatusCode> {
+    if let Some(expected) = &state.api_key {
+        match presented_api_key(request.headers()) {
+            Some(key) if keys_match(key.as_bytes(), expected.as_bytes()) => {}
+            _ => return Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// Key from `Authorization: Bearer <key>`, falling back to `x-api-key`
+fn presented_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|value| value.to_str().ok()))
+}
+
+/// Compare without short-circuiting so response timing does not leak the key
+fn keys_match(presented: &[u8], expected: &[u8]) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Serve until `shutdown` resolves, then stop accepting connections, let
+/// in-flight requests finish and drain the chain updates they started
+async fn serve(
+    listener: TcpListener,
+    state: AppState,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
+    let chain_updates = state.chain_updates.clone();
+    let app = router(state).into_make_service_with_connect_info::<SocketAddr>();
+    let result = axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await;
+
+    chain_updates.close();
+    if !chain_updates.is_empty() {
+        tracing::info!("Waiting for {} chain updates to finish", chain_updates.len());
+    }
+    chain_updates.wait().await;
+    result
+}
+
+/// Resolves on ctrl-c or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for ctrl-c: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown signal received, draining requests");
+}
+
 /// Health check endpoint
-async fn health_check() -> Json<HealthResponse> {
-    Json(HealthResponse {
-        status: "healthy".to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-    })
+async fn health_check(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let report = state.health.snapshot().await;
+    let status = match report.status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    };
+    (status, Json(report))
 }
 
 /// Submit sensor metrics and get VTI result
 async fn submit_metrics(
     State(state): State<AppState>,
     Json(request): Json<SubmitMetricsRequest>,
-) -> Result<Json<VtiResponse>, StatusCode> {
+) -> Result<Json<VtiResponse>, (StatusCode, Json<VtiResponse>)> {
+    if let Err(e) = request.validate(&state.metric_bounds) {
+        tracing::warn!("Rejected metrics from executor {}: {}", request.executor_id, e);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(VtiResponse {
+                success: false,
+                vti_result: None,
+                chain_update: None,
+                error: Some(e),
+            }),
+        ));
+    }
+
     // Convert request to SensorMetrics
     let metrics = SensorMetrics {
         executor_id: request.executor_id,
@@ -283,40 +552,467 @@ async fn submit_metrics(
             return Ok(Json(VtiResponse {
                 success: false,
                 vti_result: None,
+                chain_update: None,
                 error: Some(format!("Processing failed: {}", e)),
             }));
         }
     };
 
-    // Update ANS state on all configured chains
-    for (chain_type, client) in &state.chain_clients {
-        if let Some(vti_result) = &result {
-            // Convert string to ANSState enum
-            let suggested_state = match vti_result.suggested_state.as_str() {
-                "SAFE" => vagus_chain::ANSState::SAFE,
-                "DANGER" => vagus_chain::ANSState::DANGER,
-                "SHUTDOWN" => vagus_chain::ANSState::SHUTDOWN,
-                _ => {
-                    tracing::warn!("Unknown ANS state: {}", vti_result.suggested_state);
-                    continue;
-                }
-            };
-
-            match client.update_tone(vti_result.vti_value, suggested_state).await {
-                Ok(_) => {
-                    tracing::info!("Updated ANS state on {:?} chain", chain_type);
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to update ANS state on {:?} chain: {}", chain_type, e);
-                    // Don't fail the request if one chain update fails
-                }
-            }
+    let chain_update = result.as_ref().and_then(|vti_result| {
+        ans_state(&vti_result.suggested_state)?;
+        Some(ChainUpdate {
+            dry_run: state.dry_run,
+            vti_value: vti_result.vti_value,
+            state: vti_result.suggested_state.clone(),
+            chains: state.chain_clients.keys().copied().collect(),
+        })
+    });
+
+    if state.dry_run {
+        if let Some(update) = &chain_update {
+            tracing::info!(
+                "Dry run: would update {:?} to VTI {} ({})",
+                update.chains,
+                update.vti_value,
+                update.state
+            );
+        }
+    } else if let Some(vti_result) = &result {
+        // Update ANS state on all configured chains. The updates run as a tracked
+        // task, so neither a dropped request nor a shutdown abandons a pending tone
+        let update = state
+            .chain_updates
+            .spawn(update_chains(
+                state.chain_clients.clone(),
+                request.executor_id,
+                vti_result.clone(),
+            ));
+        if let Err(e) = update.await {
+            tracing::error!("Chain update task failed: {}", e);
         }
     }
 
     Ok(Json(VtiResponse {
         success: true,
         vti_result: result,
+        chain_update,
         error: None,
     }))
 }
+
+/// ANS state named by a suggested state, or None for "UNKNOWN"
+fn ans_state(suggested_state: &str) -> Option<vagus_chain::ANSState> {
+    match suggested_state {
+        "SAFE" => Some(vagus_chain::ANSState::SAFE),
+        "DANGER" => Some(vagus_chain::ANSState::DANGER),
+        "SHUTDOWN" => Some(vagus_chain::ANSState::SHUTDOWN),
+        _ => None,
+    }
+}
+
+/// Push an executor's VTI result to the ANS state manager of every configured chain
+async fn update_chains(
+    chain_clients: HashMap<ChainType, Arc<dyn ChainClient>>,
+    executor_id: u64,
+    vti_result: VtiResult,
+) {
+    for (chain_type, client) in &chain_clients {
+        let Some(suggested_state) = ans_state(&vti_result.suggested_state) else {
+            tracing::warn!("Unknown ANS state: {}", vti_result.suggested_state);
+            continue;
+        };
+        let vti = match vagus_chain::Vti::new(vti_result.vti_value) {
+            Ok(vti) => vti,
+            Err(e) => {
+                tracing::warn!("VTI {} out of range: {}", vti_result.vti_value, e);
+                continue;
+            }
+        };
+
+        match client.update_tone(executor_id, vti, suggested_state).await {
+            Ok(_) => {
+                tracing::info!("Updated ANS state on {:?} chain", chain_type);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to update ANS state on {:?} chain: {}", chain_type, e);
+                // Don't fail the request if one chain update fails
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::sync::{oneshot, Notify};
+    use vagus_chain::{AfferentEvidencePacket, ANSState, Event, Guard, Intent, Vti};
+
+    /// Chain whose `update_tone` blocks until the test releases it. Records
+    /// the executor of each tone update and counts every trait method called
+    #[derive(Clone, Default)]
+    struct GatedChain {
+        started: Arc<Notify>,
+        release: Arc<Notify>,
+        tone_updates: Arc<std::sync::Mutex<Vec<u64>>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl GatedChain {
+        fn called(&self) {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for GatedChain {
+        async fn submit_aep(&self, _aep: &AfferentEvidencePacket) -> Result<String> {
+            self.called();
+            Ok(String::new())
+        }
+
+        async fn issue_with_brake(
+            &self,
+            _intent: &Intent,
+            _scaled_limits_hash: &[u8; 32],
+            _expires_at: u64,
+        ) -> Result<String> {
+            self.called();
+            Ok(String::new())
+        }
+
+        async fn revoke_capability(&self, _token_id: &str, _reason: u8) -> Result<()> {
+            self.called();
+            Ok(())
+        }
+
+        async fn token_owner(&self, _token_id: &str) -> Result<Option<String>> {
+            self.called();
+            Ok(None)
+        }
+
+        async fn is_valid(&self, _token_id: &str) -> Result<bool> {
+            self.called();
+            Ok(false)
+        }
+
+        async fn active_tokens_of(&self, _executor_id: u64) -> Result<Vec<String>> {
+            self.called();
+            Ok(Vec::new())
+        }
+
+        async fn get_guard(&self, _executor_id: u64, _action_id: &[u8; 32]) -> Result<Guard> {
+            self.called();
+            Ok(Guard {
+                scalingFactor: 10000u128.into(),
+                allowed: true,
+            })
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            self.called();
+            Ok(ANSState::SAFE)
+        }
+
+        async fn update_tone(&self, executor_id: u64, _vti: Vti, _suggested_state: ANSState) -> Result<()> {
+            self.called();
+            self.started.notify_one();
+            self.release.notified().await;
+            self.tone_updates.lock().unwrap().push(executor_id);
+            Ok(())
+        }
+
+        async fn subscribe_events(&self, _callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            self.called();
+            Ok(())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            _from_block: u64,
+            _to_block: u64,
+            _event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            self.called();
+            Ok(Vec::new())
+        }
+
+        async fn health(&self) -> Result<u64> {
+            self.called();
+            Ok(0)
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn app_state(chain_clients: HashMap<ChainType, Arc<dyn ChainClient>>, api_key: Option<&str>) -> AppState {
+        app_state_with_rate_limit(chain_clients, api_key, RateLimiter::new(1000.0, 1000))
+    }
+
+    fn app_state_with_rate_limit(
+        chain_clients: HashMap<ChainType, Arc<dyn ChainClient>>,
+        api_key: Option<&str>,
+        rate_limiter: RateLimiter,
+    ) -> AppState {
+        AppState {
+            oracle: Arc::new(Mutex::new(ToneOracle::new(VtiConfig {
+                window_size: 1,
+                ..Default::default()
+            }))),
+            chain_clients,
+            health: HealthAggregator::new(),
+            chain_updates: TaskTracker::new(),
+            api_key: api_key.map(Arc::from),
+            rate_limiter: Arc::new(rate_limiter),
+            metric_bounds: test_bounds(),
+            dry_run: false,
+        }
+    }
+
+    fn test_bounds() -> MetricBounds {
+        MetricBounds {
+            max_human_distance_mm: 100_000.0,
+            min_temperature_celsius: -50.0,
+            max_temperature_celsius: 200.0,
+            max_energy_consumption_j: 1_000_000.0,
+            max_jerk_m_s3: 1_000.0,
+        }
+    }
+
+    /// Submit `metrics_body()` with `field` overridden; returns status and error
+    async fn submit_with(field: &str, value: serde_json::Value) -> (reqwest::StatusCode, Option<String>) {
+        let addr = spawn_server(app_state(HashMap::new(), None)).await;
+        let mut body = metrics_body();
+        body[field] = value;
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/vti", addr))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap();
+        (status, body["error"].as_str().map(str::to_string))
+    }
+
+    /// Serve `state` on an ephemeral port until the test ends
+    async fn spawn_server(state: AppState) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, state, std::future::pending()));
+        addr
+    }
+
+    fn metrics_body() -> serde_json::Value {
+        serde_json::json!({
+            "executor_id": 1,
+            "human_distance_mm": 1500.0,
+            "temperature_celsius": 40.0,
+            "energy_consumption_j": 10.0,
+            "jerk_m_s3": 1.0,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_accepting_and_drains_chain_updates() {
+        let chain = GatedChain::default();
+        let state = app_state(
+            HashMap::from([(ChainType::EVM, Arc::new(chain.clone()) as Arc<dyn ChainClient>)]),
+            None,
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, state, async {
+            let _ = shutdown_rx.await;
+        }));
+
+        let request = tokio::spawn(async move {
+            reqwest::Client::new()
+                .post(format!("http://{}/vti", addr))
+                .json(&metrics_body())
+                .send()
+                .await
+                .unwrap()
+        });
+        chain.started.notified().await;
+
+        shutdown_tx.send(()).unwrap();
+        // New connections are refused while the queued chain update is pending
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while tokio::net::TcpStream::connect(addr).await.is_ok() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("server kept accepting connections after shutdown");
+        assert!(!server.is_finished());
+
+        chain.release.notify_one();
+        assert_eq!(request.await.unwrap().status(), reqwest::StatusCode::OK);
+        server.await.unwrap().unwrap();
+        // The tone is set for the executor that reported the metrics
+        assert_eq!(*chain.tone_updates.lock().unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_vti_accepts_the_configured_api_key() {
+        let addr = spawn_server(app_state(HashMap::new(), Some("secret"))).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/vti", addr);
+
+        let bearer = client.post(&url).bearer_auth("secret").json(&metrics_body()).send().await.unwrap();
+        assert_eq!(bearer.status(), reqwest::StatusCode::OK);
+
+        let header = client.post(&url).header("x-api-key", "secret").json(&metrics_body()).send().await.unwrap();
+        assert_eq!(header.status(), reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_vti_rejects_a_missing_api_key() {
+        let addr = spawn_server(app_state(HashMap::new(), Some("secret"))).await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("http://{}/vti", addr))
+            .json(&metrics_body())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        // Health stays open for probes
+        let health = client.get(format!("http://{}/health", addr)).send().await.unwrap();
+        assert_eq!(health.status(), reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_vti_rejects_a_wrong_api_key() {
+        let addr = spawn_server(app_state(HashMap::new(), Some("secret"))).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/vti", addr);
+
+        for key in ["wrong", "secre", "secret2"] {
+            let response = client.post(&url).bearer_auth(key).json(&metrics_body()).send().await.unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED, "key {:?}", key);
+        }
+        let response = client.post(&url).header("x-api-key", "wrong").json(&metrics_body()).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_vti_rate_limit_answers_429_then_recovers() {
+        // Two requests at once, then one every 100ms
+        let state = app_state_with_rate_limit(HashMap::new(), None, RateLimiter::new(10.0, 2));
+        let addr = spawn_server(state).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/vti", addr);
+        let submit = || client.post(&url).json(&metrics_body()).send();
+
+        for _ in 0..2 {
+            assert_eq!(submit().await.unwrap().status(), reqwest::StatusCode::OK);
+        }
+        let limited = submit().await.unwrap();
+        assert_eq!(limited.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key(reqwest::header::RETRY_AFTER));
+
+        // Health is never limited
+        let health = client.get(format!("http://{}/health", addr)).send().await.unwrap();
+        assert_eq!(health.status(), reqwest::StatusCode::OK);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(submit().await.unwrap().status(), reqwest::StatusCode::OK);
+        assert_eq!(submit().await.unwrap().status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn test_rate_limit_must_be_positive() {
+        let parse = |rate: &str| {
+            let flag = format!("--rate-limit-per-second={}", rate);
+            Args::try_parse_from(["tone-oracle", "serve", flag.as_str()])
+        };
+        assert!(parse("2.5").is_ok());
+        for rate in ["0", "-1", "NaN", "inf", "fast"] {
+            assert!(parse(rate).is_err(), "accepted rate {}", rate);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vti_rejects_negative_distance() {
+        let (status, error) = submit_with("human_distance_mm", serde_json::json!(-1.0)).await;
+        assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+        assert!(error.unwrap().contains("human_distance_mm"));
+    }
+
+    #[tokio::test]
+    async fn test_vti_rejects_implausible_temperature() {
+        let (status, error) = submit_with("temperature_celsius", serde_json::json!(5000.0)).await;
+        assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+        assert!(error.unwrap().contains("temperature_celsius"));
+
+        let (status, _) = submit_with("temperature_celsius", serde_json::json!(-5000.0)).await;
+        assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_metrics_validation_rejects_nan() {
+        // JSON cannot carry NaN, so check the validator directly
+        let request = SubmitMetricsRequest {
+            executor_id: 1,
+            human_distance_mm: 1500.0,
+            temperature_celsius: 40.0,
+            energy_consumption_j: 10.0,
+            jerk_m_s3: f64::NAN,
+            timestamp_ms: None,
+        };
+        let error = request.validate(&test_bounds()).unwrap_err();
+        assert!(error.contains("jerk_m_s3") && error.contains("finite"), "{}", error);
+
+        let request = SubmitMetricsRequest {
+            jerk_m_s3: 1.0,
+            human_distance_mm: f64::INFINITY,
+            ..request
+        };
+        assert!(request.validate(&test_bounds()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_the_update_without_touching_chains() {
+        let chain = GatedChain::default();
+        let state = AppState {
+            dry_run: true,
+            ..app_state(
+                HashMap::from([(ChainType::EVM, Arc::new(chain.clone()) as Arc<dyn ChainClient>)]),
+                None,
+            )
+        };
+        let addr = spawn_server(state).await;
+
+        // A gated update_tone would never answer, so bound the request
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            reqwest::Client::new()
+                .post(format!("http://{}/vti", addr))
+                .json(&metrics_body())
+                .send(),
+        )
+        .await
+        .expect("dry run reached the chain")
+        .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        let vti_result = &body["vti_result"];
+        let chain_update = &body["chain_update"];
+        assert_eq!(chain_update["dry_run"], true);
+        assert_eq!(chain_update["vti_value"], vti_result["vti_value"]);
+        assert_eq!(chain_update["state"], vti_result["suggested_state"]);
+        assert_eq!(chain_update["chains"], serde_json::json!(["EVM"]));
+        assert_eq!(chain.calls.load(Ordering::SeqCst), 0);
+    }
+}
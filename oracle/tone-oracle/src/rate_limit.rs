@@ -0,0 +1,115 @@
+//! Per-source token-bucket rate limiting for the HTTP API
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Buckets kept before the least recently used one is evicted
+const MAX_TRACKED_SOURCES: usize = 1024;
+
+/// Token bucket per source address: `burst` requests at once, refilled at
+/// `per_second`
+pub struct RateLimiter {
+    burst: f64,
+    per_second: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    /// Last time the source was seen; every acquire refills the bucket
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// `per_second` must be positive; the CLI validates it
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self {
+            burst: f64::from(burst.max(1)),
+            per_second,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `source`, or return how long until one is available
+    pub fn acquire(&self, source: IpAddr) -> Result<(), Duration> {
+        self.acquire_at(source, Instant::now())
+    }
+
+    fn acquire_at(&self, source: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_SOURCES && !buckets.contains_key(&source) {
+            let least_recent = buckets
+                .iter()
+                .min_by_key(|(_, bucket)| bucket.refilled_at)
+                .map(|(source, _)| *source);
+            if let Some(least_recent) = least_recent {
+                buckets.remove(&least_recent);
+            }
+        }
+
+        let bucket = buckets.entry(source).or_insert(Bucket {
+            tokens: self.burst,
+            refilled_at: now,
+        });
+        bucket.tokens = self.refill(bucket, now);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            // A rate too small to refill within a Duration waits forever
+            Err(Duration::try_from_secs_f64((1.0 - bucket.tokens) / self.per_second)
+                .unwrap_or(Duration::MAX))
+        }
+    }
+
+    fn refill(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        (bucket.tokens + elapsed * self.per_second).min(self.burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn source(n: u32) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::from(n))
+    }
+
+    #[test]
+    fn test_full_table_evicts_the_least_recently_used_source() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let start = Instant::now();
+
+        // Source 0 is drained first, then every other slot fills up
+        assert!(limiter.acquire_at(source(0), start).is_ok());
+        for n in 1..MAX_TRACKED_SOURCES as u32 {
+            let _ = limiter.acquire_at(source(n), start + Duration::from_micros(n.into()));
+        }
+
+        // A drained source stays tracked while it is in use...
+        let later = start + Duration::from_millis(2);
+        assert!(limiter.acquire_at(source(0), later).is_err());
+
+        // ...so a newcomer evicts the least recent one instead
+        assert!(limiter.acquire_at(source(MAX_TRACKED_SOURCES as u32), later).is_ok());
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_TRACKED_SOURCES);
+        assert!(buckets.contains_key(&source(0)));
+        assert!(!buckets.contains_key(&source(1)));
+    }
+
+    #[test]
+    fn test_tiny_rate_waits_forever_instead_of_panicking() {
+        let limiter = RateLimiter::new(f64::MIN_POSITIVE, 1);
+        let now = Instant::now();
+        assert!(limiter.acquire_at(source(0), now).is_ok());
+        assert_eq!(limiter.acquire_at(source(0), now), Err(Duration::MAX));
+    }
+}
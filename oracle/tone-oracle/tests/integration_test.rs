@@ -6,7 +6,9 @@
 use ethers::prelude::*;
 use ethers::utils::Anvil;
 use std::sync::Arc;
-use tone_oracle::{BlockchainConfig, SensorMetrics, ToneOracle, VtiConfig};
+use tone_oracle::{
+    BlockchainConfig, SensorMetrics, SlewLimit, ToneOracle, VtiCalculator, VtiConfig, WindowDecay,
+};
 
 // Minimal ANS State Manager contract interface for testing
 abigen!(
@@ -182,3 +184,92 @@ async fn test_vti_sliding_window() {
         assert!(vti.vti_value >= 0);
     }
 }
+
+#[test]
+fn test_decay_weights_latest_dangerous_sample_higher() {
+    let vti_with = |decay| {
+        let mut calculator = VtiCalculator::new(VtiConfig {
+            window_size: 5,
+            decay,
+            ..Default::default()
+        });
+
+        // Four calm samples followed by a sudden jerk next to a human
+        for i in 0..5u64 {
+            let dangerous = i == 4;
+            calculator.add_metrics(SensorMetrics {
+                executor_id: 1,
+                human_distance_mm: if dangerous { 100.0 } else { 2000.0 },
+                temperature_celsius: 25.0,
+                energy_consumption_j: 100.0,
+                jerk_m_s3: if dangerous { 10.0 } else { 0.0 },
+                timestamp_ms: i * 1000,
+            });
+        }
+        calculator.compute_vti().unwrap().vti_value
+    };
+
+    let flat = vti_with(WindowDecay::Flat);
+    let linear = vti_with(WindowDecay::Linear { horizon_ms: 5000 });
+    let exponential = vti_with(WindowDecay::Exponential { half_life_ms: 1000 });
+
+    assert!(linear > flat);
+    assert!(exponential > flat);
+}
+
+#[tokio::test]
+async fn test_slew_limit_clamps_spike_but_passes_sustained_rise() {
+    let mut oracle = ToneOracle::new(VtiConfig {
+        window_size: 1,
+        slew_limit: Some(SlewLimit {
+            max_step: 1000,
+            max_clamped_windows: 1,
+        }),
+        ..Default::default()
+    });
+
+    let mut timestamp_ms = 0;
+    let mut sample = |dangerous: bool| {
+        timestamp_ms += 1000;
+        SensorMetrics {
+            executor_id: 1,
+            human_distance_mm: if dangerous { 0.0 } else { 2000.0 },
+            temperature_celsius: if dangerous { 100.0 } else { 25.0 },
+            energy_consumption_j: if dangerous { 1000.0 } else { 0.0 },
+            jerk_m_s3: if dangerous { 10.0 } else { 0.0 },
+            timestamp_ms,
+        }
+    };
+
+    let calm = oracle.process_metrics(sample(false)).await.unwrap().unwrap();
+    assert_eq!(calm.vti_value, 0);
+
+    // A one-window spike moves the VTI by at most one step
+    let spike = oracle.process_metrics(sample(true)).await.unwrap().unwrap();
+    assert_eq!(spike.vti_value, 1000);
+    assert_eq!(spike.suggested_state, "SAFE");
+    let after = oracle.process_metrics(sample(false)).await.unwrap().unwrap();
+    assert_eq!(after.vti_value, 0);
+
+    // A sustained rise is only held back for the configured windows
+    let first = oracle.process_metrics(sample(true)).await.unwrap().unwrap();
+    assert_eq!(first.vti_value, 1000);
+    let second = oracle.process_metrics(sample(true)).await.unwrap().unwrap();
+    assert_eq!(second.vti_value, 10000);
+    assert_eq!(second.suggested_state, "SHUTDOWN");
+}
+
+#[test]
+fn test_blockchain_config_debug_redacts_private_key() {
+    let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let config = BlockchainConfig {
+        rpc_url: "http://localhost:8545".to_string(),
+        private_key: private_key.to_string(),
+        ans_state_manager_address: Address::zero(),
+    };
+
+    let debug = format!("{:?}", config);
+    assert!(!debug.contains(&private_key[2..]));
+    assert!(debug.contains(r#"private_key: "<redacted>""#));
+    assert!(debug.contains("http://localhost:8545"));
+}
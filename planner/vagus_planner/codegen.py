@@ -197,6 +197,9 @@ class VagusCodeGenerator:
         lines.append("use cosmwasm_std::{Addr, Binary, Uint256};")
         lines.append("use thiserror::Error;")
         lines.append("")
+        lines.append("mod basis_points;")
+        lines.append("pub use basis_points::{BasisPoints, ScalingFactor, Vti, MAX_BASIS_POINTS};")
+        lines.append("")
 
         # Enums
         enums = self.spec_data.get('types', {}).get('enums', {})
@@ -217,6 +220,9 @@ class VagusCodeGenerator:
             fields = struct_data.get('fields', {})
             for field_name, field_info in fields.items():
                 rust_type = self._map_rust_type(field_info.get('type', 'Uint256'))
+                # Fields added after the first release stay optional on the wire
+                if 'default' in field_info:
+                    lines.append("    #[serde(default)]")
                 lines.append(f"    pub {field_name}: {rust_type},")
             lines.append("}")
             lines.append("")
@@ -0,0 +1,60 @@
+//! Block checkpoint
+//!
+//! Records the highest block processed on each source chain so a restarted
+//! relayer can backfill what it missed instead of starting from the head.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use vagus_chain::ChainType;
+
+/// Highest processed block per source chain, optionally persisted as JSON
+pub struct BlockCheckpoint {
+    path: Option<PathBuf>,
+    blocks: HashMap<ChainType, u64>,
+}
+
+impl BlockCheckpoint {
+    /// Checkpoint kept only for the lifetime of the process
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Checkpoint persisted at `path`, loading it if the file exists
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let blocks = if path.exists() {
+            serde_json::from_slice(&fs::read(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path: Some(path),
+            blocks,
+        })
+    }
+
+    /// Highest block processed on `chain_type`, if any
+    pub fn last_block(&self, chain_type: ChainType) -> Option<u64> {
+        self.blocks.get(&chain_type).copied()
+    }
+
+    /// Record a processed block; lower blocks than the checkpoint are ignored
+    pub fn record(&mut self, chain_type: ChainType, block_number: u64) -> Result<()> {
+        if self.last_block(chain_type).is_some_and(|last| last >= block_number) {
+            return Ok(());
+        }
+        self.blocks.insert(chain_type, block_number);
+
+        if let Some(path) = &self.path {
+            // Write-then-rename so a crash never leaves a truncated checkpoint
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, serde_json::to_vec(&self.blocks)?)?;
+            fs::rename(&tmp, path)?;
+        }
+        Ok(())
+    }
+}
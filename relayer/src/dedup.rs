@@ -0,0 +1,201 @@
+//! Event deduplication
+//!
+//! Subscriptions replay logs after a reconnect, so the relayer remembers the
+//! most recently processed events and skips any it has already seen. The
+//! cache can optionally be checkpointed to a file so restarts do not re-apply
+//! recent events either.
+
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use vagus_chain::{ChainType, Event};
+
+/// Identity of an on-chain event
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventKey {
+    pub chain_type: ChainType,
+    pub transaction_hash: String,
+    pub log_index: u64,
+}
+
+impl EventKey {
+    pub fn of(event: &Event) -> Self {
+        Self {
+            chain_type: event.chain_type,
+            transaction_hash: event.transaction_hash.clone(),
+            log_index: event.log_index,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let chain = match self.chain_type {
+            ChainType::EVM => "evm",
+            ChainType::Cosmos => "cosmos",
+        };
+        format!("{} {} {}", chain, self.transaction_hash, self.log_index)
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let chain_type = match parts.next()? {
+            "evm" => ChainType::EVM,
+            "cosmos" => ChainType::Cosmos,
+            _ => return None,
+        };
+        let transaction_hash = parts.next()?.to_string();
+        let log_index = parts.next()?.parse().ok()?;
+        Some(Self { chain_type, transaction_hash, log_index })
+    }
+}
+
+/// Bounded cache of processed events, evicting the least recently seen
+pub struct EventDedup {
+    capacity: usize,
+    seen: HashSet<EventKey>,
+    order: VecDeque<EventKey>,
+    checkpoint: Option<PathBuf>,
+    /// Lines appended to the checkpoint since it was last compacted
+    checkpoint_lines: usize,
+}
+
+impl EventDedup {
+    /// In-memory cache remembering up to `capacity` events
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+            checkpoint: None,
+            checkpoint_lines: 0,
+        }
+    }
+
+    /// Cache backed by a checkpoint file, loading any events it already lists
+    pub fn with_checkpoint(capacity: usize, path: PathBuf) -> Result<Self> {
+        let mut dedup = Self::new(capacity);
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                if let Some(key) = EventKey::from_line(&line?) {
+                    dedup.remember(key);
+                }
+            }
+        }
+        dedup.checkpoint = Some(path);
+        dedup.compact_checkpoint()?;
+        Ok(dedup)
+    }
+
+    pub fn contains(&self, event: &Event) -> bool {
+        self.seen.contains(&EventKey::of(event))
+    }
+
+    /// Record the event, returning false if it was already seen
+    pub fn insert(&mut self, event: &Event) -> Result<bool> {
+        let key = EventKey::of(event);
+        if self.seen.contains(&key) {
+            // Refresh recency so replayed events stay cached
+            self.order.retain(|k| k != &key);
+            self.order.push_back(key);
+            return Ok(false);
+        }
+
+        if let Some(path) = &self.checkpoint {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", key.to_line())?;
+            self.checkpoint_lines += 1;
+        }
+        self.remember(key);
+
+        // Rewrite the checkpoint once evicted entries dominate it
+        if self.checkpoint_lines > self.capacity * 2 {
+            self.compact_checkpoint()?;
+        }
+        Ok(true)
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    fn remember(&mut self, key: EventKey) {
+        if self.seen.insert(key.clone()) {
+            self.order.push_back(key);
+        }
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+    }
+
+    fn compact_checkpoint(&mut self) -> Result<()> {
+        let Some(path) = &self.checkpoint else {
+            return Ok(());
+        };
+
+        let tmp = path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        for key in &self.order {
+            writeln!(file, "{}", key.to_line())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+
+        self.checkpoint_lines = self.order.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn event(tx: &str, log_index: u64) -> Event {
+        Event {
+            chain_type: ChainType::EVM,
+            contract_address: "0x0".to_string(),
+            event_name: "VagalToneUpdated".to_string(),
+            topics: vec![],
+            data: HashMap::new(),
+            block_number: 1,
+            transaction_hash: tx.to_string(),
+            log_index,
+        }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_seen() {
+        let mut dedup = EventDedup::new(2);
+        assert!(dedup.insert(&event("0x1", 0)).unwrap());
+        assert!(dedup.insert(&event("0x1", 1)).unwrap());
+        // Seeing 0x1/0 again makes 0x1/1 the eviction candidate
+        assert!(!dedup.insert(&event("0x1", 0)).unwrap());
+        assert!(dedup.insert(&event("0x2", 0)).unwrap());
+
+        assert_eq!(dedup.len(), 2);
+        assert!(!dedup.insert(&event("0x1", 0)).unwrap());
+        assert!(dedup.insert(&event("0x1", 1)).unwrap());
+    }
+
+    #[test]
+    fn test_checkpoint_survives_restart() {
+        let path = std::env::temp_dir().join(format!("vagus-relayer-dedup-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut dedup = EventDedup::with_checkpoint(2, path.clone()).unwrap();
+        for i in 0..6 {
+            assert!(dedup.insert(&event("0x1", i)).unwrap());
+        }
+
+        // Only the last `capacity` events are restored
+        let mut restarted = EventDedup::with_checkpoint(2, path.clone()).unwrap();
+        assert_eq!(restarted.len(), 2);
+        assert!(!restarted.insert(&event("0x1", 5)).unwrap());
+        assert!(restarted.insert(&event("0x1", 0)).unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
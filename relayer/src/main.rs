@@ -2,12 +2,25 @@
 //!
 //! Monitors events on one chain and relays them to another chain with deduplication.
 
+mod checkpoint;
+mod dedup;
+mod metrics;
+
 use anyhow::Result;
-use clap::Parser;
+use checkpoint::BlockCheckpoint;
+use clap::{Parser, ValueEnum};
+use dedup::EventDedup;
+use metrics::RelayMetrics;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{info, warn, error};
-use vagus_chain::{ChainClient, ChainClientFactory, ChainConfig, ChainType, Event};
+use vagus_chain::{
+    vti_from_evm_tone, ANSState, ChainClient, ChainClientFactory, ChainConfig, ChainError,
+    ChainType, Event, Vti,
+};
+use vagus_health::{HealthAggregator, HealthStatus};
 
 /// CLI arguments
 #[derive(Parser)]
@@ -31,7 +44,7 @@ struct Args {
     target_rpc: String,
 
     /// Private key for target chain transactions
-    #[arg(long, env = "PRIVATE_KEY")]
+    #[arg(long, env = "PRIVATE_KEY", hide_env_values = true)]
     private_key: String,
 
     /// Source contract addresses (contract_name=address)
@@ -41,8 +54,59 @@ struct Args {
     /// Target contract addresses (contract_name=address)
     #[arg(long, value_parser = parse_contract_address)]
     target_contracts: Vec<(String, String)>,
+
+    /// Only relay tone updates that move the target chain to a more conservative state
+    #[arg(long)]
+    escalations_only: bool,
+
+    /// Number of recently processed events remembered for deduplication
+    #[arg(long, default_value_t = 10_000)]
+    dedup_cache_size: usize,
+
+    /// File checkpointing the dedup cache across restarts
+    #[arg(long)]
+    dedup_checkpoint: Option<PathBuf>,
+
+    /// File recording the last processed source block, used to resume after restarts
+    #[arg(long)]
+    block_checkpoint: Option<PathBuf>,
+
+    /// What to do with events the relayer does not know how to relay
+    #[arg(long, value_enum, default_value_t = UnknownEventPolicy::Ignore)]
+    unknown_events: UnknownEventPolicy,
+
+    /// Port serving the relayer's health as JSON
+    #[arg(long)]
+    health_port: Option<u16>,
+}
+
+/// Handling of source events with unrecognized names. All policies count
+/// them in `RelayMetrics`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum UnknownEventPolicy {
+    /// Skip them silently
+    #[default]
+    Ignore,
+    /// Skip them with a warning
+    Warn,
+    /// Stop relaying, so a new contract event cannot go unnoticed
+    Fail,
 }
 
+/// Options controlling which events get relayed
+#[derive(Debug, Clone, Copy, Default)]
+struct RelayOptions {
+    /// Skip tone updates that are not more conservative than the target's current state
+    escalations_only: bool,
+    /// Handling of events with unrecognized names
+    unknown_events: UnknownEventPolicy,
+}
+
+/// Error for an event name `process_event` has no handler for
+#[derive(Debug, thiserror::Error)]
+#[error("Unknown event {0}")]
+struct UnknownEventError(String);
+
 fn parse_contract_address(s: &str) -> Result<(String, String)> {
     let parts: Vec<&str> = s.split('=').collect();
     if parts.len() == 2 {
@@ -71,7 +135,7 @@ async fn main() -> Result<()> {
     let source_config = create_chain_config(
         source_chain_type,
         args.source_rpc,
-        HashMap::new(), // Source doesn't need private key
+        None, // Source doesn't need private key
         args.source_contracts,
     );
 
@@ -82,25 +146,80 @@ async fn main() -> Result<()> {
         args.target_contracts,
     );
 
+    let health = HealthAggregator::new();
+    if let Some(port) = args.health_port {
+        let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+        info!("Serving health on port {}", port);
+        tokio::spawn(health.clone().serve(listener));
+    }
+
     // Create chain clients
     let source_client = ChainClientFactory::create_client(source_config).await?;
     let target_client = ChainClientFactory::create_client(target_config).await?;
 
-    info!("Chain clients initialized successfully");
+    // Readiness probe: fail fast if either chain is unreachable
+    let source_height = source_client.health().await?;
+    let target_height = target_client.health().await?;
+    info!(
+        "Chain clients initialized successfully (source at block {}, target at block {})",
+        source_height, target_height
+    );
+    health.report("source_chain", HealthStatus::Healthy, None).await;
+    health.report("target_chain", HealthStatus::Healthy, None).await;
+
+    let metrics = Arc::new(RelayMetrics::default());
+    let health_metrics = metrics.clone();
+    health
+        .register_metric("unknown_events", move || health_metrics.unknown_events())
+        .await;
+
+    let options = RelayOptions {
+        escalations_only: args.escalations_only,
+        unknown_events: args.unknown_events,
+    };
+    if options.escalations_only {
+        info!("Relaying state escalations only");
+    }
+
+    let dedup = match args.dedup_checkpoint {
+        Some(path) => {
+            let dedup = EventDedup::with_checkpoint(args.dedup_cache_size, path.clone())?;
+            info!("Loaded {} processed events from {}", dedup.len(), path.display());
+            dedup
+        }
+        None => EventDedup::new(args.dedup_cache_size),
+    };
+
+    let checkpoint = match args.block_checkpoint {
+        Some(path) => BlockCheckpoint::load(path)?,
+        None => BlockCheckpoint::in_memory(),
+    };
 
     // Create event processing channel
     let (event_tx, event_rx) = mpsc::unbounded_channel::<Event>();
 
+    // Queue events emitted while the relayer was down ahead of live ones
+    backfill_events(&*source_client, source_chain_type, &checkpoint, &event_tx).await?;
+
     // Start event subscription on source chain
     let source_client_clone = source_client.clone();
+    let subscription_health = health.clone();
     tokio::spawn(async move {
+        subscription_health
+            .report("event_subscription", HealthStatus::Healthy, None)
+            .await;
         if let Err(e) = subscribe_to_events(source_client_clone, event_tx).await {
             error!("Event subscription failed: {}", e);
+            subscription_health
+                .report("event_subscription", HealthStatus::Unhealthy, Some(e.to_string()))
+                .await;
         }
     });
 
     // Start event processing
-    process_events(target_client, event_rx).await?;
+    let result = process_events(target_client, event_rx, options, dedup, checkpoint, metrics.clone()).await;
+    info!("Event processing stopped ({} unknown events seen)", metrics.unknown_events());
+    result?;
 
     Ok(())
 }
@@ -129,6 +248,13 @@ fn create_chain_config(
         rpc_url,
         contract_addresses,
         private_key,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        gas_limit_multiplier: None,
+        ws_url: None,
+        fee_denom: None,
+        gas_price: None,
+        gas_limit: None,
     }
 }
 
@@ -138,32 +264,128 @@ async fn subscribe_to_events(
 ) -> Result<()> {
     info!("Starting event subscription");
 
-    client.subscribe_events(move |event: Event| {
+    client.subscribe_events(Box::new(move |event: Event| {
         if let Err(e) = event_tx.send(event) {
             warn!("Failed to send event to processing queue: {}", e);
         }
-    }).await?;
+    })).await?;
 
     Ok(())
 }
 
+/// Replay source events from the checkpointed block up to the current head.
+/// The checkpointed block itself is replayed in case the relayer stopped
+/// midway through it; dedup skips the events already relayed.
+async fn backfill_events(
+    source_client: &dyn ChainClient,
+    source_chain_type: ChainType,
+    checkpoint: &BlockCheckpoint,
+    event_tx: &mpsc::UnboundedSender<Event>,
+) -> Result<()> {
+    let Some(from_block) = checkpoint.last_block(source_chain_type) else {
+        return Ok(());
+    };
+
+    let head = source_client.health().await?;
+    if head < from_block {
+        warn!("Checkpoint block {} is ahead of source head {}", from_block, head);
+        return Ok(());
+    }
+
+    let events = source_client.get_events_in_range(from_block, head, &[]).await?;
+    info!("Backfilling {} events from blocks {}..={}", events.len(), from_block, head);
+    for event in events {
+        event_tx.send(event)?;
+    }
+    Ok(())
+}
+
 async fn process_events(
     target_client: Box<dyn ChainClient>,
     mut event_rx: mpsc::UnboundedReceiver<Event>,
+    options: RelayOptions,
+    mut dedup: EventDedup,
+    mut checkpoint: BlockCheckpoint,
+    metrics: Arc<RelayMetrics>,
 ) -> Result<()> {
     info!("Starting event processing");
 
+    // Lowest block per chain with an event that failed to relay. The
+    // checkpoint stays below it so a restart backfills and retries it.
+    let mut undelivered: HashMap<ChainType, u64> = HashMap::new();
+
     while let Some(event) = event_rx.recv().await {
-        if let Err(e) = process_event(&*target_client, &event).await {
-            error!("Failed to process event {:?}: {}", event, e);
-            // Continue processing other events
+        // Take everything already queued so reflex triggers can jump ahead of it
+        let mut batch = vec![event];
+        while let Ok(event) = event_rx.try_recv() {
+            batch.push(event);
+        }
+        batch.sort_by_key(relay_priority);
+
+        let mut processed_blocks = Vec::new();
+        for event in batch {
+            // Reconnects replay logs; skip anything already relayed
+            if dedup.contains(&event) {
+                info!(
+                    "Skipping duplicate event {} ({}#{})",
+                    event.event_name, event.transaction_hash, event.log_index
+                );
+                continue;
+            }
+
+            let result = match process_event(&*target_client, &event, options).await {
+                Err(e) if e.is::<UnknownEventError>() => {
+                    let total = metrics.record_unknown_event();
+                    match options.unknown_events {
+                        UnknownEventPolicy::Ignore => Ok(()),
+                        UnknownEventPolicy::Warn => {
+                            warn!("{} ({} unknown events so far): {:?}", e, total, event);
+                            Ok(())
+                        }
+                        UnknownEventPolicy::Fail => return Err(e),
+                    }
+                }
+                result => result,
+            };
+
+            match result {
+                // Failed events stay unrecorded so a replay can retry them
+                Ok(()) => {
+                    dedup.insert(&event)?;
+                    processed_blocks.push((event.chain_type, event.block_number));
+                }
+                Err(e) => {
+                    error!("Failed to process event {:?}: {}", event, e);
+                    let lowest = undelivered.entry(event.chain_type).or_insert(event.block_number);
+                    *lowest = (*lowest).min(event.block_number);
+                    // Continue processing other events
+                }
+            }
+        }
+
+        // Checkpoint once the whole batch is done, since reordering may have
+        // relayed a later block before an earlier one
+        for (chain_type, block_number) in processed_blocks {
+            if undelivered.get(&chain_type).is_some_and(|&failed| block_number >= failed) {
+                continue;
+            }
+            checkpoint.record(chain_type, block_number)?;
         }
     }
 
     Ok(())
 }
 
-async fn process_event(target_client: &dyn ChainClient, event: &Event) -> Result<()> {
+/// Relay order within a batch of queued events; lower goes first. Reflex
+/// triggers revoke capabilities, so they must not wait behind other events.
+fn relay_priority(event: &Event) -> u8 {
+    match event.event_name.as_str() {
+        "ReflexTriggered" => 0,
+        _ => 1,
+    }
+}
+
+async fn process_event(target_client: &dyn ChainClient, event: &Event, options: RelayOptions) -> Result<()> {
     match event.event_name.as_str() {
         "CapabilityIssued" => {
             handle_capability_issued(target_client, event).await
@@ -172,7 +394,7 @@ async fn process_event(target_client: &dyn ChainClient, event: &Event) -> Result
             handle_capability_revoked(target_client, event).await
         }
         "VagalToneUpdated" => {
-            handle_tone_updated(target_client, event).await
+            handle_tone_updated(target_client, event, options).await
         }
         "AEPPosted" => {
             handle_aep_posted(target_client, event).await
@@ -180,10 +402,7 @@ async fn process_event(target_client: &dyn ChainClient, event: &Event) -> Result
         "ReflexTriggered" => {
             handle_reflex_triggered(target_client, event).await
         }
-        _ => {
-            // Ignore unknown events
-            Ok(())
-        }
+        name => Err(UnknownEventError(name.to_string()).into()),
     }
 }
 
@@ -195,41 +414,126 @@ async fn handle_capability_issued(_target_client: &dyn ChainClient, event: &Even
     Ok(())
 }
 
-async fn handle_capability_revoked(_target_client: &dyn ChainClient, event: &Event) -> Result<()> {
+/// Parse a revocation reason given either as its code or its spec name
+fn parse_revocation_reason(reason: &str) -> Option<u8> {
+    match reason {
+        "OWNER_REVOCATION" => Some(0),
+        "REFLEX_TRIGGER" => Some(1),
+        "EXPIRATION" => Some(2),
+        code => code.parse().ok().filter(|code| *code <= 2),
+    }
+}
+
+async fn handle_capability_revoked(target_client: &dyn ChainClient, event: &Event) -> Result<()> {
     info!("Processing CapabilityRevoked event: {:?}", event);
-    // TODO: Implement cross-chain capability revocation
-    // This would involve revoking the corresponding capability on target chain
-    Ok(())
+
+    // Extract token id and revocation reason from event
+    let token_id = event.data.get("tokenId").and_then(|v| v.as_str());
+    let reason = event
+        .data
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .and_then(parse_revocation_reason);
+    let (Some(token_id), Some(reason)) = (token_id, reason) else {
+        warn!("CapabilityRevoked event without a valid tokenId and reason: {:?}", event);
+        return Ok(());
+    };
+
+    revoke_on_target(target_client, token_id, reason).await
 }
 
-async fn handle_tone_updated(target_client: &dyn ChainClient, event: &Event) -> Result<()> {
+/// Revoke a capability on the target chain
+async fn revoke_on_target(target_client: &dyn ChainClient, token_id: &str, reason: u8) -> Result<()> {
+    match target_client.revoke_capability(token_id, reason).await {
+        Ok(()) => {
+            info!("Synchronized capability revocation: {} (reason {})", token_id, reason);
+            Ok(())
+        }
+        // Revocation is idempotent: a token the target never saw or already
+        // revoked needs no further action
+        Err(e)
+            if e.downcast_ref::<ChainError>().is_some_and(|e| {
+                e.is_spec_error("TokenNotFound") || e.is_spec_error("TokenAlreadyRevoked")
+            }) =>
+        {
+            warn!("Capability {} not revocable on target chain: {}", token_id, e);
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Rank ANS states from least to most conservative
+fn state_severity(state: &ANSState) -> u8 {
+    match state {
+        ANSState::SAFE => 0,
+        ANSState::DANGER => 1,
+        ANSState::SHUTDOWN => 2,
+    }
+}
+
+/// Executor whose tone relayed updates set. VagalToneUpdated names no
+/// executor on either chain, and the Cosmos tone is shared by all of them.
+const RELAYED_TONE_EXECUTOR_ID: u64 = 0;
+
+/// VTI and suggested state of a VagalToneUpdated event. EVM reports the
+/// tone as danger in ppm and the state by its uint8 code; CosmWasm reports
+/// the VTI in basis points and the state by name
+fn parse_tone_update(event: &Event) -> Result<(Vti, ANSState)> {
+    let field = |key: &str| {
+        event
+            .data
+            .get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("VagalToneUpdated event without {}", key))
+    };
+
+    let tone_str = field("tone")?;
+    let tone: u64 = tone_str
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid tone in VagalToneUpdated event: {}", tone_str))?;
+    let vti = match event.chain_type {
+        ChainType::EVM => vti_from_evm_tone(tone)?,
+        ChainType::Cosmos => Vti::new(tone)?,
+    };
+
+    let ans_state = match field("state")? {
+        "SAFE" | "0" => ANSState::SAFE,
+        "DANGER" | "1" => ANSState::DANGER,
+        "SHUTDOWN" | "2" => ANSState::SHUTDOWN,
+        state => return Err(anyhow::anyhow!("Unknown ANS state: {}", state)),
+    };
+    Ok((vti, ans_state))
+}
+
+async fn handle_tone_updated(
+    target_client: &dyn ChainClient,
+    event: &Event,
+    options: RelayOptions,
+) -> Result<()> {
     info!("Processing VagalToneUpdated event: {:?}", event);
 
-    // Extract VTI value and suggested state from event
-    if let (Some(tone_str), Some(state_str)) = (
-        event.data.get("tone"),
-        event.data.get("state"),
-    ) {
-        if let (Some(tone), Some(state)) = (
-            tone_str.as_str().and_then(|s| s.parse::<u64>().ok()),
-            state_str.as_str(),
-        ) {
-            let ans_state = match state {
-                "SAFE" => vagus_chain::ANSState::SAFE,
-                "DANGER" => vagus_chain::ANSState::DANGER,
-                "SHUTDOWN" => vagus_chain::ANSState::SHUTDOWN,
-                _ => {
-                    warn!("Unknown ANS state: {}", state);
-                    return Ok(());
-                }
-            };
+    // A malformed update fails, so it is never recorded as relayed
+    let (tone, ans_state) = parse_tone_update(event)?;
 
-            // Update tone on target chain
-            target_client.update_tone(tone, ans_state).await?;
-            info!("Synchronized tone update: {} -> {:?}", tone, ans_state);
+    // De-escalations are left to each chain in escalations-only mode
+    if options.escalations_only {
+        let target_state = target_client.get_ans_state().await?;
+        if state_severity(&ans_state) <= state_severity(&target_state) {
+            info!(
+                "Skipping non-escalating tone update: {:?} (target is {:?})",
+                ans_state, target_state
+            );
+            return Ok(());
         }
     }
 
+    // Update tone on target chain
+    target_client
+        .update_tone(RELAYED_TONE_EXECUTOR_ID, tone, ans_state.clone())
+        .await?;
+    info!("Synchronized tone update: {} -> {:?}", tone, ans_state);
+
     Ok(())
 }
 
@@ -240,9 +544,610 @@ async fn handle_aep_posted(_target_client: &dyn ChainClient, event: &Event) -> R
     Ok(())
 }
 
-async fn handle_reflex_triggered(_target_client: &dyn ChainClient, event: &Event) -> Result<()> {
+/// Revocation reason code of `CapabilityRevocationReason::REFLEX_TRIGGER`
+const REFLEX_TRIGGER_REASON: u8 = 1;
+
+/// Parse token ids encoded as spec/events.yml does for array keys on both
+/// chains: comma-separated decimal ids
+fn parse_token_ids(value: &serde_json::Value) -> Option<Vec<String>> {
+    value.as_str()?
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.bytes().all(|b| b.is_ascii_digit()).then(|| id.to_string()))
+        .collect()
+}
+
+async fn handle_reflex_triggered(target_client: &dyn ChainClient, event: &Event) -> Result<()> {
     info!("Processing ReflexTriggered event: {:?}", event);
-    // TODO: Implement cross-chain reflex synchronization
-    // This would involve triggering reflex actions on target chain
+
+    // Extract executor and the tokens the reflex revoked on the source chain
+    let executor_id = event.data.get("executorId").and_then(|v| v.as_str());
+    let token_ids = event.data.get("revokedTokens").and_then(parse_token_ids);
+    let (Some(executor_id), Some(token_ids)) = (executor_id, token_ids) else {
+        warn!("ReflexTriggered event without executorId and revokedTokens: {:?}", event);
+        return Ok(());
+    };
+
+    // Revoke every token before failing, so one error doesn't leave the rest live
+    let mut first_error = None;
+    for token_id in &token_ids {
+        if let Err(e) = revoke_on_target(target_client, token_id, REFLEX_TRIGGER_REASON).await {
+            error!("Failed to revoke capability {} for executor {}: {}", token_id, executor_id, e);
+            first_error.get_or_insert(e);
+        }
+    }
+    if let Some(e) = first_error {
+        return Err(e);
+    }
+
+    info!(
+        "Synchronized reflex for executor {}: revoked {} capabilities",
+        executor_id,
+        token_ids.len()
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use vagus_chain::{AfferentEvidencePacket, Guard, Intent, VagusError};
+
+    /// Target chain pinned to one ANS state that records relayed tone updates
+    /// and revocations. As a source it serves `history` up to block `head`.
+    #[derive(Clone)]
+    struct RecordingTarget {
+        state: ANSState,
+        tone_updates: Arc<Mutex<Vec<(u64, ANSState)>>>,
+        revocations: Arc<Mutex<Vec<(String, u8)>>>,
+        /// Tokens the target rejects with `TokenNotFound`
+        unknown_tokens: Vec<String>,
+        /// Tokens whose revocation fails with an RPC error
+        failing_tokens: Vec<String>,
+        head: u64,
+        history: Vec<Event>,
+        requested_ranges: Arc<Mutex<Vec<(u64, u64)>>>,
+    }
+
+    impl RecordingTarget {
+        fn new(state: ANSState) -> Self {
+            Self {
+                state,
+                tone_updates: Arc::new(Mutex::new(Vec::new())),
+                revocations: Arc::new(Mutex::new(Vec::new())),
+                unknown_tokens: Vec::new(),
+                failing_tokens: Vec::new(),
+                head: 0,
+                history: Vec::new(),
+                requested_ranges: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        fn with_history(self, head: u64, history: Vec<Event>) -> Self {
+            Self { head, history, ..self }
+        }
+
+        fn with_unknown_tokens(self, unknown_tokens: &[&str]) -> Self {
+            Self {
+                unknown_tokens: unknown_tokens.iter().map(|t| t.to_string()).collect(),
+                ..self
+            }
+        }
+
+        fn with_failing_tokens(self, failing_tokens: &[&str]) -> Self {
+            Self {
+                failing_tokens: failing_tokens.iter().map(|t| t.to_string()).collect(),
+                ..self
+            }
+        }
+
+        fn revoked(&self) -> Vec<(String, u8)> {
+            self.revocations.lock().unwrap().clone()
+        }
+
+        fn requested_ranges(&self) -> Vec<(u64, u64)> {
+            self.requested_ranges.lock().unwrap().clone()
+        }
+
+        fn relayed(&self) -> Vec<(u64, ANSState)> {
+            self.tone_updates.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for RecordingTarget {
+        async fn submit_aep(&self, _aep: &AfferentEvidencePacket) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn issue_with_brake(
+            &self,
+            _intent: &Intent,
+            _scaled_limits_hash: &[u8; 32],
+            _expires_at: u64,
+        ) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn revoke_capability(&self, token_id: &str, reason: u8) -> Result<()> {
+            self.revocations.lock().unwrap().push((token_id.to_string(), reason));
+            if self.unknown_tokens.iter().any(|t| t == token_id) {
+                return Err(ChainError::from_cosmwasm_error(&VagusError::TokenNotFound.to_string()).into());
+            }
+            if self.failing_tokens.iter().any(|t| t == token_id) {
+                return Err(ChainError::Rpc("connection reset".to_string()).into());
+            }
+            Ok(())
+        }
+
+        async fn token_owner(&self, _token_id: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn is_valid(&self, _token_id: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn active_tokens_of(&self, _executor_id: u64) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_guard(&self, _executor_id: u64, _action_id: &[u8; 32]) -> Result<Guard> {
+            Ok(Guard {
+                scalingFactor: 10000u128.into(),
+                allowed: true,
+            })
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            Ok(self.state.clone())
+        }
+
+        async fn update_tone(&self, _executor_id: u64, vti: Vti, suggested_state: ANSState) -> Result<()> {
+            self.tone_updates.lock().unwrap().push((vti.get(), suggested_state));
+            Ok(())
+        }
+
+        async fn subscribe_events(&self, _callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            from_block: u64,
+            to_block: u64,
+            _event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            self.requested_ranges.lock().unwrap().push((from_block, to_block));
+            Ok(self
+                .history
+                .iter()
+                .filter(|event| (from_block..=to_block).contains(&event.block_number))
+                .cloned()
+                .collect())
+        }
+
+        async fn health(&self) -> Result<u64> {
+            Ok(self.head)
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// VagalToneUpdated as the EVM ANS manager emits it for a VTI and state
+    fn tone_event(vti: u64, state: &str) -> Event {
+        let tone = vagus_chain::evm_tone_from_vti(Vti::new(vti).unwrap());
+        let state = match state {
+            "SAFE" => "0",
+            "DANGER" => "1",
+            "SHUTDOWN" => "2",
+            other => other,
+        };
+        Event {
+            chain_type: ChainType::EVM,
+            contract_address: "0x0".to_string(),
+            event_name: "VagalToneUpdated".to_string(),
+            topics: vec![],
+            data: HashMap::from([
+                ("tone".to_string(), serde_json::json!(tone.to_string())),
+                ("state".to_string(), serde_json::json!(state)),
+            ]),
+            block_number: 1,
+            transaction_hash: "0x1".to_string(),
+            log_index: 0,
+        }
+    }
+
+    fn revoked_event(token_id: &str, reason: &str) -> Event {
+        Event {
+            event_name: "CapabilityRevoked".to_string(),
+            data: HashMap::from([
+                ("tokenId".to_string(), serde_json::json!(token_id)),
+                ("reason".to_string(), serde_json::json!(reason)),
+            ]),
+            ..tone_event(0, "SAFE")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reflex_revokes_all_tokens_ahead_of_queued_events() {
+        let target = RecordingTarget::new(ANSState::SAFE);
+        let reflex = Event {
+            event_name: "ReflexTriggered".to_string(),
+            data: HashMap::from([
+                ("executorId".to_string(), serde_json::json!("42")),
+                ("revokedTokens".to_string(), serde_json::json!("11,12,13")),
+            ]),
+            transaction_hash: "0x2".to_string(),
+            ..tone_event(0, "SAFE")
+        };
+
+        // The reflex was queued behind a revocation for the same executor
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        event_tx.send(revoked_event("10", "0")).unwrap();
+        event_tx.send(reflex).unwrap();
+        drop(event_tx);
+
+        process_events(
+            Box::new(target.clone()),
+            event_rx,
+            RelayOptions::default(),
+            EventDedup::new(16),
+            BlockCheckpoint::in_memory(),
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            target.revoked(),
+            vec![
+                ("11".to_string(), REFLEX_TRIGGER_REASON),
+                ("12".to_string(), REFLEX_TRIGGER_REASON),
+                ("13".to_string(), REFLEX_TRIGGER_REASON),
+                ("10".to_string(), 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reflex_log_from_evm_contract_is_relayed() {
+        use ethers::abi::{encode, Token};
+        use ethers::contract::EthEvent;
+        use ethers::types::{Log, H256, U256};
+
+        // ReflexTriggered as emitted by ReflexArc.sol for executor 2
+        let log = Log {
+            topics: vec![vagus_chain::evm::ReflexTriggeredFilter::signature(), H256::from_low_u64_be(2)],
+            data: encode(&[
+                Token::String("danger_detected".to_string()),
+                Token::Uint(U256::from(2)),
+                Token::Uint(U256::from(1_700_000_000u64)),
+                Token::Array(vec![Token::Uint(U256::from(4)), Token::Uint(U256::from(5))]),
+            ])
+            .into(),
+            ..Default::default()
+        };
+        let target = RecordingTarget::new(ANSState::SAFE);
+
+        process_event(&target, &vagus_chain::evm::event_from_log(log), RelayOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            target.revoked(),
+            vec![
+                ("4".to_string(), REFLEX_TRIGGER_REASON),
+                ("5".to_string(), REFLEX_TRIGGER_REASON),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capability_revocation_is_relayed() {
+        let target = RecordingTarget::new(ANSState::SAFE).with_unknown_tokens(&["404"]);
+
+        process_event(&target, &revoked_event("7", "1"), RelayOptions::default())
+            .await
+            .unwrap();
+        process_event(&target, &revoked_event("8", "EXPIRATION"), RelayOptions::default())
+            .await
+            .unwrap();
+        // A token unknown to the target is only a warning
+        process_event(&target, &revoked_event("404", "0"), RelayOptions::default())
+            .await
+            .unwrap();
+        // Malformed events are not relayed
+        process_event(&target, &revoked_event("9", "7"), RelayOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            target.revoked(),
+            vec![("7".to_string(), 1), ("8".to_string(), 2), ("404".to_string(), 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_escalations_only_skips_de_escalations() {
+        let options = RelayOptions {
+            escalations_only: true,
+            ..Default::default()
+        };
+
+        // DANGER -> SAFE on the source is not mirrored onto a DANGER target
+        let target = RecordingTarget::new(ANSState::DANGER);
+        process_event(&target, &tone_event(9000, "SAFE"), options).await.unwrap();
+        assert!(target.relayed().is_empty());
+
+        // SAFE -> DANGER is mirrored onto a SAFE target
+        let target = RecordingTarget::new(ANSState::SAFE);
+        process_event(&target, &tone_event(5000, "DANGER"), options).await.unwrap();
+        assert_eq!(target.relayed(), vec![(5000, ANSState::DANGER)]);
+
+        // Without the flag every tone update is relayed
+        let target = RecordingTarget::new(ANSState::DANGER);
+        process_event(&target, &tone_event(9000, "SAFE"), RelayOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(target.relayed(), vec![(9000, ANSState::SAFE)]);
+    }
+
+    #[tokio::test]
+    async fn test_tone_updates_decoded_per_source_chain() {
+        // EVM: 350_000 ppm danger with the state's uint8 code
+        let target = RecordingTarget::new(ANSState::SAFE);
+        process_event(&target, &tone_event(6500, "DANGER"), RelayOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(target.relayed(), vec![(6500, ANSState::DANGER)]);
+
+        // CosmWasm: the VTI itself with the state's name
+        let cosmos = Event {
+            chain_type: ChainType::Cosmos,
+            data: HashMap::from([
+                ("tone".to_string(), serde_json::json!("6500")),
+                ("state".to_string(), serde_json::json!("DANGER")),
+            ]),
+            ..tone_event(0, "SAFE")
+        };
+        let target = RecordingTarget::new(ANSState::SAFE);
+        process_event(&target, &cosmos, RelayOptions::default()).await.unwrap();
+        assert_eq!(target.relayed(), vec![(6500, ANSState::DANGER)]);
+
+        // Malformed updates fail instead of being dropped as relayed
+        for (tone, state) in [("6500", "ALERT"), ("lots", "1"), ("2000000", "1")] {
+            let malformed = Event {
+                data: HashMap::from([
+                    ("tone".to_string(), serde_json::json!(tone)),
+                    ("state".to_string(), serde_json::json!(state)),
+                ]),
+                ..tone_event(0, "SAFE")
+            };
+            let target = RecordingTarget::new(ANSState::SAFE);
+            assert!(process_event(&target, &malformed, RelayOptions::default()).await.is_err());
+            assert!(target.relayed().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_events_are_processed_once() {
+        let target = RecordingTarget::new(ANSState::SAFE);
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        // A reconnect replays the same log
+        let event = tone_event(5000, "DANGER");
+        event_tx.send(event.clone()).unwrap();
+        event_tx.send(event).unwrap();
+        drop(event_tx);
+
+        process_events(
+            Box::new(target.clone()),
+            event_rx,
+            RelayOptions::default(),
+            EventDedup::new(16),
+            BlockCheckpoint::in_memory(),
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(target.relayed(), vec![(5000, ANSState::DANGER)]);
+    }
+
+    #[tokio::test]
+    async fn test_restart_resumes_from_checkpointed_block() {
+        let path = std::env::temp_dir().join(format!("vagus-relayer-checkpoint-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let at_block = |block_number: u64, tone: u64, state: &str| Event {
+            block_number,
+            transaction_hash: format!("0x{:x}", block_number),
+            ..tone_event(tone, state)
+        };
+        let history = vec![
+            at_block(5, 5000, "DANGER"),
+            at_block(7, 2000, "SHUTDOWN"),
+            at_block(9, 9000, "SAFE"),
+        ];
+
+        // First run relays up to block 7, then stops
+        let target = RecordingTarget::new(ANSState::SAFE);
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        event_tx.send(history[0].clone()).unwrap();
+        event_tx.send(history[1].clone()).unwrap();
+        drop(event_tx);
+        process_events(
+            Box::new(target.clone()),
+            event_rx,
+            RelayOptions::default(),
+            EventDedup::new(16),
+            BlockCheckpoint::load(path.clone()).unwrap(),
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+
+        // On restart the checkpoint is read back and backfill resumes from it
+        let checkpoint = BlockCheckpoint::load(path.clone()).unwrap();
+        assert_eq!(checkpoint.last_block(ChainType::EVM), Some(7));
+        assert_eq!(checkpoint.last_block(ChainType::Cosmos), None);
+
+        let source = RecordingTarget::new(ANSState::SAFE).with_history(10, history);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        backfill_events(&source, ChainType::EVM, &checkpoint, &event_tx).await.unwrap();
+        drop(event_tx);
+
+        assert_eq!(source.requested_ranges(), vec![(7, 10)]);
+        let mut backfilled = Vec::new();
+        while let Some(event) = event_rx.recv().await {
+            backfilled.push(event.block_number);
+        }
+        assert_eq!(backfilled, vec![7, 9]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_stops_below_undelivered_event() {
+        let at_block = |block_number: u64, token_id: &str| Event {
+            block_number,
+            transaction_hash: format!("0x{:x}", block_number),
+            ..revoked_event(token_id, "0")
+        };
+        let target = RecordingTarget::new(ANSState::SAFE).with_failing_tokens(&["6"]);
+        let path = std::env::temp_dir().join(format!("vagus-relayer-undelivered-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // Block 6 fails to relay; blocks 5 and 7 go through
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        for (block_number, token_id) in [(5, "5"), (6, "6"), (7, "7")] {
+            event_tx.send(at_block(block_number, token_id)).unwrap();
+        }
+        drop(event_tx);
+        process_events(
+            Box::new(target.clone()),
+            event_rx,
+            RelayOptions::default(),
+            EventDedup::new(16),
+            BlockCheckpoint::load(path.clone()).unwrap(),
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(target.revoked().len(), 3);
+
+        // A restart backfills from block 5, so block 6 is retried
+        let checkpoint = BlockCheckpoint::load(path.clone()).unwrap();
+        assert_eq!(checkpoint.last_block(ChainType::EVM), Some(5));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_relays_mock_source_to_mock_target() {
+        use vagus_chain::{MockCall, MockChainClient};
+
+        let source = MockChainClient::new();
+        let target = MockChainClient::new();
+        let issued = Event {
+            event_name: "CapabilityIssued".to_string(),
+            data: HashMap::from([("tokenId".to_string(), serde_json::json!("7"))]),
+            block_number: 2,
+            transaction_hash: "0x2".to_string(),
+            ..tone_event(0, "SAFE")
+        };
+        let revoked = Event {
+            block_number: 3,
+            transaction_hash: "0x3".to_string(),
+            ..revoked_event("7", "REFLEX_TRIGGER")
+        };
+
+        // Events before the subscription are replayed to it, later ones arrive live
+        source.emit(tone_event(5000, "DANGER"));
+        source.emit(issued);
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        subscribe_to_events(source.clone_box(), event_tx).await.unwrap();
+        source.emit(revoked.clone());
+        // A reconnect replays logs the relayer has already seen
+        source.emit(tone_event(5000, "DANGER"));
+        source.emit(revoked);
+        source.unsubscribe_all();
+
+        process_events(
+            target.clone_box(),
+            event_rx,
+            RelayOptions::default(),
+            EventDedup::new(16),
+            BlockCheckpoint::in_memory(),
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            target.calls(),
+            vec![
+                MockCall::UpdateTone {
+                    executor_id: 0,
+                    vti: Vti::new(5000).unwrap(),
+                    suggested_state: ANSState::DANGER,
+                },
+                MockCall::RevokeCapability {
+                    token_id: "7".to_string(),
+                    reason: 1,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_events_follow_policy() {
+        let unknown = Event {
+            event_name: "ExecutorPaused".to_string(),
+            transaction_hash: "0x2".to_string(),
+            ..tone_event(0, "SAFE")
+        };
+
+        for policy in [
+            UnknownEventPolicy::Ignore,
+            UnknownEventPolicy::Warn,
+            UnknownEventPolicy::Fail,
+        ] {
+            let target = RecordingTarget::new(ANSState::SAFE);
+            let metrics = Arc::new(RelayMetrics::default());
+            let (event_tx, event_rx) = mpsc::unbounded_channel();
+            event_tx.send(unknown.clone()).unwrap();
+            event_tx.send(tone_event(5000, "DANGER")).unwrap();
+            drop(event_tx);
+
+            let options = RelayOptions {
+                unknown_events: policy,
+                ..Default::default()
+            };
+            let result = process_events(
+                Box::new(target.clone()),
+                event_rx,
+                options,
+                EventDedup::new(16),
+                BlockCheckpoint::in_memory(),
+                metrics.clone(),
+            )
+            .await;
+
+            // Counted whatever the policy
+            assert_eq!(metrics.unknown_events(), 1);
+            if policy == UnknownEventPolicy::Fail {
+                assert!(result.unwrap_err().is::<UnknownEventError>());
+                assert!(target.relayed().is_empty());
+            } else {
+                result.unwrap();
+                assert_eq!(target.relayed(), vec![(5000, ANSState::DANGER)]);
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Relay metrics
+//!
+//! Counters for monitoring the relayer, shared between the processing loop
+//! and whatever reports them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+pub struct RelayMetrics {
+    unknown_events: AtomicU64,
+}
+
+impl RelayMetrics {
+    /// Count an event whose name the relayer does not handle, returning the new total
+    pub fn record_unknown_event(&self) -> u64 {
+        self.unknown_events.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn unknown_events(&self) -> u64 {
+        self.unknown_events.load(Ordering::Relaxed)
+    }
+}
@@ -3,10 +3,16 @@
 //! Cross-chain invariant and equivalence testing for EVM and CosmWasm implementations.
 
 use anyhow::Result;
-use std::collections::HashMap;
-use vagus_chain::{ChainClient, ChainConfig, ChainType};
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use vagus_chain::{ChainClient, ChainConfig, Event};
 use vagus_spec::*;
 
+pub mod scaling;
+
 /// Test harness for cross-chain invariant verification
 pub struct GoldenTestHarness {
     evm_client: Box<dyn ChainClient>,
@@ -14,28 +20,104 @@ pub struct GoldenTestHarness {
 }
 
 /// Test scenario configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct TestScenario {
     pub name: String,
+    #[serde(default)]
     pub description: String,
     pub setup_actions: Vec<TestAction>,
     pub invariant_checks: Vec<InvariantCheck>,
 }
 
+impl TestScenario {
+    /// Load a scenario from a YAML file; see `scenarios/*.yaml` for the format
+    pub fn from_yaml(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read scenario {}: {}", path.display(), e))?;
+        serde_yaml::from_str(&yaml)
+            .map_err(|e| anyhow::anyhow!("Invalid scenario {}: {}", path.display(), e))
+    }
+}
+
+/// Executor whose tone and guards the scenarios drive and compare
+const SCENARIO_EXECUTOR_ID: u64 = 0;
+
 /// Test action to perform
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum TestAction {
-    UpdateTone { vti: u64, state: ANSState },
-    SubmitAEP { aep: vagus_telemetry::AfferentEvidencePacket },
+    UpdateTone {
+        vti: u64,
+        #[serde(deserialize_with = "ans_state_from_name")]
+        state: ANSState,
+    },
+    /// Submit an AEP, first checking it against `metrics` when given
+    #[serde(rename = "submit_aep")]
+    SubmitAEP {
+        aep: vagus_telemetry::AfferentEvidencePacket,
+        #[serde(default)]
+        metrics: Option<vagus_telemetry::WindowMetrics>,
+    },
     IssueCapability {
-        intent: vagus_telemetry::Intent,
+        intent: Intent,
+        #[serde(deserialize_with = "bytes32_from_hex")]
         scaled_limits_hash: [u8; 32],
         expires_at: u64,
     },
 }
 
+/// ANS state by its spec name (`SAFE`, `DANGER`, `SHUTDOWN`)
+fn ans_state_from_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ANSState, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    match name.to_ascii_uppercase().as_str() {
+        "SAFE" => Ok(ANSState::SAFE),
+        "DANGER" => Ok(ANSState::DANGER),
+        "SHUTDOWN" => Ok(ANSState::SHUTDOWN),
+        _ => Err(serde::de::Error::custom(format!("unknown ANS state: {}", name))),
+    }
+}
+
+/// 32 bytes given as a hex string, with or without `0x`
+fn bytes32_from_hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let text = String::deserialize(deserializer)?;
+    let bytes = hex::decode(text.trim_start_matches("0x")).map_err(serde::de::Error::custom)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| serde::de::Error::custom(format!("expected 32 bytes, got {}", bytes.len())))
+}
+
+/// Capability token issued by a scenario on one chain, identified by what it
+/// was issued for rather than by the chain's own token id
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IssuedToken {
+    pub executor_id: u64,
+    pub action_id: [u8; 32],
+    /// Issued for an escape intent, so it may stay valid through SHUTDOWN
+    pub is_escape: bool,
+}
+
+impl IssuedToken {
+    fn from_intent(intent: &Intent) -> Result<Self> {
+        let executor = intent.executorId.to_be_bytes();
+        if executor[..24].iter().any(|b| *b != 0) {
+            return Err(anyhow::anyhow!("executorId {} out of range", intent.executorId));
+        }
+        let action_id = intent.actionId.as_slice().try_into().map_err(|_| {
+            anyhow::anyhow!("actionId must be 32 bytes, got {}", intent.actionId.len())
+        })?;
+        Ok(Self {
+            executor_id: u64::from_be_bytes(executor[24..].try_into().expect("8 bytes")),
+            action_id,
+            is_escape: intent.isEscape,
+        })
+    }
+}
+
 /// Invariant to check
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "invariant", rename_all = "snake_case")]
 pub enum InvariantCheck {
     /// I1: SHUTDOWN state implies no valid non-escape tokens
     ShutdownNoValidTokens,
@@ -47,7 +129,8 @@ pub enum InvariantCheck {
     EnvelopeSafetyBounds,
     /// I5: CBF projection safety (control barrier function)
     CbfProjectionSafety,
-    /// Event equivalence check
+    /// Event equivalence check; events and their keys are defined in
+    /// spec/events.yml and carry the same keys on every chain
     EventEquivalence { event_name: String },
 }
 
@@ -60,63 +143,164 @@ impl GoldenTestHarness {
         let evm_client = vagus_chain::ChainClientFactory::create_client(evm_config).await?;
         let cosmos_client = vagus_chain::ChainClientFactory::create_client(cosmos_config).await?;
 
-        Ok(Self {
+        Ok(Self::with_clients(evm_client, cosmos_client))
+    }
+
+    /// Create a harness from already constructed clients
+    pub fn with_clients(
+        evm_client: Box<dyn ChainClient>,
+        cosmos_client: Box<dyn ChainClient>,
+    ) -> Self {
+        Self {
             evm_client,
             cosmos_client,
-        })
+        }
     }
 
     /// Run a test scenario on both chains
     pub async fn run_scenario(&self, scenario: &TestScenario) -> Result<TestResults> {
-        println!("🧪 Running scenario: {}", scenario.name);
+        // Only fetch events when the scenario compares them
+        let event_names: Vec<&str> = scenario
+            .invariant_checks
+            .iter()
+            .filter_map(|invariant| match invariant {
+                InvariantCheck::EventEquivalence { event_name } => Some(event_name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let (evm_start, cosmos_start) = if event_names.is_empty() {
+            (None, None)
+        } else {
+            (Some(self.evm_client.health().await?), Some(self.cosmos_client.health().await?))
+        };
 
         // Execute setup actions
+        let mut evm_issued = BTreeMap::new();
+        let mut cosmos_issued = BTreeMap::new();
         for action in &scenario.setup_actions {
-            self.execute_action_on_both_chains(action).await?;
+            if let Some((evm_token, cosmos_token)) =
+                self.execute_action_on_both_chains(action).await?
+            {
+                if let TestAction::IssueCapability { intent, .. } = action {
+                    let issued = IssuedToken::from_intent(intent)?;
+                    evm_issued.insert(evm_token, issued.clone());
+                    cosmos_issued.insert(cosmos_token, issued);
+                }
+            }
         }
 
+        // Snapshot observable state on each chain for cross-chain comparison;
+        // a chain that cannot be queried fails the scenario rather than
+        // hiding its divergences
+        let evm_snapshot = ChainSnapshot::capture(&*self.evm_client, &evm_issued)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to snapshot EVM: {}", e))?;
+        let cosmos_snapshot = ChainSnapshot::capture(&*self.cosmos_client, &cosmos_issued)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to snapshot Cosmos: {}", e))?;
+        let divergences = evm_snapshot.diff(&cosmos_snapshot);
+        let evm_events = capture_events(&*self.evm_client, evm_start, &event_names)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to capture EVM events: {}", e))?;
+        let cosmos_events = capture_events(&*self.cosmos_client, cosmos_start, &event_names)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to capture Cosmos events: {}", e))?;
+
         // Check invariants
-        let mut results = TestResults::default();
+        let mut results = TestResults {
+            scenario: scenario.name.clone(),
+            ..Default::default()
+        };
         for invariant in &scenario.invariant_checks {
-            let evm_result = self.check_invariant(&*self.evm_client, invariant).await;
-            let cosmos_result = self.check_invariant(&*self.cosmos_client, invariant).await;
+            if let InvariantCheck::EventEquivalence { event_name } = invariant {
+                results.invariant_results.push(InvariantResult {
+                    invariant: invariant.clone(),
+                    evm_passed: true,
+                    cosmos_passed: true,
+                    evm_error: None,
+                    cosmos_error: None,
+                    divergences: event_divergences(event_name, &evm_events, &cosmos_events),
+                });
+                continue;
+            }
+
+            let evm_result = self
+                .check_invariant(&*self.evm_client, invariant, &evm_issued)
+                .await;
+            let cosmos_result = self
+                .check_invariant(&*self.cosmos_client, invariant, &cosmos_issued)
+                .await;
 
+            let fields = invariant.compared_fields();
             results.invariant_results.push(InvariantResult {
                 invariant: invariant.clone(),
                 evm_passed: evm_result.is_ok(),
                 cosmos_passed: cosmos_result.is_ok(),
                 evm_error: evm_result.err(),
                 cosmos_error: cosmos_result.err(),
+                divergences: divergences
+                    .iter()
+                    .filter(|d| fields.contains(&d.field))
+                    .cloned()
+                    .collect(),
             });
         }
 
         Ok(results)
     }
 
-    /// Execute a test action on both chains
-    async fn execute_action_on_both_chains(&self, action: &TestAction) -> Result<()> {
+    /// Execute a test action on both chains, returning issued token ids (EVM, Cosmos)
+    async fn execute_action_on_both_chains(
+        &self,
+        action: &TestAction,
+    ) -> Result<Option<(String, String)>> {
         match action {
             TestAction::UpdateTone { vti, state } => {
-                self.evm_client.update_tone(*vti, *state).await?;
-                self.cosmos_client.update_tone(*vti, *state).await?;
+                let vti = Vti::new(*vti)?;
+                self.evm_client
+                    .update_tone(SCENARIO_EXECUTOR_ID, vti, state.clone())
+                    .await?;
+                self.cosmos_client
+                    .update_tone(SCENARIO_EXECUTOR_ID, vti, state.clone())
+                    .await?;
             }
-            TestAction::SubmitAEP { aep } => {
+            TestAction::SubmitAEP { aep, metrics } => {
+                if let Some(metrics) = metrics {
+                    if !aep.verify_against(metrics) {
+                        return Err(anyhow::anyhow!(
+                            "AEP for executor {} does not commit to the supplied metrics",
+                            aep.executor_id
+                        ));
+                    }
+                }
                 self.evm_client.submit_aep(aep).await?;
                 self.cosmos_client.submit_aep(aep).await?;
             }
             TestAction::IssueCapability { intent, scaled_limits_hash, expires_at } => {
-                self.evm_client.issue_with_brake(intent, scaled_limits_hash, *expires_at).await?;
-                self.cosmos_client.issue_with_brake(intent, scaled_limits_hash, *expires_at).await?;
+                let evm_token = self
+                    .evm_client
+                    .issue_with_brake(intent, scaled_limits_hash, *expires_at)
+                    .await?;
+                let cosmos_token = self
+                    .cosmos_client
+                    .issue_with_brake(intent, scaled_limits_hash, *expires_at)
+                    .await?;
+                return Ok(Some((evm_token, cosmos_token)));
             }
         }
-        Ok(())
+        Ok(None)
     }
 
-    /// Check an invariant on a specific chain
-    async fn check_invariant(&self, client: &dyn ChainClient, invariant: &InvariantCheck) -> Result<()> {
+    /// Check an invariant on a specific chain, given the tokens the scenario issued there
+    async fn check_invariant(
+        &self,
+        client: &dyn ChainClient,
+        invariant: &InvariantCheck,
+        issued: &BTreeMap<String, IssuedToken>,
+    ) -> Result<()> {
         match invariant {
             InvariantCheck::ShutdownNoValidTokens => {
-                self.check_shutdown_no_valid_tokens(client).await
+                self.check_shutdown_no_valid_tokens(client, issued).await
             }
             InvariantCheck::DangerTokenLimitsScaled => {
                 self.check_danger_token_limits_scaled(client).await
@@ -131,22 +315,45 @@ impl GoldenTestHarness {
                 self.check_cbf_projection_safety(client).await
             }
             InvariantCheck::EventEquivalence { .. } => {
-                // Event equivalence is checked separately during action execution
+                // Event equivalence is a cross-chain comparison made in `run_scenario`
                 Ok(())
             }
         }
     }
 
-    async fn check_shutdown_no_valid_tokens(&self, client: &dyn ChainClient) -> Result<()> {
-        let ans_state = client.get_ans_state().await?;
+    /// In SHUTDOWN, every executor the scenario issued to must hold no valid
+    /// tokens except those issued for escape intents
+    async fn check_shutdown_no_valid_tokens(
+        &self,
+        client: &dyn ChainClient,
+        issued: &BTreeMap<String, IssuedToken>,
+    ) -> Result<()> {
+        if client.get_ans_state().await? != ANSState::SHUTDOWN {
+            return Ok(());
+        }
+
+        let executors: BTreeSet<u64> = issued.values().map(|token| token.executor_id).collect();
+        let mut violations = Vec::new();
+        for executor_id in executors {
+            for token_id in client.active_tokens_of(executor_id).await? {
+                if !client.is_valid(&token_id).await? {
+                    continue;
+                }
+                // Tokens the scenario did not issue have no known intent, so
+                // they cannot be shown to be escape tokens
+                if !issued.get(&token_id).is_some_and(|token| token.is_escape) {
+                    violations.push(format!("token {} of executor {}", token_id, executor_id));
+                }
+            }
+        }
 
-        if ans_state == ANSState::SHUTDOWN {
-            // In SHUTDOWN state, there should be no valid tokens
-            // This is a simplified check - in practice would query all tokens
-            // For now, just check that we can query the state
+        if violations.is_empty() {
             Ok(())
         } else {
-            Ok(())
+            Err(anyhow::anyhow!(
+                "SHUTDOWN with valid non-escape tokens: {}",
+                violations.join(", ")
+            ))
         }
     }
 
@@ -175,7 +382,7 @@ impl GoldenTestHarness {
         // Check that intent envelopes stay within safety bounds
         // This would require intent validation logic
         // For now, just verify the client works
-        let _ = client.get_guard([0; 32]).await?;
+        let _ = client.get_guard(SCENARIO_EXECUTOR_ID, &[0; 32]).await?;
         Ok(())
     }
 
@@ -183,21 +390,357 @@ impl GoldenTestHarness {
         // Check control barrier function safety
         // This is a complex control theory verification
         // For now, just verify basic functionality
-        let _ = client.get_guard([0; 32]).await?;
+        let _ = client.get_guard(SCENARIO_EXECUTOR_ID, &[0; 32]).await?;
         Ok(())
     }
 }
 
+impl InvariantCheck {
+    /// Cross-chain fields whose divergence is relevant to this invariant
+    pub fn compared_fields(&self) -> &'static [DivergenceField] {
+        match self {
+            InvariantCheck::ShutdownNoValidTokens | InvariantCheck::ReflexRevocationDelay => {
+                &[DivergenceField::AnsState, DivergenceField::TokenSet]
+            }
+            InvariantCheck::DangerTokenLimitsScaled => {
+                &[DivergenceField::AnsState, DivergenceField::ScalingFactor]
+            }
+            InvariantCheck::EnvelopeSafetyBounds | InvariantCheck::CbfProjectionSafety => {
+                &[DivergenceField::ScalingFactor, DivergenceField::ActionAllowed]
+            }
+            InvariantCheck::EventEquivalence { .. } => &[DivergenceField::Event],
+        }
+    }
+}
+
+/// Observable chain state compared across EVM and Cosmos
+#[derive(Debug, Clone)]
+pub struct ChainSnapshot {
+    pub ans_state: ANSState,
+    pub guard: Guard,
+    /// Issued tokens still valid, sorted. Each chain numbers its tokens its
+    /// own way, so they are compared by what they were issued for.
+    pub valid_tokens: Vec<IssuedToken>,
+}
+
+impl ChainSnapshot {
+    /// Capture ANS state and guard (for the zero action id) alongside which
+    /// of the scenario's issued tokens are still valid
+    pub async fn capture(
+        client: &dyn ChainClient,
+        issued: &BTreeMap<String, IssuedToken>,
+    ) -> Result<Self> {
+        let mut valid_tokens = Vec::new();
+        for (token_id, token) in issued {
+            if client.is_valid(token_id).await? {
+                valid_tokens.push(token.clone());
+            }
+        }
+        valid_tokens.sort();
+
+        Ok(Self {
+            ans_state: client.get_ans_state().await?,
+            guard: client.get_guard(SCENARIO_EXECUTOR_ID, &[0; 32]).await?,
+            valid_tokens,
+        })
+    }
+
+    /// Field-level differences between this (EVM) snapshot and a Cosmos snapshot
+    pub fn diff(&self, cosmos: &ChainSnapshot) -> Vec<Divergence> {
+        let mut divergences = Vec::new();
+        if self.ans_state != cosmos.ans_state {
+            divergences.push(Divergence {
+                key: None,
+                field: DivergenceField::AnsState,
+                evm: format!("{:?}", self.ans_state),
+                cosmos: format!("{:?}", cosmos.ans_state),
+            });
+        }
+        if self.guard.scalingFactor != cosmos.guard.scalingFactor {
+            divergences.push(Divergence {
+                key: None,
+                field: DivergenceField::ScalingFactor,
+                evm: self.guard.scalingFactor.to_string(),
+                cosmos: cosmos.guard.scalingFactor.to_string(),
+            });
+        }
+        if self.guard.allowed != cosmos.guard.allowed {
+            divergences.push(Divergence {
+                key: None,
+                field: DivergenceField::ActionAllowed,
+                evm: self.guard.allowed.to_string(),
+                cosmos: cosmos.guard.allowed.to_string(),
+            });
+        }
+        if self.valid_tokens != cosmos.valid_tokens {
+            let render = |tokens: &[IssuedToken]| {
+                let tokens: Vec<String> = tokens
+                    .iter()
+                    .map(|token| format!("{}:0x{}", token.executor_id, hex::encode(token.action_id)))
+                    .collect();
+                format!("[{}]", tokens.join(", "))
+            };
+            divergences.push(Divergence {
+                key: None,
+                field: DivergenceField::TokenSet,
+                evm: render(&self.valid_tokens),
+                cosmos: render(&cosmos.valid_tokens),
+            });
+        }
+        divergences
+    }
+}
+
+/// Field compared across chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceField {
+    AnsState,
+    ScalingFactor,
+    ActionAllowed,
+    TokenSet,
+    Event,
+}
+
+impl fmt::Display for DivergenceField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DivergenceField::AnsState => "ans_state",
+            DivergenceField::ScalingFactor => "scaling_factor",
+            DivergenceField::ActionAllowed => "action_allowed",
+            DivergenceField::TokenSet => "token_set",
+            DivergenceField::Event => "event",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Concrete values of a field that differed between chains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Divergence {
+    pub field: DivergenceField,
+    /// Part of the field that differed, e.g. `VagalToneUpdated[0].tone`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub evm: String,
+    pub cosmos: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "{} {}: EVM={} Cosmos={}", self.field, key, self.evm, self.cosmos),
+            None => write!(f, "{}: EVM={} Cosmos={}", self.field, self.evm, self.cosmos),
+        }
+    }
+}
+
+/// Events emitted after `start_height`, in emission order; none when `start_height` is unset
+async fn capture_events(
+    client: &dyn ChainClient,
+    start_height: Option<u64>,
+    event_names: &[&str],
+) -> Result<Vec<Event>> {
+    let Some(start_height) = start_height else {
+        return Ok(Vec::new());
+    };
+    let end_height = client.health().await?;
+    let mut events = client
+        .get_events_in_range(start_height + 1, end_height, event_names)
+        .await?;
+    events.sort_by_key(|event| (event.block_number, event.log_index));
+    Ok(events)
+}
+
+/// Keys of spec/events.yml compared across chains. Timestamps are left out:
+/// each chain stamps events with its own block time
+fn portable_event_keys(event_name: &str) -> Option<&'static [&'static str]> {
+    match event_name {
+        "CapabilityIssued" => Some(&[
+            "tokenId",
+            "executorId",
+            "planner",
+            "actionId",
+            "expiresAt",
+            "paramsHashSha256",
+            "paramsHashKeccak",
+            "preStateRootSha256",
+            "preStateRootKeccak",
+        ]),
+        "CapabilityRevoked" => Some(&["tokenId", "executorId", "reason"]),
+        "AEPPosted" => Some(&[
+            "executorId",
+            "stateRootSha256",
+            "stateRootKeccak",
+            "metricsHashSha256",
+            "metricsHashKeccak",
+        ]),
+        "VagalToneUpdated" => Some(&["tone", "state"]),
+        "StateChanged" => Some(&["fromState", "toState", "vti"]),
+        "ReflexTriggered" => Some(&["executorId", "reason", "revokedCount"]),
+        _ => None,
+    }
+}
+
+/// Spec key for an event attribute: CosmWasm attributes are snake_case,
+/// EVM parameters camelCase
+fn normalize_event_key(key: &str) -> String {
+    let mut normalized = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for c in key.trim_start_matches('_').chars() {
+        if c == '_' {
+            upper_next = true;
+        } else if upper_next {
+            normalized.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// Chain-independent rendering of an event value: integers in decimal
+/// whether emitted as hex or decimal, ANS states as their `uint8` code
+fn normalize_event_value(value: &serde_json::Value) -> String {
+    let text = match value {
+        serde_json::Value::String(text) => text.as_str(),
+        serde_json::Value::Array(values) => {
+            return values.iter().map(normalize_event_value).collect::<Vec<_>>().join(",");
+        }
+        serde_json::Value::Null => return String::new(),
+        other => return other.to_string(),
+    };
+    match text {
+        "SAFE" => return "0".to_string(),
+        "DANGER" => return "1".to_string(),
+        "SHUTDOWN" => return "2".to_string(),
+        _ => {}
+    }
+
+    let is_hex = |digits: &str| {
+        !digits.is_empty() && digits.len() <= 64 && digits.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    let number = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) if is_hex(digits) => U256::from_str_radix(digits, 16).ok(),
+        _ if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) => {
+            U256::from_dec_str(text).ok()
+        }
+        _ if text.len() == 64 && is_hex(text) => U256::from_str_radix(text, 16).ok(),
+        _ => None,
+    };
+    match number {
+        Some(number) => number.to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Spec keys and normalized values of an event, restricted to its portable keys
+fn normalize_event(event: &Event) -> BTreeMap<String, String> {
+    let portable = portable_event_keys(&event.event_name);
+    event
+        .data
+        .iter()
+        .map(|(key, value)| (normalize_event_key(key), normalize_event_value(value)))
+        .filter(|(key, _)| portable.is_none_or(|keys| keys.contains(&key.as_str())))
+        .collect()
+}
+
+/// Key-level differences between the `event_name` events of each chain,
+/// matched up in emission order
+fn event_divergences(event_name: &str, evm: &[Event], cosmos: &[Event]) -> Vec<Divergence> {
+    let select = |events: &[Event]| -> Vec<BTreeMap<String, String>> {
+        events
+            .iter()
+            .filter(|event| event.event_name == event_name)
+            .map(normalize_event)
+            .collect()
+    };
+    let (evm, cosmos) = (select(evm), select(cosmos));
+
+    let mut divergences = Vec::new();
+    if evm.len() != cosmos.len() {
+        divergences.push(Divergence {
+            field: DivergenceField::Event,
+            key: Some(format!("{}.count", event_name)),
+            evm: evm.len().to_string(),
+            cosmos: cosmos.len().to_string(),
+        });
+    }
+    for (index, (evm, cosmos)) in evm.iter().zip(&cosmos).enumerate() {
+        let keys: BTreeSet<&String> = evm.keys().chain(cosmos.keys()).collect();
+        for key in keys {
+            let (evm_value, cosmos_value) = (evm.get(key), cosmos.get(key));
+            if evm_value != cosmos_value {
+                let render = |value: Option<&String>| {
+                    value.cloned().unwrap_or_else(|| "<missing>".to_string())
+                };
+                divergences.push(Divergence {
+                    field: DivergenceField::Event,
+                    key: Some(format!("{}[{}].{}", event_name, index, key)),
+                    evm: render(evm_value),
+                    cosmos: render(cosmos_value),
+                });
+            }
+        }
+    }
+    divergences
+}
+
 /// Test results
 #[derive(Debug, Default)]
 pub struct TestResults {
+    pub scenario: String,
     pub invariant_results: Vec<InvariantResult>,
     pub passed: bool,
 }
 
 impl TestResults {
     pub fn passed(&self) -> bool {
-        self.invariant_results.iter().all(|r| r.evm_passed && r.cosmos_passed)
+        self.invariant_results.iter().all(|r| r.passed())
+    }
+
+    /// Human-readable report of failing invariants and their divergences
+    pub fn text_report(&self) -> String {
+        let mut out = String::new();
+        for result in self.invariant_results.iter().filter(|r| !r.passed()) {
+            out.push_str(&format!("      Invariant: {:?}\n", result.invariant));
+            if let Some(err) = &result.evm_error {
+                out.push_str(&format!("        EVM: ❌ {}\n", err));
+            }
+            if let Some(err) = &result.cosmos_error {
+                out.push_str(&format!("        Cosmos: ❌ {}\n", err));
+            }
+            for divergence in &result.divergences {
+                out.push_str(&format!("        Divergence {}\n", divergence));
+            }
+        }
+        out
+    }
+
+    /// Machine-readable report of all invariant results
+    pub fn json_report(&self) -> serde_json::Value {
+        let invariants: Vec<serde_json::Value> = self
+            .invariant_results
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "invariant": format!("{:?}", r.invariant),
+                    "passed": r.passed(),
+                    "evm_passed": r.evm_passed,
+                    "cosmos_passed": r.cosmos_passed,
+                    "evm_error": r.evm_error.as_ref().map(|e| e.to_string()),
+                    "cosmos_error": r.cosmos_error.as_ref().map(|e| e.to_string()),
+                    "divergences": r.divergences,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "scenario": self.scenario,
+            "passed": self.passed(),
+            "invariants": invariants,
+        })
     }
 }
 
@@ -209,11 +752,21 @@ pub struct InvariantResult {
     pub cosmos_passed: bool,
     pub evm_error: Option<anyhow::Error>,
     pub cosmos_error: Option<anyhow::Error>,
+    /// Cross-chain differences in the fields this invariant depends on
+    pub divergences: Vec<Divergence>,
+}
+
+impl InvariantResult {
+    /// Passed on both chains with no cross-chain divergence
+    pub fn passed(&self) -> bool {
+        self.evm_passed && self.cosmos_passed && self.divergences.is_empty()
+    }
 }
 
 /// Predefined test scenarios
 pub mod scenarios {
     use super::*;
+    use cosmwasm_std::{Binary, Uint256};
 
     /// Basic state transition scenario
     pub fn basic_state_transitions() -> TestScenario {
@@ -233,6 +786,54 @@ pub mod scenarios {
         }
     }
 
+    /// Tokens issued before SHUTDOWN must be revoked by the transition
+    pub fn shutdown_revokes_tokens() -> TestScenario {
+        let intent = Intent {
+            executorId: Uint256::from(1u64),
+            actionId: Binary::from(vec![1u8; 32]),
+            params: Binary::from(vec![]),
+            envelopeHash: Binary::from(vec![2u8; 32]),
+            preStateRoot: Binary::from(vec![3u8; 32]),
+            notBefore: Uint256::zero(),
+            notAfter: Uint256::from(u64::MAX),
+            maxDurationMs: Uint256::from(5000u64),
+            maxEnergyJ: Uint256::from(100u64),
+            planner: "planner".to_string(),
+            nonce: Uint256::from(1u64),
+            isEscape: false,
+        };
+        TestScenario {
+            name: "Shutdown Revokes Tokens".to_string(),
+            description: "Issue a capability, force SHUTDOWN and check it is no longer valid"
+                .to_string(),
+            setup_actions: vec![
+                TestAction::UpdateTone { vti: 9000, state: ANSState::SAFE },
+                TestAction::IssueCapability {
+                    intent,
+                    scaled_limits_hash: [0u8; 32],
+                    expires_at: u64::MAX,
+                },
+                TestAction::UpdateTone { vti: 1000, state: ANSState::SHUTDOWN },
+            ],
+            invariant_checks: vec![InvariantCheck::ShutdownNoValidTokens],
+        }
+    }
+
+    /// Tone updates must emit equivalent `VagalToneUpdated` events on both chains
+    pub fn tone_update_event_equivalence() -> TestScenario {
+        TestScenario {
+            name: "Tone Update Event Equivalence".to_string(),
+            description: "Compare VagalToneUpdated tone and state across chains".to_string(),
+            setup_actions: vec![
+                TestAction::UpdateTone { vti: 8000, state: ANSState::SAFE },
+                TestAction::UpdateTone { vti: 4000, state: ANSState::DANGER },
+            ],
+            invariant_checks: vec![InvariantCheck::EventEquivalence {
+                event_name: "VagalToneUpdated".to_string(),
+            }],
+        }
+    }
+
     /// Reflex arc scenario
     pub fn reflex_arc_triggering() -> TestScenario {
         TestScenario {
@@ -253,6 +854,87 @@ pub mod scenarios {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use vagus_chain::ChainType;
+
+    /// Fixed-response client used to simulate each chain
+    #[derive(Clone)]
+    struct StubClient {
+        state: ANSState,
+        scaling_factor: u64,
+        /// Tokens still valid, as if revocation had not happened
+        valid_tokens: Vec<String>,
+        /// Events reported for any block range
+        events: Vec<Event>,
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for StubClient {
+        async fn submit_aep(&self, _aep: &vagus_telemetry::AfferentEvidencePacket) -> Result<String> {
+            Ok("0x1".to_string())
+        }
+
+        async fn issue_with_brake(
+            &self,
+            _intent: &Intent,
+            _scaled_limits_hash: &[u8; 32],
+            _expires_at: u64,
+        ) -> Result<String> {
+            Ok("1".to_string())
+        }
+
+        async fn revoke_capability(&self, _token_id: &str, _reason: u8) -> Result<()> {
+            Ok(())
+        }
+
+        async fn token_owner(&self, _token_id: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn is_valid(&self, token_id: &str) -> Result<bool> {
+            Ok(self.valid_tokens.iter().any(|id| id == token_id))
+        }
+
+        async fn active_tokens_of(&self, _executor_id: u64) -> Result<Vec<String>> {
+            Ok(self.valid_tokens.clone())
+        }
+
+        async fn get_guard(&self, _executor_id: u64, _action_id: &[u8; 32]) -> Result<Guard> {
+            Ok(Guard {
+                scalingFactor: self.scaling_factor.into(),
+                allowed: self.scaling_factor > 0,
+            })
+        }
+
+        async fn get_ans_state(&self) -> Result<ANSState> {
+            Ok(self.state.clone())
+        }
+
+        async fn update_tone(&self, _executor_id: u64, _vti: Vti, _suggested_state: ANSState) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_events(&self, _callback: Box<dyn Fn(Event) + Send + Sync + 'static>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            _from_block: u64,
+            _to_block: u64,
+            _event_names: &[&str],
+        ) -> Result<Vec<Event>> {
+            Ok(self.events.clone())
+        }
+
+        async fn health(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        fn clone_box(&self) -> Box<dyn ChainClient> {
+            Box::new(self.clone())
+        }
+    }
 
     #[tokio::test]
     async fn test_golden_harness_creation() {
@@ -263,4 +945,255 @@ mod tests {
         assert_eq!(scenario.setup_actions.len(), 3);
         assert_eq!(scenario.invariant_checks.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_divergence_report_pinpoints_differing_field() {
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(StubClient {
+                state: ANSState::SHUTDOWN,
+                scaling_factor: 0,
+                valid_tokens: vec![],
+                events: vec![],
+            }),
+            Box::new(StubClient {
+                state: ANSState::DANGER,
+                scaling_factor: 0,
+                valid_tokens: vec![],
+                events: vec![],
+            }),
+        );
+        let scenario = TestScenario {
+            name: "Deliberate Divergence".to_string(),
+            description: "Chains disagree on ANS state".to_string(),
+            setup_actions: vec![],
+            invariant_checks: vec![
+                InvariantCheck::ShutdownNoValidTokens,
+                InvariantCheck::CbfProjectionSafety,
+            ],
+        };
+
+        let results = harness.run_scenario(&scenario).await.unwrap();
+        assert!(!results.passed());
+
+        let shutdown = &results.invariant_results[0];
+        assert!(shutdown.evm_passed && shutdown.cosmos_passed);
+        assert_eq!(
+            shutdown.divergences,
+            vec![Divergence {
+                field: DivergenceField::AnsState,
+                key: None,
+                evm: "SHUTDOWN".to_string(),
+                cosmos: "DANGER".to_string(),
+            }]
+        );
+
+        // Scaling agrees, so the CBF invariant is unaffected by the state divergence
+        assert!(results.invariant_results[1].passed());
+
+        let text = results.text_report();
+        assert!(text.contains("Divergence ans_state: EVM=SHUTDOWN Cosmos=DANGER"));
+        assert!(!text.contains("CbfProjectionSafety"));
+
+        let json = results.json_report();
+        let divergence = &json["invariants"][0]["divergences"][0];
+        assert_eq!(divergence["field"], "ans_state");
+        assert_eq!(divergence["evm"], "SHUTDOWN");
+        assert_eq!(divergence["cosmos"], "DANGER");
+        assert_eq!(json["passed"], false);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_invariant_catches_unrevoked_token() {
+        let stub = |valid_tokens: Vec<String>, scaling_factor| StubClient {
+            state: ANSState::SHUTDOWN,
+            scaling_factor,
+            valid_tokens,
+            events: vec![],
+        };
+        let scenario = scenarios::shutdown_revokes_tokens();
+
+        // Revocation never happened: the issued token is still valid on both chains
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec!["1".to_string()], 0)),
+            Box::new(stub(vec!["1".to_string()], 0)),
+        );
+        let results = harness.run_scenario(&scenario).await.unwrap();
+        assert!(!results.passed());
+        let shutdown = &results.invariant_results[0];
+        assert!(!shutdown.evm_passed && !shutdown.cosmos_passed);
+        let err = shutdown.evm_error.as_ref().unwrap().to_string();
+        assert_eq!(err, "SHUTDOWN with valid non-escape tokens: token 1 of executor 1");
+
+        // Revoked on SHUTDOWN
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec![], 0)),
+            Box::new(stub(vec![], 0)),
+        );
+        assert!(harness.run_scenario(&scenario).await.unwrap().passed());
+
+        // An allowed guard does not make a token an escape token
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec!["1".to_string()], 5000)),
+            Box::new(stub(vec!["1".to_string()], 5000)),
+        );
+        assert!(!harness.run_scenario(&scenario).await.unwrap().passed());
+
+        // Tokens issued for escape intents may stay valid through SHUTDOWN
+        let mut escape_scenario = scenario.clone();
+        for action in &mut escape_scenario.setup_actions {
+            if let TestAction::IssueCapability { intent, .. } = action {
+                intent.isEscape = true;
+            }
+        }
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec!["1".to_string()], 0)),
+            Box::new(stub(vec!["1".to_string()], 0)),
+        );
+        assert!(harness.run_scenario(&escape_scenario).await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_token_set_compared_by_issued_intent() {
+        let stub = |valid_tokens: Vec<String>| StubClient {
+            state: ANSState::SHUTDOWN,
+            scaling_factor: 0,
+            valid_tokens,
+            events: vec![],
+        };
+        let scenario = scenarios::shutdown_revokes_tokens();
+
+        // Revoked on Cosmos only
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec!["1".to_string()])),
+            Box::new(stub(vec![])),
+        );
+        let results = harness.run_scenario(&scenario).await.unwrap();
+        let token_set: Vec<_> = results.invariant_results[0]
+            .divergences
+            .iter()
+            .filter(|d| d.field == DivergenceField::TokenSet)
+            .collect();
+        assert_eq!(token_set.len(), 1);
+        assert_eq!(token_set[0].evm, format!("[1:0x{}]", "01".repeat(32)));
+        assert_eq!(token_set[0].cosmos, "[]");
+    }
+
+    fn tone_event(chain_type: ChainType, log_index: u64, data: &[(&str, &str)]) -> Event {
+        Event {
+            chain_type,
+            contract_address: "ans".to_string(),
+            event_name: "VagalToneUpdated".to_string(),
+            topics: vec![],
+            data: data
+                .iter()
+                .map(|(key, value)| (key.to_string(), serde_json::json!(value)))
+                .collect::<HashMap<_, _>>(),
+            block_number: 1,
+            transaction_hash: "0x1".to_string(),
+            log_index,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tone_update_events_compared_across_encodings() {
+        let stub = |events: Vec<Event>| StubClient {
+            state: ANSState::DANGER,
+            scaling_factor: 5000,
+            valid_tokens: vec![],
+            events,
+        };
+        // EVM reports indexed values as decoded topics, CosmWasm as attributes
+        // named in snake_case with state names; reported out of order
+        let evm = vec![
+            tone_event(ChainType::EVM, 1, &[("tone", "0xfa0"), ("state", "1"), ("updatedAt", "7")]),
+            tone_event(ChainType::EVM, 0, &[("tone", "8000"), ("state", "0"), ("updatedAt", "5")]),
+        ];
+        let cosmos = |second_tone: &str| {
+            vec![
+                tone_event(
+                    ChainType::Cosmos,
+                    0,
+                    &[("tone", "8000"), ("state", "SAFE"), ("updated_at", "9"), ("action", "update_tone")],
+                ),
+                tone_event(
+                    ChainType::Cosmos,
+                    1,
+                    &[("tone", second_tone), ("state", "DANGER"), ("updated_at", "11")],
+                ),
+            ]
+        };
+        let scenario = scenarios::tone_update_event_equivalence();
+
+        let harness =
+            GoldenTestHarness::with_clients(Box::new(stub(evm.clone())), Box::new(stub(cosmos("4000"))));
+        let results = harness.run_scenario(&scenario).await.unwrap();
+        assert!(results.passed(), "{}", results.text_report());
+
+        let harness =
+            GoldenTestHarness::with_clients(Box::new(stub(evm)), Box::new(stub(cosmos("4500"))));
+        let results = harness.run_scenario(&scenario).await.unwrap();
+        assert!(!results.passed());
+        assert_eq!(
+            results.invariant_results[0].divergences,
+            vec![Divergence {
+                field: DivergenceField::Event,
+                key: Some("VagalToneUpdated[1].tone".to_string()),
+                evm: "4000".to_string(),
+                cosmos: "4500".to_string(),
+            }]
+        );
+        assert!(results
+            .text_report()
+            .contains("Divergence event VagalToneUpdated[1].tone: EVM=4000 Cosmos=4500"));
+    }
+
+    #[tokio::test]
+    async fn test_yaml_scenario_runs_against_stub_clients() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/shutdown_revokes_tokens.yaml");
+        let scenario = TestScenario::from_yaml(path).unwrap();
+        assert_eq!(scenario.name, "Shutdown Revokes Tokens (YAML)");
+        assert_eq!(scenario.setup_actions.len(), 3);
+        match &scenario.setup_actions[1] {
+            TestAction::IssueCapability { intent, scaled_limits_hash, expires_at } => {
+                assert_eq!(
+                    IssuedToken::from_intent(intent).unwrap(),
+                    IssuedToken { executor_id: 1, action_id: [1u8; 32], is_escape: false }
+                );
+                assert_eq!(*scaled_limits_hash, [0xab; 32]);
+                assert_eq!(*expires_at, u64::MAX);
+            }
+            other => panic!("expected IssueCapability, got {:?}", other),
+        }
+        assert!(matches!(
+            &scenario.setup_actions[2],
+            TestAction::UpdateTone { vti: 1000, state: ANSState::SHUTDOWN }
+        ));
+        assert!(matches!(
+            &scenario.invariant_checks[..],
+            [InvariantCheck::ShutdownNoValidTokens, InvariantCheck::EventEquivalence { event_name }]
+                if event_name == "VagalToneUpdated"
+        ));
+
+        let stub = |valid_tokens: Vec<String>| StubClient {
+            state: ANSState::SHUTDOWN,
+            scaling_factor: 0,
+            valid_tokens,
+            events: vec![],
+        };
+        let harness = GoldenTestHarness::with_clients(Box::new(stub(vec![])), Box::new(stub(vec![])));
+        assert!(harness.run_scenario(&scenario).await.unwrap().passed());
+
+        let harness = GoldenTestHarness::with_clients(
+            Box::new(stub(vec!["1".to_string()])),
+            Box::new(stub(vec!["1".to_string()])),
+        );
+        assert!(!harness.run_scenario(&scenario).await.unwrap().passed());
+    }
+
+    #[test]
+    fn test_yaml_scenario_rejects_unknown_state() {
+        let yaml = "name: Bad\nsetup_actions:\n  - action: update_tone\n    vti: 5000\n    state: PANIC\ninvariant_checks: []\n";
+        let err = serde_yaml::from_str::<TestScenario>(yaml).unwrap_err();
+        assert!(err.to_string().contains("unknown ANS state: PANIC"));
+    }
 }
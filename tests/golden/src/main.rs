@@ -5,10 +5,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use vagus_chain::{ChainConfig, ChainType};
 
-mod lib;
-use lib::{GoldenTestHarness, scenarios};
+use vagus_golden_tests::{GoldenTestHarness, TestScenario, scenarios};
 
 #[derive(Parser)]
 #[command(name = "golden-tests")]
@@ -31,17 +31,31 @@ enum Commands {
         cosmos_rpc: String,
 
         /// Private key for transactions
-        #[arg(long, env = "PRIVATE_KEY")]
+        #[arg(long, env = "PRIVATE_KEY", hide_env_values = true)]
         private_key: Option<String>,
 
         /// Contract addresses (format: chain=contract=address)
         #[arg(long)]
         contracts: Vec<String>,
+
+        /// Report format
+        #[arg(long, value_enum, default_value = "text")]
+        format: ReportFormat,
+
+        /// YAML scenario to run instead of the built-in ones (repeatable)
+        #[arg(long)]
+        scenario_file: Vec<PathBuf>,
     },
     /// List available test scenarios
     List,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
@@ -49,11 +63,12 @@ async fn main() -> Result<()> {
     let args = Args::parse();
 
     match args.command {
-        Commands::Run { evm_rpc, cosmos_rpc, private_key, contracts } => {
-            run_tests(evm_rpc, cosmos_rpc, private_key, contracts).await
+        Commands::Run { evm_rpc, cosmos_rpc, private_key, contracts, format, scenario_file } => {
+            run_tests(evm_rpc, cosmos_rpc, private_key, contracts, format, scenario_file).await
         }
         Commands::List => {
-            list_scenarios()
+            list_scenarios();
+            Ok(())
         }
     }
 }
@@ -63,9 +78,11 @@ async fn run_tests(
     cosmos_rpc: String,
     private_key: Option<String>,
     contract_specs: Vec<String>,
+    format: ReportFormat,
+    scenario_files: Vec<PathBuf>,
 ) -> Result<()> {
-    println!("🧪 Starting Vagus Golden Test Suite");
-    println!("===================================");
+    eprintln!("🧪 Starting Vagus Golden Test Suite");
+    eprintln!("===================================");
 
     // Parse contract addresses
     let mut contract_addresses = HashMap::new();
@@ -99,6 +116,13 @@ async fn run_tests(
         rpc_url: evm_rpc,
         contract_addresses: evm_contracts,
         private_key: Some(private_key.clone()),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        gas_limit_multiplier: None,
+        ws_url: None,
+        fee_denom: None,
+        gas_price: None,
+        gas_limit: None,
     };
 
     // Create Cosmos config
@@ -113,56 +137,74 @@ async fn run_tests(
         rpc_url: cosmos_rpc,
         contract_addresses: cosmos_contracts,
         private_key: Some(private_key),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        gas_limit_multiplier: None,
+        ws_url: None,
+        fee_denom: None,
+        gas_price: None,
+        gas_limit: None,
     };
 
     // Create test harness
     let harness = GoldenTestHarness::new(evm_config, cosmos_config).await?;
 
     // Run test scenarios
-    let test_scenarios = vec![
-        scenarios::basic_state_transitions(),
-        scenarios::reflex_arc_triggering(),
-    ];
+    let test_scenarios = if scenario_files.is_empty() {
+        vec![
+            scenarios::basic_state_transitions(),
+            scenarios::reflex_arc_triggering(),
+            scenarios::shutdown_revokes_tokens(),
+            scenarios::tone_update_event_equivalence(),
+        ]
+    } else {
+        scenario_files
+            .iter()
+            .map(TestScenario::from_yaml)
+            .collect::<Result<Vec<_>>>()?
+    };
 
     let mut all_passed = true;
+    let mut json_reports = Vec::new();
     for scenario in test_scenarios {
-        println!("\n🎯 Running scenario: {}", scenario.name);
-        println!("   {}", scenario.description);
+        eprintln!("\n🎯 Running scenario: {}", scenario.name);
+        eprintln!("   {}", scenario.description);
 
         match harness.run_scenario(&scenario).await {
             Ok(results) => {
                 if results.passed() {
-                    println!("   ✅ PASSED");
+                    eprintln!("   ✅ PASSED");
                 } else {
-                    println!("   ❌ FAILED");
+                    eprintln!("   ❌ FAILED");
                     all_passed = false;
-
-                    for result in &results.invariant_results {
-                        if !result.evm_passed || !result.cosmos_passed {
-                            println!("      Invariant: {:?}", result.invariant);
-                            if !result.evm_passed {
-                                println!("        EVM: ❌ {:?}", result.evm_error);
-                            }
-                            if !result.cosmos_passed {
-                                println!("        Cosmos: ❌ {:?}", result.cosmos_error);
-                            }
-                        }
+                    if format == ReportFormat::Text {
+                        print!("{}", results.text_report());
                     }
                 }
+                json_reports.push(results.json_report());
             }
             Err(e) => {
-                println!("   ❌ ERROR: {}", e);
+                eprintln!("   ❌ ERROR: {}", e);
                 all_passed = false;
+                json_reports.push(serde_json::json!({
+                    "scenario": scenario.name,
+                    "passed": false,
+                    "error": e.to_string(),
+                }));
             }
         }
     }
 
-    println!("\n===================================");
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&json_reports)?);
+    }
+
+    eprintln!("\n===================================");
     if all_passed {
-        println!("🎉 All golden tests PASSED!");
+        eprintln!("🎉 All golden tests PASSED!");
         std::process::exit(0);
     } else {
-        println!("💥 Some golden tests FAILED!");
+        eprintln!("💥 Some golden tests FAILED!");
         std::process::exit(1);
     }
 }
@@ -171,9 +213,11 @@ fn list_scenarios() {
     println!("📋 Available Test Scenarios:");
     println!("============================");
 
-    let scenarios = vec![
+    let scenarios = [
         scenarios::basic_state_transitions(),
         scenarios::reflex_arc_triggering(),
+        scenarios::shutdown_revokes_tokens(),
+        scenarios::tone_update_event_equivalence(),
     ];
 
     for (i, scenario) in scenarios.iter().enumerate() {
@@ -0,0 +1,104 @@
+//! Differential testing of the brake's scaled-limit math
+//!
+//! `value * factor / 10000` is computed by both the CosmWasm and the Solidity
+//! brake; any rounding difference would let one chain over-authorize.
+
+use cosmwasm_std::Binary;
+use ethers::types::U256;
+use proptest::prelude::*;
+use vagal_brake::validate_scaled_limits;
+use vagus_spec::{ScalingFactor, MAX_DURATION_MS, MAX_ENERGY_J};
+
+/// Brakeable limits scaled by one chain, and whether that chain accepts them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaledLimits {
+    pub duration_ms: u64,
+    pub energy_j: u64,
+    pub allowed: bool,
+}
+
+/// Scaled limits as the CosmWasm brake computes and validates them
+pub fn cosmwasm_scaled_limits(max_duration_ms: u32, max_energy_j: u32, factor: u64) -> ScaledLimits {
+    let factor = ScalingFactor::new(factor).expect("factor within 0-10000");
+    ScaledLimits {
+        duration_ms: factor.apply(max_duration_ms.into()),
+        energy_j: factor.apply(max_energy_j.into()),
+        allowed: validate_scaled_limits(
+            &Binary::default(),
+            max_duration_ms.into(),
+            max_energy_j.into(),
+            factor,
+        )
+        .is_ok(),
+    }
+}
+
+/// Scaled limits as `VagalBrake._scaleAndValidateIntent` computes them, in
+/// `uint256` arithmetic on the `uint32` intent fields
+pub fn evm_scaled_limits(max_duration_ms: u32, max_energy_j: u32, factor: u64) -> ScaledLimits {
+    let scale = |value: u32| (U256::from(value) * U256::from(factor)) / U256::from(10000u64);
+    let (duration_ms, energy_j) = (scale(max_duration_ms), scale(max_energy_j));
+    ScaledLimits {
+        duration_ms: duration_ms.as_u64(),
+        energy_j: energy_j.as_u64(),
+        allowed: duration_ms <= U256::from(MAX_DURATION_MS) && energy_j <= U256::from(MAX_ENERGY_J),
+    }
+}
+
+/// `(maxDurationMs, maxEnergyJ, scalingFactor)` triples, half of them right
+/// around the limits where truncation decides acceptance
+pub fn brake_scaling_strategy() -> impl Strategy<Value = (u32, u32, u64)> {
+    prop_oneof![
+        (any::<u32>(), any::<u32>(), 0..=10000u64),
+        (1..=10000u64, -2i64..=2, -2i64..=2).prop_map(|(factor, duration_offset, energy_offset)| {
+            (
+                limit_edge(MAX_DURATION_MS, factor, duration_offset),
+                limit_edge(MAX_ENERGY_J, factor, energy_offset),
+                factor,
+            )
+        }),
+    ]
+}
+
+/// Smallest limit whose scaled value exceeds `max` under `factor`, shifted by `offset`
+fn limit_edge(max: u64, factor: u64, offset: i64) -> u32 {
+    let edge = ((max + 1) * 10000).div_ceil(factor) as i64;
+    (edge + offset).clamp(0, u32::MAX as i64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_brake_scaling_matches_across_chains(
+            (max_duration_ms, max_energy_j, factor) in brake_scaling_strategy()
+        ) {
+            let cosmwasm = cosmwasm_scaled_limits(max_duration_ms, max_energy_j, factor);
+            let evm = evm_scaled_limits(max_duration_ms, max_energy_j, factor);
+            prop_assert_eq!(
+                &cosmwasm,
+                &evm,
+                "chains diverge for maxDurationMs={} maxEnergyJ={} scalingFactor={}",
+                max_duration_ms,
+                max_energy_j,
+                factor
+            );
+        }
+    }
+
+    #[test]
+    fn test_brake_scaling_truncates_at_the_limit() {
+        // 50001 * 0.6 = 30000.6 truncates to the limit; 50002 * 0.6 exceeds it
+        assert_eq!(limit_edge(MAX_DURATION_MS, 6000, 0), 50002);
+        let at_limit = cosmwasm_scaled_limits(50001, 0, 6000);
+        assert_eq!(at_limit.duration_ms, MAX_DURATION_MS);
+        assert!(at_limit.allowed);
+        assert_eq!(at_limit, evm_scaled_limits(50001, 0, 6000));
+
+        let over_limit = cosmwasm_scaled_limits(50002, 0, 6000);
+        assert!(!over_limit.allowed);
+        assert_eq!(over_limit, evm_scaled_limits(50002, 0, 6000));
+    }
+}
@@ -1,22 +1,80 @@
+use std::collections::BTreeSet;
+
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, SubMsg, SubMsgResult, WasmMsg,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use cw_utils::nonpayable;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
-use vagus_spec::{
-    AfferentEvidencePacket, CapabilityRevocationReason, VagusError,
-};
+use ::vagus_spec::{AfferentEvidencePacket, CapabilityRevocationReason, VagusError};
+
+// Reply id of the OnAEP notification to the reflex arc
+const REFLEX_NOTIFY_REPLY_ID: u64 = 1;
+
+// Page size of VerifyHistory, in records
+const DEFAULT_VERIFY_LIMIT: u32 = 100;
+const MAX_VERIFY_LIMIT: u32 = 500;
 
 // State
-pub const LATEST_AEP: Item<AfferentEvidencePacket> = Item::new("latest_aep");
+pub const LATEST_AEP: Map<u64, AfferentEvidencePacket> = Map::new("latest_aep_by_executor");
+
+// Address allowed to manage the attestor set; the instantiator by default
+pub const ADMIN: Item<String> = Item::new("admin");
+
+// Reflex arc notified of every posted AEP; unset = the inbox works standalone
+pub const REFLEX_ARC: Item<String> = Item::new("reflex_arc");
 
 // Authorized attestors (oracle/gateway addresses)
 pub const AUTHORIZED_ATTESTORS: Item<Vec<String>> = Item::new("authorized_attestors");
 
+// Minimum number of distinct authorized attestors required per AEP (M-of-N)
+pub const MIN_ATTESTATIONS: Item<u32> = Item::new("min_attestations");
+
+// Seconds a newly added attestor must wait before its posts count; unset = none
+pub const ATTESTOR_ACTIVATION_DELAY: Item<u64> = Item::new("attestor_activation_delay");
+
+// Block time at which each attestor was added after instantiation
+pub const ATTESTOR_ADDED_AT: Map<&str, u64> = Map::new("attestor_added_at");
+
+// secp256k1 public keys registered by attestors for co-signing AEPs
+pub const ATTESTOR_PUBKEYS: Map<&str, Binary> = Map::new("attestor_pubkeys");
+
+// Per-executor AEP hash chain: (executor_id, sequence) -> record
+pub const AEP_HISTORY: Map<(u64, u64), AepRecord> = Map::new("aep_history");
+pub const AEP_CHAIN_HEAD: Map<u64, ChainHead> = Map::new("aep_chain_head");
+
+/// Hash-chained AEP entry; `hash` commits to `prev_hash` and the AEP contents
+#[cosmwasm_schema::cw_serde]
+pub struct AepRecord {
+    pub aep: AfferentEvidencePacket,
+    pub prev_hash: Binary,
+    pub hash: Binary,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct ChainHead {
+    pub length: u64,
+    pub head_hash: Binary,
+}
+
 #[cosmwasm_schema::cw_serde]
 pub struct InstantiateMsg {
     pub authorized_attestors: Vec<String>,
+    /// Defaults to 1 (any single authorized attestor)
+    pub min_attestations: Option<u32>,
+    /// Seconds before attestors added later may post or co-sign, giving
+    /// governance time to revert a mistaken authorization
+    pub activation_delay: Option<u64>,
+}
+
+/// Co-signature from an authorized attestor over the AEP digest
+#[cosmwasm_schema::cw_serde]
+pub struct Attestation {
+    pub attestor: String,
+    pub signature: Binary, // 64-byte secp256k1 (r || s)
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -28,9 +86,24 @@ pub enum ExecuteMsg {
         metrics_hash_sha256: Binary, // 32 bytes
         metrics_hash_keccak: Binary, // 32 bytes
         attestation: Binary,         // Optional attestation data
+        #[serde(default)]
+        co_attestations: Vec<Attestation>,
+        /// Canonical CBOR metrics payload; both metrics hashes must be its
+        /// SHA-256 and Keccak-256
+        metrics_cbor: Binary,
     },
     SetAuthorizedAttestors {
         attestors: Vec<String>,
+        min_attestations: Option<u32>,
+    },
+    RegisterAttestorKey {
+        pubkey: Binary, // 33 or 65 byte secp256k1 public key
+    },
+    UpdateAdmin {
+        admin: String,
+    },
+    SetReflexArc {
+        reflex_arc: Option<String>,
     },
 }
 
@@ -38,6 +111,13 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     LatestAEP { executor_id: u64 },
     IsAuthorized { attestor: String },
+    AttestationPolicy {},
+    VerifyHistory {
+        executor_id: u64,
+        /// `next` of the previous page; unset = from the first record
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -50,13 +130,33 @@ pub struct IsAuthorizedResponse {
     pub authorized: bool,
 }
 
+#[cosmwasm_schema::cw_serde]
+pub struct AttestationPolicyResponse {
+    pub min_attestations: u32,
+    pub attestor_count: u32,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct VerifyHistoryResponse {
+    /// No break found so far; final once `next` is unset
+    pub valid: bool,
+    pub length: u64,
+    /// Sequence number of the first record that breaks the chain
+    pub broken_at: Option<u64>,
+    /// Last record verified by this page, to resume from as `start_after`;
+    /// unset once the whole chain up to the head has been checked
+    pub next: Option<u64>,
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, VagusError> {
+    ADMIN.save(deps.storage, &info.sender.to_string())?;
+
     // Validate addresses
     let mut validated_attestors = Vec::new();
     for attestor in msg.authorized_attestors {
@@ -64,11 +164,19 @@ pub fn instantiate(
         validated_attestors.push(attestor);
     }
 
+    let min_attestations = msg.min_attestations.unwrap_or(1);
+    validate_min_attestations(min_attestations, &validated_attestors)?;
+
     AUTHORIZED_ATTESTORS.save(deps.storage, &validated_attestors)?;
+    MIN_ATTESTATIONS.save(deps.storage, &min_attestations)?;
+    if let Some(delay) = msg.activation_delay {
+        ATTESTOR_ACTIVATION_DELAY.save(deps.storage, &delay)?;
+    }
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("attestor_count", validated_attestors.len().to_string()))
+        .add_attribute("attestor_count", validated_attestors.len().to_string())
+        .add_attribute("min_attestations", min_attestations.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -86,6 +194,8 @@ pub fn execute(
             metrics_hash_sha256,
             metrics_hash_keccak,
             attestation,
+            co_attestations,
+            metrics_cbor,
         } => execute_post_aep(
             deps,
             env,
@@ -96,13 +206,22 @@ pub fn execute(
             metrics_hash_sha256,
             metrics_hash_keccak,
             attestation,
+            co_attestations,
+            metrics_cbor,
         ),
-        ExecuteMsg::SetAuthorizedAttestors { attestors } => {
-            execute_set_authorized_attestors(deps, info, attestors)
+        ExecuteMsg::SetAuthorizedAttestors {
+            attestors,
+            min_attestations,
+        } => execute_set_authorized_attestors(deps, env, info, attestors, min_attestations),
+        ExecuteMsg::RegisterAttestorKey { pubkey } => {
+            execute_register_attestor_key(deps, info, pubkey)
         }
+        ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, info, admin),
+        ExecuteMsg::SetReflexArc { reflex_arc } => execute_set_reflex_arc(deps, info, reflex_arc),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_post_aep(
     deps: DepsMut,
     env: Env,
@@ -113,12 +232,15 @@ pub fn execute_post_aep(
     metrics_hash_sha256: Binary,
     metrics_hash_keccak: Binary,
     _attestation: Binary,
+    co_attestations: Vec<Attestation>,
+    metrics_cbor: Binary,
 ) -> Result<Response, VagusError> {
     // Check authorization
     let attestors = AUTHORIZED_ATTESTORS.load(deps.storage)?;
     if !attestors.contains(&info.sender.to_string()) {
         return Err(VagusError::UnauthorizedAttestor);
     }
+    ensure_attestor_active(deps.as_ref(), &env, info.sender.as_str())?;
 
     // Validate hash lengths (32 bytes)
     if state_root_sha256.len() != 32
@@ -128,6 +250,43 @@ pub fn execute_post_aep(
     {
         return Err(VagusError::InvalidInput);
     }
+    verify_metrics_cbor(&metrics_cbor, &metrics_hash_sha256, &metrics_hash_keccak)?;
+
+    // The sender counts as one attestation; co-signers must be authorized,
+    // distinct, and have a registered key that verifies the AEP digest
+    let digest = aep_digest(
+        &env.block.chain_id,
+        env.contract.address.as_str(),
+        executor_id,
+        &state_root_sha256,
+        &state_root_keccak,
+        &metrics_hash_sha256,
+        &metrics_hash_keccak,
+    );
+    let mut signers = BTreeSet::new();
+    signers.insert(info.sender.to_string());
+    for co in co_attestations {
+        if !attestors.contains(&co.attestor) {
+            return Err(VagusError::UnauthorizedAttestor);
+        }
+        ensure_attestor_active(deps.as_ref(), &env, &co.attestor)?;
+        let pubkey = ATTESTOR_PUBKEYS
+            .may_load(deps.storage, &co.attestor)?
+            .ok_or(VagusError::UnauthorizedAttestor)?;
+        let verified = deps
+            .api
+            .secp256k1_verify(&digest, &co.signature, &pubkey)
+            .map_err(|_| VagusError::InvalidEvidenceFormat)?;
+        if !verified {
+            return Err(VagusError::InvalidEvidenceFormat);
+        }
+        signers.insert(co.attestor);
+    }
+
+    let min_attestations = MIN_ATTESTATIONS.may_load(deps.storage)?.unwrap_or(1);
+    if (signers.len() as u32) < min_attestations {
+        return Err(VagusError::InsufficientAttestations);
+    }
 
     let aep = AfferentEvidencePacket {
         executorId: executor_id.into(),
@@ -138,26 +297,167 @@ pub fn execute_post_aep(
         timestamp: env.block.time.seconds().into(),
     };
 
-    // Store the latest AEP (simplified - in production would store history)
-    LATEST_AEP.save(deps.storage, &aep)?;
+    // Latest AEP per executor; the full history lives in the hash chain
+    LATEST_AEP.save(deps.storage, executor_id, &aep)?;
+
+    // Append to the executor's hash chain
+    let head = AEP_CHAIN_HEAD
+        .may_load(deps.storage, executor_id)?
+        .unwrap_or_else(genesis_head);
+    let hash = Binary::from(aep_record_hash(&head.head_hash, &aep));
+    AEP_HISTORY.save(
+        deps.storage,
+        (executor_id, head.length),
+        &AepRecord {
+            aep,
+            prev_hash: head.head_hash,
+            hash: hash.clone(),
+        },
+    )?;
+    AEP_CHAIN_HEAD.save(
+        deps.storage,
+        executor_id,
+        &ChainHead {
+            length: head.length + 1,
+            head_hash: hash.clone(),
+        },
+    )?;
+
+    // Hand the evidence to the reflex arc, which decides whether to fire; a
+    // failing reflex must not drop the evidence, so its error is only reported
+    let mut messages = Vec::new();
+    if let Some(reflex_arc) = REFLEX_ARC.may_load(deps.storage)? {
+        messages.push(SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: reflex_arc,
+                msg: to_json_binary(&vagus_spec::reflex_arc::ExecuteMsg::OnAEP {
+                    executor_id,
+                    action_id: None,
+                    metrics_hash_sha256: metrics_hash_sha256.clone(),
+                    metrics_hash_keccak: metrics_hash_keccak.clone(),
+                })?,
+                funds: vec![],
+            },
+            REFLEX_NOTIFY_REPLY_ID,
+        ));
+    }
 
     Ok(Response::new()
+        .add_submessages(messages)
         .add_attribute("action", "post_aep")
         .add_attribute("executor_id", executor_id.to_string())
         .add_attribute("state_root_sha256", hex::encode(&state_root_sha256))
         .add_attribute("state_root_keccak", hex::encode(&state_root_keccak))
         .add_attribute("metrics_hash_sha256", hex::encode(&metrics_hash_sha256))
         .add_attribute("metrics_hash_keccak", hex::encode(&metrics_hash_keccak))
-        .add_attribute("timestamp", env.block.time.seconds().to_string()))
+        .add_attribute("timestamp", env.block.time.seconds().to_string())
+        .add_attribute("attestation_count", signers.len().to_string())
+        .add_attribute("aep_hash", hex::encode(&hash)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, VagusError> {
+    match (msg.id, msg.result) {
+        (REFLEX_NOTIFY_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "reflex_notification_failed")
+            .add_attribute("error", err)),
+        _ => Err(VagusError::InvalidInput),
+    }
+}
+
+// Both stacks hash the same CBOR metrics; a pair that disagrees means the
+// hashes were not computed from one payload
+fn verify_metrics_cbor(
+    cbor: &[u8],
+    metrics_hash_sha256: &Binary,
+    metrics_hash_keccak: &Binary,
+) -> Result<(), VagusError> {
+    if Sha256::digest(cbor).as_slice() != metrics_hash_sha256.as_slice()
+        || Keccak256::digest(cbor).as_slice() != metrics_hash_keccak.as_slice()
+    {
+        return Err(VagusError::CBORHashMismatch);
+    }
+    Ok(())
+}
+
+// Attestors added after instantiation only count once their delay has passed
+fn ensure_attestor_active(deps: Deps, env: &Env, attestor: &str) -> Result<(), VagusError> {
+    let delay = ATTESTOR_ACTIVATION_DELAY
+        .may_load(deps.storage)?
+        .unwrap_or(0);
+    if let Some(added_at) = ATTESTOR_ADDED_AT.may_load(deps.storage, attestor)? {
+        if env.block.time.seconds() < added_at + delay {
+            return Err(VagusError::UnauthorizedAttestor);
+        }
+    }
+    Ok(())
+}
+
+fn genesis_head() -> ChainHead {
+    ChainHead {
+        length: 0,
+        head_hash: Binary::from([0u8; 32]),
+    }
+}
+
+/// SHA-256 over the previous record hash followed by every AEP field
+pub fn aep_record_hash(prev_hash: &[u8], aep: &AfferentEvidencePacket) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash);
+    hasher.update(aep.executorId.to_be_bytes());
+    hasher.update(aep.stateRootSha256.as_slice());
+    hasher.update(aep.stateRootKeccak.as_slice());
+    hasher.update(aep.metricsHashSha256.as_slice());
+    hasher.update(aep.metricsHashKeccak.as_slice());
+    hasher.update(aep.timestamp.to_be_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// SHA-256 over the chain id and the inbox address, each prefixed with its
+/// big-endian u32 length, then executor_id (big-endian) and the four 32-byte
+/// hashes. Co-attestors sign this digest off-chain; binding it to the
+/// deployment keeps a signature from being replayed to another inbox.
+pub fn aep_digest(
+    chain_id: &str,
+    contract: &str,
+    executor_id: u64,
+    state_root_sha256: &[u8],
+    state_root_keccak: &[u8],
+    metrics_hash_sha256: &[u8],
+    metrics_hash_keccak: &[u8],
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for domain in [chain_id, contract] {
+        hasher.update((domain.len() as u32).to_be_bytes());
+        hasher.update(domain.as_bytes());
+    }
+    hasher.update(executor_id.to_be_bytes());
+    hasher.update(state_root_sha256);
+    hasher.update(state_root_keccak);
+    hasher.update(metrics_hash_sha256);
+    hasher.update(metrics_hash_keccak);
+    hasher.finalize().to_vec()
+}
+
+fn validate_min_attestations(
+    min_attestations: u32,
+    attestors: &[String],
+) -> Result<(), VagusError> {
+    let distinct: BTreeSet<&String> = attestors.iter().collect();
+    if min_attestations == 0 || min_attestations as usize > distinct.len() {
+        return Err(VagusError::InvalidInput);
+    }
+    Ok(())
 }
 
 pub fn execute_set_authorized_attestors(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     attestors: Vec<String>,
+    min_attestations: Option<u32>,
 ) -> Result<Response, VagusError> {
-    // Only contract admin can change attestors (simplified)
-    // In production, this would check for admin privileges
+    ensure_admin(&deps, &info)?;
 
     let mut validated_attestors = Vec::new();
     for attestor in attestors {
@@ -165,11 +465,100 @@ pub fn execute_set_authorized_attestors(
         validated_attestors.push(attestor);
     }
 
+    let min_attestations = match min_attestations {
+        Some(min) => min,
+        None => MIN_ATTESTATIONS.may_load(deps.storage)?.unwrap_or(1),
+    };
+    validate_min_attestations(min_attestations, &validated_attestors)?;
+
+    // Start the activation clock for new attestors; removed ones start over if re-added
+    let previous = AUTHORIZED_ATTESTORS.load(deps.storage)?;
+    for attestor in &previous {
+        if !validated_attestors.contains(attestor) {
+            ATTESTOR_ADDED_AT.remove(deps.storage, attestor);
+        }
+    }
+    for attestor in &validated_attestors {
+        if !previous.contains(attestor) {
+            ATTESTOR_ADDED_AT.save(deps.storage, attestor, &env.block.time.seconds())?;
+        }
+    }
+
     AUTHORIZED_ATTESTORS.save(deps.storage, &validated_attestors)?;
+    MIN_ATTESTATIONS.save(deps.storage, &min_attestations)?;
 
     Ok(Response::new()
         .add_attribute("action", "set_authorized_attestors")
-        .add_attribute("attestor_count", validated_attestors.len().to_string()))
+        .add_attribute("attestor_count", validated_attestors.len().to_string())
+        .add_attribute("min_attestations", min_attestations.to_string()))
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: String,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+
+    let admin = deps.api.addr_validate(&admin)?.to_string();
+    ADMIN.save(deps.storage, &admin)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_admin")
+        .add_attribute("admin", admin))
+}
+
+pub fn execute_set_reflex_arc(
+    deps: DepsMut,
+    info: MessageInfo,
+    reflex_arc: Option<String>,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+
+    match &reflex_arc {
+        Some(reflex_arc) => {
+            deps.api.addr_validate(reflex_arc)?;
+            REFLEX_ARC.save(deps.storage, reflex_arc)?;
+        }
+        None => REFLEX_ARC.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_reflex_arc")
+        .add_attribute(
+            "reflex_arc",
+            reflex_arc.unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+fn ensure_admin(deps: &DepsMut, info: &MessageInfo) -> Result<(), VagusError> {
+    if info.sender.to_string() != ADMIN.load(deps.storage)? {
+        return Err(VagusError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn execute_register_attestor_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, VagusError> {
+    // Keys are bound to the sending account, so only attestors can register their own
+    let attestors = AUTHORIZED_ATTESTORS.load(deps.storage)?;
+    let attestor = info.sender.to_string();
+    if !attestors.contains(&attestor) {
+        return Err(VagusError::UnauthorizedAttestor);
+    }
+
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(VagusError::InvalidInput);
+    }
+
+    ATTESTOR_PUBKEYS.save(deps.storage, &attestor, &pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_attestor_key")
+        .add_attribute("attestor", attestor))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -181,12 +570,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsAuthorized { attestor } => {
             to_json_binary(&query_is_authorized(deps, attestor)?)
         }
+        QueryMsg::AttestationPolicy {} => to_json_binary(&query_attestation_policy(deps)?),
+        QueryMsg::VerifyHistory {
+            executor_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_verify_history(deps, executor_id, start_after, limit)?),
     }
 }
 
-fn query_latest_aep(deps: Deps, _executor_id: u64) -> StdResult<LatestAEPResponse> {
-    // Simplified - doesn't filter by executor_id, just returns latest
-    let aep = LATEST_AEP.may_load(deps.storage)?;
+fn query_latest_aep(deps: Deps, executor_id: u64) -> StdResult<LatestAEPResponse> {
+    let aep = LATEST_AEP.may_load(deps.storage, executor_id)?;
     Ok(LatestAEPResponse { aep })
 }
 
@@ -195,3 +589,776 @@ fn query_is_authorized(deps: Deps, attestor: String) -> StdResult<IsAuthorizedRe
     let authorized = attestors.contains(&attestor);
     Ok(IsAuthorizedResponse { authorized })
 }
+
+fn query_attestation_policy(deps: Deps) -> StdResult<AttestationPolicyResponse> {
+    let attestors = AUTHORIZED_ATTESTORS.load(deps.storage)?;
+    let min_attestations = MIN_ATTESTATIONS.may_load(deps.storage)?.unwrap_or(1);
+    Ok(AttestationPolicyResponse {
+        min_attestations,
+        attestor_count: attestors.len() as u32,
+    })
+}
+
+fn query_verify_history(
+    deps: Deps,
+    executor_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VerifyHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_VERIFY_LIMIT).min(MAX_VERIFY_LIMIT) as u64;
+    let head = AEP_CHAIN_HEAD
+        .may_load(deps.storage, executor_id)?
+        .unwrap_or_else(genesis_head);
+    let broken = |seq| VerifyHistoryResponse {
+        valid: false,
+        length: head.length,
+        broken_at: Some(seq),
+        next: None,
+    };
+
+    // Resume from the record the previous page ended on
+    let (start, mut expected_prev) = match start_after {
+        None => (0, genesis_head().head_hash),
+        Some(seq) if seq >= head.length => {
+            return Err(StdError::generic_err(format!(
+                "start_after {} is past the chain length {}",
+                seq, head.length
+            )));
+        }
+        Some(seq) => match AEP_HISTORY.may_load(deps.storage, (executor_id, seq))? {
+            Some(record) => (seq + 1, record.hash),
+            None => return Ok(broken(seq)),
+        },
+    };
+    let end = head.length.min(start.saturating_add(limit.max(1)));
+
+    // Recompute each link from the stored contents and the expected predecessor
+    for seq in start..end {
+        let record = AEP_HISTORY.may_load(deps.storage, (executor_id, seq))?;
+        let intact = match record {
+            Some(record) => {
+                let recomputed = aep_record_hash(&expected_prev, &record.aep);
+                let intact =
+                    record.prev_hash == expected_prev && record.hash.as_slice() == recomputed;
+                expected_prev = record.hash;
+                intact
+            }
+            None => false,
+        };
+        if !intact {
+            return Ok(broken(seq));
+        }
+    }
+
+    if end < head.length {
+        return Ok(VerifyHistoryResponse {
+            valid: true,
+            length: head.length,
+            broken_at: None,
+            next: Some(end - 1),
+        });
+    }
+
+    // The head must commit to the last record
+    if expected_prev != head.head_hash {
+        return Ok(broken(head.length));
+    }
+
+    Ok(VerifyHistoryResponse {
+        valid: true,
+        length: head.length,
+        broken_at: None,
+        next: None,
+    })
+}
+
+// Helper modules for cross-contract calls
+pub mod vagus_spec {
+    use super::*;
+
+    pub mod reflex_arc {
+        use super::*;
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum ExecuteMsg {
+            OnAEP {
+                executor_id: u64,
+                action_id: Option<Binary>,
+                metrics_hash_sha256: Binary,
+                metrics_hash_keccak: Binary,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, Addr, Empty, OwnedDeps, Uint256};
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    const ATTESTORS: [&str; 3] = ["attestor1", "attestor2", "attestor3"];
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+    }
+
+    // {"vti": 8000} in canonical CBOR
+    const METRICS_CBOR: [u8; 8] = [0xa1, 0x63, b'v', b't', b'i', 0x19, 0x1f, 0x40];
+
+    fn post_msg(co_attestations: Vec<Attestation>) -> ExecuteMsg {
+        post_msg_for(1, &METRICS_CBOR, co_attestations)
+    }
+
+    fn post_msg_for(
+        executor_id: u64,
+        metrics_cbor: &[u8],
+        co_attestations: Vec<Attestation>,
+    ) -> ExecuteMsg {
+        ExecuteMsg::PostAEP {
+            executor_id,
+            state_root_sha256: Binary::from([1u8; 32]),
+            state_root_keccak: Binary::from([2u8; 32]),
+            metrics_hash_sha256: Binary::from(Sha256::digest(metrics_cbor).as_slice()),
+            metrics_hash_keccak: Binary::from(Keccak256::digest(metrics_cbor).as_slice()),
+            attestation: Binary::default(),
+            co_attestations,
+            metrics_cbor: Binary::from(metrics_cbor),
+        }
+    }
+
+    fn co_sign(attestor: &str, key: &SigningKey) -> Attestation {
+        let env = mock_env();
+        co_sign_for(&env.block.chain_id, env.contract.address.as_str(), attestor, key)
+    }
+
+    // Co-signature of `post_msg`'s AEP for the inbox `contract` on `chain_id`
+    fn co_sign_for(chain_id: &str, contract: &str, attestor: &str, key: &SigningKey) -> Attestation {
+        let mut preimage = Vec::new();
+        for domain in [chain_id, contract] {
+            preimage.extend_from_slice(&(domain.len() as u32).to_be_bytes());
+            preimage.extend_from_slice(domain.as_bytes());
+        }
+        preimage.extend_from_slice(&1u64.to_be_bytes());
+        preimage.extend_from_slice(&[1u8; 32]);
+        preimage.extend_from_slice(&[2u8; 32]);
+        preimage.extend_from_slice(&Sha256::digest(METRICS_CBOR));
+        preimage.extend_from_slice(&Keccak256::digest(METRICS_CBOR));
+        // k256 hashes the preimage with SHA-256, matching aep_digest
+        let signature: Signature = key.sign(&preimage);
+        Attestation {
+            attestor: attestor.to_string(),
+            signature: Binary::from(signature.to_bytes().as_slice()),
+        }
+    }
+
+    fn setup(min_attestations: u32) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            InstantiateMsg {
+                authorized_attestors: ATTESTORS.iter().map(|a| a.to_string()).collect(),
+                min_attestations: Some(min_attestations),
+                activation_delay: None,
+            },
+        )
+        .unwrap();
+
+        for (i, attestor) in ATTESTORS.iter().enumerate() {
+            let pubkey = signing_key(i as u8 + 1)
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(attestor, &[]),
+                ExecuteMsg::RegisterAttestorKey {
+                    pubkey: Binary::from(pubkey),
+                },
+            )
+            .unwrap();
+        }
+        deps
+    }
+
+    #[test]
+    fn test_post_aep_rejected_below_threshold() {
+        let mut deps = setup(2);
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::InsufficientAttestations));
+
+        // Repeating the sender's own attestation does not count twice
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![co_sign("attestor1", &signing_key(1))]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::InsufficientAttestations));
+
+        let latest: LatestAEPResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::LatestAEP { executor_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(latest.aep.is_none());
+    }
+
+    #[test]
+    fn test_post_aep_accepted_with_distinct_attestations() {
+        let mut deps = setup(2);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![co_sign("attestor2", &signing_key(2))]),
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "attestation_count" && a.value == "2"));
+
+        let latest: LatestAEPResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::LatestAEP { executor_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(latest.aep.is_some());
+    }
+
+    #[test]
+    fn test_post_aep_rejects_bad_co_signature() {
+        let mut deps = setup(2);
+
+        // attestor2 entry signed with attestor3's key
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![co_sign("attestor2", &signing_key(3))]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::InvalidEvidenceFormat));
+    }
+
+    #[test]
+    fn test_post_aep_rejects_co_signature_for_other_deployment() {
+        let mut deps = setup(2);
+        let env = mock_env();
+
+        // Signed for another inbox on this chain, and for this inbox's
+        // address on another chain
+        for (chain_id, contract) in [
+            (env.block.chain_id.as_str(), "other_inbox"),
+            ("other-chain", env.contract.address.as_str()),
+        ] {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("attestor1", &[]),
+                post_msg(vec![co_sign_for(chain_id, contract, "attestor2", &signing_key(2))]),
+            )
+            .unwrap_err();
+            assert!(matches!(err, VagusError::InvalidEvidenceFormat));
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![co_sign("attestor2", &signing_key(2))]),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_min_attestations_bounded_by_attestor_set() {
+        let mut deps = mock_dependencies();
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            InstantiateMsg {
+                authorized_attestors: vec!["attestor1".to_string()],
+                min_attestations: Some(2),
+                activation_delay: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::InvalidInput));
+    }
+
+    #[test]
+    fn test_latest_aep_is_tracked_per_executor() {
+        let mut deps = setup(1);
+        for (executor_id, metrics) in [(1u64, b"first"), (2u64, b"other")] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("attestor1", &[]),
+                post_msg_for(executor_id, metrics, vec![]),
+            )
+            .unwrap();
+        }
+
+        let latest = |executor_id: u64| -> LatestAEPResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::LatestAEP { executor_id },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let first = latest(1).aep.unwrap();
+        assert_eq!(first.executorId, Uint256::from(1u64));
+        assert_eq!(first.metricsHashSha256, Binary::from(Sha256::digest(b"first").as_slice()));
+        let second = latest(2).aep.unwrap();
+        assert_eq!(second.executorId, Uint256::from(2u64));
+        assert_eq!(second.metricsHashSha256, Binary::from(Sha256::digest(b"other").as_slice()));
+        assert!(latest(3).aep.is_none());
+    }
+
+    #[test]
+    fn test_only_admin_manages_attestors() {
+        let mut deps = setup(1);
+        let set_attestors = ExecuteMsg::SetAuthorizedAttestors {
+            attestors: vec!["attestor2".to_string(), "attestor3".to_string()],
+            min_attestations: None,
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            set_attestors.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::Unauthorized));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            set_attestors,
+        )
+        .unwrap();
+
+        // The removed attestor can no longer post evidence
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_msg(vec![]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::UnauthorizedAttestor));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor2", &[]),
+            post_msg(vec![]),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_update_admin_hands_over_attestor_management() {
+        let mut deps = setup(1);
+        let update_admin = ExecuteMsg::UpdateAdmin {
+            admin: "dao".to_string(),
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            update_admin.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::Unauthorized));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            update_admin,
+        )
+        .unwrap();
+        assert_eq!(ADMIN.load(&deps.storage).unwrap(), "dao");
+
+        let set_attestors = ExecuteMsg::SetAuthorizedAttestors {
+            attestors: vec!["attestor1".to_string()],
+            min_attestations: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            set_attestors.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::Unauthorized));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao", &[]),
+            set_attestors,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_metrics_hashes_must_match_cbor_payload() {
+        let mut deps = setup(1);
+        let cbor = Binary::from(METRICS_CBOR);
+        let post_with = |sha256: &[u8], keccak: &[u8]| ExecuteMsg::PostAEP {
+            executor_id: 1,
+            state_root_sha256: Binary::from([1u8; 32]),
+            state_root_keccak: Binary::from([2u8; 32]),
+            metrics_hash_sha256: Binary::from(sha256),
+            metrics_hash_keccak: Binary::from(keccak),
+            attestation: Binary::default(),
+            co_attestations: vec![],
+            metrics_cbor: cbor.clone(),
+        };
+        let sha256 = Sha256::digest(cbor.as_slice());
+        let keccak = Keccak256::digest(cbor.as_slice());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_with(&sha256, &keccak),
+        )
+        .unwrap();
+
+        // A keccak over a different payload breaks the pair
+        let other_keccak = Keccak256::digest(b"other metrics");
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("attestor1", &[]),
+            post_with(&sha256, &other_keccak),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::CBORHashMismatch));
+    }
+
+    fn verify_history(deps: Deps) -> VerifyHistoryResponse {
+        verify_page(deps, None, None)
+    }
+
+    fn verify_page(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> VerifyHistoryResponse {
+        let msg = QueryMsg::VerifyHistory {
+            executor_id: 1,
+            start_after,
+            limit,
+        };
+        from_json(query(deps, mock_env(), msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_history_detects_tampering() {
+        let mut deps = setup(1);
+
+        let mut env = mock_env();
+        for _ in 0..3 {
+            env.block.time = env.block.time.plus_seconds(10);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("attestor1", &[]),
+                post_msg(vec![]),
+            )
+            .unwrap();
+        }
+
+        let res = verify_history(deps.as_ref());
+        assert!(res.valid);
+        assert_eq!(res.length, 3);
+
+        // Each record links to its predecessor
+        let first = AEP_HISTORY.load(&deps.storage, (1, 0)).unwrap();
+        let second = AEP_HISTORY.load(&deps.storage, (1, 1)).unwrap();
+        assert_eq!(second.prev_hash, first.hash);
+
+        // Rewrite the middle AEP's metrics without fixing up its hash
+        let mut tampered = second;
+        tampered.aep.metricsHashSha256 = Binary::from([9u8; 32]);
+        AEP_HISTORY
+            .save(&mut deps.storage, (1, 1), &tampered)
+            .unwrap();
+
+        let res = verify_history(deps.as_ref());
+        assert!(!res.valid);
+        assert_eq!(res.broken_at, Some(1));
+
+        // Re-hashing the tampered record still breaks the next link
+        tampered.hash = Binary::from(aep_record_hash(&tampered.prev_hash, &tampered.aep));
+        AEP_HISTORY
+            .save(&mut deps.storage, (1, 1), &tampered)
+            .unwrap();
+
+        let res = verify_history(deps.as_ref());
+        assert!(!res.valid);
+        assert_eq!(res.broken_at, Some(2));
+    }
+
+    #[test]
+    fn test_verify_history_in_pages() {
+        let mut deps = setup(1);
+
+        let mut env = mock_env();
+        for _ in 0..5 {
+            env.block.time = env.block.time.plus_seconds(10);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("attestor1", &[]),
+                post_msg(vec![]),
+            )
+            .unwrap();
+        }
+
+        // Pages of two: 0-1, 2-3, then 4 plus the head
+        let mut cursors = vec![];
+        let mut start_after = None;
+        loop {
+            let res = verify_page(deps.as_ref(), start_after, Some(2));
+            assert!(res.valid);
+            assert_eq!(res.length, 5);
+            match res.next {
+                Some(next) => {
+                    cursors.push(next);
+                    start_after = Some(next);
+                }
+                None => break,
+            }
+        }
+        assert_eq!(cursors, vec![1, 3]);
+
+        // A break is reported by the page that covers it
+        let mut tampered = AEP_HISTORY.load(&deps.storage, (1, 3)).unwrap();
+        tampered.aep.metricsHashSha256 = Binary::from([9u8; 32]);
+        AEP_HISTORY
+            .save(&mut deps.storage, (1, 3), &tampered)
+            .unwrap();
+
+        let first = verify_page(deps.as_ref(), None, Some(2));
+        assert!(first.valid);
+        assert_eq!(first.next, Some(1));
+        let second = verify_page(deps.as_ref(), first.next, Some(2));
+        assert!(!second.valid);
+        assert_eq!(second.broken_at, Some(3));
+        assert_eq!(second.next, None);
+
+        // A cursor past the chain is refused
+        let msg = QueryMsg::VerifyHistory {
+            executor_id: 1,
+            start_after: Some(5),
+            limit: None,
+        };
+        assert!(query(deps.as_ref(), mock_env(), msg).is_err());
+    }
+
+    // Stand-in reflex arc that rejects every notification
+    fn failing_reflex_execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: vagus_spec::reflex_arc::ExecuteMsg,
+    ) -> Result<Response, VagusError> {
+        Err(VagusError::CircuitBreakerOpen)
+    }
+
+    fn failing_reflex_instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> Result<Response, VagusError> {
+        Ok(Response::new())
+    }
+
+    fn failing_reflex_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&Empty {})
+    }
+
+    #[test]
+    fn test_failing_reflex_does_not_drop_evidence() {
+        let mut app = App::default();
+        let inbox_code = app.store_code(Box::new(
+            ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+        ));
+        let reflex_code = app.store_code(Box::new(ContractWrapper::new(
+            failing_reflex_execute,
+            failing_reflex_instantiate,
+            failing_reflex_query,
+        )));
+        let inbox = app
+            .instantiate_contract(
+                inbox_code,
+                Addr::unchecked("admin"),
+                &InstantiateMsg {
+                    authorized_attestors: vec!["attestor1".to_string()],
+                    min_attestations: None,
+                    activation_delay: None,
+                },
+                &[],
+                "AfferentInbox",
+                None,
+            )
+            .unwrap();
+        let reflex = app
+            .instantiate_contract(
+                reflex_code,
+                Addr::unchecked("admin"),
+                &Empty {},
+                &[],
+                "ReflexArc",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            inbox.clone(),
+            &ExecuteMsg::SetReflexArc {
+                reflex_arc: Some(reflex.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("attestor1"),
+                inbox.clone(),
+                &post_msg(vec![]),
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "reflex_notification_failed"));
+
+        let latest: LatestAEPResponse = app
+            .wrap()
+            .query_wasm_smart(inbox, &QueryMsg::LatestAEP { executor_id: 1 })
+            .unwrap();
+        assert!(latest.aep.is_some());
+    }
+
+    #[test]
+    fn test_new_attestor_rejected_until_activation_delay_passes() {
+        let mut app = App::default();
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let inbox = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("admin"),
+                &InstantiateMsg {
+                    authorized_attestors: vec!["attestor1".to_string()],
+                    min_attestations: None,
+                    activation_delay: Some(3600),
+                },
+                &[],
+                "AfferentInbox",
+                None,
+            )
+            .unwrap();
+
+        // Attestors present at instantiation are active immediately
+        app.execute_contract(
+            Addr::unchecked("attestor1"),
+            inbox.clone(),
+            &post_msg(vec![]),
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            inbox.clone(),
+            &ExecuteMsg::SetAuthorizedAttestors {
+                attestors: vec!["attestor1".to_string(), "attestor2".to_string()],
+                min_attestations: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Authorized, but still under observation
+        let is_authorized: IsAuthorizedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                inbox.clone(),
+                &QueryMsg::IsAuthorized {
+                    attestor: "attestor2".to_string(),
+                },
+            )
+            .unwrap();
+        assert!(is_authorized.authorized);
+        assert!(app
+            .execute_contract(
+                Addr::unchecked("attestor2"),
+                inbox.clone(),
+                &post_msg(vec![]),
+                &[]
+            )
+            .is_err());
+
+        app.update_block(|block| block.time = block.time.plus_seconds(3599));
+        assert!(app
+            .execute_contract(
+                Addr::unchecked("attestor2"),
+                inbox.clone(),
+                &post_msg(vec![]),
+                &[]
+            )
+            .is_err());
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1));
+        app.execute_contract(
+            Addr::unchecked("attestor2"),
+            inbox.clone(),
+            &post_msg(vec![]),
+            &[],
+        )
+        .unwrap();
+    }
+}
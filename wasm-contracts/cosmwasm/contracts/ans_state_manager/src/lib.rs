@@ -1,9 +1,11 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+    Uint128,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use std::collections::HashSet;
 
-use vagus_spec::{ANSState, Guard, VagusError, VagalToneIndicator};
+use vagus_spec::{ANSState, BasisPoints, Guard, ScalingFactor, VagusError, VagalToneIndicator, Vti};
 
 // State
 pub const CURRENT_STATE: Item<ANSState> = Item::new("current_state");
@@ -14,6 +16,38 @@ pub const LAST_STATE_CHANGE: Item<u64> = Item::new("last_state_change");
 pub const MIN_STATE_RESIDENCY: Item<u64> = Item::new("min_state_residency");
 pub const SAFE_THRESHOLD: Item<u64> = Item::new("safe_threshold");     // 8000 (80%)
 pub const DANGER_THRESHOLD: Item<u64> = Item::new("danger_threshold"); // 6000 (60%)
+pub const SHUTDOWN_THRESHOLD: Item<u64> = Item::new("shutdown_threshold"); // 3000 (30%)
+pub const ADMIN: Item<String> = Item::new("admin");
+pub const AUTHORIZED_ORACLES: Item<HashSet<String>> = Item::new("authorized_oracles");
+pub const VAGUS_DAO: Item<String> = Item::new("vagus_dao");
+pub const REFLEX_ARC: Item<String> = Item::new("reflex_arc"); // may tighten action scaling caps
+pub const CAPABILITY_ISSUER: Item<String> = Item::new("capability_issuer"); // per-executor pause flags
+
+// Multi-oracle tone; without an aggregation policy the latest report wins
+pub const TONE_AGGREGATION: Item<ToneAggregation> = Item::new("tone_aggregation");
+pub const ORACLE_REPORTS: Map<&str, OracleReport> = Map::new("oracle_reports");
+
+// Guard policy
+pub const ESCAPE_ACTIONS: Map<&[u8], bool> = Map::new("escape_actions");
+pub const ACTION_SCALING_CAPS: Map<&[u8], u64> = Map::new("action_scaling_caps");
+pub const MAX_TONE_AGE: Item<u64> = Item::new("max_tone_age"); // seconds, unset = never stale
+
+/// How tone reports from several oracles combine into the VTI the state follows
+#[cosmwasm_schema::cw_serde]
+pub enum ToneAggregation {
+    /// Lowest (most dangerous) VTI reported within `window` seconds
+    Conservative { window: u64 },
+    /// Median VTI reported within `window` seconds; the lower of the two
+    /// middle values when the count is even
+    Median { window: u64 },
+}
+
+/// Latest tone reported by an oracle
+#[cosmwasm_schema::cw_serde]
+pub struct OracleReport {
+    pub vti: u64, // basis points
+    pub timestamp: u64,
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct InstantiateMsg {
@@ -21,18 +55,42 @@ pub struct InstantiateMsg {
     pub min_state_residency: u64, // seconds
     pub safe_threshold: u64,      // basis points
     pub danger_threshold: u64,    // basis points
+    /// Tone below which any state escalates straight to SHUTDOWN;
+    /// defaults to half the danger threshold
+    pub shutdown_threshold: Option<u64>, // basis points
+    pub authorized_oracles: Vec<String>,
+    pub vagus_dao: String,
 }
 
 #[cosmwasm_schema::cw_serde]
 pub enum ExecuteMsg {
     UpdateTone { vti: u64, suggested: ANSState },
+    // Admin guard policy
+    SetEscapeAction { action_id: Binary, escape: bool },
+    SetActionScalingCap { action_id: Binary, cap: Option<u64> },
+    SetMaxToneAge { max_age: Option<u64> },
+    SetReflexArc { reflex_arc: String },
+    SetCapabilityIssuer { capability_issuer: String },
+    // Governance
+    SetAuthorizedOracles { oracles: Vec<String> },
+    SetToneAggregation { aggregation: Option<ToneAggregation> },
 }
 
 #[cosmwasm_schema::cw_serde]
 pub enum QueryMsg {
     CurrentState {},
     CurrentTone {},
-    GuardFor { action_id: Binary },
+    GuardFor {
+        action_id: Binary,
+        #[serde(default)]
+        executor_id: Option<u64>,
+    },
+    GuardForDetailed {
+        action_id: Binary,
+        #[serde(default)]
+        executor_id: Option<u64>,
+    },
+    IsEscapeAction { action_id: Binary },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -51,15 +109,50 @@ pub struct GuardForResponse {
     pub guard: Guard,
 }
 
+/// The rule that determined a guard decision
+#[cosmwasm_schema::cw_serde]
+pub enum GuardRule {
+    /// Scaling follows the current ANS state
+    GlobalState { state: ANSState },
+    /// Escape action allowed through SHUTDOWN at DANGER scaling
+    EscapeAllowlist,
+    /// Tone older than `max_age` seconds; SAFE is treated as DANGER
+    StaleTone { age: u64, max_age: u64 },
+    /// Per-action cap lower than the state-derived scaling
+    ActionScalingCap { cap: u64 },
+    /// Executor paused at the capability issuer; nothing passes
+    ExecutorPaused { executor_id: u64 },
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct IsEscapeActionResponse {
+    pub escape: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct GuardForDetailedResponse {
+    pub guard: Guard,
+    pub state: ANSState,
+    pub rule: GuardRule,
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, VagusError> {
     // Validate thresholds
-    if msg.safe_threshold <= msg.danger_threshold || msg.safe_threshold > 10000 {
+    let safe_threshold = BasisPoints::new(msg.safe_threshold)?;
+    let danger_threshold = BasisPoints::new(msg.danger_threshold)?;
+    if safe_threshold <= danger_threshold {
+        return Err(VagusError::InvalidInput);
+    }
+    let shutdown_threshold = msg
+        .shutdown_threshold
+        .unwrap_or(msg.danger_threshold / 2);
+    if BasisPoints::new(shutdown_threshold)? >= danger_threshold {
         return Err(VagusError::InvalidInput);
     }
 
@@ -68,6 +161,20 @@ pub fn instantiate(
     MIN_STATE_RESIDENCY.save(deps.storage, &msg.min_state_residency)?;
     SAFE_THRESHOLD.save(deps.storage, &msg.safe_threshold)?;
     DANGER_THRESHOLD.save(deps.storage, &msg.danger_threshold)?;
+    SHUTDOWN_THRESHOLD.save(deps.storage, &shutdown_threshold)?;
+    ADMIN.save(deps.storage, &info.sender.to_string())?;
+
+    // Only authorized oracles may report tone
+    let mut oracles = HashSet::new();
+    for oracle in msg.authorized_oracles {
+        deps.api.addr_validate(&oracle)?;
+        oracles.insert(oracle);
+    }
+    AUTHORIZED_ORACLES.save(deps.storage, &oracles)?;
+
+    // Initialize governance
+    deps.api.addr_validate(&msg.vagus_dao)?;
+    VAGUS_DAO.save(deps.storage, &msg.vagus_dao)?;
 
     // Initialize tone to neutral
     let initial_tone = VagalToneIndicator {
@@ -91,7 +198,24 @@ pub fn execute(
 ) -> Result<Response, VagusError> {
     match msg {
         ExecuteMsg::UpdateTone { vti, suggested } => {
-            execute_update_tone(deps, env, info, vti, suggested)
+            execute_update_tone(deps, env, info, Vti::new(vti)?, suggested)
+        }
+        ExecuteMsg::SetEscapeAction { action_id, escape } => {
+            execute_set_escape_action(deps, info, action_id, escape)
+        }
+        ExecuteMsg::SetActionScalingCap { action_id, cap } => {
+            execute_set_action_scaling_cap(deps, info, action_id, cap)
+        }
+        ExecuteMsg::SetMaxToneAge { max_age } => execute_set_max_tone_age(deps, info, max_age),
+        ExecuteMsg::SetReflexArc { reflex_arc } => execute_set_reflex_arc(deps, info, reflex_arc),
+        ExecuteMsg::SetCapabilityIssuer { capability_issuer } => {
+            execute_set_capability_issuer(deps, info, capability_issuer)
+        }
+        ExecuteMsg::SetAuthorizedOracles { oracles } => {
+            execute_set_authorized_oracles(deps, info, oracles)
+        }
+        ExecuteMsg::SetToneAggregation { aggregation } => {
+            execute_set_tone_aggregation(deps, info, aggregation)
         }
     }
 }
@@ -99,24 +223,39 @@ pub fn execute(
 pub fn execute_update_tone(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
-    vti: u64,
+    info: MessageInfo,
+    vti: Vti,
     suggested: ANSState,
 ) -> Result<Response, VagusError> {
-    // Validate VTI range
-    if vti > 10000 {
-        return Err(VagusError::InvalidToneValue);
+    // Only authorized oracles can report tone
+    let oracles = AUTHORIZED_ORACLES.load(deps.storage)?;
+    if !oracles.contains(&info.sender.to_string()) {
+        return Err(VagusError::Unauthorized);
     }
 
+    let current_time = env.block.time.seconds();
+    let reported = vti;
+    ORACLE_REPORTS.save(
+        deps.storage,
+        info.sender.as_str(),
+        &OracleReport {
+            vti: vti.get(),
+            timestamp: current_time,
+        },
+    )?;
+    let vti = aggregate_tone(deps.as_ref(), &oracles, current_time, reported)?;
+
     let current_state = CURRENT_STATE.load(deps.storage)?;
     let last_change = LAST_STATE_CHANGE.load(deps.storage)?;
     let min_residency = MIN_STATE_RESIDENCY.load(deps.storage)?;
-    let safe_threshold = SAFE_THRESHOLD.load(deps.storage)?;
-    let danger_threshold = DANGER_THRESHOLD.load(deps.storage)?;
+    let safe_threshold = BasisPoints::new(SAFE_THRESHOLD.load(deps.storage)?)?;
+    let danger_threshold = BasisPoints::new(DANGER_THRESHOLD.load(deps.storage)?)?;
+    let shutdown_threshold = BasisPoints::new(SHUTDOWN_THRESHOLD.load(deps.storage)?)?;
 
-    // Check hysteresis (prevent rapid state changes)
-    let current_time = env.block.time.seconds();
-    if last_change != 0 && current_time < last_change + min_residency {
+    // Check hysteresis (prevent rapid state changes); an emergency
+    // escalation to SHUTDOWN is never held back
+    let emergency = vti.basis_points() < shutdown_threshold && current_state != ANSState::SHUTDOWN;
+    if !emergency && last_change != 0 && current_time < last_change + min_residency {
         return Err(VagusError::StateChangeTooFrequent);
     }
 
@@ -126,6 +265,7 @@ pub fn execute_update_tone(
         vti,
         safe_threshold,
         danger_threshold,
+        shutdown_threshold,
     );
 
     // Override with suggested state if more conservative
@@ -151,24 +291,241 @@ pub fn execute_update_tone(
 
     let mut response = Response::new()
         .add_attribute("action", "update_tone")
-        .add_attribute("vti", vti.to_string())
+        .add_attribute("vti", reported.to_string())
         .add_attribute("tone", vti.to_string())
         .add_attribute("state", format!("{:?}", final_state))
         .add_attribute("updated_at", current_time.to_string());
 
     if state_changed {
-        response = response.add_attribute("state_changed", "true");
+        response = response
+            .add_attribute("state_changed", "true")
+            .add_event(state_changed_event(&current_state, &final_state, vti, current_time));
     }
 
     Ok(response)
 }
 
+/// Combine the recent reports of authorized oracles under the configured
+/// policy; without one the latest report is the tone
+fn aggregate_tone(
+    deps: Deps,
+    oracles: &HashSet<String>,
+    now: u64,
+    latest: Vti,
+) -> Result<Vti, VagusError> {
+    let Some(aggregation) = TONE_AGGREGATION.may_load(deps.storage)? else {
+        return Ok(latest);
+    };
+    let window = match aggregation {
+        ToneAggregation::Conservative { window } | ToneAggregation::Median { window } => window,
+    };
+
+    let mut recent = Vec::new();
+    for oracle in oracles {
+        if let Some(report) = ORACLE_REPORTS.may_load(deps.storage, oracle)? {
+            if now.saturating_sub(report.timestamp) <= window {
+                recent.push(Vti::new(report.vti)?);
+            }
+        }
+    }
+    recent.sort();
+
+    // The caller's own report is always recent, so there is at least one
+    let aggregated = match aggregation {
+        ToneAggregation::Conservative { .. } => recent.first(),
+        ToneAggregation::Median { .. } => recent.get(recent.len().saturating_sub(1) / 2),
+    };
+    Ok(aggregated.copied().unwrap_or(latest))
+}
+
+/// `StateChanged` event with the keys from spec/events.yml, as emitted by the
+/// EVM ANSStateManager: `fromState`, `toState` (0=SAFE, 1=DANGER, 2=SHUTDOWN),
+/// `vti` (basis points) and `timestamp` (seconds)
+pub fn state_changed_event(from: &ANSState, to: &ANSState, vti: Vti, timestamp: u64) -> Event {
+    Event::new("StateChanged")
+        .add_attribute("fromState", state_code(from).to_string())
+        .add_attribute("toState", state_code(to).to_string())
+        .add_attribute("vti", vti.to_string())
+        .add_attribute("timestamp", timestamp.to_string())
+}
+
+fn state_code(state: &ANSState) -> u8 {
+    match state {
+        ANSState::SAFE => 0,
+        ANSState::DANGER => 1,
+        ANSState::SHUTDOWN => 2,
+    }
+}
+
+fn ensure_admin(deps: &DepsMut, info: &MessageInfo) -> Result<(), VagusError> {
+    if info.sender.to_string() != ADMIN.load(deps.storage)? {
+        return Err(VagusError::Unauthorized);
+    }
+    Ok(())
+}
+
+// The reflex arc scales actions down in response to dangerous evidence
+fn ensure_admin_or_reflex(deps: &DepsMut, info: &MessageInfo) -> Result<(), VagusError> {
+    let sender = info.sender.to_string();
+    if sender == ADMIN.load(deps.storage)?
+        || REFLEX_ARC.may_load(deps.storage)?.is_some_and(|reflex_arc| sender == reflex_arc)
+    {
+        return Ok(());
+    }
+    Err(VagusError::Unauthorized)
+}
+
+pub fn execute_set_escape_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    action_id: Binary,
+    escape: bool,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+
+    if escape {
+        ESCAPE_ACTIONS.save(deps.storage, action_id.as_slice(), &true)?;
+    } else {
+        ESCAPE_ACTIONS.remove(deps.storage, action_id.as_slice());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_escape_action")
+        .add_attribute("action_id", action_id.to_base64())
+        .add_attribute("escape", escape.to_string()))
+}
+
+pub fn execute_set_action_scaling_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    action_id: Binary,
+    cap: Option<u64>,
+) -> Result<Response, VagusError> {
+    ensure_admin_or_reflex(&deps, &info)?;
+
+    match cap {
+        Some(cap) => {
+            let cap = ScalingFactor::new(cap)?;
+            ACTION_SCALING_CAPS.save(deps.storage, action_id.as_slice(), &cap.get())?;
+        }
+        None => ACTION_SCALING_CAPS.remove(deps.storage, action_id.as_slice()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_action_scaling_cap")
+        .add_attribute("action_id", action_id.to_base64())
+        .add_attribute("cap", cap.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string())))
+}
+
+pub fn execute_set_max_tone_age(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_age: Option<u64>,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+
+    match max_age {
+        Some(max_age) => MAX_TONE_AGE.save(deps.storage, &max_age)?,
+        None => MAX_TONE_AGE.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_tone_age")
+        .add_attribute("max_age", max_age.map(|a| a.to_string()).unwrap_or_else(|| "none".to_string())))
+}
+
+pub fn execute_set_reflex_arc(
+    deps: DepsMut,
+    info: MessageInfo,
+    reflex_arc: String,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+    deps.api.addr_validate(&reflex_arc)?;
+
+    REFLEX_ARC.save(deps.storage, &reflex_arc)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_reflex_arc")
+        .add_attribute("reflex_arc", reflex_arc))
+}
+
+pub fn execute_set_capability_issuer(
+    deps: DepsMut,
+    info: MessageInfo,
+    capability_issuer: String,
+) -> Result<Response, VagusError> {
+    ensure_admin(&deps, &info)?;
+    deps.api.addr_validate(&capability_issuer)?;
+
+    CAPABILITY_ISSUER.save(deps.storage, &capability_issuer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_capability_issuer")
+        .add_attribute("capability_issuer", capability_issuer))
+}
+
+pub fn execute_set_authorized_oracles(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracles: Vec<String>,
+) -> Result<Response, VagusError> {
+    // Only DAO can set authorized oracles
+    let dao = VAGUS_DAO.load(deps.storage)?;
+    if info.sender.to_string() != dao {
+        return Err(VagusError::Unauthorized);
+    }
+
+    let mut authorized = HashSet::new();
+    for oracle in oracles {
+        deps.api.addr_validate(&oracle)?;
+        authorized.insert(oracle);
+    }
+    let count = authorized.len();
+    AUTHORIZED_ORACLES.save(deps.storage, &authorized)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_authorized_oracles")
+        .add_attribute("count", count.to_string()))
+}
+
+pub fn execute_set_tone_aggregation(
+    deps: DepsMut,
+    info: MessageInfo,
+    aggregation: Option<ToneAggregation>,
+) -> Result<Response, VagusError> {
+    // Like the oracle set, how oracles are combined is up to the DAO
+    let dao = VAGUS_DAO.load(deps.storage)?;
+    if info.sender.to_string() != dao {
+        return Err(VagusError::Unauthorized);
+    }
+
+    match &aggregation {
+        Some(aggregation) => TONE_AGGREGATION.save(deps.storage, aggregation)?,
+        None => TONE_AGGREGATION.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tone_aggregation")
+        .add_attribute(
+            "aggregation",
+            aggregation.map(|a| format!("{:?}", a)).unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
 fn determine_state_with_hysteresis(
     current: ANSState,
-    vti: u64,
-    safe_threshold: u64,
-    danger_threshold: u64,
+    vti: Vti,
+    safe_threshold: BasisPoints,
+    danger_threshold: BasisPoints,
+    shutdown_threshold: BasisPoints,
 ) -> ANSState {
+    let vti = vti.basis_points();
+
+    // Very low VTI triggers shutdown from any state
+    if vti < shutdown_threshold {
+        return ANSState::SHUTDOWN;
+    }
+
     match current {
         ANSState::SAFE => {
             if vti < danger_threshold {
@@ -180,9 +537,6 @@ fn determine_state_with_hysteresis(
         ANSState::DANGER => {
             if vti >= safe_threshold {
                 ANSState::SAFE
-            } else if vti < danger_threshold / 2 {
-                // Very low VTI triggers shutdown
-                ANSState::SHUTDOWN
             } else {
                 ANSState::DANGER
             }
@@ -215,8 +569,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::CurrentState {} => to_json_binary(&query_current_state(deps)?),
         QueryMsg::CurrentTone {} => to_json_binary(&query_current_tone(deps)?),
-        QueryMsg::GuardFor { action_id } => {
-            to_json_binary(&query_guard_for(deps, env, action_id)?)
+        QueryMsg::GuardFor { action_id, executor_id } => {
+            to_json_binary(&query_guard_for(deps, env, action_id, executor_id)?)
+        }
+        QueryMsg::GuardForDetailed { action_id, executor_id } => {
+            to_json_binary(&query_guard_for_detailed(deps, env, action_id, executor_id)?)
+        }
+        QueryMsg::IsEscapeAction { action_id } => {
+            to_json_binary(&query_is_escape_action(deps, action_id)?)
         }
     }
 }
@@ -232,20 +592,645 @@ fn query_current_tone(deps: Deps) -> StdResult<CurrentToneResponse> {
     Ok(CurrentToneResponse { tone })
 }
 
-fn query_guard_for(deps: Deps, _env: Env, _action_id: Binary) -> StdResult<GuardForResponse> {
+fn query_guard_for(
+    deps: Deps,
+    env: Env,
+    action_id: Binary,
+    executor_id: Option<u64>,
+) -> StdResult<GuardForResponse> {
+    let detailed = query_guard_for_detailed(deps, env, action_id, executor_id)?;
+    Ok(GuardForResponse { guard: detailed.guard })
+}
+
+fn query_guard_for_detailed(
+    deps: Deps,
+    env: Env,
+    action_id: Binary,
+    executor_id: Option<u64>,
+) -> StdResult<GuardForDetailedResponse> {
     let state = CURRENT_STATE.load(deps.storage)?;
+    let tone = CURRENT_TONE.load(deps.storage)?;
+    let escape = ESCAPE_ACTIONS.has(deps.storage, action_id.as_slice());
+    let cap = ACTION_SCALING_CAPS.may_load(deps.storage, action_id.as_slice())?;
+    let max_tone_age = MAX_TONE_AGE.may_load(deps.storage)?;
+
+    let tone_updated_at = u64::try_from(Uint128::try_from(tone.timestamp)?.u128()).unwrap_or(u64::MAX);
+    let tone_age = env.block.time.seconds().saturating_sub(tone_updated_at);
 
-    // Simplified guard logic - in production this would be action-specific
-    let scaling_factor = match state {
-        ANSState::SAFE => 10000u64,    // 100%
-        ANSState::DANGER => 5000u64,    // 50%
-        ANSState::SHUTDOWN => 0u64,     // 0%
+    let paused_executor = match (executor_id, CAPABILITY_ISSUER.may_load(deps.storage)?) {
+        (Some(executor_id), Some(capability_issuer)) => deps
+            .querier
+            .query_wasm_smart::<capability_issuer::IsExecutorPausedResponse>(
+                capability_issuer,
+                &capability_issuer::QueryMsg::IsExecutorPaused { executor_id },
+            )?
+            .paused
+            .then_some(executor_id),
+        _ => None,
     };
 
-    let guard = Guard {
-        scalingFactor: scaling_factor.into(),
-        allowed: scaling_factor > 0,
+    let (scaling_factor, rule) =
+        guard_decision(&state, escape, paused_executor, cap, max_tone_age, tone_age);
+
+    Ok(GuardForDetailedResponse {
+        guard: Guard::with_scaling_factor(scaling_factor),
+        state,
+        rule,
+    })
+}
+
+fn query_is_escape_action(deps: Deps, action_id: Binary) -> StdResult<IsEscapeActionResponse> {
+    Ok(IsEscapeActionResponse {
+        escape: ESCAPE_ACTIONS.has(deps.storage, action_id.as_slice()),
+    })
+}
+
+fn state_scaling(state: &ANSState) -> ScalingFactor {
+    match state {
+        ANSState::SAFE => ScalingFactor::FULL,
+        ANSState::DANGER => ScalingFactor::saturating(5000), // 50%
+        ANSState::SHUTDOWN => ScalingFactor::NONE,
+    }
+}
+
+/// Decide an action's scaling and report the rule that settled it.
+/// A paused executor is held at zero scaling, escapes included, matching the
+/// issuer's refusal to issue for it; escape and stale-tone rules replace the
+/// state scaling; a per-action cap then applies only if it is
+/// stricter.
+fn guard_decision(
+    state: &ANSState,
+    escape: bool,
+    paused_executor: Option<u64>,
+    cap: Option<u64>,
+    max_tone_age: Option<u64>,
+    tone_age: u64,
+) -> (ScalingFactor, GuardRule) {
+    let (scaling_factor, rule) = match (state, paused_executor) {
+        (_, Some(executor_id)) => (ScalingFactor::NONE, GuardRule::ExecutorPaused { executor_id }),
+        (ANSState::SHUTDOWN, None) if escape => {
+            (state_scaling(&ANSState::DANGER), GuardRule::EscapeAllowlist)
+        }
+        (ANSState::SAFE, None) if max_tone_age.is_some_and(|max_age| tone_age > max_age) => (
+            state_scaling(&ANSState::DANGER),
+            GuardRule::StaleTone {
+                age: tone_age,
+                max_age: max_tone_age.unwrap_or_default(),
+            },
+        ),
+        _ => (state_scaling(state), GuardRule::GlobalState { state: state.clone() }),
     };
 
-    Ok(GuardForResponse { guard })
+    match cap.map(ScalingFactor::saturating) {
+        Some(cap) if cap < scaling_factor => (cap, GuardRule::ActionScalingCap { cap: cap.get() }),
+        _ => (scaling_factor, rule),
+    }
+}
+
+// Mirror of the capability issuer's pause query
+mod capability_issuer {
+    #[cosmwasm_schema::cw_serde]
+    pub enum QueryMsg {
+        IsExecutorPaused { executor_id: u64 },
+    }
+
+    #[cosmwasm_schema::cw_serde]
+    pub struct IsExecutorPausedResponse {
+        pub paused: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{Addr, Empty, Uint256};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+    const ADMIN_ADDR: &str = "admin";
+    const ORACLE_ADDR: &str = "oracle";
+    const DAO_ADDR: &str = "dao";
+
+    fn ans_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+
+    #[test]
+    fn test_guard_rationale_in_shutdown() {
+        let mut app = App::default();
+        let code_id = app.store_code(ans_contract());
+        let ans = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN_ADDR),
+                &InstantiateMsg {
+                    initial_state: ANSState::SHUTDOWN,
+                    min_state_residency: 60,
+                    safe_threshold: 8000,
+                    danger_threshold: 6000,
+                    shutdown_threshold: None,
+                    authorized_oracles: vec![ORACLE_ADDR.to_string()],
+                    vagus_dao: DAO_ADDR.to_string(),
+                },
+                &[],
+                "ANSStateManager",
+                None,
+            )
+            .unwrap();
+
+        let normal_action = Binary::from(vec![1u8; 32]);
+        let escape_action = Binary::from(vec![2u8; 32]);
+
+        // Only the admin may allowlist escape actions
+        let allow = ExecuteMsg::SetEscapeAction {
+            action_id: escape_action.clone(),
+            escape: true,
+        };
+        assert!(app
+            .execute_contract(Addr::unchecked("planner"), ans.clone(), &allow, &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked(ADMIN_ADDR), ans.clone(), &allow, &[])
+            .unwrap();
+
+        let detailed = |app: &App, action_id: &Binary| -> GuardForDetailedResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    ans.clone(),
+                    &QueryMsg::GuardForDetailed {
+                        action_id: action_id.clone(),
+                        executor_id: None,
+                    },
+                )
+                .unwrap()
+        };
+
+        let normal = detailed(&app, &normal_action);
+        assert_eq!(normal.state, ANSState::SHUTDOWN);
+        assert_eq!(normal.rule, GuardRule::GlobalState { state: ANSState::SHUTDOWN });
+        assert!(!normal.guard.allowed);
+
+        let escape = detailed(&app, &escape_action);
+        assert_eq!(escape.state, ANSState::SHUTDOWN);
+        assert_eq!(escape.rule, GuardRule::EscapeAllowlist);
+        assert!(escape.guard.allowed);
+        assert_eq!(escape.guard.scalingFactor, Uint256::from(5000u64));
+
+        let is_escape = |action_id: &Binary| -> bool {
+            app.wrap()
+                .query_wasm_smart::<IsEscapeActionResponse>(
+                    ans.clone(),
+                    &QueryMsg::IsEscapeAction {
+                        action_id: action_id.clone(),
+                    },
+                )
+                .unwrap()
+                .escape
+        };
+        assert!(is_escape(&escape_action));
+        assert!(!is_escape(&normal_action));
+
+        // GuardFor agrees with the detailed decision
+        let plain: GuardForResponse = app
+            .wrap()
+            .query_wasm_smart(
+                ans,
+                &QueryMsg::GuardFor {
+                    action_id: escape_action,
+                    executor_id: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(plain.guard, escape.guard);
+    }
+
+    #[test]
+    fn test_guard_decision_rules() {
+        assert_eq!(
+            guard_decision(&ANSState::SAFE, false, None, Some(2000), None, 0),
+            (ScalingFactor::saturating(2000), GuardRule::ActionScalingCap { cap: 2000 })
+        );
+        // A cap looser than the state scaling leaves the state rule in charge
+        assert_eq!(
+            guard_decision(&ANSState::DANGER, false, None, Some(8000), None, 0),
+            (
+                ScalingFactor::saturating(5000),
+                GuardRule::GlobalState { state: ANSState::DANGER }
+            )
+        );
+        assert_eq!(
+            guard_decision(&ANSState::SAFE, false, None, None, Some(300), 301),
+            (
+                ScalingFactor::saturating(5000),
+                GuardRule::StaleTone { age: 301, max_age: 300 }
+            )
+        );
+        assert_eq!(
+            guard_decision(&ANSState::SAFE, false, None, None, Some(300), 300),
+            (ScalingFactor::FULL, GuardRule::GlobalState { state: ANSState::SAFE })
+        );
+        // A paused executor is held at zero even in SAFE, escapes included
+        for escape in [false, true] {
+            assert_eq!(
+                guard_decision(&ANSState::SAFE, escape, Some(7), None, None, 0),
+                (ScalingFactor::NONE, GuardRule::ExecutorPaused { executor_id: 7 })
+            );
+        }
+    }
+
+    fn instantiate_ans(app: &mut App, min_state_residency: u64) -> Addr {
+        let code_id = app.store_code(ans_contract());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+            &InstantiateMsg {
+                initial_state: ANSState::SAFE,
+                min_state_residency,
+                safe_threshold: 8000,
+                danger_threshold: 6000,
+                shutdown_threshold: None,
+                authorized_oracles: vec![ORACLE_ADDR.to_string()],
+                vagus_dao: DAO_ADDR.to_string(),
+            },
+            &[],
+            "ANSStateManager",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn current_state(app: &App, ans: &Addr) -> ANSState {
+        let res: CurrentStateResponse = app
+            .wrap()
+            .query_wasm_smart(ans.clone(), &QueryMsg::CurrentState {})
+            .unwrap();
+        res.state
+    }
+
+    const PAUSED_EXECUTOR: u64 = 7;
+
+    /// Capability issuer stand-in that reports only `PAUSED_EXECUTOR` paused
+    fn paused_issuer_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+            |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+            |_, _, msg: capability_issuer::QueryMsg| -> StdResult<Binary> {
+                let capability_issuer::QueryMsg::IsExecutorPaused { executor_id } = msg;
+                to_json_binary(&capability_issuer::IsExecutorPausedResponse {
+                    paused: executor_id == PAUSED_EXECUTOR,
+                })
+            },
+        ))
+    }
+
+    #[test]
+    fn test_paused_executor_gets_conservative_guard() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 60);
+        let issuer_code = app.store_code(paused_issuer_contract());
+        let issuer = app
+            .instantiate_contract(
+                issuer_code,
+                Addr::unchecked(ADMIN_ADDR),
+                &Empty {},
+                &[],
+                "CapabilityIssuer",
+                None,
+            )
+            .unwrap();
+
+        let set_issuer = ExecuteMsg::SetCapabilityIssuer {
+            capability_issuer: issuer.to_string(),
+        };
+        assert!(app
+            .execute_contract(Addr::unchecked("planner"), ans.clone(), &set_issuer, &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked(ADMIN_ADDR), ans.clone(), &set_issuer, &[])
+            .unwrap();
+
+        let normal_action = Binary::from(vec![1u8; 32]);
+        let escape_action = Binary::from(vec![2u8; 32]);
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            ans.clone(),
+            &ExecuteMsg::SetEscapeAction {
+                action_id: escape_action.clone(),
+                escape: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let detailed = |action_id: &Binary, executor_id: Option<u64>| -> GuardForDetailedResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    ans.clone(),
+                    &QueryMsg::GuardForDetailed {
+                        action_id: action_id.clone(),
+                        executor_id,
+                    },
+                )
+                .unwrap()
+        };
+
+        // The paused executor is blocked while the fleet stays SAFE
+        let paused = detailed(&normal_action, Some(PAUSED_EXECUTOR));
+        assert_eq!(paused.state, ANSState::SAFE);
+        assert_eq!(
+            paused.rule,
+            GuardRule::ExecutorPaused {
+                executor_id: PAUSED_EXECUTOR
+            }
+        );
+        assert!(!paused.guard.allowed);
+
+        let paused_escape = detailed(&escape_action, Some(PAUSED_EXECUTOR));
+        assert!(!paused_escape.guard.allowed);
+
+        // Other executors, and executor-agnostic queries, keep full scaling
+        for executor_id in [Some(PAUSED_EXECUTOR + 1), None] {
+            let other = detailed(&normal_action, executor_id);
+            assert_eq!(other.rule, GuardRule::GlobalState { state: ANSState::SAFE });
+            assert_eq!(other.guard.scalingFactor, Uint256::from(10000u64));
+        }
+    }
+
+    #[test]
+    fn test_authorized_oracle_updates_tone() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+
+        app.execute_contract(
+            Addr::unchecked(ORACLE_ADDR),
+            ans.clone(),
+            &ExecuteMsg::UpdateTone {
+                vti: 5000,
+                suggested: ANSState::SAFE,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(current_state(&app, &ans), ANSState::DANGER);
+    }
+
+    #[test]
+    fn test_unauthorized_sender_cannot_update_tone() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+
+        // Not even the admin may report tone
+        for sender in ["mallory", ADMIN_ADDR] {
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(sender),
+                    ans.clone(),
+                    &ExecuteMsg::UpdateTone {
+                        vti: 5000,
+                        suggested: ANSState::SAFE,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<VagusError>(),
+                Some(VagusError::Unauthorized)
+            ));
+        }
+        assert_eq!(current_state(&app, &ans), ANSState::SAFE);
+    }
+
+    #[test]
+    fn test_dao_reconfigures_authorized_oracles() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+        let rotate = ExecuteMsg::SetAuthorizedOracles {
+            oracles: vec!["new_oracle".to_string()],
+        };
+
+        // Only the DAO may change the oracle set
+        for sender in [ORACLE_ADDR, ADMIN_ADDR] {
+            assert!(app
+                .execute_contract(Addr::unchecked(sender), ans.clone(), &rotate, &[])
+                .is_err());
+        }
+        app.execute_contract(Addr::unchecked(DAO_ADDR), ans.clone(), &rotate, &[])
+            .unwrap();
+
+        let update = ExecuteMsg::UpdateTone {
+            vti: 5000,
+            suggested: ANSState::SAFE,
+        };
+        assert!(app
+            .execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update, &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked("new_oracle"), ans.clone(), &update, &[])
+            .unwrap();
+        assert_eq!(current_state(&app, &ans), ANSState::DANGER);
+    }
+
+    #[test]
+    fn test_very_low_tone_escalates_straight_to_shutdown() {
+        let update = |vti| ExecuteMsg::UpdateTone {
+            vti,
+            suggested: ANSState::SAFE,
+        };
+
+        // SAFE skips DANGER on a catastrophic reading
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 60);
+        app.execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update(1000), &[])
+            .unwrap();
+        assert_eq!(current_state(&app, &ans), ANSState::SHUTDOWN);
+
+        // Within the residency period only the emergency escalation goes through
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 60);
+        app.execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update(5000), &[])
+            .unwrap();
+        assert_eq!(current_state(&app, &ans), ANSState::DANGER);
+        assert!(app
+            .execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update(9000), &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update(1000), &[])
+            .unwrap();
+        assert_eq!(current_state(&app, &ans), ANSState::SHUTDOWN);
+    }
+
+    #[test]
+    fn test_shutdown_threshold_must_be_below_danger() {
+        let mut app = App::default();
+        let code_id = app.store_code(ans_contract());
+        let res = app.instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN_ADDR),
+            &InstantiateMsg {
+                initial_state: ANSState::SAFE,
+                min_state_residency: 0,
+                safe_threshold: 8000,
+                danger_threshold: 6000,
+                shutdown_threshold: Some(6000),
+                authorized_oracles: vec![ORACLE_ADDR.to_string()],
+                vagus_dao: DAO_ADDR.to_string(),
+            },
+            &[],
+            "ANSStateManager",
+            None,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_transition_emits_state_changed_event() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+        let update = |vti| ExecuteMsg::UpdateTone {
+            vti,
+            suggested: ANSState::SAFE,
+        };
+
+        let res = app
+            .execute_contract(Addr::unchecked(ORACLE_ADDR), ans.clone(), &update(5000), &[])
+            .unwrap();
+        let event = res
+            .events
+            .iter()
+            .find(|e| e.ty == "wasm-StateChanged")
+            .expect("StateChanged event");
+        let attr = |key: &str| {
+            event
+                .attributes
+                .iter()
+                .find(|a| a.key == key)
+                .map(|a| a.value.clone())
+        };
+        assert_eq!(attr("fromState").as_deref(), Some("0"));
+        assert_eq!(attr("toState").as_deref(), Some("1"));
+        assert_eq!(attr("vti").as_deref(), Some("5000"));
+        assert_eq!(
+            attr("timestamp"),
+            Some(app.block_info().time.seconds().to_string())
+        );
+
+        // No transition, no event
+        let res = app
+            .execute_contract(Addr::unchecked(ORACLE_ADDR), ans, &update(5500), &[])
+            .unwrap();
+        assert!(!res.events.iter().any(|e| e.ty == "wasm-StateChanged"));
+    }
+
+    #[test]
+    fn test_silent_oracle_cannot_leave_guards_permissive() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+        app.execute_contract(
+            Addr::unchecked(ADMIN_ADDR),
+            ans.clone(),
+            &ExecuteMsg::SetMaxToneAge { max_age: Some(300) },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(ORACLE_ADDR),
+            ans.clone(),
+            &ExecuteMsg::UpdateTone {
+                vti: 9000,
+                suggested: ANSState::SAFE,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let guard = |app: &App| -> Guard {
+            let res: GuardForResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    ans.clone(),
+                    &QueryMsg::GuardFor {
+                        action_id: Binary::from(vec![1u8; 32]),
+                        executor_id: None,
+                    },
+                )
+                .unwrap();
+            res.guard
+        };
+        assert_eq!(guard(&app).scalingFactor, Uint256::from(10000u64));
+
+        // The oracle goes quiet past the staleness threshold
+        app.update_block(|block| block.time = block.time.plus_seconds(301));
+
+        assert_eq!(current_state(&app, &ans), ANSState::SAFE);
+        let stale = guard(&app);
+        assert!(stale.allowed);
+        assert_eq!(stale.scalingFactor, Uint256::from(5000u64));
+    }
+
+    #[test]
+    fn test_tone_aggregation_across_oracles() {
+        let mut app = App::default();
+        let ans = instantiate_ans(&mut app, 0);
+        app.execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            ans.clone(),
+            &ExecuteMsg::SetAuthorizedOracles {
+                oracles: ["oracle_a", "oracle_b", "oracle_c"].map(String::from).to_vec(),
+            },
+            &[],
+        )
+        .unwrap();
+        let set_policy = |app: &mut App, aggregation| {
+            app.execute_contract(
+                Addr::unchecked(DAO_ADDR),
+                ans.clone(),
+                &ExecuteMsg::SetToneAggregation {
+                    aggregation: Some(aggregation),
+                },
+                &[],
+            )
+            .unwrap();
+        };
+        let report = |app: &mut App, oracle: &str, vti| {
+            app.execute_contract(
+                Addr::unchecked(oracle),
+                ans.clone(),
+                &ExecuteMsg::UpdateTone {
+                    vti,
+                    suggested: ANSState::SAFE,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+
+        // Conservative: the more dangerous of two divergent reports wins
+        set_policy(&mut app, ToneAggregation::Conservative { window: 60 });
+        report(&mut app, "oracle_a", 9000);
+        report(&mut app, "oracle_b", 5000);
+        assert_eq!(current_state(&app, &ans), ANSState::DANGER);
+        report(&mut app, "oracle_a", 9500);
+        assert_eq!(current_state(&app, &ans), ANSState::DANGER);
+
+        // Once the dangerous report leaves the window it no longer counts
+        app.update_block(|block| block.time = block.time.plus_seconds(61));
+        report(&mut app, "oracle_a", 9000);
+        assert_eq!(current_state(&app, &ans), ANSState::SAFE);
+
+        // Median: a single faulty oracle cannot force a shutdown
+        set_policy(&mut app, ToneAggregation::Median { window: 60 });
+        report(&mut app, "oracle_b", 9000);
+        report(&mut app, "oracle_c", 1000);
+        assert_eq!(current_state(&app, &ans), ANSState::SAFE);
+        let tone: CurrentToneResponse = app
+            .wrap()
+            .query_wasm_smart(ans.clone(), &QueryMsg::CurrentTone {})
+            .unwrap();
+        assert_eq!(tone.tone.value, Uint256::from(9000u64));
+
+        // Only the DAO chooses the policy
+        assert!(app
+            .execute_contract(
+                Addr::unchecked(ADMIN_ADDR),
+                ans,
+                &ExecuteMsg::SetToneAggregation { aggregation: None },
+                &[],
+            )
+            .is_err());
+    }
 }
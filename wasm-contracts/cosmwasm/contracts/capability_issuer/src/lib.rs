@@ -1,24 +1,40 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    Uint256, Timestamp,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Uint128, Uint256, Timestamp,
 };
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Item, Map, PrefixBound};
 use cw721_base::Cw721Contract;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
-use vagus_spec::{CapabilityRevocationReason, TokenMeta, VagusError};
+use ::vagus_spec::{ANSState, CapabilityRevocationReason, TokenMeta, VagusError};
 
 // State
 pub const NEXT_TOKEN_ID: Item<u64> = Item::new("next_token_id");
 pub const AUTHORIZED_EXECUTORS: Item<HashSet<String>> = Item::new("authorized_executors");
 pub const REFLEX_ARC: Item<String> = Item::new("reflex_arc");
+pub const ANS_STATE_MANAGER: Item<String> = Item::new("ans_state_manager"); // gates validity during SHUTDOWN
 
 // Token metadata storage (simplified cw721)
 pub const TOKENS: Map<String, TokenMeta> = Map::new("tokens"); // token_id -> metadata
 pub const OWNERS: Map<String, String> = Map::new("owners"); // token_id -> owner
 pub const OWNED_TOKENS: Map<(String, String), ()> = Map::new("owned_tokens"); // (owner, token_id) -> ()
+pub const EXPIRY_INDEX: Map<(u64, String), ()> = Map::new("expiry_index"); // (expires_at, token_id) -> ()
+pub const EXECUTOR_TOKENS: Map<u64, Vec<String>> = Map::new("executor_tokens"); // executor_id -> token_ids counted against its cap
+pub const USED_NONCES: Map<(String, u64), ()> = Map::new("used_nonces"); // (planner, nonce) -> ()
+
+// Cap on simultaneously-active tokens per executor
+pub const MAX_ACTIVE_PER_EXECUTOR: Item<u64> = Item::new("max_active_per_executor");
+const DEFAULT_MAX_ACTIVE_PER_EXECUTOR: u64 = 16;
+
+// Expired-token GC batch sizes
+const DEFAULT_EXPIRE_LIMIT: u32 = 30;
+const MAX_EXPIRE_LIMIT: u32 = 100;
+
+// ActiveTokensOf page sizes
+const DEFAULT_ACTIVE_TOKENS_LIMIT: u32 = 30;
+const MAX_ACTIVE_TOKENS_LIMIT: u32 = 100;
 
 // Governance
 pub const VAGUS_DAO: Item<String> = Item::new("vagus_dao");
@@ -55,19 +71,27 @@ pub const CIRCUIT_BREAKER_RECOVERY: Item<u64> = Item::new("circuit_breaker_recov
 pub const RATE_LIMIT_WINDOWS: Map<String, Vec<u64>> = Map::new("rate_limit_windows");
 pub const CIRCUIT_BREAKERS: Map<String, CircuitBreaker> = Map::new("circuit_breakers");
 
+// Optional cap on an executor's issuance across all of its actions
+pub const EXECUTOR_RATE_LIMIT: Item<RateLimitConfig> = Item::new("executor_rate_limit");
+pub const EXECUTOR_RATE_LIMIT_WINDOWS: Map<u64, Vec<u64>> = Map::new("executor_rate_limit_windows");
+
 // Emergency pause state
 pub const EMERGENCY_PAUSED: Item<bool> = Item::new("emergency_paused");
+pub const PAUSED_EXECUTORS: Map<u64, ()> = Map::new("paused_executors"); // executor_id -> ()
 
 #[cosmwasm_schema::cw_serde]
 pub struct InstantiateMsg {
     pub authorized_executors: Vec<String>,
     pub reflex_arc: Option<String>,
+    /// Without it tokens stay valid whatever the ANS state
+    pub ans_state_manager: Option<String>,
     pub vagus_dao: String,
     pub rate_limit_window_size: Option<u64>,
     pub rate_limit_max_requests: Option<u64>,
     pub circuit_breaker_threshold: Option<u64>,
     pub circuit_breaker_timeout: Option<u64>,
     pub circuit_breaker_recovery: Option<u64>,
+    pub max_active_per_executor: Option<u64>,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -84,6 +108,8 @@ pub enum ExecuteMsg {
         intent_max_energy_j: u64,
         intent_planner: String,
         intent_nonce: u64,
+        #[serde(default)]
+        intent_is_escape: bool,
         scaled_limits_hash: Binary,
         expires_at: u64,
     },
@@ -91,6 +117,14 @@ pub enum ExecuteMsg {
         token_id: String,
         reason: CapabilityRevocationReason,
     },
+    // Count a failed `Issue` towards the circuit breaker. A rejected issuance
+    // reverts, so the executor reports it here once the failure is handled
+    RecordIssueFailure {
+        executor_id: u64,
+        action_id: Binary,
+        not_before: u64,
+        not_after: u64,
+    },
     // Governance operations
     SetReflexArc {
         reflex_arc: String,
@@ -99,20 +133,43 @@ pub enum ExecuteMsg {
         window_size: u64,
         max_requests: u64,
     },
+    SetExecutorRateLimit {
+        rate_limit: Option<RateLimitConfig>,
+    },
     SetCircuitBreakerParams {
         threshold: u64,
         timeout: u64,
         recovery: u64,
     },
+    SetMaxActivePerExecutor {
+        max_active: u64,
+    },
     EmergencyPause {},
     EmergencyUnpause {},
+    // Quarantine a single executor without pausing the whole contract
+    PauseExecutor {
+        executor_id: u64,
+    },
+    UnpauseExecutor {
+        executor_id: u64,
+    },
+    // Permissionless GC of tokens past their expiry
+    ExpireTokens {
+        limit: Option<u32>,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
 pub enum QueryMsg {
     IsValid { token_id: String },
-    ActiveTokensOf { executor_id: u64 },
+    ActiveTokensOf {
+        executor_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     TokenInfo { token_id: String },
+    TokenOwner { token_id: String },
+    IsExecutorPaused { executor_id: u64 },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -130,6 +187,16 @@ pub struct TokenInfoResponse {
     pub token: Option<TokenMeta>,
 }
 
+#[cosmwasm_schema::cw_serde]
+pub struct TokenOwnerResponse {
+    pub owner: Option<String>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct IsExecutorPausedResponse {
+    pub paused: bool,
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -156,6 +223,11 @@ pub fn instantiate(
         REFLEX_ARC.save(deps.storage, &reflex_arc)?;
     }
 
+    if let Some(ans_state_manager) = msg.ans_state_manager {
+        deps.api.addr_validate(&ans_state_manager)?;
+        ANS_STATE_MANAGER.save(deps.storage, &ans_state_manager)?;
+    }
+
     // Initialize rate limiter defaults
     let rate_limit = RateLimitConfig {
         window_size: msg.rate_limit_window_size.unwrap_or(3600), // 1 hour default
@@ -168,6 +240,11 @@ pub fn instantiate(
     CIRCUIT_BREAKER_TIMEOUT.save(deps.storage, &msg.circuit_breaker_timeout.unwrap_or(300))?; // 5 minutes
     CIRCUIT_BREAKER_RECOVERY.save(deps.storage, &msg.circuit_breaker_recovery.unwrap_or(3))?;
 
+    MAX_ACTIVE_PER_EXECUTOR.save(
+        deps.storage,
+        &msg.max_active_per_executor.unwrap_or(DEFAULT_MAX_ACTIVE_PER_EXECUTOR),
+    )?;
+
     // Initialize emergency pause state
     EMERGENCY_PAUSED.save(deps.storage, &false)?;
 
@@ -200,7 +277,8 @@ pub fn execute(
             intent_max_duration_ms: _,
             intent_max_energy_j: _,
             intent_planner,
-            intent_nonce: _,
+            intent_nonce,
+            intent_is_escape,
             scaled_limits_hash,
             expires_at,
         } => execute_issue(
@@ -212,30 +290,49 @@ pub fn execute(
             intent_not_before,
             intent_not_after,
             intent_planner,
+            intent_nonce,
+            intent_is_escape,
             scaled_limits_hash,
             expires_at,
         ),
         ExecuteMsg::Revoke { token_id, reason } => {
             execute_revoke(deps, env, info, token_id, reason)
         }
+        ExecuteMsg::RecordIssueFailure { executor_id, action_id, not_before, not_after } => {
+            execute_record_issue_failure(deps, env, info, executor_id, action_id, not_before, not_after)
+        }
         ExecuteMsg::SetReflexArc { reflex_arc } => {
             execute_set_reflex_arc(deps, info, reflex_arc)
         }
         ExecuteMsg::SetRateLimit { window_size, max_requests } => {
             execute_set_rate_limit(deps, info, window_size, max_requests)
         }
+        ExecuteMsg::SetExecutorRateLimit { rate_limit } => {
+            execute_set_executor_rate_limit(deps, info, rate_limit)
+        }
         ExecuteMsg::SetCircuitBreakerParams { threshold, timeout, recovery } => {
             execute_set_circuit_breaker_params(deps, info, threshold, timeout, recovery)
         }
+        ExecuteMsg::SetMaxActivePerExecutor { max_active } => {
+            execute_set_max_active_per_executor(deps, info, max_active)
+        }
         ExecuteMsg::EmergencyPause {} => {
             execute_emergency_pause(deps, info)
         }
         ExecuteMsg::EmergencyUnpause {} => {
             execute_emergency_unpause(deps, info)
         }
+        ExecuteMsg::PauseExecutor { executor_id } => {
+            execute_set_executor_paused(deps, info, executor_id, true)
+        }
+        ExecuteMsg::UnpauseExecutor { executor_id } => {
+            execute_set_executor_paused(deps, info, executor_id, false)
+        }
+        ExecuteMsg::ExpireTokens { limit } => execute_expire_tokens(deps, env, limit),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_issue(
     deps: DepsMut,
     env: Env,
@@ -245,6 +342,8 @@ pub fn execute_issue(
     not_before: u64,
     not_after: u64,
     planner: String,
+    nonce: u64,
+    is_escape: bool,
     scaled_limits_hash: Binary,
     expires_at: u64,
 ) -> Result<Response, VagusError> {
@@ -254,18 +353,41 @@ pub fn execute_issue(
         return Err(VagusError::Unauthorized);
     }
 
-    // Validate time constraints (closed interval [not_before, not_after])
+    if PAUSED_EXECUTORS.has(deps.storage, executor_id) {
+        return Err(VagusError::ExecutorPaused);
+    }
+
+    // Each of a planner's intents is issued at most once
+    if USED_NONCES.has(deps.storage, (planner.clone(), nonce)) {
+        return Err(VagusError::NonceAlreadyUsed);
+    }
+
+    // The token may not outlive the intent's authorization window
     let current_time = env.block.time.seconds();
-    if current_time < not_before || current_time > not_after {
-        return Err(VagusError::IntentExpired);
+    if not_before > not_after || expires_at > not_after || expires_at <= current_time {
+        return Err(VagusError::InvalidInput);
     }
 
     // ER7: Check circuit breaker first
     let key = format!("{}_{}", executor_id, hex::encode(&action_id));
     check_circuit_breaker(deps.storage, &key, current_time)?;
 
+    // Validate time constraints (closed interval [not_before, not_after])
+    if current_time < not_before || current_time > not_after {
+        return Err(VagusError::IntentExpired);
+    }
+
     // ER7: Check rate limits (sliding window)
-    check_rate_limit(deps.storage, &key, current_time)?;
+    let windows = check_rate_limits(deps.storage, &key, executor_id, current_time)?;
+
+    // Reject while the executor already holds the maximum number of active tokens
+    let mut executor_tokens = active_executor_tokens(deps.storage, executor_id, current_time)?;
+    let max_active = MAX_ACTIVE_PER_EXECUTOR.load(deps.storage)?;
+    if executor_tokens.len() as u64 >= max_active {
+        return Err(VagusError::TooManyActiveTokens);
+    }
+
+    save_rate_limits(deps.storage, &key, executor_id, windows)?;
 
     // Generate token ID
     let token_id_num = NEXT_TOKEN_ID.load(deps.storage)?;
@@ -282,12 +404,17 @@ pub fn execute_issue(
         expiresAt: expires_at.into(),
         revoked: false,
         revokedAt: 0u64.into(),
+        isEscape: is_escape,
     };
 
     // Store token data
     TOKENS.save(deps.storage, token_id.clone(), &token_meta)?;
     OWNERS.save(deps.storage, token_id.clone(), &planner)?;
     OWNED_TOKENS.save(deps.storage, (planner.clone(), token_id.clone()), &())?;
+    EXPIRY_INDEX.save(deps.storage, (expires_at, token_id.clone()), &())?;
+    executor_tokens.push(token_id.clone());
+    EXECUTOR_TOKENS.save(deps.storage, executor_id, &executor_tokens)?;
+    USED_NONCES.save(deps.storage, (planner.clone(), nonce), &())?;
 
     // Record circuit breaker success
     record_circuit_success(deps.storage, &key)?;
@@ -298,7 +425,43 @@ pub fn execute_issue(
         .add_attribute("executor_id", executor_id.to_string())
         .add_attribute("planner", planner)
         .add_attribute("issued_at", current_time.to_string())
-        .add_attribute("expires_at", expires_at.to_string()))
+        .add_attribute("expires_at", expires_at.to_string())
+        .add_attribute("is_escape", is_escape.to_string()))
+}
+
+pub fn execute_record_issue_failure(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    executor_id: u64,
+    action_id: Binary,
+    not_before: u64,
+    not_after: u64,
+) -> Result<Response, VagusError> {
+    let executors = AUTHORIZED_EXECUTORS.load(deps.storage)?;
+    if !executors.contains(&info.sender.to_string()) {
+        return Err(VagusError::Unauthorized);
+    }
+
+    // Only the rejections the breaker guards against are counted, re-derived
+    // from state rather than taken from the caller
+    let current_time = env.block.time.seconds();
+    let key = format!("{}_{}", executor_id, hex::encode(&action_id));
+    let Some(reason) =
+        issue_rejection(deps.storage, &key, executor_id, not_before, not_after, current_time)?
+    else {
+        return Ok(Response::new()
+            .add_attribute("action", "record_issue_failure")
+            .add_attribute("executor_id", executor_id.to_string()));
+    };
+    let cb = record_circuit_failure(deps.storage, &key, current_time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "record_issue_failure")
+        .add_attribute("executor_id", executor_id.to_string())
+        .add_attribute("rejected", reason.to_string())
+        .add_attribute("failure_count", cb.failure_count.to_string())
+        .add_attribute("circuit_state", format!("{:?}", cb.state)))
 }
 
 pub fn execute_revoke(
@@ -333,6 +496,18 @@ pub fn execute_revoke(
 
     TOKENS.save(deps.storage, token_id.clone(), &token)?;
 
+    // Drop the token from its executor's index
+    let executor_id = Uint128::try_from(token.executorId)
+        .ok()
+        .and_then(|id| u64::try_from(id.u128()).ok());
+    if let Some(executor_id) = executor_id {
+        EXECUTOR_TOKENS.update(deps.storage, executor_id, |token_ids| -> StdResult<_> {
+            let mut token_ids = token_ids.unwrap_or_default();
+            token_ids.retain(|id| id != &token_id);
+            Ok(token_ids)
+        })?;
+    }
+
     Ok(Response::new()
         .add_attribute("action", "revoke")
         .add_attribute("token_id", token_id)
@@ -340,14 +515,63 @@ pub fn execute_revoke(
         .add_attribute("revoked_at", current_time.to_string()))
 }
 
+pub fn execute_expire_tokens(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, VagusError> {
+    let limit = limit.unwrap_or(DEFAULT_EXPIRE_LIMIT).min(MAX_EXPIRE_LIMIT) as usize;
+    let current_time = env.block.time.seconds();
+
+    // Tokens are valid while expires_at > now, so everything at or below now is expired
+    let expired: Vec<(u64, String)> = EXPIRY_INDEX
+        .prefix_range(
+            deps.storage,
+            None,
+            Some(PrefixBound::inclusive(current_time)),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<_>>()?;
+
+    for (expires_at, token_id) in &expired {
+        if let Some(owner) = OWNERS.may_load(deps.storage, token_id.clone())? {
+            OWNED_TOKENS.remove(deps.storage, (owner, token_id.clone()));
+        }
+        OWNERS.remove(deps.storage, token_id.clone());
+        TOKENS.remove(deps.storage, token_id.clone());
+        EXPIRY_INDEX.remove(deps.storage, (*expires_at, token_id.clone()));
+    }
+
+    let token_ids: Vec<String> = expired.into_iter().map(|(_, token_id)| token_id).collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "expire_tokens")
+        .add_attribute("expired_count", token_ids.len().to_string())
+        .add_attribute("token_ids", token_ids.join(",")))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::IsValid { token_id } => to_json_binary(&query_is_valid(deps, _env, token_id)?),
-        QueryMsg::ActiveTokensOf { executor_id } => {
-            to_json_binary(&query_active_tokens_of(deps, _env, executor_id)?)
-        }
+        QueryMsg::ActiveTokensOf {
+            executor_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_active_tokens_of(
+            deps,
+            _env,
+            executor_id,
+            start_after,
+            limit,
+        )?),
         QueryMsg::TokenInfo { token_id } => to_json_binary(&query_token_info(deps, token_id)?),
+        QueryMsg::TokenOwner { token_id } => to_json_binary(&query_token_owner(deps, token_id)?),
+        QueryMsg::IsExecutorPaused { executor_id } => {
+            to_json_binary(&query_is_executor_paused(deps, executor_id)?)
+        }
     }
 }
 
@@ -360,22 +584,64 @@ fn query_is_valid(deps: Deps, env: Env, token_id: String) -> StdResult<IsValidRe
     // Check if expired or revoked
     let valid = !token.revoked && token.expiresAt > env.block.time.seconds().into();
 
+    // I1: only escape tokens stay valid through SHUTDOWN
+    if valid && !token.isEscape && ans_shutdown(deps)? {
+        return Ok(IsValidResponse { valid: false });
+    }
+
     Ok(IsValidResponse { valid })
 }
 
-fn query_active_tokens_of(deps: Deps, env: Env, executor_id: u64) -> StdResult<ActiveTokensOfResponse> {
-    // Simplified - in production would use more efficient indexing
-    let mut active_tokens = Vec::new();
+fn query_active_tokens_of(
+    deps: Deps,
+    env: Env,
+    executor_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ActiveTokensOfResponse> {
+    let limit = limit
+        .unwrap_or(DEFAULT_ACTIVE_TOKENS_LIMIT)
+        .min(MAX_ACTIVE_TOKENS_LIMIT) as usize;
     let current_time = env.block.time.seconds();
 
-    // This is inefficient for production - would need proper indexing
-    // For MVP, we'll iterate through all tokens (assuming small number)
-    // In production, maintain separate index: executor_id -> [token_ids]
+    let mut candidates = EXECUTOR_TOKENS
+        .may_load(deps.storage, executor_id)?
+        .unwrap_or_default();
+    candidates.sort_by(|a, b| token_id_order(a).cmp(&token_id_order(b)));
 
-    // Placeholder: return empty for now
-    Ok(ActiveTokensOfResponse {
-        token_ids: active_tokens,
-    })
+    // Page in token id order; start_after need not still be in the index
+    let mut token_ids = Vec::with_capacity(limit);
+    for token_id in candidates {
+        if token_ids.len() >= limit {
+            break;
+        }
+        if let Some(start_after) = &start_after {
+            if token_id_order(&token_id) <= token_id_order(start_after) {
+                continue;
+            }
+        }
+        if let Some(token) = TOKENS.may_load(deps.storage, token_id.clone())? {
+            if !token.revoked && token.expiresAt > current_time.into() {
+                token_ids.push(token_id);
+            }
+        }
+    }
+    Ok(ActiveTokensOfResponse { token_ids })
+}
+
+fn ans_shutdown(deps: Deps) -> StdResult<bool> {
+    let Some(ans_manager) = ANS_STATE_MANAGER.may_load(deps.storage)? else {
+        return Ok(false);
+    };
+    let current: vagus_spec::ans_state_manager::CurrentStateResponse = deps
+        .querier
+        .query_wasm_smart(&ans_manager, &vagus_spec::ans_state_manager::QueryMsg::CurrentState {})?;
+    Ok(current.state == ANSState::SHUTDOWN)
+}
+
+// Token ids are decimal counters, so order by length first to sort numerically
+fn token_id_order(token_id: &str) -> (usize, &str) {
+    (token_id.len(), token_id)
 }
 
 fn query_token_info(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse> {
@@ -383,14 +649,46 @@ fn query_token_info(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse
     Ok(TokenInfoResponse { token })
 }
 
+fn query_token_owner(deps: Deps, token_id: String) -> StdResult<TokenOwnerResponse> {
+    let owner = OWNERS.may_load(deps.storage, token_id)?;
+    Ok(TokenOwnerResponse { owner })
+}
+
+fn query_is_executor_paused(deps: Deps, executor_id: u64) -> StdResult<IsExecutorPausedResponse> {
+    Ok(IsExecutorPausedResponse {
+        paused: PAUSED_EXECUTORS.has(deps.storage, executor_id),
+    })
+}
+
+// Token ids of the executor that are neither revoked nor expired; revoked,
+// expired and garbage-collected entries are pruned lazily here
+fn active_executor_tokens(
+    storage: &dyn cosmwasm_std::Storage,
+    executor_id: u64,
+    current_time: u64,
+) -> StdResult<Vec<String>> {
+    let token_ids = EXECUTOR_TOKENS
+        .may_load(storage, executor_id)?
+        .unwrap_or_default();
+
+    let mut active = Vec::with_capacity(token_ids.len());
+    for token_id in token_ids {
+        if let Some(token) = TOKENS.may_load(storage, token_id.clone())? {
+            if !token.revoked && token.expiresAt > current_time.into() {
+                active.push(token_id);
+            }
+        }
+    }
+    Ok(active)
+}
+
 // Helper functions for rate limiting and circuit breaker
 
-fn check_circuit_breaker(
-    storage: &mut dyn cosmwasm_std::Storage,
+fn load_circuit_breaker(
+    storage: &dyn cosmwasm_std::Storage,
     key: &str,
-    current_time: u64,
-) -> Result<(), VagusError> {
-    let mut cb = CIRCUIT_BREAKERS
+) -> StdResult<CircuitBreaker> {
+    Ok(CIRCUIT_BREAKERS
         .may_load(storage, key.to_string())?
         .unwrap_or(CircuitBreaker {
             state: CircuitState::Closed,
@@ -398,7 +696,15 @@ fn check_circuit_breaker(
             last_failure_time: 0,
             success_count: 0,
             next_attempt_time: 0,
-        });
+        }))
+}
+
+fn check_circuit_breaker(
+    storage: &mut dyn cosmwasm_std::Storage,
+    key: &str,
+    current_time: u64,
+) -> Result<(), VagusError> {
+    let mut cb = load_circuit_breaker(storage, key)?;
 
     if matches!(cb.state, CircuitState::Open) {
         if current_time < cb.next_attempt_time {
@@ -413,19 +719,65 @@ fn check_circuit_breaker(
     Ok(())
 }
 
-fn check_rate_limit(
-    storage: &mut dyn cosmwasm_std::Storage,
+// Both sliding windows with `current_time` admitted; the executor window is
+// `None` when no per-executor limit is configured
+struct RateLimitWindows {
+    action: Vec<u64>,
+    executor: Option<Vec<u64>>,
+}
+
+// Checks the action and executor limits without writing either, so a request
+// refused by one limit is never counted against the other
+fn check_rate_limits(
+    storage: &dyn cosmwasm_std::Storage,
     key: &str,
+    executor_id: u64,
     current_time: u64,
-) -> Result<(), VagusError> {
+) -> Result<RateLimitWindows, VagusError> {
     let rate_limit = GLOBAL_RATE_LIMIT.load(storage)?;
-    let mut windows = RATE_LIMIT_WINDOWS
+    let mut action = RATE_LIMIT_WINDOWS
         .may_load(storage, key.to_string())?
         .unwrap_or_default();
+    admit_in_window(&mut action, &rate_limit, current_time)?;
+
+    let executor = match EXECUTOR_RATE_LIMIT.may_load(storage)? {
+        Some(rate_limit) => {
+            let mut windows = EXECUTOR_RATE_LIMIT_WINDOWS
+                .may_load(storage, executor_id)?
+                .unwrap_or_default();
+            admit_in_window(&mut windows, &rate_limit, current_time)?;
+            Some(windows)
+        }
+        None => None,
+    };
 
-    // Remove timestamps outside the window
-    let window_start = current_time.saturating_sub(rate_limit.window_size);
-    windows.retain(|&timestamp| timestamp > window_start);
+    Ok(RateLimitWindows { action, executor })
+}
+
+fn save_rate_limits(
+    storage: &mut dyn cosmwasm_std::Storage,
+    key: &str,
+    executor_id: u64,
+    windows: RateLimitWindows,
+) -> StdResult<()> {
+    RATE_LIMIT_WINDOWS.save(storage, key.to_string(), &windows.action)?;
+    if let Some(executor) = windows.executor {
+        EXECUTOR_RATE_LIMIT_WINDOWS.save(storage, executor_id, &executor)?;
+    }
+    Ok(())
+}
+
+// Sliding window: record `current_time` unless the window is already full
+fn admit_in_window(
+    windows: &mut Vec<u64>,
+    rate_limit: &RateLimitConfig,
+    current_time: u64,
+) -> Result<(), VagusError> {
+    // Remove timestamps outside the window; before `window_size` has elapsed
+    // since time 0, every timestamp is still inside it
+    if let Some(window_start) = current_time.checked_sub(rate_limit.window_size) {
+        windows.retain(|&timestamp| timestamp > window_start);
+    }
 
     // Check if we're over the limit
     if windows.len() >= rate_limit.max_requests as usize {
@@ -434,8 +786,6 @@ fn check_rate_limit(
 
     // Add current timestamp
     windows.push(current_time);
-    RATE_LIMIT_WINDOWS.save(storage, key.to_string(), &windows)?;
-
     Ok(())
 }
 
@@ -443,15 +793,7 @@ fn record_circuit_success(
     storage: &mut dyn cosmwasm_std::Storage,
     key: &str,
 ) -> Result<(), VagusError> {
-    let mut cb = CIRCUIT_BREAKERS
-        .may_load(storage, key.to_string())?
-        .unwrap_or(CircuitBreaker {
-            state: CircuitState::Closed,
-            failure_count: 0,
-            last_failure_time: 0,
-            success_count: 0,
-            next_attempt_time: 0,
-        });
+    let mut cb = load_circuit_breaker(storage, key)?;
 
     if matches!(cb.state, CircuitState::HalfOpen) {
         cb.success_count += 1;
@@ -471,6 +813,61 @@ fn record_circuit_success(
     Ok(())
 }
 
+// The rejection an issuance would meet now that counts towards the circuit
+// breaker, if any; nothing is counted while the breaker is still open
+fn issue_rejection(
+    storage: &dyn cosmwasm_std::Storage,
+    key: &str,
+    executor_id: u64,
+    not_before: u64,
+    not_after: u64,
+    current_time: u64,
+) -> Result<Option<VagusError>, VagusError> {
+    let cb = load_circuit_breaker(storage, key)?;
+    if matches!(cb.state, CircuitState::Open) && current_time < cb.next_attempt_time {
+        return Ok(None);
+    }
+
+    if current_time < not_before || current_time > not_after {
+        return Ok(Some(VagusError::IntentExpired));
+    }
+
+    match check_rate_limits(storage, key, executor_id, current_time) {
+        Ok(_) => {}
+        Err(VagusError::RateLimited) => return Ok(Some(VagusError::RateLimited)),
+        Err(err) => return Err(err),
+    }
+
+    let executor_tokens = active_executor_tokens(storage, executor_id, current_time)?;
+    if executor_tokens.len() as u64 >= MAX_ACTIVE_PER_EXECUTOR.load(storage)? {
+        return Ok(Some(VagusError::TooManyActiveTokens));
+    }
+
+    Ok(None)
+}
+
+// Counts a rejected issuance and opens the breaker at the threshold
+fn record_circuit_failure(
+    storage: &mut dyn cosmwasm_std::Storage,
+    key: &str,
+    current_time: u64,
+) -> Result<CircuitBreaker, VagusError> {
+    let mut cb = load_circuit_breaker(storage, key)?;
+    cb.failure_count += 1;
+    cb.last_failure_time = current_time;
+
+    // Any failure while half-open re-opens the breaker immediately
+    let threshold = CIRCUIT_BREAKER_THRESHOLD.load(storage)?;
+    if matches!(cb.state, CircuitState::HalfOpen) || cb.failure_count >= threshold {
+        cb.state = CircuitState::Open;
+        cb.success_count = 0;
+        cb.next_attempt_time = current_time + CIRCUIT_BREAKER_TIMEOUT.load(storage)?;
+    }
+
+    CIRCUIT_BREAKERS.save(storage, key.to_string(), &cb)?;
+    Ok(cb)
+}
+
 // Governance execution functions
 
 pub fn execute_set_reflex_arc(
@@ -516,6 +913,34 @@ pub fn execute_set_rate_limit(
         .add_attribute("max_requests", max_requests.to_string()))
 }
 
+pub fn execute_set_executor_rate_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    rate_limit: Option<RateLimitConfig>,
+) -> Result<Response, VagusError> {
+    // Only DAO can set rate limits
+    let dao = VAGUS_DAO.load(deps.storage)?;
+    if info.sender.to_string() != dao {
+        return Err(VagusError::Unauthorized);
+    }
+
+    let mut response = Response::new().add_attribute("action", "set_executor_rate_limit");
+    match rate_limit {
+        Some(rate_limit) => {
+            EXECUTOR_RATE_LIMIT.save(deps.storage, &rate_limit)?;
+            response = response
+                .add_attribute("window_size", rate_limit.window_size.to_string())
+                .add_attribute("max_requests", rate_limit.max_requests.to_string());
+        }
+        None => {
+            EXECUTOR_RATE_LIMIT.remove(deps.storage);
+            response = response.add_attribute("rate_limit", "none");
+        }
+    }
+
+    Ok(response)
+}
+
 pub fn execute_set_circuit_breaker_params(
     deps: DepsMut,
     info: MessageInfo,
@@ -540,6 +965,24 @@ pub fn execute_set_circuit_breaker_params(
         .add_attribute("recovery", recovery.to_string()))
 }
 
+pub fn execute_set_max_active_per_executor(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_active: u64,
+) -> Result<Response, VagusError> {
+    // Only DAO can set the active token cap
+    let dao = VAGUS_DAO.load(deps.storage)?;
+    if info.sender.to_string() != dao {
+        return Err(VagusError::Unauthorized);
+    }
+
+    MAX_ACTIVE_PER_EXECUTOR.save(deps.storage, &max_active)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_active_per_executor")
+        .add_attribute("max_active", max_active.to_string()))
+}
+
 pub fn execute_emergency_pause(
     deps: DepsMut,
     info: MessageInfo,
@@ -571,3 +1014,966 @@ pub fn execute_emergency_unpause(
     Ok(Response::new()
         .add_attribute("action", "emergency_unpause"))
 }
+
+pub fn execute_set_executor_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    executor_id: u64,
+    paused: bool,
+) -> Result<Response, VagusError> {
+    // Only DAO can pause or unpause an executor
+    let dao = VAGUS_DAO.load(deps.storage)?;
+    if info.sender.to_string() != dao {
+        return Err(VagusError::Unauthorized);
+    }
+
+    let action = if paused {
+        PAUSED_EXECUTORS.save(deps.storage, executor_id, &())?;
+        "pause_executor"
+    } else {
+        PAUSED_EXECUTORS.remove(deps.storage, executor_id);
+        "unpause_executor"
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("executor_id", executor_id.to_string()))
+}
+
+// Helper modules for cross-contract calls
+pub mod vagus_spec {
+    use super::*;
+
+    pub mod ans_state_manager {
+        use super::*;
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum QueryMsg {
+            CurrentState {},
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct CurrentStateResponse {
+            pub state: ANSState,
+            pub since: u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{Addr, Empty, OwnedDeps};
+    use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+    use proptest::prelude::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    const DAO: &str = "dao";
+    const EXECUTOR: &str = "executor";
+    const PLANNER: &str = "planner";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(DAO, &[]),
+            InstantiateMsg {
+                authorized_executors: vec![EXECUTOR.to_string()],
+                reflex_arc: None,
+                ans_state_manager: None,
+                vagus_dao: DAO.to_string(),
+                rate_limit_window_size: None,
+                rate_limit_max_requests: None,
+                circuit_breaker_threshold: None,
+                circuit_breaker_timeout: None,
+                circuit_breaker_recovery: None,
+                max_active_per_executor: None,
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    fn issuer_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+
+    // Multi-test counterpart of `setup`
+    fn deploy_issuer(app: &mut App) -> Addr {
+        let code_id = app.store_code(issuer_contract());
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(DAO),
+            &InstantiateMsg {
+                authorized_executors: vec![EXECUTOR.to_string()],
+                reflex_arc: None,
+                ans_state_manager: None,
+                vagus_dao: DAO.to_string(),
+                rate_limit_window_size: None,
+                rate_limit_max_requests: None,
+                circuit_breaker_threshold: None,
+                circuit_breaker_timeout: None,
+                circuit_breaker_recovery: None,
+                max_active_per_executor: None,
+            },
+            &[],
+            "CapabilityIssuer",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn issued_token_id(res: &AppResponse) -> String {
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == "token_id")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    fn issue_msg(env: &Env, executor_id: u64, action: u8, expires_at: u64) -> ExecuteMsg {
+        let now = env.block.time.seconds();
+        ExecuteMsg::Issue {
+            intent_executor_id: executor_id,
+            intent_action_id: Binary::from([action; 32]),
+            intent_params: Binary::default(),
+            intent_envelope_hash: Binary::from([0u8; 32]),
+            intent_pre_state_root: Binary::from([0u8; 32]),
+            intent_not_before: now,
+            intent_not_after: now + 3600,
+            intent_max_duration_ms: 1000,
+            intent_max_energy_j: 10,
+            intent_planner: PLANNER.to_string(),
+            intent_nonce: next_nonce(),
+            intent_is_escape: false,
+            scaled_limits_hash: Binary::from([0u8; 32]),
+            expires_at,
+        }
+    }
+
+    // Fresh nonce for every issued intent, as a planner would use
+    fn next_nonce() -> u64 {
+        static NONCE: AtomicU64 = AtomicU64::new(1);
+        NONCE.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn issue(deps: DepsMut, env: &Env, executor_id: u64, action: u8, expires_at: u64) -> String {
+        let res = execute(
+            deps,
+            env.clone(),
+            mock_info(EXECUTOR, &[]),
+            issue_msg(env, executor_id, action, expires_at),
+        )
+        .unwrap();
+        res.attributes
+            .iter()
+            .find(|a| a.key == "token_id")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_replayed_nonce_is_rejected() {
+        let mut deps = setup();
+        let env = mock_env();
+        let expires_at = env.block.time.seconds() + 600;
+        let issue_as = |deps: DepsMut, planner: &str, nonce: u64| {
+            let mut msg = issue_msg(&env, 1, 1, expires_at);
+            if let ExecuteMsg::Issue {
+                intent_planner,
+                intent_nonce,
+                ..
+            } = &mut msg
+            {
+                *intent_planner = planner.to_string();
+                *intent_nonce = nonce;
+            }
+            execute(deps, env.clone(), mock_info(EXECUTOR, &[]), msg)
+        };
+
+        issue_as(deps.as_mut(), PLANNER, 7).unwrap();
+        let err = issue_as(deps.as_mut(), PLANNER, 7).unwrap_err();
+        assert!(matches!(err, VagusError::NonceAlreadyUsed));
+
+        // Nonces are per planner
+        issue_as(deps.as_mut(), "other_planner", 7).unwrap();
+    }
+
+    #[test]
+    fn test_expiry_must_fall_within_intent_window() {
+        let mut deps = setup();
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let issue_with = |deps: DepsMut, not_before: u64, not_after: u64, expires_at: u64| {
+            let mut msg = issue_msg(&env, 1, 1, expires_at);
+            if let ExecuteMsg::Issue {
+                intent_not_before,
+                intent_not_after,
+                ..
+            } = &mut msg
+            {
+                *intent_not_before = not_before;
+                *intent_not_after = not_after;
+            }
+            execute(deps, env.clone(), mock_info(EXECUTOR, &[]), msg)
+        };
+
+        for (not_before, not_after, expires_at) in [
+            // Inverted window
+            (now + 1, now, now + 1),
+            // Token outlives the window
+            (now, now + 600, now + 601),
+            // Token already expired
+            (now, now + 600, now),
+        ] {
+            let err = issue_with(deps.as_mut(), not_before, not_after, expires_at).unwrap_err();
+            assert!(matches!(err, VagusError::InvalidInput));
+        }
+
+        // Expiring exactly at the end of the window, or just after now, is fine
+        issue_with(deps.as_mut(), now, now + 600, now + 600).unwrap();
+        issue_with(deps.as_mut(), now, now + 600, now + 1).unwrap();
+    }
+
+    #[test]
+    fn test_executor_rate_limit_spans_actions() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let now = env.block.time.seconds();
+        let set_limit = ExecuteMsg::SetExecutorRateLimit {
+            rate_limit: Some(RateLimitConfig {
+                window_size: 60,
+                max_requests: 3,
+            }),
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(EXECUTOR, &[]),
+            set_limit.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::Unauthorized));
+        execute(deps.as_mut(), env.clone(), mock_info(DAO, &[]), set_limit).unwrap();
+
+        // Each action is well under its own limit, but the executor is not
+        for action in 1..=3 {
+            issue(deps.as_mut(), &env, 1, action, now + 600);
+        }
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(EXECUTOR, &[]),
+            issue_msg(&env, 1, 4, now + 600),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VagusError::RateLimited));
+        // The refused request is not counted against the action's own window
+        let key = format!("1_{}", hex::encode([4u8; 32]));
+        assert!(!RATE_LIMIT_WINDOWS.has(&deps.storage, key));
+
+        // Other executors have their own window
+        issue(deps.as_mut(), &env, 2, 1, now + 600);
+
+        env.block.time = env.block.time.plus_seconds(60);
+        issue(deps.as_mut(), &env, 1, 4, now + 600);
+    }
+
+    #[test]
+    fn test_expire_tokens_prunes_only_expired() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let now = env.block.time.seconds();
+
+        let t1 = issue(deps.as_mut(), &env, 1, 1, now + 100);
+        let t2 = issue(deps.as_mut(), &env, 1, 2, now + 200);
+        let t3 = issue(deps.as_mut(), &env, 1, 3, now + 300);
+        assert_eq!(
+            EXPIRY_INDEX
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            3
+        );
+
+        // Nothing has expired yet
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireTokens { limit: None },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "expired_count" && a.value == "0"));
+
+        env.block.time = env.block.time.plus_seconds(200);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireTokens { limit: None },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "expired_count" && a.value == "2"));
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "token_ids" && a.value == format!("{},{}", t1, t2)));
+
+        assert!(TOKENS
+            .may_load(&deps.storage, t1.clone())
+            .unwrap()
+            .is_none());
+        assert!(TOKENS
+            .may_load(&deps.storage, t2.clone())
+            .unwrap()
+            .is_none());
+        assert!(OWNERS.may_load(&deps.storage, t1).unwrap().is_none());
+        assert!(TOKENS
+            .may_load(&deps.storage, t3.clone())
+            .unwrap()
+            .is_some());
+
+        let remaining: Vec<(u64, String)> = EXPIRY_INDEX
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()
+            .unwrap();
+        assert_eq!(remaining, vec![(now + 300, t3)]);
+    }
+
+    #[test]
+    fn test_expire_tokens_respects_limit() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let now = env.block.time.seconds();
+
+        for action in 1..=3 {
+            issue(deps.as_mut(), &env, 1, action, now + 10);
+        }
+
+        env.block.time = env.block.time.plus_seconds(10);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireTokens { limit: Some(2) },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "expired_count" && a.value == "2"));
+        assert_eq!(
+            EXPIRY_INDEX
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_only_escape_tokens_stay_valid_in_shutdown() {
+        use cosmwasm_std::{ContractResult, SystemResult};
+
+        const ANS: &str = "ans";
+        fn set_ans_state(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, state: ANSState) {
+            deps.querier.update_wasm(move |_| {
+                let response = vagus_spec::ans_state_manager::CurrentStateResponse {
+                    state: state.clone(),
+                    since: 0,
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            });
+        }
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(DAO, &[]),
+            InstantiateMsg {
+                authorized_executors: vec![EXECUTOR.to_string()],
+                reflex_arc: None,
+                ans_state_manager: Some(ANS.to_string()),
+                vagus_dao: DAO.to_string(),
+                rate_limit_window_size: None,
+                rate_limit_max_requests: None,
+                circuit_breaker_threshold: None,
+                circuit_breaker_timeout: None,
+                circuit_breaker_recovery: None,
+                max_active_per_executor: None,
+            },
+        )
+        .unwrap();
+        set_ans_state(&mut deps, ANSState::SAFE);
+
+        let env = mock_env();
+        let expires_at = env.block.time.seconds() + 600;
+        let ordinary = issue(deps.as_mut(), &env, 1, 1, expires_at);
+        let mut msg = issue_msg(&env, 1, 2, expires_at);
+        if let ExecuteMsg::Issue { intent_is_escape, .. } = &mut msg {
+            *intent_is_escape = true;
+        }
+        let res = execute(deps.as_mut(), env.clone(), mock_info(EXECUTOR, &[]), msg).unwrap();
+        let escape = res
+            .attributes
+            .iter()
+            .find(|a| a.key == "token_id")
+            .unwrap()
+            .value
+            .clone();
+
+        let token = query_token_info(deps.as_ref(), escape.clone()).unwrap().token.unwrap();
+        assert!(token.isEscape);
+        let is_valid = |deps: Deps, token_id: &str| {
+            query_is_valid(deps, env.clone(), token_id.to_string()).unwrap().valid
+        };
+        assert!(is_valid(deps.as_ref(), &ordinary));
+        assert!(is_valid(deps.as_ref(), &escape));
+
+        set_ans_state(&mut deps, ANSState::SHUTDOWN);
+        assert!(!is_valid(deps.as_ref(), &ordinary));
+        assert!(is_valid(deps.as_ref(), &escape));
+
+        // Leaving SHUTDOWN restores ordinary tokens that were never revoked
+        set_ans_state(&mut deps, ANSState::DANGER);
+        assert!(is_valid(deps.as_ref(), &ordinary));
+    }
+
+    #[test]
+    fn test_repeated_failures_open_circuit_breaker() {
+        let mut app = App::default();
+        let issuer = deploy_issuer(&mut app);
+        for msg in [
+            ExecuteMsg::SetRateLimit {
+                window_size: 60,
+                max_requests: 1,
+            },
+            ExecuteMsg::SetCircuitBreakerParams {
+                threshold: 3,
+                timeout: 120,
+                recovery: 1,
+            },
+        ] {
+            app.execute_contract(Addr::unchecked(DAO), issuer.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        let now = app.block_info().time.seconds();
+        let try_issue = |app: &mut App, action: u8| {
+            let env = Env {
+                block: app.block_info(),
+                ..mock_env()
+            };
+            app.execute_contract(
+                Addr::unchecked(EXECUTOR),
+                issuer.clone(),
+                &issue_msg(&env, 1, action, now + 3600),
+                &[],
+            )
+        };
+        let record_failure = |app: &mut App, action: u8| {
+            app.execute_contract(
+                Addr::unchecked(EXECUTOR),
+                issuer.clone(),
+                &ExecuteMsg::RecordIssueFailure {
+                    executor_id: 1,
+                    action_id: Binary::from([action; 32]),
+                    not_before: now,
+                    not_after: now + 3600,
+                },
+                &[],
+            )
+            .unwrap()
+        };
+        let attribute = |res: &AppResponse, key: &str| {
+            res.events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .find(|a| a.key == key)
+                .map(|a| a.value.clone())
+        };
+
+        // Nothing to count while issuance would succeed
+        let res = record_failure(&mut app, 1);
+        assert_eq!(attribute(&res, "failure_count"), None);
+
+        // The first issue uses up the rate limit; the next ones revert, and
+        // each reported failure is counted
+        try_issue(&mut app, 1).unwrap();
+        for failures in 1..=2 {
+            let err = try_issue(&mut app, 1).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<VagusError>(),
+                Some(VagusError::RateLimited)
+            ));
+            let res = record_failure(&mut app, 1);
+            assert_eq!(
+                attribute(&res, "rejected").as_deref(),
+                Some("Request rate exceeds configured limits")
+            );
+            assert_eq!(attribute(&res, "failure_count"), Some(failures.to_string()));
+            assert_eq!(attribute(&res, "circuit_state").as_deref(), Some("Closed"));
+        }
+
+        // The third failure reaches the threshold
+        try_issue(&mut app, 1).unwrap_err();
+        let res = record_failure(&mut app, 1);
+        assert_eq!(attribute(&res, "circuit_state").as_deref(), Some("Open"));
+
+        // Open: rejected even after the rate-limit window has passed, and the
+        // rejection is not counted again
+        app.update_block(|block| block.time = block.time.plus_seconds(61));
+        let err = try_issue(&mut app, 1).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::CircuitBreakerOpen)
+        ));
+        let res = record_failure(&mut app, 1);
+        assert_eq!(attribute(&res, "failure_count"), None);
+
+        // Other actions of the executor are unaffected
+        try_issue(&mut app, 2).unwrap();
+
+        // After the timeout the breaker half-opens and a success closes it
+        app.update_block(|block| block.time = block.time.plus_seconds(60));
+        try_issue(&mut app, 1).unwrap();
+
+        // Only authorized executors may report failures
+        let err = app
+            .execute_contract(
+                Addr::unchecked("stranger"),
+                issuer.clone(),
+                &ExecuteMsg::RecordIssueFailure {
+                    executor_id: 1,
+                    action_id: Binary::from([1u8; 32]),
+                    not_before: now,
+                    not_after: now + 3600,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn test_issue_rejected_beyond_active_cap() {
+        let mut app = App::default();
+        let issuer = deploy_issuer(&mut app);
+
+        // Only the DAO may change the cap
+        let set_cap = ExecuteMsg::SetMaxActivePerExecutor { max_active: 3 };
+        assert!(app
+            .execute_contract(Addr::unchecked(EXECUTOR), issuer.clone(), &set_cap, &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked(DAO), issuer.clone(), &set_cap, &[])
+            .unwrap();
+
+        let block = app.block_info();
+        let env = Env {
+            block: block.clone(),
+            ..mock_env()
+        };
+        let expires_at = block.time.seconds() + 600;
+        let issue_as_executor = |app: &mut App, executor_id: u64, action: u8| {
+            app.execute_contract(
+                Addr::unchecked(EXECUTOR),
+                issuer.clone(),
+                &issue_msg(&env, executor_id, action, expires_at),
+                &[],
+            )
+        };
+
+        let mut token_ids = Vec::new();
+        for action in 1..=3 {
+            let res = issue_as_executor(&mut app, 1, action).unwrap();
+            token_ids.push(issued_token_id(&res));
+        }
+
+        // At the cap: the next issue for this executor is rejected
+        let err = issue_as_executor(&mut app, 1, 4).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::TooManyActiveTokens)
+        ));
+
+        // The cap is per executor
+        issue_as_executor(&mut app, 2, 1).unwrap();
+
+        // Revoking one frees a slot
+        app.execute_contract(
+            Addr::unchecked(PLANNER),
+            issuer.clone(),
+            &ExecuteMsg::Revoke {
+                token_id: token_ids[0].clone(),
+                reason: CapabilityRevocationReason::OWNER_REVOCATION,
+            },
+            &[],
+        )
+        .unwrap();
+        issue_as_executor(&mut app, 1, 4).unwrap();
+        assert!(issue_as_executor(&mut app, 1, 5).is_err());
+    }
+
+    #[test]
+    fn test_paused_executor_is_blocked_while_others_issue() {
+        let mut app = App::default();
+        let issuer = deploy_issuer(&mut app);
+
+        let block = app.block_info();
+        let env = Env {
+            block: block.clone(),
+            ..mock_env()
+        };
+        let expires_at = block.time.seconds() + 600;
+        let issue_as_executor = |app: &mut App, executor_id: u64, action: u8| {
+            app.execute_contract(
+                Addr::unchecked(EXECUTOR),
+                issuer.clone(),
+                &issue_msg(&env, executor_id, action, expires_at),
+                &[],
+            )
+        };
+        let is_paused = |app: &App, executor_id: u64| {
+            app.wrap()
+                .query_wasm_smart::<IsExecutorPausedResponse>(
+                    issuer.clone(),
+                    &QueryMsg::IsExecutorPaused { executor_id },
+                )
+                .unwrap()
+                .paused
+        };
+
+        // Only the DAO may pause an executor
+        let pause = ExecuteMsg::PauseExecutor { executor_id: 1 };
+        assert!(app
+            .execute_contract(Addr::unchecked(EXECUTOR), issuer.clone(), &pause, &[])
+            .is_err());
+        app.execute_contract(Addr::unchecked(DAO), issuer.clone(), &pause, &[])
+            .unwrap();
+        assert!(is_paused(&app, 1));
+        assert!(!is_paused(&app, 2));
+
+        // The paused executor is blocked; the other keeps issuing
+        let err = issue_as_executor(&mut app, 1, 1).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::ExecutorPaused)
+        ));
+        issue_as_executor(&mut app, 2, 1).unwrap();
+        issue_as_executor(&mut app, 2, 2).unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(DAO),
+            issuer.clone(),
+            &ExecuteMsg::UnpauseExecutor { executor_id: 1 },
+            &[],
+        )
+        .unwrap();
+        assert!(!is_paused(&app, 1));
+        issue_as_executor(&mut app, 1, 1).unwrap();
+    }
+
+    #[test]
+    fn test_active_tokens_of_pages_through_all_tokens() {
+        let mut deps = setup();
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(DAO, &[]),
+            ExecuteMsg::SetMaxActivePerExecutor { max_active: 200 },
+        )
+        .unwrap();
+
+        // Distinct actions keep each issuance in its own rate-limit window
+        let issued: Vec<String> = (0..150u8)
+            .map(|action| issue(deps.as_mut(), &env, 1, action, now + 600))
+            .collect();
+
+        let page = |start_after: Option<String>, limit: Option<u32>| {
+            query_active_tokens_of(deps.as_ref(), env.clone(), 1, start_after, limit)
+                .unwrap()
+                .token_ids
+        };
+
+        // Oversized limits are clamped
+        assert_eq!(page(None, Some(1000)).len(), MAX_ACTIVE_TOKENS_LIMIT as usize);
+        assert_eq!(page(None, None).len(), DEFAULT_ACTIVE_TOKENS_LIMIT as usize);
+
+        let mut walked = Vec::new();
+        let mut start_after = None;
+        loop {
+            let token_ids = page(start_after, Some(40));
+            if token_ids.is_empty() {
+                break;
+            }
+            start_after = token_ids.last().cloned();
+            walked.extend(token_ids);
+        }
+        assert_eq!(walked, issued);
+    }
+
+    #[test]
+    fn test_active_tokens_of_lists_each_executors_live_tokens() {
+        let mut app = App::default();
+        let issuer = deploy_issuer(&mut app);
+
+        let env = Env {
+            block: app.block_info(),
+            ..mock_env()
+        };
+        let now = env.block.time.seconds();
+        let issue_as_executor = |app: &mut App, executor_id: u64, action: u8, expires_at: u64| {
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(EXECUTOR),
+                    issuer.clone(),
+                    &issue_msg(&env, executor_id, action, expires_at),
+                    &[],
+                )
+                .unwrap();
+            issued_token_id(&res)
+        };
+        let active_tokens_of = |app: &App, executor_id: u64| {
+            app.wrap()
+                .query_wasm_smart::<ActiveTokensOfResponse>(
+                    issuer.clone(),
+                    &QueryMsg::ActiveTokensOf {
+                        executor_id,
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+                .token_ids
+        };
+
+        let a1 = issue_as_executor(&mut app, 1, 1, now + 600);
+        let a2 = issue_as_executor(&mut app, 1, 2, now + 600);
+        let a3 = issue_as_executor(&mut app, 1, 3, now + 60);
+        let b1 = issue_as_executor(&mut app, 2, 1, now + 600);
+        let b2 = issue_as_executor(&mut app, 2, 2, now + 600);
+        assert_eq!(active_tokens_of(&app, 1), vec![a1.clone(), a2.clone(), a3]);
+        assert_eq!(active_tokens_of(&app, 2), vec![b1.clone(), b2.clone()]);
+        assert!(active_tokens_of(&app, 3).is_empty());
+
+        // Revoked and expired tokens drop out
+        app.execute_contract(
+            Addr::unchecked(PLANNER),
+            issuer.clone(),
+            &ExecuteMsg::Revoke {
+                token_id: a2,
+                reason: CapabilityRevocationReason::OWNER_REVOCATION,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(60));
+
+        assert_eq!(active_tokens_of(&app, 1), vec![a1]);
+        assert_eq!(active_tokens_of(&app, 2), vec![b1, b2]);
+    }
+
+    #[test]
+    fn test_token_owner_matches_issuing_planner() {
+        let mut app = App::default();
+        let issuer = deploy_issuer(&mut app);
+
+        let env = Env {
+            block: app.block_info(),
+            ..mock_env()
+        };
+        let now = env.block.time.seconds();
+        let mut issue_for_planner = |planner: &str, action: u8| {
+            let mut msg = issue_msg(&env, 1, action, now + 600);
+            if let ExecuteMsg::Issue { intent_planner, .. } = &mut msg {
+                *intent_planner = planner.to_string();
+            }
+            let res = app
+                .execute_contract(Addr::unchecked(EXECUTOR), issuer.clone(), &msg, &[])
+                .unwrap();
+            issued_token_id(&res)
+        };
+
+        let first = issue_for_planner(PLANNER, 1);
+        let second = issue_for_planner("other_planner", 2);
+
+        let owner_of = |token_id: &str| {
+            app.wrap()
+                .query_wasm_smart::<TokenOwnerResponse>(
+                    issuer.clone(),
+                    &QueryMsg::TokenOwner {
+                        token_id: token_id.to_string(),
+                    },
+                )
+                .unwrap()
+                .owner
+        };
+        assert_eq!(owner_of(&first), Some(PLANNER.to_string()));
+        assert_eq!(owner_of(&second), Some("other_planner".to_string()));
+        assert_eq!(owner_of("999"), None);
+    }
+
+    /// Request times in block order: bursts sharing a block time, separated by random gaps
+    fn clustered_bursts() -> impl Strategy<Value = Vec<u64>> {
+        prop::collection::vec((0..50u64, 1..8usize), 1..20).prop_map(|bursts| {
+            let mut now = 0;
+            bursts
+                .into_iter()
+                .flat_map(|(gap, size)| {
+                    now += gap;
+                    std::iter::repeat_n(now, size)
+                })
+                .collect()
+        })
+    }
+
+    /// Request times at a fixed interval, possibly zero
+    fn evenly_spaced() -> impl Strategy<Value = Vec<u64>> {
+        (0..100u64, 0..20u64, 1..60u64)
+            .prop_map(|(start, interval, count)| (0..count).map(|i| start + i * interval).collect())
+    }
+
+    /// Replay `timestamps` through the limiter and check every window of
+    /// `window_size` ending at an admitted request
+    fn check_sliding_window(
+        timestamps: &[u64],
+        window_size: u64,
+        max_requests: u64,
+    ) -> Result<(), TestCaseError> {
+        let rate_limit = RateLimitConfig { window_size, max_requests };
+        let mut windows = Vec::new();
+        let admitted: Vec<u64> = timestamps
+            .iter()
+            .copied()
+            .filter(|&now| admit_in_window(&mut windows, &rate_limit, now).is_ok())
+            .collect();
+
+        for &end in &admitted {
+            let in_window = admitted
+                .iter()
+                .filter(|&&timestamp| timestamp <= end && timestamp + window_size > end)
+                .count();
+            prop_assert!(
+                in_window as u64 <= max_requests,
+                "{} requests admitted in the window ending at {} (admitted: {:?})",
+                in_window,
+                end,
+                admitted
+            );
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_rate_limit_holds_for_bursts(
+            timestamps in clustered_bursts(),
+            window_size in 1..100u64,
+            max_requests in 1..10u64,
+        ) {
+            check_sliding_window(&timestamps, window_size, max_requests)?;
+        }
+
+        #[test]
+        fn test_rate_limit_holds_for_even_spacing(
+            timestamps in evenly_spaced(),
+            window_size in 1..100u64,
+            max_requests in 1..10u64,
+        ) {
+            check_sliding_window(&timestamps, window_size, max_requests)?;
+        }
+    }
+
+    /// One request against the breaker: seconds since the previous request,
+    /// and whether the issuance succeeds if the breaker serves it
+    type BreakerStep = (u64, bool);
+
+    /// Replay `steps` through the breaker and check its state machine after each one
+    fn check_breaker_trace(
+        threshold: u64,
+        timeout: u64,
+        recovery: u64,
+        steps: &[BreakerStep],
+    ) -> Result<(), TestCaseError> {
+        let mut storage = MockStorage::new();
+        CIRCUIT_BREAKER_THRESHOLD.save(&mut storage, &threshold).unwrap();
+        CIRCUIT_BREAKER_TIMEOUT.save(&mut storage, &timeout).unwrap();
+        CIRCUIT_BREAKER_RECOVERY.save(&mut storage, &recovery).unwrap();
+        let key = "1_01";
+
+        let mut now = 0;
+        let mut half_open_successes = 0;
+        let mut trace = Vec::new();
+        for &(elapsed, succeeds) in steps {
+            now += elapsed;
+            let before = load_circuit_breaker(&storage, key).unwrap();
+            let served = check_circuit_breaker(&mut storage, key, now).is_ok();
+            trace.push(format!("t={} {:?} served={} succeeds={}", now, before.state, served, succeeds));
+
+            let blocked = before.state == CircuitState::Open && now < before.next_attempt_time;
+            prop_assert_eq!(served, !blocked, "breaker served while open: {:?}", trace);
+            if !served {
+                continue;
+            }
+
+            let checked = load_circuit_breaker(&storage, key).unwrap();
+            if before.state == CircuitState::Open && checked.state == CircuitState::HalfOpen {
+                half_open_successes = 0;
+            }
+            if succeeds {
+                record_circuit_success(&mut storage, key).unwrap();
+                if checked.state == CircuitState::HalfOpen {
+                    half_open_successes += 1;
+                }
+            } else {
+                record_circuit_failure(&mut storage, key, now).unwrap();
+            }
+
+            let after = load_circuit_breaker(&storage, key).unwrap();
+            if checked.state == CircuitState::HalfOpen && after.state == CircuitState::Closed {
+                prop_assert_eq!(
+                    half_open_successes,
+                    recovery,
+                    "closed without {} consecutive successes: {:?}",
+                    recovery,
+                    trace
+                );
+            }
+            if after.state == CircuitState::Closed && succeeds {
+                prop_assert_eq!(after.failure_count, 0, "failures kept on close: {:?}", trace);
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_circuit_breaker_state_machine(
+            threshold in 1..6u64,
+            timeout in 1..100u64,
+            recovery in 1..5u64,
+            steps in prop::collection::vec((0..150u64, any::<bool>()), 1..60),
+        ) {
+            check_breaker_trace(threshold, timeout, recovery, &steps)?;
+        }
+    }
+}
@@ -1,18 +1,29 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    WasmMsg, SubMsg,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response,
+    StdResult, SubMsg, SubMsgResult, Uint128, Uint256, WasmMsg,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
-use vagus_spec::{CapabilityRevocationReason, VagusError};
+use ::vagus_spec::{ANSState, CapabilityRevocationReason, ScalingFactor, VagalToneIndicator, VagusError};
 
 // State
 pub const AFFerent_INBOX: Item<String> = Item::new("afferent_inbox");
 pub const CAPABILITY_ISSUER: Item<String> = Item::new("capability_issuer");
-pub const LAST_TRIGGER: Item<u64> = Item::new("last_trigger");
+pub const ANS_STATE_MANAGER: Item<String> = Item::new("ans_state_manager");
+pub const REFLEX_STATE: Item<ReflexState> = Item::new("reflex_state");
 pub const REFLEX_COOLDOWN: Item<u64> = Item::new("reflex_cooldown");
+pub const ADMIN: Item<String> = Item::new("admin");
 
-// Reflex thresholds (simplified)
+// Per-action response policy; actions without an entry are revoked
+pub const ACTION_RESPONSES: Map<&[u8], ReflexResponse> = Map::new("action_responses");
+
+// Page size when listing an executor's active tokens on the issuer
+const ACTIVE_TOKENS_PAGE_LIMIT: u32 = 100;
+
+// Reply id of the SHUTDOWN escalation to the ANS manager
+const SHUTDOWN_REPLY_ID: u64 = 1;
+
+// Reflex thresholds; a tone below a threshold is that dangerous, as in the ANS
 pub const DANGER_VTI_THRESHOLD: Item<u64> = Item::new("danger_vti_threshold");
 pub const SHUTDOWN_VTI_THRESHOLD: Item<u64> = Item::new("shutdown_vti_threshold");
 
@@ -20,15 +31,40 @@ pub const SHUTDOWN_VTI_THRESHOLD: Item<u64> = Item::new("shutdown_vti_threshold"
 pub struct InstantiateMsg {
     pub afferent_inbox: String,
     pub capability_issuer: String,
+    pub ans_state_manager: String,
     pub reflex_cooldown: u64,
     pub danger_vti_threshold: u64,
     pub shutdown_vti_threshold: u64,
 }
 
+/// What the reflex does when dangerous evidence arrives for an action
+#[cosmwasm_schema::cw_serde]
+pub enum ReflexResponse {
+    /// Revoke all of the executor's capabilities
+    Revoke {},
+    /// Cap the action's scaling on the ANS manager, in basis points
+    Scale { cap: u64 },
+    /// Revoke all of the executor's capabilities and force the ANS to SHUTDOWN;
+    /// the reflex must be an authorized oracle on the ANS manager, or only the
+    /// revocation takes effect
+    Shutdown {},
+}
+
+impl ReflexResponse {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReflexResponse::Revoke {} => "revoke",
+            ReflexResponse::Scale { .. } => "scale",
+            ReflexResponse::Shutdown {} => "shutdown",
+        }
+    }
+}
+
 #[cosmwasm_schema::cw_serde]
 pub enum ExecuteMsg {
     OnAEP {
         executor_id: u64,
+        action_id: Option<Binary>,
         metrics_hash_sha256: Binary,
         metrics_hash_keccak: Binary,
     },
@@ -36,35 +72,79 @@ pub enum ExecuteMsg {
         executor_id: u64,
         reason: String,
     },
+    // Admin response policy
+    SetActionResponse {
+        action_id: Binary,
+        response: Option<ReflexResponse>,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
 pub enum QueryMsg {
-    // No queries for this contract
+    ReflexState {},
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct ReflexStateResponse {
+    pub state: ReflexState,
+}
+
+/// Cooldown state machine deciding whether a trigger may fire
+#[cosmwasm_schema::cw_serde]
+pub enum ReflexState {
+    /// Ready to trigger; remembers the severity of the last trigger
+    Idle { last_severity: Option<DangerLevel> },
+    /// Triggered at `severity`; until `until` (seconds) only a more severe
+    /// danger triggers again
+    Cooling { until: u64, severity: DangerLevel },
+}
+
+impl ReflexState {
+    /// The state as of `now`; cooling ends once `until` is reached
+    pub fn at(self, now: u64) -> Self {
+        match self {
+            ReflexState::Cooling { until, severity } if now >= until => ReflexState::Idle {
+                last_severity: Some(severity),
+            },
+            state => state,
+        }
+    }
+
+    /// Whether a trigger at `severity` may fire in this state
+    pub fn admits(&self, severity: DangerLevel) -> bool {
+        match self {
+            ReflexState::Idle { .. } => true,
+            ReflexState::Cooling { severity: cooling, .. } => severity > *cooling,
+        }
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, VagusError> {
     // Validate addresses
     deps.api.addr_validate(&msg.afferent_inbox)?;
     deps.api.addr_validate(&msg.capability_issuer)?;
+    deps.api.addr_validate(&msg.ans_state_manager)?;
 
     AFFerent_INBOX.save(deps.storage, &msg.afferent_inbox)?;
     CAPABILITY_ISSUER.save(deps.storage, &msg.capability_issuer)?;
-    LAST_TRIGGER.save(deps.storage, &0)?;
+    ANS_STATE_MANAGER.save(deps.storage, &msg.ans_state_manager)?;
+    REFLEX_STATE.save(deps.storage, &ReflexState::Idle { last_severity: None })?;
     REFLEX_COOLDOWN.save(deps.storage, &msg.reflex_cooldown)?;
     DANGER_VTI_THRESHOLD.save(deps.storage, &msg.danger_vti_threshold)?;
     SHUTDOWN_VTI_THRESHOLD.save(deps.storage, &msg.shutdown_vti_threshold)?;
+    ADMIN.save(deps.storage, &info.sender.to_string())?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("afferent_inbox", msg.afferent_inbox)
         .add_attribute("capability_issuer", msg.capability_issuer)
+        .add_attribute("ans_state_manager", msg.ans_state_manager)
         .add_attribute("reflex_cooldown", msg.reflex_cooldown.to_string()))
 }
 
@@ -78,6 +158,7 @@ pub fn execute(
     match msg {
         ExecuteMsg::OnAEP {
             executor_id,
+            action_id,
             metrics_hash_sha256,
             metrics_hash_keccak,
         } => execute_on_aep(
@@ -85,12 +166,16 @@ pub fn execute(
             env,
             info,
             executor_id,
+            action_id,
             metrics_hash_sha256,
             metrics_hash_keccak,
         ),
         ExecuteMsg::ManualTrigger { executor_id, reason } => {
             execute_manual_trigger(deps, env, info, executor_id, reason)
         }
+        ExecuteMsg::SetActionResponse { action_id, response } => {
+            execute_set_action_response(deps, info, action_id, response)
+        }
     }
 }
 
@@ -99,8 +184,9 @@ pub fn execute_on_aep(
     env: Env,
     info: MessageInfo,
     executor_id: u64,
-    metrics_hash_sha256: Binary,
-    metrics_hash_keccak: Binary,
+    action_id: Option<Binary>,
+    _metrics_hash_sha256: Binary,
+    _metrics_hash_keccak: Binary,
 ) -> Result<Response, VagusError> {
     // Only afferent inbox can trigger reflex
     let afferent_inbox = AFFerent_INBOX.load(deps.storage)?;
@@ -108,98 +194,267 @@ pub fn execute_on_aep(
         return Err(VagusError::Unauthorized);
     }
 
-    // Check cooldown
-    let last_trigger = LAST_TRIGGER.load(deps.storage)?;
-    let cooldown = REFLEX_COOLDOWN.load(deps.storage)?;
-    let current_time = env.block.time.seconds();
+    // The evidence only carries hashes; the tone the ANS derived from it
+    // decides whether the reflex fires
+    let tone = current_tone(deps.as_ref())?;
+    let danger = match analyze_tone_for_danger(deps.as_ref(), tone)? {
+        Some(danger) => danger,
+        None => return Ok(Response::new().add_attribute("action", "on_aep_no_trigger")),
+    };
 
-    if current_time < last_trigger + cooldown {
+    // Check cooldown; a more severe danger cuts it short
+    let current_time = env.block.time.seconds();
+    if !REFLEX_STATE.load(deps.storage)?.at(current_time).admits(danger) {
         // Cooldown not elapsed, skip trigger but don't error
         return Ok(Response::new().add_attribute("action", "on_aep_cooldown"));
     }
 
-    // Analyze metrics to determine if reflex should trigger
-    // Simplified: just check if hashes indicate dangerous conditions
-    // In production, would decode and analyze actual metrics
-
-    let should_trigger = analyze_metrics_for_danger(&metrics_hash_sha256, &metrics_hash_keccak)?;
-
-    if !should_trigger {
-        return Ok(Response::new().add_attribute("action", "on_aep_no_trigger"));
+    // The action's configured response, revoking when none is set
+    let response = match &action_id {
+        Some(action_id) => ACTION_RESPONSES.may_load(deps.storage, action_id.as_slice())?,
+        None => None,
     }
+    .unwrap_or(ReflexResponse::Revoke {});
 
-    // Trigger reflex: revoke all capabilities for this executor
-    let revoked_count = trigger_capability_revocation(deps, executor_id)?;
+    let (messages, revoked_tokens) = match &response {
+        ReflexResponse::Revoke {} => trigger_capability_revocation(deps.as_ref(), executor_id)?,
+        ReflexResponse::Scale { cap } => {
+            // Only configured actions can scale, so the action id is present
+            let action_id = action_id.clone().ok_or(VagusError::InvalidInput)?;
+            (vec![scale_action(deps.as_ref(), action_id, *cap)?], vec![])
+        }
+        ReflexResponse::Shutdown {} => {
+            let (mut messages, revoked_tokens) =
+                trigger_capability_revocation(deps.as_ref(), executor_id)?;
+            messages.push(escalate_to_shutdown(deps.as_ref(), tone)?);
+            (messages, revoked_tokens)
+        }
+    };
 
-    // Update last trigger time
-    LAST_TRIGGER.save(deps.storage, &current_time)?;
+    start_cooling(deps, current_time, danger)?;
 
     Ok(Response::new()
+        .add_submessages(messages)
+        .add_events(reflex_triggered_event(executor_id, "danger_detected", &revoked_tokens, current_time))
         .add_attribute("action", "reflex_triggered")
         .add_attribute("executor_id", executor_id.to_string())
+        .add_attribute("danger", danger.as_str())
+        .add_attribute("response", response.as_str())
         .add_attribute("triggered_at", current_time.to_string())
-        .add_attribute("revoked_count", revoked_count.to_string()))
+        .add_attribute("revoked_count", revoked_tokens.len().to_string()))
 }
 
 pub fn execute_manual_trigger(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     executor_id: u64,
     reason: String,
 ) -> Result<Response, VagusError> {
-    // Check cooldown
-    let last_trigger = LAST_TRIGGER.load(deps.storage)?;
-    let cooldown = REFLEX_COOLDOWN.load(deps.storage)?;
-    let current_time = env.block.time.seconds();
+    // Only the admin may revoke an executor's capabilities by hand
+    if info.sender.to_string() != ADMIN.load(deps.storage)? {
+        return Err(VagusError::Unauthorized);
+    }
 
-    if current_time < last_trigger + cooldown {
+    // Check cooldown; manual triggers count as danger, so they never
+    // override a cooldown
+    let current_time = env.block.time.seconds();
+    if !REFLEX_STATE.load(deps.storage)?.at(current_time).admits(DangerLevel::Danger) {
         return Err(VagusError::InvalidInput);
     }
 
     // Trigger reflex: revoke all capabilities for this executor
-    let revoked_count = trigger_capability_revocation(deps, executor_id)?;
+    let (revocations, revoked_tokens) = trigger_capability_revocation(deps.as_ref(), executor_id)?;
 
-    // Update last trigger time
-    LAST_TRIGGER.save(deps.storage, &current_time)?;
+    start_cooling(deps, current_time, DangerLevel::Danger)?;
 
     Ok(Response::new()
+        .add_submessages(revocations)
+        .add_events(reflex_triggered_event(executor_id, &reason, &revoked_tokens, current_time))
         .add_attribute("action", "manual_reflex_triggered")
         .add_attribute("executor_id", executor_id.to_string())
         .add_attribute("reason", reason)
-        .add_attribute("revoked_count", revoked_count.to_string())
+        .add_attribute("revoked_count", revoked_tokens.len().to_string())
         .add_attribute("triggered_at", current_time.to_string()))
 }
 
-fn analyze_metrics_for_danger(
-    _metrics_hash_sha256: &Binary,
-    _metrics_hash_keccak: &Binary,
-) -> Result<bool, VagusError> {
-    // Simplified analysis - in production would:
-    // 1. Query ANS state manager for current VTI
-    // 2. Decode metrics and check against thresholds
-    // 3. Apply hysteresis logic
+/// `ReflexTriggered` event with the keys from spec/events.yml, as emitted by
+/// the EVM ReflexArc. Like it, nothing is emitted when no token was revoked.
+/// `revokedTokens` holds the comma-separated token ids.
+fn reflex_triggered_event(
+    executor_id: u64,
+    reason: &str,
+    revoked_tokens: &[String],
+    triggered_at: u64,
+) -> Option<Event> {
+    if revoked_tokens.is_empty() {
+        return None;
+    }
+    Some(
+        Event::new("ReflexTriggered")
+            .add_attribute("executorId", executor_id.to_string())
+            .add_attribute("reason", reason)
+            .add_attribute("revokedCount", revoked_tokens.len().to_string())
+            .add_attribute("triggeredAt", triggered_at.to_string())
+            .add_attribute("revokedTokens", revoked_tokens.join(",")),
+    )
+}
 
-    // For MVP: randomly trigger reflex 10% of the time (simulating dangerous conditions)
-    // In production: implement proper metrics analysis
+pub fn execute_set_action_response(
+    deps: DepsMut,
+    info: MessageInfo,
+    action_id: Binary,
+    response: Option<ReflexResponse>,
+) -> Result<Response, VagusError> {
+    if info.sender.to_string() != ADMIN.load(deps.storage)? {
+        return Err(VagusError::Unauthorized);
+    }
+
+    match &response {
+        Some(response) => {
+            if let ReflexResponse::Scale { cap } = response {
+                ScalingFactor::new(*cap)?;
+            }
+            ACTION_RESPONSES.save(deps.storage, action_id.as_slice(), response)?;
+        }
+        None => ACTION_RESPONSES.remove(deps.storage, action_id.as_slice()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_action_response")
+        .add_attribute("action_id", action_id.to_base64())
+        .add_attribute("response", response.as_ref().map(ReflexResponse::as_str).unwrap_or("none")))
+}
 
-    Ok(rand::random::<u8>() < 25) // ~10% chance
+// Enter the cooldown after a trigger at `severity`
+fn start_cooling(deps: DepsMut, now: u64, severity: DangerLevel) -> Result<(), VagusError> {
+    let cooldown = REFLEX_COOLDOWN.load(deps.storage)?;
+    REFLEX_STATE.save(
+        deps.storage,
+        &ReflexState::Cooling {
+            until: now + cooldown,
+            severity,
+        },
+    )?;
+    Ok(())
 }
 
-fn trigger_capability_revocation(deps: DepsMut, executor_id: u64) -> Result<u64, VagusError> {
-    // Query active tokens for this executor
-    let capability_issuer = CAPABILITY_ISSUER.load(deps.storage)?;
+/// How far the current tone has fallen, least severe first
+#[cosmwasm_schema::cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum DangerLevel {
+    Danger,
+    Shutdown,
+}
+
+impl DangerLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            DangerLevel::Danger => "danger",
+            DangerLevel::Shutdown => "shutdown",
+        }
+    }
+}
+
+// The ANS manager's current tone
+fn current_tone(deps: Deps) -> Result<Uint256, VagusError> {
+    let ans_state_manager = ANS_STATE_MANAGER.load(deps.storage)?;
+    let response: vagus_spec::ans_state_manager::CurrentToneResponse = deps
+        .querier
+        .query_wasm_smart(
+            ans_state_manager,
+            &vagus_spec::ans_state_manager::QueryMsg::CurrentTone {},
+        )?;
+    Ok(response.tone.value)
+}
+
+// Compare the tone against the reflex thresholds
+fn analyze_tone_for_danger(deps: Deps, tone: Uint256) -> Result<Option<DangerLevel>, VagusError> {
+    if tone < Uint256::from(SHUTDOWN_VTI_THRESHOLD.load(deps.storage)?) {
+        Ok(Some(DangerLevel::Shutdown))
+    } else if tone < Uint256::from(DANGER_VTI_THRESHOLD.load(deps.storage)?) {
+        Ok(Some(DangerLevel::Danger))
+    } else {
+        Ok(None)
+    }
+}
+
+// Cap the action's scaling on the ANS manager
+fn scale_action(deps: Deps, action_id: Binary, cap: u64) -> Result<SubMsg, VagusError> {
+    let msg = vagus_spec::ans_state_manager::ExecuteMsg::SetActionScalingCap {
+        action_id,
+        cap: Some(cap),
+    };
+    Ok(SubMsg::new(WasmMsg::Execute {
+        contract_addr: ANS_STATE_MANAGER.load(deps.storage)?,
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    }))
+}
 
-    // In production, would query CapabilityIssuer for active tokens of executor
-    // For MVP, we'll simulate revoking some tokens
+// Push the ANS manager to SHUTDOWN, reporting the tone it already holds; a
+// failed escalation is handled in `reply` so the revocations still go through
+fn escalate_to_shutdown(deps: Deps, tone: Uint256) -> Result<SubMsg, VagusError> {
+    let vti = Uint128::try_from(tone)
+        .ok()
+        .and_then(|tone| u64::try_from(tone.u128()).ok())
+        .ok_or(VagusError::InvalidInput)?;
+    let msg = vagus_spec::ans_state_manager::ExecuteMsg::UpdateTone {
+        vti,
+        suggested: ANSState::SHUTDOWN,
+    };
+    Ok(SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: ANS_STATE_MANAGER.load(deps.storage)?,
+            msg: to_json_binary(&msg)?,
+            funds: vec![],
+        },
+        SHUTDOWN_REPLY_ID,
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, VagusError> {
+    match (msg.id, msg.result) {
+        (SHUTDOWN_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "shutdown_escalation_failed")
+            .add_attribute("error", err)),
+        _ => Err(VagusError::InvalidInput),
+    }
+}
 
-    // Placeholder: assume we revoke 3 tokens (in reality would query and revoke all active ones)
-    let active_tokens = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+// Revoke submessages for the executor's capabilities, with the ids they
+// revoke; a failing revoke reverts the whole trigger so tokens are never
+// left half-revoked
+fn trigger_capability_revocation(
+    deps: Deps,
+    executor_id: u64,
+) -> Result<(Vec<SubMsg>, Vec<String>), VagusError> {
+    let capability_issuer = CAPABILITY_ISSUER.load(deps.storage)?;
+
+    // Page through the executor's active tokens on the issuer
+    let mut active_tokens = Vec::new();
+    loop {
+        let page: vagus_spec::capability_issuer::ActiveTokensOfResponse =
+            deps.querier.query_wasm_smart(
+                capability_issuer.clone(),
+                &vagus_spec::capability_issuer::QueryMsg::ActiveTokensOf {
+                    executor_id,
+                    start_after: active_tokens.last().cloned(),
+                    limit: Some(ACTIVE_TOKENS_PAGE_LIMIT),
+                },
+            )?;
+        let last_page = page.token_ids.len() < ACTIVE_TOKENS_PAGE_LIMIT as usize;
+        active_tokens.extend(page.token_ids);
+        if last_page {
+            break;
+        }
+    }
 
     let mut messages = Vec::new();
-    for token_id in active_tokens {
+    for token_id in &active_tokens {
         let revoke_msg = vagus_spec::capability_issuer::ExecuteMsg::Revoke {
-            token_id,
+            token_id: token_id.clone(),
             reason: CapabilityRevocationReason::REFLEX_TRIGGER,
         };
 
@@ -212,23 +467,45 @@ fn trigger_capability_revocation(deps: DepsMut, executor_id: u64) -> Result<u64,
         messages.push(SubMsg::new(wasm_msg));
     }
 
-    // Store messages for execution
-    // In a real implementation, we'd return these in the Response
-    // For now, just return count
-
-    Ok(messages.len() as u64)
+    Ok((messages, active_tokens))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-    // No queries implemented
-    Err(cosmwasm_std::StdError::not_found("QueryMsg"))
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::ReflexState {} => to_json_binary(&query_reflex_state(deps, env)?),
+    }
+}
+
+fn query_reflex_state(deps: Deps, env: Env) -> StdResult<ReflexStateResponse> {
+    let state = REFLEX_STATE.load(deps.storage)?.at(env.block.time.seconds());
+    Ok(ReflexStateResponse { state })
 }
 
 // Helper modules for cross-contract calls
 pub mod vagus_spec {
     use super::*;
 
+    pub mod ans_state_manager {
+        use super::*;
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum ExecuteMsg {
+            UpdateTone { vti: u64, suggested: ANSState },
+            SetActionScalingCap { action_id: Binary, cap: Option<u64> },
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum QueryMsg {
+            CurrentTone {},
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct CurrentToneResponse {
+            pub tone: VagalToneIndicator,
+        }
+    }
+
     pub mod capability_issuer {
         use super::*;
 
@@ -239,5 +516,649 @@ pub mod vagus_spec {
                 reason: CapabilityRevocationReason,
             },
         }
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum QueryMsg {
+            ActiveTokensOf {
+                executor_id: u64,
+                start_after: Option<String>,
+                limit: Option<u32>,
+            },
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct ActiveTokensOfResponse {
+            pub token_ids: Vec<String>,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{Addr, Empty};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+    use sha2::{Digest, Sha256};
+    use sha3::Keccak256;
+
+    const DAO: &str = "dao";
+    const EXECUTOR: &str = "executor";
+    const PLANNER: &str = "planner";
+    const ATTESTOR: &str = "attestor";
+    const ORACLE: &str = "oracle";
+    // {"vti": 8000} in canonical CBOR, posted with its SHA-256 and Keccak-256
+    const METRICS_CBOR: [u8; 8] = [0xa1, 0x63, b'v', b't', b'i', 0x19, 0x1f, 0x40];
+
+    fn issuer_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            capability_issuer::execute,
+            capability_issuer::instantiate,
+            capability_issuer::query,
+        ))
+    }
+
+    fn ans_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            ans_state_manager::execute,
+            ans_state_manager::instantiate,
+            ans_state_manager::query,
+        ))
+    }
+
+    fn inbox_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(
+            ContractWrapper::new(
+                afferent_inbox::execute,
+                afferent_inbox::instantiate,
+                afferent_inbox::query,
+            )
+            .with_reply(afferent_inbox::reply),
+        )
+    }
+
+    fn reflex_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+    }
+
+    struct Deployment {
+        app: App,
+        inbox: Addr,
+        issuer: Addr,
+        ans: Addr,
+        reflex: Addr,
+    }
+
+    /// Wire inbox -> reflex -> issuer and reflex -> ANS, with tokens 1-3
+    /// issued to executor 1
+    fn deploy() -> Deployment {
+        let mut app = App::default();
+        let inbox_code = app.store_code(inbox_contract());
+        let issuer_code = app.store_code(issuer_contract());
+        let ans_code = app.store_code(ans_contract());
+        let reflex_code = app.store_code(reflex_contract());
+
+        let inbox = app
+            .instantiate_contract(
+                inbox_code,
+                Addr::unchecked(DAO),
+                &afferent_inbox::InstantiateMsg {
+                    authorized_attestors: vec![ATTESTOR.to_string()],
+                    min_attestations: None,
+                    activation_delay: None,
+                },
+                &[],
+                "AfferentInbox",
+                None,
+            )
+            .unwrap();
+        let issuer = app
+            .instantiate_contract(
+                issuer_code,
+                Addr::unchecked(DAO),
+                &capability_issuer::InstantiateMsg {
+                    authorized_executors: vec![EXECUTOR.to_string()],
+                    reflex_arc: None,
+                    ans_state_manager: None,
+                    vagus_dao: DAO.to_string(),
+                    rate_limit_window_size: None,
+                    rate_limit_max_requests: None,
+                    circuit_breaker_threshold: None,
+                    circuit_breaker_timeout: None,
+                    circuit_breaker_recovery: None,
+                    max_active_per_executor: None,
+                },
+                &[],
+                "CapabilityIssuer",
+                None,
+            )
+            .unwrap();
+        let ans = app
+            .instantiate_contract(
+                ans_code,
+                Addr::unchecked(DAO),
+                &ans_state_manager::InstantiateMsg {
+                    initial_state: ::vagus_spec::ANSState::SAFE,
+                    min_state_residency: 0,
+                    safe_threshold: 8000,
+                    danger_threshold: 6000,
+                    shutdown_threshold: None,
+                    authorized_oracles: vec![ORACLE.to_string()],
+                    vagus_dao: DAO.to_string(),
+                },
+                &[],
+                "ANSStateManager",
+                None,
+            )
+            .unwrap();
+        let reflex = app
+            .instantiate_contract(
+                reflex_code,
+                Addr::unchecked(DAO),
+                &InstantiateMsg {
+                    afferent_inbox: inbox.to_string(),
+                    capability_issuer: issuer.to_string(),
+                    ans_state_manager: ans.to_string(),
+                    reflex_cooldown: 60,
+                    danger_vti_threshold: 6000,
+                    shutdown_vti_threshold: 3000,
+                },
+                &[],
+                "ReflexArc",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(DAO),
+            issuer.clone(),
+            &capability_issuer::ExecuteMsg::SetReflexArc {
+                reflex_arc: reflex.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(DAO),
+            inbox.clone(),
+            &afferent_inbox::ExecuteMsg::SetReflexArc {
+                reflex_arc: Some(reflex.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let mut deployment = Deployment {
+            app,
+            inbox,
+            issuer,
+            ans,
+            reflex,
+        };
+        for action in 1..=3u8 {
+            issue(&mut deployment, 1, action);
+        }
+        deployment
+    }
+
+    fn issue(deployment: &mut Deployment, executor_id: u64, action: u8) {
+        let now = deployment.app.block_info().time.seconds();
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(EXECUTOR),
+                deployment.issuer.clone(),
+                &capability_issuer::ExecuteMsg::Issue {
+                    intent_executor_id: executor_id,
+                    intent_action_id: Binary::from([action; 32]),
+                    intent_params: Binary::default(),
+                    intent_envelope_hash: Binary::from([0u8; 32]),
+                    intent_pre_state_root: Binary::from([0u8; 32]),
+                    intent_not_before: now,
+                    intent_not_after: now + 3600,
+                    intent_max_duration_ms: 1000,
+                    intent_max_energy_j: 10,
+                    intent_planner: PLANNER.to_string(),
+                    intent_nonce: executor_id * 256 + action as u64,
+                    intent_is_escape: false,
+                    scaled_limits_hash: Binary::from([0u8; 32]),
+                    expires_at: now + 600,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn revoked(deployment: &Deployment, token_ids: &[&str]) -> Vec<bool> {
+        token_ids
+            .iter()
+            .map(|token_id| {
+                let info: capability_issuer::TokenInfoResponse = deployment
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        deployment.issuer.clone(),
+                        &capability_issuer::QueryMsg::TokenInfo {
+                            token_id: token_id.to_string(),
+                        },
+                    )
+                    .unwrap();
+                info.token.unwrap().revoked
+            })
+            .collect()
+    }
+
+    fn set_tone(deployment: &mut Deployment, vti: u64) {
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(ORACLE),
+                deployment.ans.clone(),
+                &ans_state_manager::ExecuteMsg::UpdateTone {
+                    vti,
+                    suggested: ::vagus_spec::ANSState::SAFE,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn on_aep(deployment: &mut Deployment, action_id: Option<Binary>) -> String {
+        let res = deployment
+            .app
+            .execute_contract(
+                deployment.inbox.clone(),
+                deployment.reflex.clone(),
+                &ExecuteMsg::OnAEP {
+                    executor_id: 1,
+                    action_id,
+                    metrics_hash_sha256: Binary::from([0u8; 32]),
+                    metrics_hash_keccak: Binary::from([0u8; 32]),
+                },
+                &[],
+            )
+            .unwrap();
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == "action")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    fn manual_trigger(deployment: &mut Deployment, executor_id: u64) -> String {
+        let res = deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.reflex.clone(),
+                &ExecuteMsg::ManualTrigger {
+                    executor_id,
+                    reason: "test".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == "revoked_count")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_manual_trigger_revokes_tokens_on_issuer() {
+        let mut deployment = deploy();
+
+        assert_eq!(manual_trigger(&mut deployment, 1), "3");
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_manual_trigger_rejects_strangers() {
+        let mut deployment = deploy();
+
+        let err = deployment
+            .app
+            .execute_contract(
+                Addr::unchecked("mallory"),
+                deployment.reflex.clone(),
+                &ExecuteMsg::ManualTrigger {
+                    executor_id: 1,
+                    reason: "test".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::Unauthorized)
+        ));
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![false, false, false]);
+        assert_eq!(reflex_state(&deployment), ReflexState::Idle { last_severity: None });
+    }
+
+    #[test]
+    fn test_trigger_revokes_only_the_executors_tokens() {
+        let mut deployment = deploy();
+        issue(&mut deployment, 2, 1);
+        issue(&mut deployment, 2, 2);
+
+        assert_eq!(manual_trigger(&mut deployment, 2), "2");
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![false, false, false]);
+        assert_eq!(revoked(&deployment, &["4", "5"]), vec![true, true]);
+
+        // An executor without tokens triggers no revocations
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(60));
+        assert_eq!(manual_trigger(&mut deployment, 3), "0");
+    }
+
+    #[test]
+    fn test_trigger_reports_revoked_token_ids() {
+        let mut deployment = deploy();
+        issue(&mut deployment, 2, 1);
+        issue(&mut deployment, 2, 2);
+        let revoked_token_ids = |res: &cw_multi_test::AppResponse| {
+            let event = res
+                .events
+                .iter()
+                .find(|e| e.ty == "wasm-ReflexTriggered")
+                .expect("ReflexTriggered event");
+            let attribute = |key: &str| {
+                event.attributes.iter().find(|a| a.key == key).unwrap().value.clone()
+            };
+            assert_eq!(attribute("revokedCount"), attribute("revokedTokens").split(',').count().to_string());
+            attribute("revokedTokens")
+        };
+
+        let res = deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.reflex.clone(),
+                &ExecuteMsg::ManualTrigger {
+                    executor_id: 2,
+                    reason: "test".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(revoked_token_ids(&res), "4,5");
+        assert_eq!(revoked(&deployment, &["4", "5"]), vec![true, true]);
+
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(60));
+        set_tone(&mut deployment, 5000);
+        let res = deployment
+            .app
+            .execute_contract(
+                deployment.inbox.clone(),
+                deployment.reflex.clone(),
+                &ExecuteMsg::OnAEP {
+                    executor_id: 1,
+                    action_id: None,
+                    metrics_hash_sha256: Binary::from([0u8; 32]),
+                    metrics_hash_keccak: Binary::from([0u8; 32]),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(revoked_token_ids(&res), "1,2,3");
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_on_aep_triggers_only_on_dangerous_tone() {
+        let mut deployment = deploy();
+
+        set_tone(&mut deployment, 9000);
+        assert_eq!(on_aep(&mut deployment, None), "on_aep_no_trigger");
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![false, false, false]);
+
+        set_tone(&mut deployment, 5000);
+        assert_eq!(on_aep(&mut deployment, None), "reflex_triggered");
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_posted_aep_under_danger_revokes_through_inbox() {
+        let mut deployment = deploy();
+        let post_aep = afferent_inbox::ExecuteMsg::PostAEP {
+            executor_id: 1,
+            state_root_sha256: Binary::from([1u8; 32]),
+            state_root_keccak: Binary::from([2u8; 32]),
+            metrics_hash_sha256: Binary::from(Sha256::digest(METRICS_CBOR).as_slice()),
+            metrics_hash_keccak: Binary::from(Keccak256::digest(METRICS_CBOR).as_slice()),
+            attestation: Binary::default(),
+            co_attestations: vec![],
+            metrics_cbor: Binary::from(METRICS_CBOR),
+        };
+
+        set_tone(&mut deployment, 9000);
+        deployment
+            .app
+            .execute_contract(Addr::unchecked(ATTESTOR), deployment.inbox.clone(), &post_aep, &[])
+            .unwrap();
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![false, false, false]);
+
+        set_tone(&mut deployment, 5000);
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(ATTESTOR), deployment.inbox.clone(), &post_aep, &[])
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "reflex_triggered"));
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_on_aep_scales_action_configured_to_scale() {
+        let mut deployment = deploy();
+        let scaled_action = Binary::from([1u8; 32]);
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.ans.clone(),
+                &ans_state_manager::ExecuteMsg::SetReflexArc {
+                    reflex_arc: deployment.reflex.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Only the admin configures responses
+        let set_response = ExecuteMsg::SetActionResponse {
+            action_id: scaled_action.clone(),
+            response: Some(ReflexResponse::Scale { cap: 2000 }),
+        };
+        assert!(deployment
+            .app
+            .execute_contract(Addr::unchecked("mallory"), deployment.reflex.clone(), &set_response, &[])
+            .is_err());
+        deployment
+            .app
+            .execute_contract(Addr::unchecked(DAO), deployment.reflex.clone(), &set_response, &[])
+            .unwrap();
+
+        set_tone(&mut deployment, 5000);
+        assert_eq!(on_aep(&mut deployment, Some(scaled_action.clone())), "reflex_triggered");
+
+        // The action is capped on the ANS manager and nothing is revoked
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![false, false, false]);
+        let guard_for = |action_id: Binary| {
+            let res: ans_state_manager::GuardForDetailedResponse = deployment
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    deployment.ans.clone(),
+                    &ans_state_manager::QueryMsg::GuardForDetailed {
+                        action_id,
+                        executor_id: None,
+                    },
+                )
+                .unwrap();
+            res.rule
+        };
+        assert_eq!(
+            guard_for(scaled_action),
+            ans_state_manager::GuardRule::ActionScalingCap { cap: 2000 }
+        );
+        assert_eq!(
+            guard_for(Binary::from([2u8; 32])),
+            ans_state_manager::GuardRule::GlobalState {
+                state: ::vagus_spec::ANSState::DANGER
+            }
+        );
+    }
+
+    #[test]
+    fn test_shutdown_response_revokes_even_if_escalation_fails() {
+        let mut deployment = deploy();
+        let shutdown_action = Binary::from([1u8; 32]);
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.reflex.clone(),
+                &ExecuteMsg::SetActionResponse {
+                    action_id: shutdown_action.clone(),
+                    response: Some(ReflexResponse::Shutdown {}),
+                },
+                &[],
+            )
+            .unwrap();
+        let attribute = |res: &cw_multi_test::AppResponse, key: &str| {
+            res.events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .find(|a| a.key == key)
+                .map(|a| a.value.clone())
+        };
+        let trigger = |deployment: &mut Deployment| {
+            deployment
+                .app
+                .execute_contract(
+                    deployment.inbox.clone(),
+                    deployment.reflex.clone(),
+                    &ExecuteMsg::OnAEP {
+                        executor_id: 1,
+                        action_id: Some(shutdown_action.clone()),
+                        metrics_hash_sha256: Binary::from([0u8; 32]),
+                        metrics_hash_keccak: Binary::from([0u8; 32]),
+                    },
+                    &[],
+                )
+                .unwrap()
+        };
+
+        let ans_state = |deployment: &Deployment| {
+            let res: ans_state_manager::CurrentStateResponse = deployment
+                .app
+                .wrap()
+                .query_wasm_smart(deployment.ans.clone(), &ans_state_manager::QueryMsg::CurrentState {})
+                .unwrap();
+            res.state
+        };
+
+        // The reflex is not an oracle on the ANS manager, so the escalation
+        // is refused; the executor's tokens are revoked all the same
+        set_tone(&mut deployment, 5000);
+        let res = trigger(&mut deployment);
+        assert_eq!(attribute(&res, "response").as_deref(), Some("shutdown"));
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "shutdown_escalation_failed"));
+        assert_eq!(revoked(&deployment, &["1", "2", "3"]), vec![true, true, true]);
+        assert_eq!(ans_state(&deployment), ::vagus_spec::ANSState::DANGER);
+
+        // Once authorized, the escalation lands on the ANS manager
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.ans.clone(),
+                &ans_state_manager::ExecuteMsg::SetAuthorizedOracles {
+                    oracles: vec![ORACLE.to_string(), deployment.reflex.to_string()],
+                },
+                &[],
+            )
+            .unwrap();
+        for action in 4..=5u8 {
+            issue(&mut deployment, 1, action);
+        }
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(60));
+        let res = trigger(&mut deployment);
+        assert!(!res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "shutdown_escalation_failed"));
+        assert_eq!(revoked(&deployment, &["4", "5"]), vec![true, true]);
+        assert_eq!(ans_state(&deployment), ::vagus_spec::ANSState::SHUTDOWN);
+    }
+
+    fn reflex_state(deployment: &Deployment) -> ReflexState {
+        let res: ReflexStateResponse = deployment
+            .app
+            .wrap()
+            .query_wasm_smart(deployment.reflex.clone(), &QueryMsg::ReflexState {})
+            .unwrap();
+        res.state
+    }
+
+    #[test]
+    fn test_reflex_state_transitions() {
+        let mut deployment = deploy();
+        assert_eq!(reflex_state(&deployment), ReflexState::Idle { last_severity: None });
+
+        // Idle -> Cooling on trigger
+        set_tone(&mut deployment, 5000);
+        assert_eq!(on_aep(&mut deployment, None), "reflex_triggered");
+        let triggered_at = deployment.app.block_info().time.seconds();
+        assert_eq!(
+            reflex_state(&deployment),
+            ReflexState::Cooling {
+                until: triggered_at + 60,
+                severity: DangerLevel::Danger,
+            }
+        );
+
+        // Equal severity waits out the cooldown; higher severity bypasses it
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(10));
+        assert_eq!(on_aep(&mut deployment, None), "on_aep_cooldown");
+        set_tone(&mut deployment, 2000);
+        assert_eq!(on_aep(&mut deployment, None), "reflex_triggered");
+        assert_eq!(
+            reflex_state(&deployment),
+            ReflexState::Cooling {
+                until: triggered_at + 70,
+                severity: DangerLevel::Shutdown,
+            }
+        );
+
+        // Back to Idle once the cooldown has passed
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(60));
+        assert_eq!(
+            reflex_state(&deployment),
+            ReflexState::Idle {
+                last_severity: Some(DangerLevel::Shutdown),
+            }
+        );
     }
 }
@@ -1,19 +1,43 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    WasmMsg, SubMsg,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdResult, SubMsg, SubMsgResult, WasmMsg,
 };
 use cw_storage_plus::Item;
 
-use vagus_spec::{ANSState, Guard, VagusError, MAX_DURATION_MS, MAX_ENERGY_J};
+use ::vagus_spec::{
+    ANSState, AfferentEvidencePacket, Guard, ScalingFactor, VagusError, MAX_DURATION_MS,
+    MAX_ENERGY_J,
+};
 
 // State
 pub const ANS_STATE_MANAGER: Item<String> = Item::new("ans_state_manager");
 pub const CAPABILITY_ISSUER: Item<String> = Item::new("capability_issuer");
+pub const AFFERENT_INBOX: Item<String> = Item::new("afferent_inbox");
+pub const SKIP_PRE_STATE_CHECK: Item<bool> = Item::new("skip_pre_state_check");
+
+// The issuance forwarded to the capability issuer, kept for `reply` to report
+// if it fails
+const PENDING_ISSUE: Item<PendingIssue> = Item::new("pending_issue");
+
+// Reply id of the issuance forwarded to the capability issuer
+const ISSUE_REPLY_ID: u64 = 1;
+
+#[cosmwasm_schema::cw_serde]
+struct PendingIssue {
+    executor_id: u64,
+    action_id: Binary,
+    not_before: u64,
+    not_after: u64,
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct InstantiateMsg {
     pub ans_state_manager: String,
     pub capability_issuer: String,
+    pub afferent_inbox: String,
+    /// Issue without matching intents against the latest evidence; for testing only
+    #[serde(default)]
+    pub skip_pre_state_check: bool,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -30,6 +54,8 @@ pub enum ExecuteMsg {
         intent_max_energy_j: u64,
         intent_planner: String,
         intent_nonce: u64,
+        #[serde(default)]
+        intent_is_escape: bool,
         scaled_limits_hash: Binary,
         expires_at: u64,
     },
@@ -50,14 +76,18 @@ pub fn instantiate(
     // Validate addresses
     deps.api.addr_validate(&msg.ans_state_manager)?;
     deps.api.addr_validate(&msg.capability_issuer)?;
+    deps.api.addr_validate(&msg.afferent_inbox)?;
 
     ANS_STATE_MANAGER.save(deps.storage, &msg.ans_state_manager)?;
     CAPABILITY_ISSUER.save(deps.storage, &msg.capability_issuer)?;
+    AFFERENT_INBOX.save(deps.storage, &msg.afferent_inbox)?;
+    SKIP_PRE_STATE_CHECK.save(deps.storage, &msg.skip_pre_state_check)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("ans_state_manager", msg.ans_state_manager)
-        .add_attribute("capability_issuer", msg.capability_issuer))
+        .add_attribute("capability_issuer", msg.capability_issuer)
+        .add_attribute("afferent_inbox", msg.afferent_inbox))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -80,6 +110,7 @@ pub fn execute(
             intent_max_energy_j,
             intent_planner,
             intent_nonce,
+            intent_is_escape,
             scaled_limits_hash,
             expires_at,
         } => execute_issue_with_brake(
@@ -97,6 +128,7 @@ pub fn execute(
             intent_max_energy_j,
             intent_planner,
             intent_nonce,
+            intent_is_escape,
             scaled_limits_hash,
             expires_at,
         ),
@@ -118,37 +150,84 @@ pub fn execute_issue_with_brake(
     intent_max_energy_j: u64,
     intent_planner: String,
     intent_nonce: u64,
+    intent_is_escape: bool,
     scaled_limits_hash: Binary,
     expires_at: u64,
 ) -> Result<Response, VagusError> {
+    // A paused executor is blocked outright, whatever the ANS state allows
+    let capability_issuer = CAPABILITY_ISSUER.load(deps.storage)?;
+    let executor_paused: vagus_spec::capability_issuer::IsExecutorPausedResponse =
+        deps.querier.query_wasm_smart(
+            &capability_issuer,
+            &vagus_spec::capability_issuer::QueryMsg::IsExecutorPaused {
+                executor_id: intent_executor_id,
+            },
+        )?;
+    if executor_paused.paused {
+        return Err(VagusError::ExecutorPaused);
+    }
+
+    // The intent must have been planned from the latest posted evidence
+    if !SKIP_PRE_STATE_CHECK.load(deps.storage)? {
+        verify_pre_state_root(deps.as_ref(), intent_executor_id, &intent_pre_state_root)?;
+    }
+
     // Query ANS state manager for guard
     let ans_manager = ANS_STATE_MANAGER.load(deps.storage)?;
-    let guard: Guard = deps.querier.query_wasm_smart(
-        &ans_manager,
-        &vagus_spec::ans_state_manager::QueryMsg::GuardFor {
-            action_id: intent_action_id.clone(),
-        },
-    )?;
+    let guard: Guard = deps
+        .querier
+        .query_wasm_smart::<vagus_spec::ans_state_manager::GuardForResponse>(
+            &ans_manager,
+            &vagus_spec::ans_state_manager::QueryMsg::GuardFor {
+                action_id: intent_action_id.clone(),
+                executor_id: Some(intent_executor_id),
+            },
+        )?
+        .guard;
 
     // Check if execution is blocked
     if !guard.allowed {
         return Err(VagusError::ANSBlocked);
     }
 
+    // Escape tokens outlive SHUTDOWN, so only allowlisted actions may claim one
+    if intent_is_escape {
+        let escape: vagus_spec::ans_state_manager::IsEscapeActionResponse =
+            deps.querier.query_wasm_smart(
+                &ans_manager,
+                &vagus_spec::ans_state_manager::QueryMsg::IsEscapeAction {
+                    action_id: intent_action_id.clone(),
+                },
+            )?;
+        if !escape.escape {
+            return Err(VagusError::ANSBlocked);
+        }
+    }
+
     // Apply scaling to brakeable parameters
-    let scaled_params = apply_scaling(&intent_params, guard.scalingFactor.u128() as u64)?;
+    let scaling_factor = guard.scaling_factor()?;
+    let scaled_params = apply_scaling(&intent_params, scaling_factor)?;
 
     // Validate scaled limits against intent constraints
     validate_scaled_limits(
         &scaled_params,
         intent_max_duration_ms,
         intent_max_energy_j,
-        guard.scalingFactor.u128() as u64,
+        scaling_factor,
     )?;
 
-    // Issue capability token via CapabilityIssuer
-    let capability_issuer = CAPABILITY_ISSUER.load(deps.storage)?;
+    PENDING_ISSUE.save(
+        deps.storage,
+        &PendingIssue {
+            executor_id: intent_executor_id,
+            action_id: intent_action_id.clone(),
+            not_before: intent_not_before,
+            not_after: intent_not_after,
+        },
+    )?;
 
+    // Issue capability token via CapabilityIssuer; a rejection is handled in
+    // `reply` so the issuer's circuit breaker can count it
     let issue_msg = vagus_spec::capability_issuer::ExecuteMsg::Issue {
         intent_executor_id,
         intent_action_id,
@@ -161,6 +240,7 @@ pub fn execute_issue_with_brake(
         intent_max_energy_j,
         intent_planner: intent_planner.clone(),
         intent_nonce,
+        intent_is_escape,
         scaled_limits_hash,
         expires_at,
     };
@@ -172,15 +252,63 @@ pub fn execute_issue_with_brake(
     };
 
     Ok(Response::new()
-        .add_message(wasm_msg)
+        .add_submessage(SubMsg::reply_on_error(wasm_msg, ISSUE_REPLY_ID))
         .add_attribute("action", "issue_with_brake")
         .add_attribute("executor_id", intent_executor_id.to_string())
         .add_attribute("planner", intent_planner)
-        .add_attribute("scaling_factor", guard.scalingFactor.to_string())
+        .add_attribute("scaling_factor", scaling_factor.to_string())
         .add_attribute("allowed", guard.allowed.to_string()))
 }
 
-fn apply_scaling(params: &Binary, scaling_factor: u64) -> Result<Binary, VagusError> {
+// A failed issuance reverted everything the issuer wrote, including its
+// breaker bookkeeping; report it back so the failure is counted. The brake's
+// transaction then succeeds without issuing a token.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, VagusError> {
+    match (msg.id, msg.result) {
+        (ISSUE_REPLY_ID, SubMsgResult::Err(err)) => {
+            let pending = PENDING_ISSUE.load(deps.storage)?;
+            PENDING_ISSUE.remove(deps.storage);
+
+            let record_msg = vagus_spec::capability_issuer::ExecuteMsg::RecordIssueFailure {
+                executor_id: pending.executor_id,
+                action_id: pending.action_id,
+                not_before: pending.not_before,
+                not_after: pending.not_after,
+            };
+            Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: CAPABILITY_ISSUER.load(deps.storage)?,
+                    msg: to_json_binary(&record_msg)?,
+                    funds: vec![],
+                })
+                .add_attribute("action", "issue_failed")
+                .add_attribute("executor_id", pending.executor_id.to_string())
+                .add_attribute("error", err))
+        }
+        _ => Err(VagusError::InvalidInput),
+    }
+}
+
+fn verify_pre_state_root(
+    deps: Deps,
+    executor_id: u64,
+    pre_state_root: &Binary,
+) -> Result<(), VagusError> {
+    let afferent_inbox = AFFERENT_INBOX.load(deps.storage)?;
+    let latest: vagus_spec::afferent_inbox::LatestAEPResponse = deps.querier.query_wasm_smart(
+        &afferent_inbox,
+        &vagus_spec::afferent_inbox::QueryMsg::LatestAEP { executor_id },
+    )?;
+
+    // Without evidence for the executor there is nothing the intent can match
+    match latest.aep {
+        Some(aep) if aep.stateRootKeccak == *pre_state_root => Ok(()),
+        _ => Err(VagusError::StateMismatch),
+    }
+}
+
+fn apply_scaling(params: &Binary, scaling_factor: ScalingFactor) -> Result<Binary, VagusError> {
     // Simplified scaling - in production this would parse and scale specific fields
     // For MVP, just return original params (assume scaling is handled elsewhere)
     // Real implementation would need to parse CBOR/ABI encoded params and scale brakeable fields
@@ -191,21 +319,21 @@ fn apply_scaling(params: &Binary, scaling_factor: u64) -> Result<Binary, VagusEr
     Ok(params.clone())
 }
 
-fn validate_scaled_limits(
+/// Reject intents whose limits, scaled down by the guard, still exceed the
+/// absolute `MAX_DURATION_MS`/`MAX_ENERGY_J`
+pub fn validate_scaled_limits(
     _scaled_params: &Binary,
     max_duration_ms: u64,
     max_energy_j: u64,
-    scaling_factor: u64,
+    scaling_factor: ScalingFactor,
 ) -> Result<(), VagusError> {
     // Check duration limit
-    let scaled_duration = (max_duration_ms as u128 * scaling_factor as u128) / 10000;
-    if scaled_duration > MAX_DURATION_MS as u128 {
+    if scaling_factor.apply(max_duration_ms) > MAX_DURATION_MS {
         return Err(VagusError::ANSLimitExceeded);
     }
 
     // Check energy limit
-    let scaled_energy = (max_energy_j as u128 * scaling_factor as u128) / 10000;
-    if scaled_energy > MAX_ENERGY_J as u128 {
+    if scaling_factor.apply(max_energy_j) > MAX_ENERGY_J {
         return Err(VagusError::ANSLimitExceeded);
     }
 
@@ -227,7 +355,35 @@ pub mod vagus_spec {
 
         #[cosmwasm_schema::cw_serde]
         pub enum QueryMsg {
-            GuardFor { action_id: Binary },
+            GuardFor {
+                action_id: Binary,
+                executor_id: Option<u64>,
+            },
+            IsEscapeAction { action_id: Binary },
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct GuardForResponse {
+            pub guard: Guard,
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct IsEscapeActionResponse {
+            pub escape: bool,
+        }
+    }
+
+    pub mod afferent_inbox {
+        use super::*;
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum QueryMsg {
+            LatestAEP { executor_id: u64 },
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct LatestAEPResponse {
+            pub aep: Option<AfferentEvidencePacket>,
         }
     }
 
@@ -248,9 +404,478 @@ pub mod vagus_spec {
                 intent_max_energy_j: u64,
                 intent_planner: String,
                 intent_nonce: u64,
+                #[serde(default)]
+                intent_is_escape: bool,
                 scaled_limits_hash: Binary,
                 expires_at: u64,
             },
+            RecordIssueFailure {
+                executor_id: u64,
+                action_id: Binary,
+                not_before: u64,
+                not_after: u64,
+            },
         }
+
+        #[cosmwasm_schema::cw_serde]
+        pub enum QueryMsg {
+            IsExecutorPaused { executor_id: u64 },
+        }
+
+        #[cosmwasm_schema::cw_serde]
+        pub struct IsExecutorPausedResponse {
+            pub paused: bool,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{Addr, Empty};
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+    use sha2::{Digest, Sha256};
+    use sha3::Keccak256;
+
+    const DAO: &str = "dao";
+    const ATTESTOR: &str = "attestor";
+    const ORACLE: &str = "oracle";
+    const PLANNER: &str = "planner";
+    // {"vti": 8000} in canonical CBOR, posted with its SHA-256 and Keccak-256
+    const METRICS_CBOR: [u8; 8] = [0xa1, 0x63, b'v', b't', b'i', 0x19, 0x1f, 0x40];
+    // cw-multi-test addresses contracts in instantiation order; the brake
+    // is the fourth and must be authorized on the issuer up front
+    const BRAKE: &str = "contract3";
+
+    fn inbox_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(
+            ContractWrapper::new(
+                afferent_inbox::execute,
+                afferent_inbox::instantiate,
+                afferent_inbox::query,
+            )
+            .with_reply(afferent_inbox::reply),
+        )
+    }
+
+    fn ans_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            ans_state_manager::execute,
+            ans_state_manager::instantiate,
+            ans_state_manager::query,
+        ))
+    }
+
+    fn issuer_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            capability_issuer::execute,
+            capability_issuer::instantiate,
+            capability_issuer::query,
+        ))
+    }
+
+    fn brake_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+    }
+
+    fn reflex_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(
+            ContractWrapper::new(reflex_arc::execute, reflex_arc::instantiate, reflex_arc::query)
+                .with_reply(reflex_arc::reply),
+        )
+    }
+
+    struct Deployment {
+        app: App,
+        inbox: Addr,
+        ans: Addr,
+        issuer: Addr,
+        brake: Addr,
+    }
+
+    fn deploy() -> Deployment {
+        let mut app = App::default();
+        let inbox_code = app.store_code(inbox_contract());
+        let ans_code = app.store_code(ans_contract());
+        let issuer_code = app.store_code(issuer_contract());
+        let brake_code = app.store_code(brake_contract());
+
+        let inbox = app
+            .instantiate_contract(
+                inbox_code,
+                Addr::unchecked(DAO),
+                &afferent_inbox::InstantiateMsg {
+                    authorized_attestors: vec![ATTESTOR.to_string()],
+                    min_attestations: None,
+                    activation_delay: None,
+                },
+                &[],
+                "AfferentInbox",
+                None,
+            )
+            .unwrap();
+        let ans = app
+            .instantiate_contract(
+                ans_code,
+                Addr::unchecked(DAO),
+                &ans_state_manager::InstantiateMsg {
+                    initial_state: ANSState::SAFE,
+                    min_state_residency: 0,
+                    safe_threshold: 8000,
+                    danger_threshold: 6000,
+                    shutdown_threshold: None,
+                    authorized_oracles: vec![ORACLE.to_string()],
+                    vagus_dao: DAO.to_string(),
+                },
+                &[],
+                "ANSStateManager",
+                None,
+            )
+            .unwrap();
+        let issuer = app
+            .instantiate_contract(
+                issuer_code,
+                Addr::unchecked(DAO),
+                &capability_issuer::InstantiateMsg {
+                    authorized_executors: vec![BRAKE.to_string()],
+                    reflex_arc: None,
+                    ans_state_manager: Some(ans.to_string()),
+                    vagus_dao: DAO.to_string(),
+                    rate_limit_window_size: None,
+                    rate_limit_max_requests: None,
+                    circuit_breaker_threshold: None,
+                    circuit_breaker_timeout: None,
+                    circuit_breaker_recovery: None,
+                    max_active_per_executor: None,
+                },
+                &[],
+                "CapabilityIssuer",
+                None,
+            )
+            .unwrap();
+        let brake = app
+            .instantiate_contract(
+                brake_code,
+                Addr::unchecked(DAO),
+                &InstantiateMsg {
+                    ans_state_manager: ans.to_string(),
+                    capability_issuer: issuer.to_string(),
+                    afferent_inbox: inbox.to_string(),
+                    skip_pre_state_check: false,
+                },
+                &[],
+                "VagalBrake",
+                None,
+            )
+            .unwrap();
+        assert_eq!(brake, Addr::unchecked(BRAKE));
+
+        Deployment { app, inbox, ans, issuer, brake }
+    }
+
+    fn issue_msg(deployment: &Deployment, nonce: u64, pre_state_root: Binary) -> ExecuteMsg {
+        let now = deployment.app.block_info().time.seconds();
+        ExecuteMsg::IssueWithBrake {
+            intent_executor_id: 1,
+            intent_action_id: Binary::from([1u8; 32]),
+            intent_params: Binary::default(),
+            intent_envelope_hash: Binary::from([0u8; 32]),
+            intent_pre_state_root: pre_state_root,
+            intent_not_before: now,
+            intent_not_after: now + 3600,
+            intent_max_duration_ms: 1000,
+            intent_max_energy_j: 10,
+            intent_planner: PLANNER.to_string(),
+            intent_nonce: nonce,
+            intent_is_escape: false,
+            scaled_limits_hash: Binary::from([0u8; 32]),
+            expires_at: now + 600,
+        }
+    }
+
+    fn post_aep(deployment: &mut Deployment, state_root: Binary) {
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(ATTESTOR),
+                deployment.inbox.clone(),
+                &afferent_inbox::ExecuteMsg::PostAEP {
+                    executor_id: 1,
+                    state_root_sha256: Binary::from([1u8; 32]),
+                    state_root_keccak: state_root,
+                    metrics_hash_sha256: Binary::from(Sha256::digest(METRICS_CBOR).as_slice()),
+                    metrics_hash_keccak: Binary::from(Keccak256::digest(METRICS_CBOR).as_slice()),
+                    attestation: Binary::default(),
+                    co_attestations: vec![],
+                    metrics_cbor: Binary::from(METRICS_CBOR),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pre_state_root_must_match_latest_aep() {
+        let mut deployment = deploy();
+        let state_root = Binary::from([2u8; 32]);
+
+        // No evidence posted yet
+        let msg = issue_msg(&deployment, 1, state_root.clone());
+        let err = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::StateMismatch)
+        ));
+
+        post_aep(&mut deployment, state_root.clone());
+
+        let msg = issue_msg(&deployment, 2, Binary::from([9u8; 32]));
+        let err = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::StateMismatch)
+        ));
+
+        let msg = issue_msg(&deployment, 3, state_root);
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "issue"));
+    }
+
+    /// Wire a reflex arc to the inbox and the issuer, as its admin `DAO`
+    fn deploy_reflex(deployment: &mut Deployment) -> Addr {
+        let reflex_code = deployment.app.store_code(reflex_contract());
+        let reflex = deployment
+            .app
+            .instantiate_contract(
+                reflex_code,
+                Addr::unchecked(DAO),
+                &reflex_arc::InstantiateMsg {
+                    afferent_inbox: deployment.inbox.to_string(),
+                    capability_issuer: deployment.issuer.to_string(),
+                    ans_state_manager: deployment.ans.to_string(),
+                    reflex_cooldown: 30,
+                    danger_vti_threshold: 6000,
+                    shutdown_vti_threshold: 3000,
+                },
+                &[],
+                "ReflexArc",
+                None,
+            )
+            .unwrap();
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.issuer.clone(),
+                &capability_issuer::ExecuteMsg::SetReflexArc {
+                    reflex_arc: reflex.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.inbox.clone(),
+                &afferent_inbox::ExecuteMsg::SetReflexArc {
+                    reflex_arc: Some(reflex.to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+        reflex
+    }
+
+    fn active_tokens(deployment: &Deployment) -> Vec<String> {
+        let active: capability_issuer::ActiveTokensOfResponse = deployment
+            .app
+            .wrap()
+            .query_wasm_smart(
+                deployment.issuer.clone(),
+                &capability_issuer::QueryMsg::ActiveTokensOf {
+                    executor_id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        active.token_ids
+    }
+
+    #[test]
+    fn test_reflex_revokes_token_issued_through_brake() {
+        let mut deployment = deploy();
+        let reflex = deploy_reflex(&mut deployment);
+        let state_root = Binary::from([2u8; 32]);
+        post_aep(&mut deployment, state_root.clone());
+
+        let msg = issue_msg(&deployment, 1, state_root);
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap();
+        let token_id = res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == "token_id")
+            .map(|a| a.value.clone())
+            .expect("issued token_id");
+        assert_eq!(active_tokens(&deployment), vec![token_id.clone()]);
+
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                reflex,
+                &reflex_arc::ExecuteMsg::ManualTrigger {
+                    executor_id: 1,
+                    reason: "danger".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        let info: capability_issuer::TokenInfoResponse = deployment
+            .app
+            .wrap()
+            .query_wasm_smart(
+                deployment.issuer.clone(),
+                &capability_issuer::QueryMsg::TokenInfo { token_id },
+            )
+            .unwrap();
+        assert!(info.token.unwrap().revoked);
+        assert!(active_tokens(&deployment).is_empty());
+    }
+
+    #[test]
+    fn test_escape_intent_requires_allowlisted_action() {
+        let mut deployment = deploy();
+        let state_root = Binary::from([2u8; 32]);
+        post_aep(&mut deployment, state_root.clone());
+        let escape_msg = |deployment: &Deployment, nonce: u64| {
+            let mut msg = issue_msg(deployment, nonce, state_root.clone());
+            let ExecuteMsg::IssueWithBrake { intent_is_escape, .. } = &mut msg;
+            *intent_is_escape = true;
+            msg
+        };
+
+        let msg = escape_msg(&deployment, 1);
+        let err = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VagusError>(),
+            Some(VagusError::ANSBlocked)
+        ));
+
+        deployment
+            .app
+            .execute_contract(
+                Addr::unchecked(DAO),
+                deployment.ans.clone(),
+                &ans_state_manager::ExecuteMsg::SetEscapeAction {
+                    action_id: Binary::from([1u8; 32]),
+                    escape: true,
+                },
+                &[],
+            )
+            .unwrap();
+
+        let msg = escape_msg(&deployment, 2);
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "is_escape" && a.value == "true"));
+    }
+
+    #[test]
+    fn test_rejected_issuance_is_counted_by_issuer_breaker() {
+        let mut deployment = deploy();
+        let state_root = Binary::from([2u8; 32]);
+        post_aep(&mut deployment, state_root.clone());
+        for msg in [
+            capability_issuer::ExecuteMsg::SetRateLimit {
+                window_size: 60,
+                max_requests: 1,
+            },
+            capability_issuer::ExecuteMsg::SetCircuitBreakerParams {
+                threshold: 2,
+                timeout: 120,
+                recovery: 1,
+            },
+        ] {
+            deployment
+                .app
+                .execute_contract(Addr::unchecked(DAO), deployment.issuer.clone(), &msg, &[])
+                .unwrap();
+        }
+        let attribute = |res: &cw_multi_test::AppResponse, key: &str| {
+            res.events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .find(|a| a.key == key)
+                .map(|a| a.value.clone())
+        };
+
+        let msg = issue_msg(&deployment, 1, state_root.clone());
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap();
+        assert!(attribute(&res, "token_id").is_some());
+
+        // Rate limited: no token, but the failure outlives the transaction
+        for (nonce, failures, state) in [(2, "1", "Closed"), (3, "2", "Open")] {
+            let msg = issue_msg(&deployment, nonce, state_root.clone());
+            let res = deployment
+                .app
+                .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+                .unwrap();
+            assert_eq!(attribute(&res, "token_id"), None);
+            assert!(res
+                .events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .any(|a| a.key == "action" && a.value == "issue_failed"));
+            assert_eq!(
+                attribute(&res, "rejected").as_deref(),
+                Some("Request rate exceeds configured limits")
+            );
+            assert_eq!(attribute(&res, "failure_count").as_deref(), Some(failures));
+            assert_eq!(attribute(&res, "circuit_state").as_deref(), Some(state));
+        }
+
+        // The open breaker turns the action away even once the window passed
+        deployment
+            .app
+            .update_block(|block| block.time = block.time.plus_seconds(61));
+        let msg = issue_msg(&deployment, 4, state_root);
+        let res = deployment
+            .app
+            .execute_contract(Addr::unchecked(PLANNER), deployment.brake.clone(), &msg, &[])
+            .unwrap();
+        assert_eq!(attribute(&res, "token_id"), None);
+        assert_eq!(attribute(&res, "failure_count"), None);
     }
 }
@@ -0,0 +1,278 @@
+//! Range-checked basis-point values
+//!
+//! VTI values, thresholds, and scaling factors share the 0-10000 basis-point
+//! range but are not interchangeable. `Vti` and `ScalingFactor` wrap
+//! `BasisPoints` so one cannot be passed where the other is expected:
+//!
+//! ```compile_fail
+//! use vagus_spec::{ScalingFactor, Vti};
+//!
+//! fn update_tone(_vti: Vti) {}
+//!
+//! update_tone(ScalingFactor::FULL);
+//! ```
+
+use cosmwasm_std::{Uint128, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{Guard, VagusError};
+
+/// Upper bound of the basis-point range (100%)
+pub const MAX_BASIS_POINTS: u64 = 10000;
+
+/// A value between 0 and 10000 basis points inclusive
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct BasisPoints(u64);
+
+impl BasisPoints {
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(MAX_BASIS_POINTS);
+
+    /// Create a value, rejecting anything above 10000
+    pub fn new(value: u64) -> Result<Self, VagusError> {
+        if value > MAX_BASIS_POINTS {
+            return Err(VagusError::InvalidInput);
+        }
+        Ok(Self(value))
+    }
+
+    /// Create a value, clamping anything above 10000
+    pub const fn saturating(value: u64) -> Self {
+        if value > MAX_BASIS_POINTS {
+            Self(MAX_BASIS_POINTS)
+        } else {
+            Self(value)
+        }
+    }
+
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Scale `amount` by this fraction of 10000, rounding down
+    pub fn apply(self, amount: u64) -> u64 {
+        ((amount as u128 * self.0 as u128) / MAX_BASIS_POINTS as u128) as u64
+    }
+}
+
+impl TryFrom<u64> for BasisPoints {
+    type Error = VagusError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<Uint256> for BasisPoints {
+    type Error = VagusError;
+
+    fn try_from(value: Uint256) -> Result<Self, Self::Error> {
+        let value = Uint128::try_from(value).map_err(|_| VagusError::InvalidInput)?;
+        u64::try_from(value.u128())
+            .map_err(|_| VagusError::InvalidInput)
+            .and_then(Self::new)
+    }
+}
+
+impl From<BasisPoints> for u64 {
+    fn from(value: BasisPoints) -> Self {
+        value.0
+    }
+}
+
+impl From<BasisPoints> for Uint256 {
+    fn from(value: BasisPoints) -> Self {
+        Uint256::from(value.0)
+    }
+}
+
+impl fmt::Display for BasisPoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Vagal tone indicator, in basis points
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct Vti(BasisPoints);
+
+impl Vti {
+    /// Create a VTI, rejecting values above 10000 as `InvalidToneValue`
+    pub fn new(value: u64) -> Result<Self, VagusError> {
+        BasisPoints::new(value)
+            .map(Self)
+            .map_err(|_| VagusError::InvalidToneValue)
+    }
+
+    pub fn get(self) -> u64 {
+        self.0.get()
+    }
+
+    pub fn basis_points(self) -> BasisPoints {
+        self.0
+    }
+}
+
+impl From<BasisPoints> for Vti {
+    fn from(value: BasisPoints) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<u64> for Vti {
+    type Error = VagusError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Vti> for u64 {
+    fn from(value: Vti) -> Self {
+        value.get()
+    }
+}
+
+impl From<Vti> for Uint256 {
+    fn from(value: Vti) -> Self {
+        value.0.into()
+    }
+}
+
+impl fmt::Display for Vti {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Fraction of an intent's brakeable limits allowed by the current guard
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct ScalingFactor(BasisPoints);
+
+impl ScalingFactor {
+    pub const NONE: Self = Self(BasisPoints::ZERO);
+    pub const FULL: Self = Self(BasisPoints::MAX);
+
+    pub fn new(value: u64) -> Result<Self, VagusError> {
+        BasisPoints::new(value).map(Self)
+    }
+
+    pub const fn saturating(value: u64) -> Self {
+        Self(BasisPoints::saturating(value))
+    }
+
+    pub fn get(self) -> u64 {
+        self.0.get()
+    }
+
+    pub fn basis_points(self) -> BasisPoints {
+        self.0
+    }
+
+    /// Scale a brakeable limit, rounding down
+    pub fn apply(self, amount: u64) -> u64 {
+        self.0.apply(amount)
+    }
+}
+
+impl From<BasisPoints> for ScalingFactor {
+    fn from(value: BasisPoints) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<u64> for ScalingFactor {
+    type Error = VagusError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<ScalingFactor> for u64 {
+    fn from(value: ScalingFactor) -> Self {
+        value.get()
+    }
+}
+
+impl From<ScalingFactor> for Uint256 {
+    fn from(value: ScalingFactor) -> Self {
+        value.0.into()
+    }
+}
+
+impl fmt::Display for ScalingFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Guard {
+    /// Build a guard that allows execution whenever the factor is non-zero
+    pub fn with_scaling_factor(scaling_factor: ScalingFactor) -> Self {
+        Self {
+            scalingFactor: scaling_factor.into(),
+            allowed: scaling_factor > ScalingFactor::NONE,
+        }
+    }
+
+    /// The guard's scaling factor, validated to lie within 0-10000
+    pub fn scaling_factor(&self) -> Result<ScalingFactor, VagusError> {
+        BasisPoints::try_from(self.scalingFactor).map(ScalingFactor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_construction_fails() {
+        assert!(BasisPoints::new(MAX_BASIS_POINTS).is_ok());
+        assert!(matches!(BasisPoints::new(10001), Err(VagusError::InvalidInput)));
+        assert!(matches!(Vti::new(10001), Err(VagusError::InvalidToneValue)));
+        assert!(matches!(ScalingFactor::new(u64::MAX), Err(VagusError::InvalidInput)));
+
+        let guard = Guard {
+            scalingFactor: Uint256::from(20000u64),
+            allowed: true,
+        };
+        assert!(guard.scaling_factor().is_err());
+    }
+
+    #[test]
+    fn test_deserialization_enforces_range() {
+        let vti: Vti = cosmwasm_std::from_json(b"7500").unwrap();
+        assert_eq!(vti.get(), 7500);
+        assert_eq!(cosmwasm_std::to_json_vec(&vti).unwrap(), b"7500");
+
+        assert!(cosmwasm_std::from_json::<Vti>(b"10001").is_err());
+        assert!(cosmwasm_std::from_json::<ScalingFactor>(b"10001").is_err());
+    }
+
+    #[test]
+    fn test_scaling_factor_apply() {
+        let half = ScalingFactor::new(5000).unwrap();
+        assert_eq!(half.apply(30000), 15000);
+        assert_eq!(ScalingFactor::FULL.apply(1000), 1000);
+        assert_eq!(ScalingFactor::NONE.apply(1000), 0);
+        assert_eq!(BasisPoints::MAX.apply(u64::MAX), u64::MAX);
+        assert_eq!(ScalingFactor::saturating(20000), ScalingFactor::FULL);
+
+        let guard = Guard::with_scaling_factor(half);
+        assert!(guard.allowed);
+        assert_eq!(guard.scaling_factor().unwrap(), half);
+        assert!(!Guard::with_scaling_factor(ScalingFactor::NONE).allowed);
+    }
+}
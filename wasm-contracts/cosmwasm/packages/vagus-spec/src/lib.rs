@@ -5,6 +5,9 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Binary, Uint256};
 use thiserror::Error;
 
+mod basis_points;
+pub use basis_points::{BasisPoints, ScalingFactor, Vti, MAX_BASIS_POINTS};
+
 #[cw_serde]
 pub enum ANSState {
     SAFE,
@@ -32,6 +35,8 @@ pub struct Intent {
     pub maxEnergyJ: Uint256,
     pub planner: String,
     pub nonce: Uint256,
+    #[serde(default)]
+    pub isEscape: bool,
 }
 
 #[cw_serde]
@@ -44,6 +49,8 @@ pub struct TokenMeta {
     pub expiresAt: Uint256,
     pub revoked: bool,
     pub revokedAt: Uint256,
+    #[serde(default)]
+    pub isEscape: bool,
 }
 
 #[cw_serde]
@@ -93,6 +100,10 @@ pub enum VagusError {
     TokenAlreadyRevoked,
     #[error("Caller not authorized to revoke this token")]
     UnauthorizedRevocation,
+    #[error("Executor already holds the maximum number of active capability tokens")]
+    TooManyActiveTokens,
+    #[error("Executor is paused and cannot be issued capability tokens")]
+    ExecutorPaused,
     #[error("Execution blocked by ANS shutdown state")]
     ANSBlocked,
     #[error("Scaled parameter exceeds ANS limits")]
@@ -101,6 +112,8 @@ pub enum VagusError {
     UnauthorizedAttestor,
     #[error("Evidence packet format is invalid")]
     InvalidEvidenceFormat,
+    #[error("Evidence packet lacks the required number of distinct attestations")]
+    InsufficientAttestations,
     #[error("Request rate exceeds configured limits")]
     RateLimited,
     #[error("Circuit breaker is in open state, blocking requests")]